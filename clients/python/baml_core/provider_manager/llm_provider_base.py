@@ -1,5 +1,6 @@
 import abc
 import json
+import re
 import traceback
 import typing
 import aiohttp
@@ -73,6 +74,162 @@ def _update_template_with_vars(
     return prompt
 
 
+# One step of a prompt variable: `.field`, `[0]`, `["key"]` or `[*]`.
+_REPLACER_SEGMENT = re.compile(
+    r'\.?([A-Za-z_][A-Za-z0-9_]*)|\[(\d+)\]|\["([^"\]]*)"\]|\[(\*)\]'
+)
+
+
+def _parse_replacer(path: str) -> typing.List[typing.Tuple[typing.Optional[str], ...]]:
+    segments = []
+    pos = 0
+    while pos < len(path):
+        match = _REPLACER_SEGMENT.match(path, pos)
+        if match is None:
+            raise ValueError(f"Invalid prompt variable: {path}")
+        segments.append(match.groups())
+        pos = match.end()
+    return segments
+
+
+def _format_replacer(replacer: str, params: typing.Mapping[str, typing.Any]) -> str:
+    """
+    Renders a prompt variable, e.g. `{arg.items[0]["key"].name}`, with the
+    function's arguments. `[*]` renders every item of a list, or every value of
+    a map, on its own line.
+    """
+    return _resolve_replacer(params, _parse_replacer(replacer[1:-1]))
+
+
+def _format_replacers(
+    replacers: typing.Iterable[str], params: typing.Mapping[str, typing.Any]
+) -> typing.Dict[str, str]:
+    """
+    Renders the prompt variables that use the function's arguments. Variables
+    that use the item of a `{#for}` block are rendered by `_expand_blocks`.
+    """
+    return {
+        k: _format_replacer(k, params)
+        for k in replacers
+        if _parse_replacer(k[1:-1])[0][0] in params
+    }
+
+
+def _resolve_replacer(
+    value: typing.Any,
+    segments: typing.List[typing.Tuple[typing.Optional[str], ...]],
+) -> str:
+    for i, (field, index, key, each) in enumerate(segments):
+        if each is not None:
+            items = value.values() if isinstance(value, typing.Mapping) else value
+            return "\n".join(
+                _resolve_replacer(item, segments[i + 1 :]) for item in items
+            )
+        value = _step(value, field, index, key)
+    return format(value)
+
+
+def _step(
+    value: typing.Any,
+    field: typing.Optional[str],
+    index: typing.Optional[str],
+    key: typing.Optional[str],
+) -> typing.Any:
+    if field is not None:
+        if isinstance(value, typing.Mapping):
+            return value[field]
+        return getattr(value, field)
+    if index is not None:
+        return value[int(index)]
+    return value[key]
+
+
+# `{#if arg.context}`, `{#else}`, `{#for doc in arg.docs}` and `{#end}`.
+_BLOCK_MARKER = re.compile(r"\{#(?:if ([^}]+)|else|for (\w+) in ([^}]+)|end)\}")
+# A prompt variable, e.g. `{doc.title}`.
+_REPLACER = re.compile(r"\{[A-Za-z_][^{}]*\}")
+
+
+def _expand_blocks(template: str, params: typing.Mapping[str, typing.Any]) -> str:
+    """
+    Renders the `{#if}` and `{#for}` blocks of a prompt, along with the prompt
+    variables that use the item of a `{#for}` block.
+
+    The condition of an `{#if}` is unset if it's null, false, or an empty
+    string, list or map.
+    """
+    if "{#" not in template:
+        return template
+
+    # Each block is (marker match, parts before `{#else}`, parts).
+    root: typing.List[typing.Any] = []
+    stack: typing.List[typing.Tuple[typing.Any, typing.Optional[list], list]] = []
+    pos = 0
+    for match in _BLOCK_MARKER.finditer(template):
+        parts = stack[-1][2] if stack else root
+        parts.append(template[pos : match.start()])
+        pos = match.end()
+        if match.group(0) == "{#else}":
+            marker, _, parts = stack.pop()
+            stack.append((marker, parts, []))
+        elif match.group(0) == "{#end}":
+            block = stack.pop()
+            (stack[-1][2] if stack else root).append(block)
+        else:
+            stack.append((match, None, []))
+    root.append(template[pos:])
+    return _render_blocks(root, params, {})
+
+
+def _render_blocks(
+    parts: typing.List[typing.Any],
+    params: typing.Mapping[str, typing.Any],
+    scope: typing.Mapping[str, typing.Any],
+) -> str:
+    rendered = []
+    for part in parts:
+        if isinstance(part, str):
+            rendered.append(
+                _REPLACER.sub(
+                    lambda m: _format_replacer(m.group(0), scope)
+                    if _parse_replacer(m.group(0)[1:-1])[0][0] in scope
+                    else m.group(0),
+                    part,
+                )
+            )
+            continue
+
+        marker, then, body = part
+        condition, item, collection = marker.groups()
+        if condition is not None:
+            if then is None:
+                then, body = body, []
+            value = _lookup(condition, params, scope)
+            is_set = value is not None and value is not False
+            if isinstance(value, (str, list, tuple, typing.Mapping)):
+                is_set = len(value) > 0
+            rendered.append(_render_blocks(then if is_set else body, params, scope))
+        else:
+            items = _lookup(collection, params, scope) or []
+            if isinstance(items, typing.Mapping):
+                items = items.values()
+            for value in items:
+                rendered.append(_render_blocks(body, params, {**scope, item: value}))
+    return "".join(rendered)
+
+
+def _lookup(
+    path: str,
+    params: typing.Mapping[str, typing.Any],
+    scope: typing.Mapping[str, typing.Any],
+) -> typing.Any:
+    segments = _parse_replacer(path)
+    value: typing.Any = scope if segments[0][0] in scope else params
+    for field, index, key, _ in segments:
+        value = _step(value, field, index, key)
+    return value
+
+
 def _redact(value: typing.Any) -> typing.Any:
     if isinstance(value, str):
         if len(value) > 4:
@@ -11,6 +11,8 @@ from .llm_response import LLMResponse
 from .llm_provider_base import (
     AbstractLLMProvider,
     LLMChatMessage,
+    _expand_blocks,
+    _format_replacers,
     _update_template_with_vars,
 )
 
@@ -63,11 +65,15 @@ class LLMChatProvider(AbstractLLMProvider):
         replacers: typing.Iterable[str],
         params: typing.Dict[str, typing.Any],
     ) -> LLMResponse:
-        updates = {k: k.format(**params) for k in replacers}
+        updates = _format_replacers(replacers, params)
         if len(message_templates) == 1 and isinstance(message_templates[0], list):
             chats = message_templates[0]
         else:
             chats = typing.cast(typing.List[LLMChatMessage], message_templates)
+        chats = [
+            {"role": msg["role"], "content": _expand_blocks(msg["content"], params)}
+            for msg in chats
+        ]
 
         create_event(
             "llm_prompt_template",
@@ -107,11 +113,15 @@ class LLMChatProvider(AbstractLLMProvider):
         replacers: typing.Iterable[str],
         params: typing.Dict[str, typing.Any],
     ) -> typing.AsyncIterator[LLMResponse]:
-        updates = {k: k.format(**params) for k in replacers}
+        updates = _format_replacers(replacers, params)
         if len(message_templates) == 1 and isinstance(message_templates[0], list):
             chats = message_templates[0]
         else:
             chats = typing.cast(typing.List[LLMChatMessage], message_templates)
+        chats = [
+            {"role": msg["role"], "content": _expand_blocks(msg["content"], params)}
+            for msg in chats
+        ]
 
         create_event(
             "llm_prompt_template",
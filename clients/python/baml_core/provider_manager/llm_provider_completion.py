@@ -7,6 +7,8 @@ from .llm_response import LLMResponse
 from .llm_provider_base import (
     AbstractLLMProvider,
     LLMChatMessage,
+    _expand_blocks,
+    _format_replacers,
     _update_template_with_vars,
 )
 
@@ -43,7 +45,8 @@ class LLMProvider(AbstractLLMProvider):
         replacers: typing.Iterable[str],
         params: typing.Dict[str, typing.Any],
     ) -> LLMResponse:
-        updates = {k: k.format(**params) for k in replacers}
+        template = _expand_blocks(template, params)
+        updates = _format_replacers(replacers, params)
         create_event(
             "llm_prompt_template",
             {
@@ -69,7 +72,8 @@ class LLMProvider(AbstractLLMProvider):
         replacers: typing.Iterable[str],
         params: typing.Dict[str, typing.Any],
     ) -> typing.AsyncIterator[LLMResponse]:
-        updates = {k: k.format(**params) for k in replacers}
+        template = _expand_blocks(template, params)
+        updates = _format_replacers(replacers, params)
         create_event(
             "llm_prompt_template",
             {
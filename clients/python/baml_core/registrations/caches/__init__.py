@@ -1,5 +1,7 @@
 from .gloo_cache import GlooCache
+from .cassette_cache import CassetteCache
 
 __all__ = [
     "GlooCache",
+    "CassetteCache",
 ]
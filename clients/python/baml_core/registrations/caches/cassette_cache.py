@@ -0,0 +1,93 @@
+import contextvars
+import json
+import os
+import re
+import typing
+
+from baml_core.services.api_types import (
+    CacheRequest,
+    CacheResponse,
+    LLMOutputModel,
+    LLMOutputModelMetadata,
+)
+from baml_core.cache_manager import register_cache_provider, AbstractCacheProvider
+
+# The pytest case name of the running test, e.g. "test_hello[Greet-v1]".
+current_test_case: "contextvars.ContextVar[typing.Optional[str]]" = (
+    contextvars.ContextVar("baml_cassette_test_case", default=None)
+)
+
+
+class CassetteError(Exception):
+    """
+    A response recorded by `baml test run --record` could not be read.
+    """
+
+
+def _cassette_path(directory: str, case_name: str) -> typing.Optional[str]:
+    match = re.fullmatch(r"test_(.+)\[(.+?)-(.+)\]", case_name)
+    if match is None:
+        return None
+    test, function, impl = match.groups()
+    return os.path.join(directory, function, test, f"{impl}.cassette")
+
+
+def load_cassette(directory: str, case_name: str) -> typing.Dict[str, typing.Any]:
+    """
+    The cassette recorded for the pytest case `case_name`. Raises a
+    `CassetteError` if it is missing or corrupt.
+    """
+    path = _cassette_path(directory, case_name)
+    if path is None:
+        raise CassetteError(f"`{case_name}` is not a BAML test case.")
+    try:
+        with open(path, encoding="utf-8") as f:
+            cassette = json.load(f)
+    except (OSError, ValueError) as e:
+        raise CassetteError(
+            f"Could not read the recorded response {path}: {e}. Record it again with `baml test run --record`."
+        ) from e
+    if not isinstance(cassette, dict) or not isinstance(
+        cassette.get("raw_output"), str
+    ):
+        raise CassetteError(
+            f"The recorded response {path} has no `raw_output`. Record it again with `baml test run --record`."
+        )
+    return cassette
+
+
+@register_cache_provider("baml-cassette")
+@typing.final
+class CassetteCache(AbstractCacheProvider):
+    """
+    Answers with the responses `baml test run --record` saved, so
+    `baml test run --replay` never calls the model.
+    """
+
+    def __init__(self, directory: str) -> None:
+        super().__init__()
+        self.__directory = directory
+
+    def get_llm_request(
+        self, cache_request: CacheRequest
+    ) -> typing.Optional[CacheResponse]:
+        case_name = current_test_case.get()
+        if case_name is None or _cassette_path(self.__directory, case_name) is None:
+            return None
+
+        cassette = load_cassette(self.__directory, case_name)
+        client = cassette.get("client") or {}
+        return CacheResponse(
+            model_name=client.get("model", ""),
+            llm_output=LLMOutputModel(
+                raw_text=cassette["raw_output"],
+                metadata=LLMOutputModelMetadata(),
+            ),
+            latency_ms=0,
+        )
+
+    def save_llm_request(
+        self, cache_request: CacheRequest, response: CacheResponse
+    ) -> None:
+        # `baml test` writes the cassettes itself, from the logs of the test.
+        pass
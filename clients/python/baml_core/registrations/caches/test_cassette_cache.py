@@ -0,0 +1,35 @@
+import json
+import pathlib
+
+import pytest
+from baml_core.registrations.caches.cassette_cache import (
+    CassetteError,
+    load_cassette,
+)
+
+
+def write_cassette(directory: pathlib.Path, impl: str, content: str) -> None:
+    path = directory / "Greet" / "hello" / f"{impl}.cassette"
+    path.parent.mkdir(parents=True, exist_ok=True)
+    path.write_text(content, encoding="utf-8")
+
+
+def test_loads_recorded_response(tmp_path: pathlib.Path) -> None:
+    write_cassette(tmp_path, "v1", json.dumps({"raw_output": "Hi Bob"}))
+    cassette = load_cassette(str(tmp_path), "test_hello[Greet-v1]")
+    assert cassette["raw_output"] == "Hi Bob"
+
+
+def test_missing_cassette_is_an_error(tmp_path: pathlib.Path) -> None:
+    with pytest.raises(CassetteError):
+        load_cassette(str(tmp_path), "test_hello[Greet-v1]")
+
+
+def test_corrupt_cassette_is_an_error(tmp_path: pathlib.Path) -> None:
+    write_cassette(tmp_path, "v1", '{"raw_output": "Hi')
+    with pytest.raises(CassetteError):
+        load_cassette(str(tmp_path), "test_hello[Greet-v1]")
+
+    write_cassette(tmp_path, "v2", json.dumps({"output": "Hi"}))
+    with pytest.raises(CassetteError):
+        load_cassette(str(tmp_path), "test_hello[Greet-v2]")
@@ -10,10 +10,12 @@ else:
     from .openai_chat_provider_1 import OpenAIChatProvider
     from .openai_completion_provider_1 import OpenAICompletionProvider
 from .fallback_provider import FallbackProvider
+from .round_robin_provider import RoundRobinProvider
 
 
 __all__ = [
     "FallbackProvider",
+    "RoundRobinProvider",
     "AnthropicProvider",
     "OpenAIChatProvider",
     "OpenAICompletionProvider",
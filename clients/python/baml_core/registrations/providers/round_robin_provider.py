@@ -0,0 +1,131 @@
+import itertools
+import typing
+
+from baml_core.provider_manager import (
+    register_llm_provider,
+    LLMResponse,
+    LLMManager,
+    LLMChatMessage,
+)
+from baml_core.provider_manager.llm_provider_base import AbstractLLMProvider
+
+
+def _parse_strategy(strategy: typing.Optional[typing.Any]) -> typing.List[str]:
+    if strategy is None:
+        raise ValueError("RoundRobinProvider requires a strategy option")
+    if not isinstance(strategy, list) or not all(
+        isinstance(item, str) for item in strategy
+    ):
+        raise ValueError(
+            f"RoundRobinProvider requires a strategy option as a list of client names. Got: {strategy}"
+        )
+    return strategy
+
+
+@register_llm_provider("baml-round-robin")
+@typing.final
+class RoundRobinProvider(AbstractLLMProvider):
+    """
+    Sends each request to the next client in the strategy, in turn.
+    """
+
+    __kwargs: typing.Dict[str, typing.Any]
+    __strategy: typing.Union[typing.Iterator[AbstractLLMProvider], None]
+
+    def _to_error_code(self, e: Exception) -> typing.Optional[int]:
+        return None
+
+    def __init__(
+        self, *, options: typing.Dict[str, typing.Any], **kwargs: typing.Any
+    ) -> None:
+        super().__init__(**kwargs)
+        self.__strategy_raw = _parse_strategy(options.pop("strategy", None))
+        self.__kwargs = options
+        self.__strategy = None
+
+    def _next(self) -> AbstractLLMProvider:
+        if self.__strategy is None:
+            raise ValueError(
+                "RoundRobinProvider not initialized. Did you call baml_init()?"
+            )
+        return next(self.__strategy)
+
+    def _validate(self) -> None:
+        if self.__strategy is not None:
+            # Already validated nothing to change.
+            return
+
+        assert (
+            len(self.__kwargs) == 0
+        ), f"RoundRobinProvider has unexpected options: {self.__kwargs}"
+        assert (
+            len(self.__strategy_raw) > 0
+        ), "RoundRobinProvider requires a strategy of at least 1"
+
+        del self.__kwargs
+
+        self.__strategy = itertools.cycle(
+            [LLMManager.get_llm(name) for name in self.__strategy_raw]
+        )
+        del self.__strategy_raw
+
+    async def _run_prompt_internal(self, prompt: str) -> LLMResponse:
+        return await self._next().run_prompt(prompt)
+
+    async def _run_prompt_template_internal(
+        self,
+        *,
+        template: str,
+        replacers: typing.Iterable[str],
+        params: typing.Dict[str, typing.Any],
+    ) -> LLMResponse:
+        return await self._next().run_prompt_template(
+            template=template, replacers=replacers, params=params
+        )
+
+    async def _run_chat_internal(
+        self, *messages: typing.Union[LLMChatMessage, typing.List[LLMChatMessage]]
+    ) -> LLMResponse:
+        return await self._next().run_chat(*messages)
+
+    async def _run_chat_template_internal(
+        self,
+        *message_templates: typing.Union[LLMChatMessage, typing.List[LLMChatMessage]],
+        replacers: typing.Iterable[str],
+        params: typing.Dict[str, typing.Any],
+    ) -> LLMResponse:
+        return await self._next().run_chat_template(
+            *message_templates, replacers=replacers, params=params
+        )
+
+    async def _run_prompt_template_internal_stream(
+        self,
+        *,
+        template: str,
+        replacers: typing.Iterable[str],
+        params: typing.Dict[str, typing.Any],
+    ) -> typing.AsyncIterator[LLMResponse]:
+        async for r in self._next().run_prompt_template_stream(
+            template=template, replacers=replacers, params=params
+        ):
+            yield r
+
+    async def _run_chat_internal_stream(
+        self, *messages: typing.Union[LLMChatMessage, typing.List[LLMChatMessage]]
+    ) -> typing.AsyncIterator[LLMResponse]:
+        # Without replacers, a chat template is sent as is.
+        async for r in self._next().run_chat_template_stream(
+            *messages, replacers=[], params={}
+        ):
+            yield r
+
+    async def _run_chat_template_internal_stream(
+        self,
+        *message_templates: typing.Union[LLMChatMessage, typing.List[LLMChatMessage]],
+        replacers: typing.Iterable[str],
+        params: typing.Dict[str, typing.Any],
+    ) -> typing.AsyncIterator[LLMResponse]:
+        async for r in self._next().run_chat_template_stream(
+            *message_templates, replacers=replacers, params=params
+        ):
+            yield r
@@ -3,6 +3,7 @@ import typing
 from .base_deserialzier import BaseDeserializer, ITypeDefinition, Diagnostics
 from .enum_deserializer import EnumDeserializer
 from .object_deserializer import ObjectDeserializer
+from .primitive_deserialzier import LiteralDeserializer
 from .complex_deserializer import (
     ListDeserializer,
     UnionDeserializer,
@@ -52,6 +53,8 @@ class Deserializer(typing.Generic[T]):
             return UnionDeserializer(
                 *dfn["choices"],
             )
+        if dfn["type"] == "Literal":
+            return typing.cast(BaseDeserializer[T], LiteralDeserializer(dfn["values"]))
         if dfn["type"] == "Optional":
             return typing.cast(
                 BaseDeserializer[T], OptionalDeserializer(item=dfn["item"])
@@ -44,10 +44,18 @@ class ObjectDeserializer(BaseDeserializer[T]):
     ):
         super().__init__(rank=5)
         self.__model = model
-        self.__fields = _generate_type_definitions_for_model(model)
+        # Read on first use: a recursive model only has its fields resolved once
+        # every model it refers to is defined.
+        self.__fields: typing.Optional[typing.Dict[str, ITypeDefinition]] = None
         # This field is alias to value.
         self.__alias_to_field = alias_to_field
 
+    @property
+    def _fields(self) -> typing.Dict[str, ITypeDefinition]:
+        if self.__fields is None:
+            self.__fields = _generate_type_definitions_for_model(self.__model)
+        return self.__fields
+
     def copy_with_aliases(
         self, aliases: typing.Dict[str, typing.Optional[str]]
     ) -> "ObjectDeserializer[T]":
@@ -83,7 +91,7 @@ class ObjectDeserializer(BaseDeserializer[T]):
 
             if key in self.__alias_to_field:
                 key = self.__alias_to_field[key]
-            meta = self.__fields.get(key)
+            meta = self._fields.get(key)
             if meta is None:
                 diagnostics.push_unkown_warning(f"Unknown key {key}")
                 continue
@@ -53,3 +53,35 @@ class NoneDeserializer(BaseDeserializer[None]):
         # We don't actually care about the value of raw, since we're just
         # returning None.
         return Result.from_value(None)
+
+
+@typing.final
+class LiteralDeserializer(BaseDeserializer[typing.Union[str, int, bool]]):
+    __values: typing.List[typing.Union[str, int, bool]]
+
+    def __init__(self, values: typing.List[typing.Union[str, int, bool]]) -> None:
+        self.__values = values
+        super().__init__(rank=5)
+
+    def coerce(
+        self,
+        raw: RawWrapper,
+        diagnostics: Diagnostics,
+        from_lut: CheckLutFn[typing.Union[str, int, bool]],
+    ) -> Result[typing.Union[str, int, bool]]:
+        for value in self.__values:
+            # bool is a subclass of int, so check it first.
+            if isinstance(value, bool):
+                if raw.as_bool() == value:
+                    return Result.from_value(value)
+            elif isinstance(value, int):
+                if raw.as_int() == value:
+                    return Result.from_value(value)
+            else:
+                parsed = raw.as_smart_str(inner=True)
+                if parsed is not None and parsed.strip().lower() == value.lower():
+                    return Result.from_value(value)
+        diagnostics.push_unknown_error(
+            f"Expected one of: {', '.join(repr(v) for v in self.__values)}"
+        )
+        return Result.failed()
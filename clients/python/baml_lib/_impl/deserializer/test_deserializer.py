@@ -1,5 +1,5 @@
 from pydantic import BaseModel
-from typing import List, Optional
+from typing import List, Literal, Optional
 from baml_lib._impl.deserializer import Deserializer, register_deserializer
 from enum import Enum
 import pytest
@@ -100,6 +100,25 @@ def test_enum_list_from_list() -> None:
     assert res == [Category.TWO]
 
 
+def test_literal() -> None:
+    deserializer = Deserializer[Literal["ok", "error"]](Literal["ok", "error"])
+    res = deserializer.from_string('"Error"')
+    assert res == "error"
+
+
+def test_literal_missing() -> None:
+    deserializer = Deserializer[Literal["ok", "error"]](Literal["ok", "error"])
+    with pytest.raises(Exception):
+        deserializer.from_string("pending")
+
+
+def test_literal_int_and_bool() -> None:
+    deserializer = Deserializer[Literal[1, 2]](Literal[1, 2])
+    assert deserializer.from_string("2") == 2
+    deserializer = Deserializer[Literal[True]](Literal[True])
+    assert deserializer.from_string("true") is True
+
+
 @register_deserializer({})
 class BasicObj(BaseModel):
     foo: str
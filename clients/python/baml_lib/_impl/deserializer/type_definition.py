@@ -24,6 +24,11 @@ class PrimitiveTypeDefinition(typing.TypedDict):
     ]
 
 
+class LiteralTypeDefinition(typing.TypedDict):
+    type: typing.Literal["Literal"]
+    values: typing.List[typing.Union[str, int, bool]]
+
+
 class NamedTypeDefinition(typing.TypedDict):
     type: typing.Literal["Ref"]
     ref: typing.Union[typing.Type[BaseModel], typing.Type[Enum]]
@@ -38,6 +43,7 @@ ITypeDefinition = typing.Union[
     UnionTypeDefinition,
     OptionalTypeDefinition,
     PrimitiveTypeDefinition,
+    LiteralTypeDefinition,
     NamedTypeDefinition,
     NoneTypeDefinition,
 ]
@@ -59,6 +65,12 @@ def __get_optional_type(sub_type: ITypeDefinition) -> OptionalTypeDefinition:
     return {"type": "Optional", "item": sub_type}
 
 
+def __get_literal_type(
+    values: typing.List[typing.Union[str, int, bool]],
+) -> LiteralTypeDefinition:
+    return {"type": "Literal", "values": values}
+
+
 def __get_named_type(
     t: typing.Union[typing.Type[BaseModel], typing.Type[Enum]],
 ) -> NamedTypeDefinition:
@@ -71,6 +83,8 @@ def type_to_definition(t: typing.Type[typing.Any]) -> ITypeDefinition:
 
     if hasattr(t, "__origin__"):
         origin = t.__origin__
+        if origin == typing.Literal:
+            return __get_literal_type(list(t.__args__))
         if origin == typing.Union:
             # Special case for Optional types (Union[X, NoneType])
             if len(t.__args__) == 2 and type(None) in t.__args__:
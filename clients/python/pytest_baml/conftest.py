@@ -1,4 +1,6 @@
+import os
 import pytest
+from baml_core.cache_manager import CacheManager
 from baml_core.logger import logger
 from baml_lib import baml_init
 from .pytest_baml import BamlPytestPlugin
@@ -41,7 +43,12 @@ def pytest_configure(config: pytest.Config) -> None:
     # BAML INIT here
     # Add optional stage parameter to baml_init
     # baml_init(), returns api wrapper we can use
-    baml_conf = baml_init(stage="test", enable_cache=True)
+    # When replaying, only the recorded responses are used.
+    replay = os.environ.get("BAML_TEST_CASSETTE_MODE") == "replay"
+    cassette_dir = os.environ["BAML_TEST_CASSETTE_DIR"] if replay else None
+    baml_conf = baml_init(stage="test", enable_cache=not replay)
+    if cassette_dir is not None:
+        CacheManager.add_cache("baml-cassette", directory=cassette_dir)
 
     # Get which tests to include/exclude
 
@@ -51,6 +58,7 @@ def pytest_configure(config: pytest.Config) -> None:
             ipc_channel=config.getoption("baml_ipc"),
             include_filters=config.getoption("baml_include"),
             exclude_filters=config.getoption("baml_exclude"),
+            cassette_dir=cassette_dir,
         ),
         "pytest_baml",
     )
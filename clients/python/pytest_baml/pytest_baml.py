@@ -11,6 +11,10 @@ from baml_core.services import api_types
 import re
 
 from baml_core.otel import flush_trace_logs, add_message_transformer_hook
+from baml_core.registrations.caches.cassette_cache import (
+    current_test_case,
+    load_cassette,
+)
 from .ipc_channel import IPCChannel, NoopIPCChannel
 
 
@@ -101,6 +105,7 @@ class BamlPytestPlugin:
         ipc_channel: typing.Optional[int],
         include_filters: typing.List[str] = [],
         exclude_filters: typing.List[str] = [],
+        cassette_dir: typing.Optional[str] = None,
     ) -> None:
         self.__gloo_tests: typing.Dict[str, TestCaseMetadata] = {}
         self.__completed_tests: typing.Set[str] = set()
@@ -117,6 +122,8 @@ class BamlPytestPlugin:
         self.__exclude_filters = (
             "|".join(map(_to_regex_filter, exclude_filters)) or None
         )
+        # Set when replaying recorded responses instead of calling the model.
+        self.__cassette_dir = cassette_dir
 
         if ipc_channel is not None:
             add_message_transformer_hook(lambda log: self.__ipc.send("log", log))
@@ -309,6 +316,12 @@ class BamlPytestPlugin:
         )
 
         item.obj = _trace_internal(item.obj, __tags__=tags)  # type: ignore
+        # Lets a replayed test find its recorded responses.
+        current_test_case.set(meta.case_name)
+        if self.__cassette_dir is not None:
+            # The cache falls back to the model on any error, so a test whose
+            # recording can't be read must fail before it runs.
+            load_cassette(self.__cassette_dir, meta.case_name)
 
     @pytest.hookimpl(tryfirst=True)
     def pytest_runtest_makereport(
@@ -12,6 +12,10 @@ export interface IClient {
     run_prompt_template: (prompt_template: string, templates: {
         [key: string]: string;
     }) => Promise<string>;
+    // Yields the response as the model generates it, a delta at a time.
+    run_prompt_template_stream: (prompt_template: string, templates: {
+        [key: string]: string;
+    }) => AsyncIterable<string>;
 }
 
 class ClientManager {
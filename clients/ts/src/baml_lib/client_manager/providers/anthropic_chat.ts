@@ -30,13 +30,18 @@ class AnthropicClient implements IClient {
         }
     }
 
-    async run_chat(prompt: ChatMessage | ChatMessage[]): Promise<string> {
+    // The completions API takes the turns as one prompt that ends with the assistant's turn.
+    private prompt(prompt: ChatMessage | ChatMessage[]): string {
         const chats = Array.isArray(prompt) ? prompt : [prompt];
+        return chats.map((chat) => {
+            const role = chat.role === "user" ? Anthropic.HUMAN_PROMPT : Anthropic.AI_PROMPT;
+            return `${role} ${chat.content}`;
+        }).join("") + Anthropic.AI_PROMPT;
+    }
+
+    async run_chat(prompt: ChatMessage | ChatMessage[]): Promise<string> {
         const response = await this.client.completions.create({
-            prompt: chats.map((chat) => ({
-                role: chat.role === "user" ? Anthropic.HUMAN_PROMPT : Anthropic.AI_PROMPT,
-                content: chat.content,
-            })).join(""),
+            prompt: this.prompt(prompt),
             ...this.params,
         });
 
@@ -47,6 +52,19 @@ class AnthropicClient implements IClient {
 
         return message;
     }
+    async *run_chat_stream(prompt: ChatMessage | ChatMessage[]): AsyncIterable<string> {
+        const stream = await this.client.completions.create({
+            prompt: this.prompt(prompt),
+            ...this.params,
+            stream: true,
+        });
+
+        for await (const chunk of stream) {
+            if (chunk.completion) {
+                yield chunk.completion;
+            }
+        }
+    }
     async run_prompt(prompt: string): Promise<string> {
         return await this.run_chat({
             role: "user",
@@ -71,6 +89,16 @@ class AnthropicClient implements IClient {
             content: prompt_template,
         }, templates);
     }
+    run_prompt_template_stream(prompt_template: string, templates: { [key: string]: string; }): AsyncIterable<string> {
+        Object.entries(templates).forEach(([key, value]) => {
+            prompt_template = prompt_template.replaceAll(key, value);
+        });
+
+        return this.run_chat_stream({
+            role: "user",
+            content: prompt_template,
+        });
+    }
 }
 
 clientManager.registerProvider("baml-anthropic", {
@@ -1,58 +1,107 @@
 import { ChatMessage, clientManager, IClient } from "../client_manager";
 
+// An entry of `strategy`: the name of a client, or a map that also says when to fall back to it.
+type StrategyItem = string | {
+    client: string;
+    on_status_code?: number[];
+    // Retry policies are not supported by the TypeScript client yet.
+    retry_policy?: string;
+};
+
+type ChainItem = {
+    clientName: string;
+    onStatusCode: number[] | null;
+};
+
+function parseStrategyItem(item: StrategyItem): ChainItem {
+    if (typeof item === "string") {
+        return { clientName: item, onStatusCode: null };
+    }
+    if (typeof item === "object" && item !== null && typeof item.client === "string") {
+        return { clientName: item.client, onStatusCode: item.on_status_code ?? null };
+    }
+    throw new Error(`FallbackClient requires a strategy of client names or { client, on_status_code, retry_policy } maps. Got: ${JSON.stringify(item)}`);
+}
+
+// The HTTP status of a failed request. The OpenAI and Anthropic SDKs both set `status` on their errors.
+function toStatusCode(e: unknown): number | undefined {
+    const status = (e as { status?: unknown } | null)?.status;
+    return typeof status === "number" ? status : undefined;
+}
+
 class FallbackClient implements IClient {
-    private fallbackNames: string[];
-    private fallbacks: IClient[];
+    private strategy: ChainItem[];
 
     constructor(params: { [key: string]: any }) {
-        this.fallbacks = [];
-        this.fallbackNames = params.strategy;
+        if (!Array.isArray(params.strategy) || params.strategy.length === 0) {
+            throw new Error("FallbackClient requires a strategy of at least 1 client");
+        }
+        this.strategy = params.strategy.map(parseStrategyItem);
     }
 
-    async run_chat(prompt: ChatMessage | ChatMessage[]): Promise<string> {
-        for (const fallback of this.fallbackNames) {
-            try {
-                const client = clientManager.getClient(fallback);
-                return await client.run_chat(prompt);
-            } catch (e) {
-                console.log(e);
-            }
+    // Whether to try `item` after the previous client failed with `error`. The first client is
+    // always tried; later ones only on the status codes they list, if any.
+    private shouldTry(idx: number, item: ChainItem, error: unknown): boolean {
+        if (idx === 0 || item.onStatusCode === null) {
+            return true;
         }
-        throw new Error("All fallbacks failed");
+        const status = toStatusCode(error);
+        return status !== undefined && item.onStatusCode.includes(status);
     }
-    async run_prompt(prompt: string): Promise<string> {
-        for (const fallback of this.fallbackNames) {
+
+    private async runStrategy(run: (client: IClient) => Promise<string>): Promise<string> {
+        let lastError: unknown = undefined;
+        for (const [idx, item] of this.strategy.entries()) {
+            if (!this.shouldTry(idx, item, lastError)) {
+                continue;
+            }
             try {
-                const client = clientManager.getClient(fallback);
-                return await client.run_prompt(prompt);
+                return await run(clientManager.getClient(item.clientName));
             } catch (e) {
                 console.log(e);
+                lastError = e;
             }
         }
-        throw new Error("All fallbacks failed");
+        throw lastError;
+    }
+
+    async run_chat(prompt: ChatMessage | ChatMessage[]): Promise<string> {
+        return await this.runStrategy((client) => client.run_chat(prompt));
+    }
+    async run_prompt(prompt: string): Promise<string> {
+        return await this.runStrategy((client) => client.run_prompt(prompt));
     }
 
     async run_chat_template(prompt_template: ChatMessage | ChatMessage[], templates: { [key: string]: string; }): Promise<string> {
-        for (const fallback of this.fallbackNames) {
-            try {
-                const client = clientManager.getClient(fallback);
-                return await client.run_chat_template(prompt_template, templates);
-            } catch (e) {
-                console.log(e);
-            }
-        }
-        throw new Error("All fallbacks failed");
+        return await this.runStrategy((client) => client.run_chat_template(prompt_template, templates));
     }
     async run_prompt_template(prompt_template: string, templates: { [key: string]: string; }): Promise<string> {
-        for (const fallback of this.fallbackNames) {
+        return await this.runStrategy((client) => client.run_prompt_template(prompt_template, templates));
+    }
+    async *run_prompt_template_stream(prompt_template: string, templates: { [key: string]: string; }): AsyncIterable<string> {
+        let lastError: unknown = undefined;
+        for (const [idx, item] of this.strategy.entries()) {
+            if (!this.shouldTry(idx, item, lastError)) {
+                continue;
+            }
+            let streamed = false;
             try {
-                const client = clientManager.getClient(fallback);
-                return await client.run_prompt_template(prompt_template, templates);
+                const client = clientManager.getClient(item.clientName);
+                for await (const delta of client.run_prompt_template_stream(prompt_template, templates)) {
+                    streamed = true;
+                    yield delta;
+                }
+                return;
             } catch (e) {
+                // What was already yielded can't be taken back, so only fall back before that.
+                if (streamed) {
+                    throw e;
+                }
                 console.log(e);
+                lastError = e;
             }
         }
-        throw new Error("All fallbacks failed");
+        throw lastError;
     }
 }
 
@@ -1,4 +1,5 @@
 import "./anthropic_chat"
 import "./openai_chat"
 import "./openai_completion"
-import "./fallback"
\ No newline at end of file
+import "./fallback"
+import "./round_robin"
\ No newline at end of file
@@ -53,6 +53,24 @@ class OpenAIClient implements IClient {
 
         return message;
     }
+    async *run_chat_stream(prompt: ChatMessage | ChatMessage[]): AsyncIterable<string> {
+        const chats = Array.isArray(prompt) ? prompt : [prompt];
+        const stream = await this.client.chat.completions.create({
+            messages: chats.map((chat) => ({
+                role: chat.role as 'user',
+                content: chat.content,
+            })),
+            ...this.params,
+            stream: true,
+        });
+
+        for await (const chunk of stream) {
+            const delta = chunk.choices[0]?.delta?.content;
+            if (delta) {
+                yield delta;
+            }
+        }
+    }
     async run_prompt(prompt: string): Promise<string> {
         return await this.run_chat({
             role: "user",
@@ -77,6 +95,16 @@ class OpenAIClient implements IClient {
             content: prompt_template,
         }, templates);
     }
+    run_prompt_template_stream(prompt_template: string, templates: { [key: string]: string; }): AsyncIterable<string> {
+        Object.entries(templates).forEach(([key, value]) => {
+            prompt_template = prompt_template.replaceAll(key, value);
+        });
+
+        return this.run_chat_stream({
+            role: "user",
+            content: prompt_template,
+        });
+    }
 }
 
 clientManager.registerProvider("baml-openai-chat", {
@@ -53,6 +53,20 @@ class OpenAIClient implements IClient {
 
         return message;
     }
+    async *run_prompt_stream(prompt: string): AsyncIterable<string> {
+        const stream = await this.client.completions.create({
+            prompt,
+            ...this.params,
+            stream: true,
+        });
+
+        for await (const chunk of stream) {
+            const delta = chunk.choices[0]?.text;
+            if (delta) {
+                yield delta;
+            }
+        }
+    }
 
     async run_chat_template(prompt_template: ChatMessage | ChatMessage[], templates: { [key: string]: string; }): Promise<string> {
         const chats = Array.isArray(prompt_template) ? prompt_template : [prompt_template];
@@ -72,6 +86,13 @@ class OpenAIClient implements IClient {
 
         return this.run_prompt(prompt_template);
     }
+    run_prompt_template_stream(prompt_template: string, templates: { [key: string]: string; }): AsyncIterable<string> {
+        Object.entries(templates).forEach(([key, value]) => {
+            prompt_template = prompt_template.replaceAll(key, value);
+        });
+
+        return this.run_prompt_stream(prompt_template);
+    }
 }
 
 clientManager.registerProvider("baml-openai-completion", {
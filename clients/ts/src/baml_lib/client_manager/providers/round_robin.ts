@@ -0,0 +1,40 @@
+import { ChatMessage, clientManager, IClient } from "../client_manager";
+
+class RoundRobinClient implements IClient {
+    private clientNames: string[];
+    private next: number;
+
+    constructor(params: { [key: string]: any }) {
+        this.clientNames = params.strategy;
+        this.next = 0;
+    }
+
+    private nextClient(): IClient {
+        const name = this.clientNames[this.next];
+        this.next = (this.next + 1) % this.clientNames.length;
+        return clientManager.getClient(name);
+    }
+
+    async run_chat(prompt: ChatMessage | ChatMessage[]): Promise<string> {
+        return await this.nextClient().run_chat(prompt);
+    }
+    async run_prompt(prompt: string): Promise<string> {
+        return await this.nextClient().run_prompt(prompt);
+    }
+
+    async run_chat_template(prompt_template: ChatMessage | ChatMessage[], templates: { [key: string]: string; }): Promise<string> {
+        return await this.nextClient().run_chat_template(prompt_template, templates);
+    }
+    async run_prompt_template(prompt_template: string, templates: { [key: string]: string; }): Promise<string> {
+        return await this.nextClient().run_prompt_template(prompt_template, templates);
+    }
+    run_prompt_template_stream(prompt_template: string, templates: { [key: string]: string; }): AsyncIterable<string> {
+        return this.nextClient().run_prompt_template_stream(prompt_template, templates);
+    }
+}
+
+clientManager.registerProvider("baml-round-robin", {
+    createClient: (name: string, options: { [key: string]: any }): IClient => {
+        return new RoundRobinClient(options);
+    },
+});
@@ -1,11 +1,12 @@
 import { JSONSchema7, JSONSchema7Definition } from "json-schema";
 import { BaseDeserializer } from "./base_deserializer";
-import { NoneDeserializer, PrimitiveDeserializer } from "./primitive_deserializer";
+import { LiteralDeserializer, NoneDeserializer, PrimitiveDeserializer } from "./primitive_deserializer";
 import { EnumDeserializer } from "./enum_deserializer";
 import { ObjectDeserializer } from "./object_deserializer";
 import { fromValue } from "./raw_wrapper/loader";
 import { Diagnostics } from "./diagnostics";
 import { ListDeserializer, UnionDeserializer } from "./complex_deserializer";
+import { completePartialJson } from "./partial_json";
 
 const DefaultDeserializerLUT = {
     None: new NoneDeserializer(),
@@ -37,7 +38,11 @@ const registerObjectDeserializer = <T extends Record<string, any>>(schema: JSONS
 class Deserializer<T> {
     private overrides: Map<string, EnumDeserializer<any> | ObjectDeserializer<any>> = new Map();
 
-    constructor(private schema: JSONSchema7, private target: JSONSchema7) {
+    /**
+     * With `partial`, `coerce` takes responses that may be cut off, e.g. because they are still
+     * streaming in: their JSON is closed where it was cut off, and objects may miss fields.
+     */
+    constructor(private schema: JSONSchema7, private target: JSONSchema7, private partial: boolean = false) {
     }
 
     overload(name: string, aliases: Record<string, string>) {
@@ -80,6 +85,14 @@ class Deserializer<T> {
             throw new Error(`Boolean types are not supported`);
         }
         const t = this.getInterface(_t);
+        // Only enums are registered, so any other `const` or `enum` is a literal type.
+        const isEnum = t.title !== undefined && GeneratedDeserializerLUT.has(t.title);
+        if (t.const !== undefined || (t.enum !== undefined && !isEnum)) {
+            const values = t.const !== undefined ? [t.const] : t.enum!;
+            return new LiteralDeserializer(values.filter(
+                (v): v is string | number | boolean => ["string", "number", "boolean"].includes(typeof v)
+            ));
+        }
         if (t.type === "object" || t.enum) {
             const name = t.title;
             if (name === undefined) {
@@ -89,6 +102,9 @@ class Deserializer<T> {
             if (deserializer === undefined) {
                 throw new Error(`Deserializer for ${name} not found`);
             }
+            if (this.partial && deserializer instanceof ObjectDeserializer) {
+                return deserializer.as_partial();
+            }
             return deserializer;
         } else if (t.type === "string") {
             return DefaultDeserializerLUT.string;
@@ -122,9 +138,19 @@ class Deserializer<T> {
     
     }
 
+    private complete_json(value: string): string {
+        const target = this.getInterface(this.target);
+        if (target.type === "object") {
+            return completePartialJson(value, "{");
+        } else if (target.type === "array") {
+            return completePartialJson(value, "[");
+        }
+        return value;
+    }
+
     coerce(value: string): T {
         const d = new Diagnostics(value);
-        const raw = fromValue(value, d);
+        const raw = fromValue(this.partial ? this.complete_json(value) : value, d);
         
         const deserializer = this.get_deserializer(this.target);
         const response = deserializer.coerce(raw, d, this.get_deserializer.bind(this));
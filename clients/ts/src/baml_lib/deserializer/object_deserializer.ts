@@ -45,6 +45,11 @@ class ObjectDeserializer<T extends Record<string, any>> extends BaseDeserializer
         return new ObjectDeserializer(this.name, this.fields, this.required_fields, aliases);
     }
 
+    // Responses that are cut off may miss any field.
+    as_partial(): ObjectDeserializer<T> {
+        return new ObjectDeserializer(this.name, this.fields, [], Object.fromEntries(this.aliases));
+    }
+
     coerce(raw: RawWrapper, diagnostics: Diagnostics, fromLut: CheckLutFn<any>): Result<T> {
         diagnostics.pushScope(this.name);
         const result: any = {};
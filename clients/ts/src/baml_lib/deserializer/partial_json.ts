@@ -0,0 +1,167 @@
+/**
+ * Parses JSON that may be cut off, e.g. because the response is still streaming in.
+ *
+ * Ported from partialjson (https://github.com/iw4p/partialjson).
+ * Copyright (c) 2023 Nima Akbarzadeh. MIT License.
+ */
+
+type Parsed = [any, string];
+
+const parseAny = (s: string): Parsed => {
+    s = s.trimStart();
+    if (s.length === 0) {
+        throw new SyntaxError("Unexpected end of JSON input");
+    }
+    switch (s[0]) {
+        case "[":
+            return parseArray(s);
+        case "{":
+            return parseObject(s);
+        case '"':
+            return parseString(s);
+        case "t":
+            return parseLiteral(s, "true", true);
+        case "f":
+            return parseLiteral(s, "false", false);
+        case "n":
+            return parseLiteral(s, "null", null);
+        default:
+            if ("0123456789-".includes(s[0])) {
+                return parseNumber(s);
+            }
+            throw new SyntaxError(`Unexpected token ${s[0]} in JSON`);
+    }
+};
+
+const parseArray = (s: string): Parsed => {
+    const acc: any[] = [];
+    s = s.slice(1).trimStart();
+    while (s.length > 0 && s[0] !== "]") {
+        const [value, rest] = parseAny(s);
+        acc.push(value);
+        s = rest.trimStart();
+        if (s.startsWith(",")) {
+            s = s.slice(1).trimStart();
+        }
+    }
+    return [acc, s.slice(1)];
+};
+
+const parseObject = (s: string): Parsed => {
+    const acc: { [key: string]: any } = {};
+    s = s.slice(1).trimStart();
+    while (s.length > 0 && s[0] !== "}") {
+        if (s[0] !== '"') {
+            throw new SyntaxError(`Unexpected token ${s[0]} in JSON`);
+        }
+        const [key, afterKey] = parseString(s);
+        s = afterKey.trimStart();
+        // A key whose value hasn't started yet is left out.
+        if (s.length === 0) {
+            break;
+        }
+        if (s[0] !== ":") {
+            throw new SyntaxError(`Expected ':' after key ${key} in JSON`);
+        }
+        s = s.slice(1).trimStart();
+        if (s.length === 0) {
+            break;
+        }
+        const [value, rest] = parseAny(s);
+        acc[key] = value;
+        s = rest.trimStart();
+        if (s.startsWith(",")) {
+            s = s.slice(1).trimStart();
+        }
+    }
+    return [acc, s.slice(1)];
+};
+
+const parseString = (s: string): Parsed => {
+    let end = 1;
+    while (end < s.length && s[end] !== '"') {
+        end += s[end] === "\\" ? 2 : 1;
+    }
+    if (end < s.length) {
+        return [JSON.parse(s.slice(0, end + 1)), s.slice(end + 1)];
+    }
+    // Close the string, without the escape sequence it may have been cut off in.
+    const body = end > s.length ? s.slice(1, -1) : s.slice(1).replace(/\\u[0-9a-fA-F]{0,3}$/, "");
+    return [JSON.parse(`"${body}"`), ""];
+};
+
+const parseNumber = (s: string): Parsed => {
+    const num = /^[-+0-9.eE]+/.exec(s)![0];
+    const rest = s.slice(num.length);
+    const value = Number(num);
+    if (Number.isNaN(value)) {
+        // A number cut off before its first digit, e.g. `-`.
+        if (rest.length === 0) {
+            return [null, ""];
+        }
+        throw new SyntaxError(`Invalid number ${num} in JSON`);
+    }
+    return [value, rest];
+};
+
+const parseLiteral = (s: string, literal: string, value: boolean | null): Parsed => {
+    if (s.startsWith(literal)) {
+        return [value, s.slice(literal.length)];
+    }
+    if (literal.startsWith(s)) {
+        return [value, ""];
+    }
+    throw new SyntaxError(`Unexpected token ${s[0]} in JSON`);
+};
+
+/**
+ * Parses `s`, closing the strings, arrays and objects it leaves open. A key whose value hasn't
+ * started yet is left out.
+ */
+const parsePartialJson = (s: string): any => {
+    try {
+        return JSON.parse(s);
+    } catch {
+        return parseAny(s)[0];
+    }
+};
+
+/**
+ * The first JSON object or array of `text` (whichever `open` starts), as JSON that is closed
+ * where the text was cut off. `text` is returned as is if it has none.
+ */
+const completePartialJson = (text: string, open: "{" | "["): string => {
+    const close = open === "{" ? "}" : "]";
+    const start = text.indexOf(open);
+    if (start < 0) {
+        return text;
+    }
+
+    // Brackets inside strings don't count.
+    let depth = 0;
+    let inString = false;
+    let escaped = false;
+    let end = text.length;
+    for (let i = start; i < text.length; i++) {
+        const c = text[i];
+        if (inString) {
+            if (escaped) {
+                escaped = false;
+            } else if (c === "\\") {
+                escaped = true;
+            } else if (c === '"') {
+                inString = false;
+            }
+        } else if (c === '"') {
+            inString = true;
+        } else if (c === open) {
+            depth++;
+        } else if (c === close && --depth === 0) {
+            end = i + 1;
+            break;
+        }
+    }
+    return JSON.stringify(parsePartialJson(text.slice(start, end)));
+};
+
+export { parsePartialJson, completePartialJson };
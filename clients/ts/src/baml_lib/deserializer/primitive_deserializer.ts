@@ -34,4 +34,34 @@ class NoneDeserializer extends BaseDeserializer<null> {
     }
 }
 
-export { NoneDeserializer, PrimitiveDeserializer };
\ No newline at end of file
+type Literal = string | number | boolean;
+
+class LiteralDeserializer extends BaseDeserializer<Literal> {
+    constructor(private readonly values: Literal[]) {
+        super(5);
+    }
+
+    coerce(raw: RawWrapper, diagnostics: Diagnostics, fromLut: CheckLutFn<Literal>): Result<Literal> {
+        for (const value of this.values) {
+            if (typeof value === "boolean") {
+                if (raw.as_bool() === value) {
+                    return Result.from_value(value);
+                }
+            } else if (typeof value === "number") {
+                if (raw.as_int() === value) {
+                    return Result.from_value(value);
+                }
+            } else {
+                // Strings may differ in case.
+                const parsed = raw.as_smart_str(false);
+                if (parsed !== undefined && parsed.trim().toLowerCase() === value.toLowerCase()) {
+                    return Result.from_value(value);
+                }
+            }
+        }
+        diagnostics.pushUnknownError(`Expected one of: ${this.values.map(v => JSON.stringify(v)).join(", ")}`);
+        return Result.failed();
+    }
+}
+
+export { LiteralDeserializer, NoneDeserializer, PrimitiveDeserializer };
\ No newline at end of file
@@ -0,0 +1,29 @@
+/**
+ * Whether the condition of an `{#if}` block is set: it's unset if it's null,
+ * false, or an empty string, list or map.
+ */
+const promptIsSet = (value: unknown): boolean => {
+    if (value === null || value === undefined || value === false) {
+        return false;
+    }
+    if (typeof value === 'string' || Array.isArray(value)) {
+        return value.length > 0;
+    }
+    if (typeof value === 'object') {
+        return Object.keys(value).length > 0;
+    }
+    return true;
+};
+
+/**
+ * The items a `{#for}` block loops over: the items of a list, or the values
+ * of a map.
+ */
+const promptItems = <T>(value: T[] | { [key: string]: T } | null | undefined): T[] => {
+    if (value === null || value === undefined) {
+        return [];
+    }
+    return Array.isArray(value) ? value : Object.values(value);
+};
+
+export { promptIsSet, promptItems };
@@ -0,0 +1,68 @@
+import { Deserializer } from "../deserializer/deserializer";
+
+/** A delta of a streamed response, and what the response so far parses to. */
+interface PartialValue<PartialT> {
+    delta: string;
+    /** Whether the response so far could be parsed, e.g. not if it's cut off inside an enum. */
+    is_parseable: boolean;
+    parsed: PartialT | null;
+}
+
+/**
+ * The response of a function as the model generates it. Iterating over it yields the partial
+ * result after every delta, and `getFinalResponse` waits for the whole result. The request is
+ * only sent once either is used.
+ */
+class BamlStream<T, PartialT> implements AsyncIterable<PartialValue<PartialT>> {
+    private deltas: AsyncIterator<string> | undefined = undefined;
+    private text = "";
+    private done = false;
+
+    constructor(
+        private readonly stream: () => AsyncIterable<string>,
+        private readonly partialDeserializer: Deserializer<PartialT>,
+        private readonly deserializer: Deserializer<T>
+    ) {
+    }
+
+    /** The next delta of the response, or undefined once it's complete. */
+    private async next(): Promise<string | undefined> {
+        if (this.done) {
+            return undefined;
+        }
+        if (this.deltas === undefined) {
+            this.deltas = this.stream()[Symbol.asyncIterator]();
+        }
+        const delta = await this.deltas.next();
+        if (delta.done) {
+            this.done = true;
+            return undefined;
+        }
+        this.text += delta.value;
+        return delta.value;
+    }
+
+    private parsePartial(delta: string): PartialValue<PartialT> {
+        try {
+            return { delta, is_parseable: true, parsed: this.partialDeserializer.coerce(this.text) };
+        } catch {
+            return { delta, is_parseable: false, parsed: null };
+        }
+    }
+
+    async *[Symbol.asyncIterator](): AsyncIterator<PartialValue<PartialT>> {
+        for (let delta = await this.next(); delta !== undefined; delta = await this.next()) {
+            yield this.parsePartial(delta);
+        }
+    }
+
+    /** The whole result, once the rest of the response has arrived. */
+    async getFinalResponse(): Promise<T> {
+        while (!this.done) {
+            await this.next();
+        }
+        return this.deserializer.coerce(this.text);
+    }
+}
+
+export { BamlStream, PartialValue };
@@ -121,6 +121,25 @@ registerObjectDeserializer({
     required: ["foo"]
 }, {})
 
+describe("Literal Deserializer", () => {
+    const schema: JSONSchema7 = { definitions: {} };
+
+    test("literal_from_string", () => {
+        const deserializer = new Deserializer<"ok" | "error">(schema, { type: "string", enum: ["ok", "error"] });
+        expect(deserializer.coerce("\"Error\"")).toBe("error");
+    });
+
+    test("literal_missing", () => {
+        const deserializer = new Deserializer<"ok" | "error">(schema, { type: "string", enum: ["ok", "error"] });
+        expect(() => deserializer.coerce("pending")).toThrow();
+    });
+
+    test("literal_int_and_bool", () => {
+        expect(new Deserializer<1>(schema, { type: "integer", const: 1 }).coerce("1")).toBe(1);
+        expect(new Deserializer<true>(schema, { type: "boolean", const: true }).coerce("true")).toBe(true);
+    });
+});
+
 describe("Object Deserializer", () => {
     const schema: JSONSchema7 = {
         definitions: {
@@ -0,0 +1,106 @@
+import { ChatMessage, clientManager, IClient } from "../src/baml_lib/client_manager/client_manager";
+import "../src/baml_lib/client_manager/providers/fallback";
+
+class StatusError extends Error {
+    constructor(public status: number) {
+        super(`Request failed with status ${status}`);
+    }
+}
+
+// Answers with its own name, or fails with `status` when one is given.
+class FakeClient implements IClient {
+    calls = 0;
+
+    constructor(private name: string, private status?: number) { }
+
+    private async answer(): Promise<string> {
+        this.calls += 1;
+        if (this.status !== undefined) {
+            throw new StatusError(this.status);
+        }
+        return this.name;
+    }
+
+    run_chat(_prompt: ChatMessage | ChatMessage[]): Promise<string> {
+        return this.answer();
+    }
+    run_prompt(_prompt: string): Promise<string> {
+        return this.answer();
+    }
+    run_chat_template(_prompt_template: ChatMessage | ChatMessage[], _templates: { [key: string]: string; }): Promise<string> {
+        return this.answer();
+    }
+    run_prompt_template(_prompt_template: string, _templates: { [key: string]: string; }): Promise<string> {
+        return this.answer();
+    }
+    async *run_prompt_template_stream(_prompt_template: string, _templates: { [key: string]: string; }): AsyncIterable<string> {
+        yield await this.answer();
+    }
+}
+
+const fakes: { [name: string]: FakeClient } = {};
+
+clientManager.registerProvider("test-fake", {
+    createClient: (name: string, options: { [key: string]: any }): IClient => {
+        fakes[name] = new FakeClient(name, options.status);
+        return fakes[name];
+    },
+});
+
+clientManager.createClient("RateLimited", "test-fake", { status: 429 });
+clientManager.createClient("Broken", "test-fake", { status: 500 });
+clientManager.createClient("Backup", "test-fake", {});
+clientManager.createClient("Spare", "test-fake", {});
+
+beforeEach(() => {
+    Object.values(fakes).forEach((fake) => fake.calls = 0);
+});
+
+describe("Fallback Client", () => {
+    test("falls_back_by_name", async () => {
+        const client = clientManager.createClient("ByName", "baml-fallback", {
+            strategy: ["Broken", "Backup"],
+        });
+        expect(await client.run_prompt("hi")).toBe("Backup");
+    });
+
+    test("falls_back_on_listed_status_code", async () => {
+        const client = clientManager.createClient("OnRateLimit", "baml-fallback", {
+            strategy: ["RateLimited", { client: "Backup", on_status_code: [429] }],
+        });
+        expect(await client.run_chat({ role: "user", content: "hi" })).toBe("Backup");
+    });
+
+    test("skips_clients_for_other_status_codes", async () => {
+        const client = clientManager.createClient("OnOtherCode", "baml-fallback", {
+            strategy: ["Broken", { client: "Backup", on_status_code: [429] }, { client: "Spare" }],
+        });
+        expect(await client.run_prompt_template("hi", {})).toBe("Spare");
+        expect(fakes["Backup"].calls).toBe(0);
+    });
+
+    test("rethrows_the_last_error", async () => {
+        const client = clientManager.createClient("NoMatch", "baml-fallback", {
+            strategy: ["Broken", { client: "Backup", on_status_code: [429], retry_policy: "Quick" }],
+        });
+        await expect(client.run_prompt("hi")).rejects.toMatchObject({ status: 500 });
+        expect(fakes["Backup"].calls).toBe(0);
+    });
+
+    test("streams_from_the_fallback", async () => {
+        const client = clientManager.createClient("Streamed", "baml-fallback", {
+            strategy: ["RateLimited", { client: "Backup", on_status_code: [429, 503] }],
+        });
+        const deltas: string[] = [];
+        for await (const delta of client.run_prompt_template_stream("hi", {})) {
+            deltas.push(delta);
+        }
+        expect(deltas).toEqual(["Backup"]);
+    });
+
+    test("rejects_unknown_strategy_items", () => {
+        expect(() => clientManager.createClient("Invalid", "baml-fallback", {
+            strategy: [{ on_status_code: [429] }],
+        })).toThrow();
+    });
+});
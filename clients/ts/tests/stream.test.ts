@@ -0,0 +1,133 @@
+import { JSONSchema7 } from "json-schema";
+import { Deserializer, registerObjectDeserializer } from "../src/baml_lib/deserializer/deserializer";
+import { completePartialJson } from "../src/baml_lib/deserializer/partial_json";
+import { BamlStream } from "../src/baml_lib/stream/baml_stream";
+
+describe("Partial JSON", () => {
+    test("closes_cut_off_json", () => {
+        expect(completePartialJson('{"a": "he', "{")).toBe('{"a":"he"}');
+        expect(completePartialJson('{"a": [1, 2', "{")).toBe('{"a":[1,2]}');
+        expect(completePartialJson('[{"a": tr', "[")).toBe('[{"a":true}]');
+    });
+
+    test("leaves_out_keys_without_values", () => {
+        expect(completePartialJson('{"a": 1, "b', "{")).toBe('{"a":1}');
+        expect(completePartialJson('{"a": 1, "b": ', "{")).toBe('{"a":1}');
+    });
+
+    test("takes_the_first_json_of_the_text", () => {
+        expect(completePartialJson('Sure! {"a": {"b": 1}} and {"c": 2}', "{")).toBe('{"a":{"b":1}}');
+        expect(completePartialJson("No JSON yet", "{")).toBe("No JSON yet");
+    });
+
+    test("ignores_brackets_inside_strings", () => {
+        expect(completePartialJson('{"answer": "close with }", "score": 2} done', "{")).toBe('{"answer":"close with }","score":2}');
+        expect(completePartialJson('[{"t": "a ] b", "d": true}, {"t": "c"}] and [1]', "[")).toBe('[{"t":"a ] b","d":true},{"t":"c"}]');
+        expect(completePartialJson('{"a": "say \\"}\\" now", "b": [1', "{")).toBe('{"a":"say \\"}\\" now","b":[1]}');
+    });
+});
+
+interface Step {
+    title: string;
+    done: boolean;
+}
+
+interface PartialStep {
+    title: string | null;
+    done: boolean | null;
+}
+
+registerObjectDeserializer({
+    title: "Step",
+    type: "object",
+    properties: {
+        title: {
+            type: "string"
+        },
+        done: {
+            type: "boolean"
+        }
+    },
+    required: ["title", "done"]
+}, {})
+
+const schema: JSONSchema7 = {
+    definitions: {
+        Step: {
+            title: "Step",
+            type: "object",
+            properties: {
+                title: {
+                    type: "string"
+                },
+                done: {
+                    type: "boolean"
+                }
+            },
+            required: ["title", "done"]
+        }
+    }
+};
+const target: JSONSchema7 = { type: "array", items: { $ref: "#/definitions/Step" } };
+
+describe("Partial Deserializer", () => {
+    test("fields_may_be_missing", () => {
+        const deserializer = new Deserializer<PartialStep[]>(schema, target, true);
+        expect(deserializer.coerce('Steps: [{"title": "Shop", "done": true}, {"title": "Co')).toEqual([
+            { title: "Shop", done: true },
+            { title: "Co", done: null },
+        ]);
+    });
+
+    test("complete_deserializer_needs_fields", () => {
+        const deserializer = new Deserializer<Step>(schema, { $ref: "#/definitions/Step" });
+        expect(() => deserializer.coerce('{"title": "Co"}')).toThrow();
+    });
+});
+
+async function* deltas(...chunks: string[]): AsyncIterable<string> {
+    for (const chunk of chunks) {
+        yield chunk;
+    }
+}
+
+describe("BamlStream", () => {
+    const stream = (...chunks: string[]) => new BamlStream<Step[], PartialStep[]>(
+        () => deltas(...chunks),
+        new Deserializer<PartialStep[]>(schema, target, true),
+        new Deserializer<Step[]>(schema, target)
+    );
+
+    test("yields_partial_results", async () => {
+        const partials = [];
+        for await (const partial of stream('[{"title": "Sh', 'op", "done": false}', ', {"title": "Cook", "done": true}]')) {
+            partials.push(partial);
+        }
+        expect(partials.map(p => p.delta)).toEqual(['[{"title": "Sh', 'op", "done": false}', ', {"title": "Cook", "done": true}]']);
+        expect(partials.map(p => p.parsed)).toEqual([
+            [{ title: "Sh", done: null }],
+            [{ title: "Shop", done: false }],
+            [{ title: "Shop", done: false }, { title: "Cook", done: true }],
+        ]);
+    });
+
+    test("brackets_inside_strings_do_not_end_the_json", async () => {
+        const partials = [];
+        for await (const partial of stream('[{"title": "a ] b", "done": true}, {"title": "c', '"}] or {maybe}')) {
+            partials.push(partial);
+        }
+        expect(partials.map(p => p.parsed)).toEqual([
+            [{ title: "a ] b", done: true }, { title: "c", done: null }],
+            [{ title: "a ] b", done: true }, { title: "c", done: null }],
+        ]);
+    });
+
+    test("final_response_waits_for_the_rest", async () => {
+        const s = stream('[{"title": "Shop", ', '"done": false}]');
+        for await (const partial of s) {
+            expect(partial.parsed).toEqual([{ title: "Shop", done: null }]);
+            break;
+        }
+        expect(await s.getFinalResponse()).toEqual([{ title: "Shop", done: false }]);
+    });
+});
@@ -8,9 +8,9 @@ use baml_lib::{
 use colored::*;
 use log::info;
 
-use crate::{builder::dir_utils::get_src_files, errors::CliError, update::version_check};
+use crate::{errors::CliError, update::version_check};
 
-pub(crate) use crate::builder::dir_utils::{get_baml_src, get_src_dir};
+pub(crate) use crate::builder::dir_utils::{get_baml_src, get_src_dir, get_src_files};
 
 pub fn build(
     baml_dir: &Option<String>,
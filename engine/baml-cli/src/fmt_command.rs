@@ -0,0 +1,71 @@
+use baml_lib::internal_baml_schema_ast::{ast::IndentationType, reformat};
+use colored::Colorize;
+use log::info;
+
+use crate::{
+    builder::{get_baml_src, get_src_files},
+    errors::CliError,
+};
+
+pub fn run(baml_dir: &Option<String>, check: bool) -> Result<(), CliError> {
+    let baml_dir = get_baml_src(baml_dir)?;
+
+    let mut unparsable = Vec::new();
+    let mut unformatted = Vec::new();
+
+    for path in get_src_files(&baml_dir)? {
+        if path.extension().and_then(|ext| ext.to_str()) != Some("baml") {
+            continue;
+        }
+
+        let contents = std::fs::read_to_string(&path).map_err(|e| (&path, e))?;
+        let formatted = match reformat(&contents, IndentationType::default()) {
+            Some(formatted) => formatted,
+            None => {
+                unparsable.push(path);
+                continue;
+            }
+        };
+
+        if formatted == contents {
+            continue;
+        }
+
+        if !check {
+            std::fs::write(&path, formatted).map_err(|e| (&path, e))?;
+            info!("Formatted {}", path.to_string_lossy().green());
+        }
+        unformatted.push(path);
+    }
+
+    if !unparsable.is_empty() {
+        return Err(format!(
+            "Failed to parse, run `baml build` for details:\n{}",
+            unparsable
+                .iter()
+                .map(|p| format!("  {}", p.to_string_lossy()))
+                .collect::<Vec<_>>()
+                .join("\n")
+        )
+        .into());
+    }
+
+    if check && !unformatted.is_empty() {
+        return Err(format!(
+            "{} file(s) are not formatted:\n{}",
+            unformatted.len(),
+            unformatted
+                .iter()
+                .map(|p| format!("  {}", p.to_string_lossy().yellow()))
+                .collect::<Vec<_>>()
+                .join("\n")
+        )
+        .into());
+    }
+
+    if unformatted.is_empty() {
+        info!("All files are formatted");
+    }
+
+    Ok(())
+}
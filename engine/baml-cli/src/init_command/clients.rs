@@ -116,7 +116,7 @@ fn anthropic_clients<T: From<&'static str> + AsRef<str>>() -> Vec<ClientConfig<T
             provider: "baml-anthropic".into(),
             name: "Claude".into(),
             params: vec![
-                ("model_name", "claude-2.1"),
+                ("model", "claude-2.1"),
                 ("api_key", "env.ANTHROPIC_API_KEY"),
             ],
         },
@@ -125,7 +125,7 @@ fn anthropic_clients<T: From<&'static str> + AsRef<str>>() -> Vec<ClientConfig<T
             provider: "baml-anthropic".into(),
             name: "ClaudeInstant".into(),
             params: vec![
-                ("model_name", "claude-instant-1.2"),
+                ("model", "claude-instant-1.2"),
                 ("api_key", "env.ANTHROPIC_API_KEY"),
             ],
         },
@@ -5,8 +5,10 @@ use std::io::Write;
 mod builder;
 mod command;
 mod errors;
+mod fmt_command;
 mod import_command;
 mod init_command;
+mod prompt_command;
 mod shell;
 mod test_command;
 mod update;
@@ -39,6 +41,10 @@ enum Commands {
     Test(TestArgs),
     /// Imports content into a BAML project.
     Import(ImportArgs),
+    /// Formats the .baml files of a BAML project.
+    Fmt(FmtArgs),
+    /// Prints the prompt an impl sends to the model for the input of a test.
+    Prompt(PromptArgs),
 }
 
 #[derive(Args, Debug)]
@@ -79,6 +85,22 @@ pub struct TestArgs {
     /// Specifies a port for the test playground. Hidden from help text.
     #[arg(long, hide = true)]
     playground_port: Option<u16>,
+
+    /// Records the responses of the model to `__cassettes__`, for `--replay`.
+    #[arg(long, conflicts_with = "replay")]
+    record: bool,
+
+    /// Replays the responses recorded with `--record` instead of calling the model.
+    #[arg(long)]
+    replay: bool,
+
+    /// Writes the results of `run` to `--output` in this format.
+    #[arg(long, value_enum, requires = "output")]
+    reporter: Option<TestReporter>,
+
+    /// The file to write the report to.
+    #[arg(long, requires = "reporter")]
+    output: Option<std::path::PathBuf>,
 }
 
 impl fmt::Display for TestAction {
@@ -96,6 +118,14 @@ enum TestAction {
     List,
 }
 
+#[derive(ValueEnum, Debug, Clone, Copy)]
+enum TestReporter {
+    /// JUnit XML, as read by most CI systems.
+    Junit,
+    /// A JSON object with every test's result, prompt and output.
+    Json,
+}
+
 #[derive(Args, Debug)]
 struct ImportArgs {
     /// Optional: Specifies the directory of the BAML project to which the content will be imported.
@@ -107,6 +137,32 @@ struct ImportArgs {
     content: String,
 }
 
+#[derive(Args, Debug)]
+struct FmtArgs {
+    /// Optional: Specifies the directory of the BAML project to format.
+    #[arg(long)]
+    baml_dir: Option<String>,
+
+    /// Only checks whether the files are formatted, without writing any changes.
+    #[arg(long)]
+    check: bool,
+}
+
+#[derive(Args, Debug)]
+struct PromptArgs {
+    /// Optional: Specifies the directory of the BAML project.
+    #[arg(long)]
+    baml_dir: Option<String>,
+
+    /// The impl whose prompt to print, as `<Function>:<Impl>`.
+    #[arg()]
+    target: String,
+
+    /// The test of the function whose input fills in the prompt.
+    #[arg(long)]
+    test: String,
+}
+
 pub(crate) fn main() {
     const NAME: &str = concat!("[", env!("CARGO_PKG_NAME"), "]");
     env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info"))
@@ -140,6 +196,9 @@ pub(crate) fn main() {
                 import_command::run(&args.content, &baml_dir, &config, schema)
             })
         }
+        Commands::Fmt(args) => fmt_command::run(&args.baml_dir, args.check),
+        Commands::Prompt(args) => builder::build(&args.baml_dir)
+            .and_then(|(_, _, schema)| prompt_command::run(&args.target, &args.test, &schema)),
     };
 
     if let Err(error) = response {
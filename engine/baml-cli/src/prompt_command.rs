@@ -0,0 +1,32 @@
+use baml_lib::{internal_baml_parser_database::RenderedPrompt, ValidatedSchema};
+use colored::Colorize;
+
+use crate::errors::CliError;
+
+/// Prints the prompt `target` (`<Function>:<Impl>`) sends to the model for the input of `test`.
+pub fn run(target: &str, test: &str, schema: &ValidatedSchema) -> Result<(), CliError> {
+    let Some((function, r#impl)) = target.split_once(':') else {
+        return Err(format!("Expected `<Function>:<Impl>`, got `{}`", target).into());
+    };
+    let db = &schema.db;
+    let variant = db.find_variant_by_name(function, r#impl)?;
+    if variant.properties().input_adapter.is_some() {
+        return Err(format!(
+            "Impl `{}` has an input adapter, which only the generated clients can run, so its prompt can't be previewed.",
+            r#impl
+        )
+        .into());
+    }
+    let input = db.find_test_input(function, test)?;
+
+    match variant.render_prompt(&input) {
+        RenderedPrompt::Completion(prompt) => println!("{}", prompt),
+        RenderedPrompt::Chat(messages) => {
+            for message in messages {
+                println!("{}", format!("{}:", message.role).bold());
+                println!("{}\n", message.content.trim_end());
+            }
+        }
+    }
+    Ok(())
+}
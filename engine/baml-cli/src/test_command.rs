@@ -1,11 +1,23 @@
 use colored::*;
-use std::{path::PathBuf, str::FromStr};
+use std::{path::Path, str::FromStr};
 
 use baml_lib::{internal_baml_schema_ast::ast::WithName, Configuration, ValidatedSchema};
 
-use crate::{errors::CliError, test_command::test_state::RunState, TestAction, TestArgs};
+use crate::{
+    errors::CliError,
+    test_command::{
+        cassette::{CassetteMode, Cassettes},
+        report::Report,
+        test_state::RunState,
+    },
+    TestAction, TestArgs,
+};
 
+mod cassette;
+mod executor;
 mod ipc_comms;
+mod protocol;
+mod report;
 mod run_test_with_forward;
 mod run_test_with_watcher;
 mod run_tests;
@@ -87,7 +99,7 @@ fn matches_filters(
 
 pub fn run(
     command: &TestArgs,
-    _baml_dir: &PathBuf,
+    baml_dir: &Path,
     config: &Configuration,
     schema: ValidatedSchema,
 ) -> Result<(), CliError> {
@@ -115,8 +127,13 @@ pub fn run(
             let funcwalker = test_case.walk_function();
             let function = funcwalker.name();
             let test = test_case.name();
+            let impls = &test_case.test_case().impls;
             funcwalker
                 .walk_variants()
+                // Tests that name their impls only run against those.
+                .filter(|variant| {
+                    impls.is_empty() || impls.iter().any(|(name, _)| name == variant.name())
+                })
                 .filter_map(|variant| {
                     let r#impl = variant.name();
                     num_tests += 1;
@@ -141,7 +158,18 @@ pub fn run(
         x => x,
     });
 
-    let state = RunState::from_tests(schema.db, &selected_tests);
+    let cassettes = match (command.record, command.replay) {
+        (true, _) => Some(Cassettes::new(baml_dir, CassetteMode::Record)),
+        (_, true) => Some(Cassettes::new(baml_dir, CassetteMode::Replay)),
+        _ => None,
+    };
+    let mut state = RunState::from_tests(schema.db, &selected_tests);
+    if let Some(cassettes) = &cassettes {
+        state = state.with_cassettes(cassettes.clone());
+    }
+    if let (Some(reporter), Some(output)) = (command.reporter, &command.output) {
+        state = state.with_report(Report::new(reporter, output.clone()));
+    }
 
     // Print some information about the tests we are going to run
     let summary = format!(
@@ -164,6 +192,22 @@ pub fn run(
                 return Err("No generators are available".into());
             }
 
+            // Replaying must never fall back to calling the model.
+            if let Some(cassettes) = cassettes.filter(|c| c.mode() == CassetteMode::Replay) {
+                let missing = cassettes.missing(&selected_tests);
+                if !missing.is_empty() {
+                    return Err(format!(
+                        "No recorded responses for:\n{}\nRecord them with `baml test run --record`.",
+                        missing
+                            .iter()
+                            .map(|t| format!("  {}:{}:{}", t.function, t.r#impl, t.test))
+                            .collect::<Vec<_>>()
+                            .join("\n")
+                    )
+                    .into());
+                }
+            }
+
             // Find the selected generator
             let generator = &config
                 .generators
@@ -196,6 +240,7 @@ pub fn run(
             // Run the tests
             run_tests::run_tests(
                 state,
+                executor::Executor::for_language(&generator.language),
                 &test_dir,
                 &generator.test_command,
                 &selected_tests,
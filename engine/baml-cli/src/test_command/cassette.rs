@@ -0,0 +1,186 @@
+//! Recorded LLM responses, so tests can run without calling the model.
+//!
+//! `baml test run --record` saves the raw response of every test to
+//! `__cassettes__/<function>/<test>/<impl>.cassette`, next to `__tests__`, along with a hash of
+//! the rendered prompt and the client's options.
+//!
+//! `baml test run --replay` hands the cassettes to the executor through `BAML_TEST_CASSETTE_MODE`
+//! and `BAML_TEST_CASSETTE_DIR`. The executor returns the recorded `raw_output` instead of
+//! calling the model, and still reports the prompt it rendered, so any test whose prompt or client
+//! options changed since the recording fails.
+//!
+//! Cassettes hold JSON, but don't use the `.json` extension so they aren't loaded as test files.
+
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+
+use super::protocol::{Prompt, TestId};
+
+/// The directory cassettes are kept in, inside the BAML project.
+pub(crate) const CASSETTE_DIR: &str = "__cassettes__";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum CassetteMode {
+    Record,
+    Replay,
+}
+
+impl CassetteMode {
+    fn as_str(&self) -> &'static str {
+        match self {
+            CassetteMode::Record => "record",
+            CassetteMode::Replay => "replay",
+        }
+    }
+}
+
+/// One recorded response.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub(crate) struct Cassette {
+    #[serde(flatten)]
+    pub test: TestId,
+    pub prompt_hash: String,
+    pub prompt: Prompt,
+    #[serde(default)]
+    pub client: Option<Value>,
+    pub raw_output: String,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct Cassettes {
+    dir: PathBuf,
+    mode: CassetteMode,
+}
+
+impl Cassettes {
+    pub(crate) fn new(baml_dir: &Path, mode: CassetteMode) -> Self {
+        Self {
+            dir: baml_dir.join(CASSETTE_DIR),
+            mode,
+        }
+    }
+
+    pub(crate) fn mode(&self) -> CassetteMode {
+        self.mode
+    }
+
+    fn path(&self, test: &TestId) -> PathBuf {
+        self.dir
+            .join(&test.function)
+            .join(&test.test)
+            .join(format!("{}.cassette", test.r#impl))
+    }
+
+    /// Tells the executor where the cassettes are, and what to do with them.
+    pub(crate) fn configure(&self, cmd: &mut Command) {
+        cmd.env("BAML_TEST_CASSETTE_MODE", self.mode.as_str())
+            .env("BAML_TEST_CASSETTE_DIR", &self.dir);
+    }
+
+    /// The selected tests that have no cassette to replay.
+    pub(crate) fn missing(&self, tests: &[(String, String, String)]) -> Vec<TestId> {
+        tests
+            .iter()
+            .map(|(function, test, r#impl)| TestId {
+                function: function.clone(),
+                r#impl: r#impl.clone(),
+                test: test.clone(),
+            })
+            .filter(|test| !self.path(test).is_file())
+            .collect()
+    }
+
+    pub(crate) fn record(
+        &self,
+        test: &TestId,
+        prompt: &Prompt,
+        template_args: &HashMap<String, String>,
+        client: Option<&Value>,
+        raw_output: &str,
+    ) -> Result<(), String> {
+        let cassette = Cassette {
+            test: test.clone(),
+            prompt_hash: prompt_hash(prompt, template_args, client),
+            prompt: prompt.clone(),
+            client: client.cloned(),
+            raw_output: raw_output.to_string(),
+        };
+        let path = self.path(test);
+        let content = serde_json::to_string_pretty(&cassette).map_err(|e| e.to_string())?;
+        path.parent()
+            .map(fs::create_dir_all)
+            .transpose()
+            .and_then(|_| fs::write(&path, content + "\n"))
+            .map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+
+    /// Checks that a replayed test rendered the same prompt, with the same client options, as
+    /// when it was recorded.
+    pub(crate) fn check(
+        &self,
+        test: &TestId,
+        prompt: &Prompt,
+        template_args: &HashMap<String, String>,
+        client: Option<&Value>,
+    ) -> Result<(), String> {
+        let path = self.path(test);
+        let cassette = fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|content| {
+                serde_json::from_str::<Cassette>(&content).map_err(|e| e.to_string())
+            })
+            .map_err(|e| format!("Failed to read cassette {}: {}", path.display(), e))?;
+
+        if cassette.prompt_hash != prompt_hash(prompt, template_args, client) {
+            return Err(format!(
+                "The prompt or client options changed since {} was recorded. Re-record it with `baml test run --record`.",
+                path.display()
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// A hash of the prompt, with its template args filled in, and of the client's options.
+pub(crate) fn prompt_hash(
+    prompt: &Prompt,
+    template_args: &HashMap<String, String>,
+    client: Option<&Value>,
+) -> String {
+    let rendered = match prompt.render(template_args) {
+        Prompt::Completion(text) => json!(text),
+        Prompt::Chat(messages) => messages
+            .iter()
+            .map(|message| json!({ "role": message.role, "content": message.content }))
+            .collect(),
+    };
+
+    let mut hasher = Sha256::new();
+    hasher.update(json!({ "prompt": rendered, "client": client.map(sorted_keys) }).to_string());
+    format!("{:x}", hasher.finalize())
+}
+
+/// Sorts the keys of every object, so reordering client options doesn't change the hash.
+fn sorted_keys(value: &Value) -> Value {
+    match value {
+        Value::Object(entries) => {
+            let mut entries = entries.iter().collect::<Vec<_>>();
+            entries.sort_by_key(|(key, _)| *key);
+            entries
+                .into_iter()
+                .map(|(k, v)| (k.clone(), sorted_keys(v)))
+                .collect::<serde_json::Map<_, _>>()
+                .into()
+        }
+        Value::Array(items) => items.iter().map(sorted_keys).collect(),
+        _ => value.clone(),
+    }
+}
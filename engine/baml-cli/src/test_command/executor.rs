@@ -0,0 +1,592 @@
+use std::{path::Path, process::Command, sync::Arc};
+
+use baml_lib::GeneratorLanguage;
+use serde_json::json;
+use tokio::{
+    io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+    net::{TcpListener, TcpStream},
+    sync::Mutex,
+};
+
+use crate::{errors::CliError, shell::build_shell_command};
+
+use super::{
+    cassette::Cassettes,
+    ipc_comms,
+    protocol::{self, TestEvent},
+    test_state::RunState,
+};
+
+/// How `baml test` talks to a generator's `test_command`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Executor {
+    /// The pytest plugin shipped with the Python client, which sends its own messages separated
+    /// by `<END_MSG>`.
+    Pytest,
+    /// Any other runner, speaking the JSON-lines protocol in [`protocol`].
+    JsonLines,
+}
+
+impl Executor {
+    pub(crate) fn for_language(language: &GeneratorLanguage) -> Self {
+        match language {
+            GeneratorLanguage::Python => Executor::Pytest,
+            GeneratorLanguage::TypeScript | GeneratorLanguage::Rust => Executor::JsonLines,
+        }
+    }
+
+    /// Builds the command that runs the selected tests and reports to `port`.
+    pub(crate) fn command(
+        &self,
+        test_command: &str,
+        output_path: &Path,
+        selected_tests: &[(String, String, String)],
+        port: u16,
+        cassettes: Option<&Cassettes>,
+    ) -> Result<Command, CliError> {
+        let mut args = shellwords::split(test_command)
+            .map_err(|e| CliError::StringError(format!("Failed to parse test command: {}", e)))?;
+
+        match self {
+            Executor::Pytest => {
+                ["-v", "-s", "--color=yes", "baml_client", "-s", "--rootdir"]
+                    .iter()
+                    .for_each(|arg| args.push(arg.to_string()));
+
+                args.push(output_path.to_string_lossy().to_string());
+
+                selected_tests.iter().for_each(|(function, test, r#impl)| {
+                    args.push("--pytest-baml-include".into());
+                    args.push(format!("{}:{}:{}", function, r#impl, test));
+                });
+
+                args.push("--pytest-baml-ipc".into());
+                args.push(format!("{}", port));
+
+                let mut cmd = build_shell_command(args);
+                if let Some(cassettes) = cassettes {
+                    cassettes.configure(&mut cmd);
+                }
+                Ok(cmd)
+            }
+            Executor::JsonLines => {
+                let selection = selected_tests
+                    .iter()
+                    .map(|(function, test, r#impl)| {
+                        json!({ "function": function, "impl": r#impl, "test": test })
+                    })
+                    .collect::<Vec<_>>();
+
+                let mut cmd = build_shell_command(args);
+                cmd.env("BAML_TEST_PROTOCOL", protocol::PROTOCOL_VERSION)
+                    .env("BAML_TEST_PORT", port.to_string())
+                    .env("BAML_TEST_DIR", output_path)
+                    .env(
+                        "BAML_TEST_SELECTION",
+                        serde_json::Value::from(selection).to_string(),
+                    );
+                if let Some(cassettes) = cassettes {
+                    cassettes.configure(&mut cmd);
+                }
+                Ok(cmd)
+            }
+        }
+    }
+
+    fn parse_line(&self, line: &str) -> Result<Option<TestEvent>, String> {
+        match self {
+            Executor::Pytest => {
+                let message = line.trim_end().trim_end_matches("<END_MSG>");
+                if message.is_empty() {
+                    return Ok(None);
+                }
+                ipc_comms::handle_message(message)
+                    .map(|message| message.into_event())
+                    .ok_or_else(|| format!("Failed to parse message: {}", message))
+            }
+            Executor::JsonLines => protocol::parse_line(line)
+                .map_err(|e| format!("Failed to parse message: {}\n{}", e, line)),
+        }
+    }
+}
+
+async fn handle_connection(
+    stream: TcpStream,
+    executor: Executor,
+    state: Arc<Mutex<RunState>>,
+    forward_port: Option<u16>,
+) -> tokio::io::Result<()> {
+    let mut lines = BufReader::new(stream).lines();
+    while let Some(line) = lines.next_line().await? {
+        if let Some(port) = forward_port {
+            _ = forward_to_port(port, &format!("{}\n", line)).await;
+        }
+
+        match executor.parse_line(&line) {
+            Ok(Some(event)) => state.lock().await.add_event(event),
+            Ok(None) => {}
+            Err(e) => log::error!("{}", e),
+        }
+    }
+    Ok(())
+}
+
+/// Starts the server executors report to, and returns its port. Every message received is
+/// also forwarded as is to `forward_port`, if set.
+pub(crate) async fn listen(
+    executor: Executor,
+    state: Arc<Mutex<RunState>>,
+    forward_port: Option<u16>,
+) -> std::io::Result<u16> {
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let port = listener.local_addr()?.port();
+
+    tokio::spawn(async move {
+        loop {
+            let (socket, _) = listener.accept().await.unwrap();
+            let state = state.clone();
+            tokio::spawn(handle_connection(socket, executor, state, forward_port));
+        }
+    });
+
+    Ok(port)
+}
+
+async fn forward_to_port(port: u16, message: &str) -> tokio::io::Result<()> {
+    const HOST: &str = "127.0.0.1";
+    // Forward message to the port.
+    let mut stream = TcpStream::connect(format!("{}:{}", HOST, port)).await?;
+    stream.write_all(message.as_bytes()).await
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::Write,
+        net::TcpStream,
+        path::PathBuf,
+        process::ExitStatus,
+        sync::Arc,
+        time::{Duration, Instant},
+    };
+
+    use baml_lib::internal_baml_parser_database::ParserDatabase;
+    use serde_json::{json, Value};
+    use tokio::sync::Mutex;
+
+    use super::{listen, Executor};
+    use crate::test_command::{
+        cassette::{CassetteMode, Cassettes},
+        protocol::TestId,
+        report::Report,
+        test_state::{RunState, TestOutcome},
+    };
+    use crate::TestReporter;
+
+    /// Not a test on its own: the executor `driver_runs_a_json_lines_executor` spawns. It passes
+    /// the first selected test, fails the second, and cancels the rest.
+    #[test]
+    fn fake_executor() {
+        let Ok(port) = std::env::var("BAML_TEST_PORT") else {
+            return;
+        };
+        assert_eq!(std::env::var("BAML_TEST_PROTOCOL").unwrap(), "jsonl-v1");
+        assert!(std::env::var("BAML_TEST_DIR")
+            .unwrap()
+            .ends_with("baml_client"));
+        let selection: Vec<Value> =
+            serde_json::from_str(&std::env::var("BAML_TEST_SELECTION").unwrap()).unwrap();
+
+        let mut stream = TcpStream::connect(format!("127.0.0.1:{}", port)).unwrap();
+        let mut send = |event: Value| writeln!(stream, "{}", event).unwrap();
+
+        send(json!({ "event": "run_started", "dashboard_url": null }));
+        send(json!({ "event": "a_future_event" }));
+        for (idx, test) in selection.iter().enumerate() {
+            let mut event = test.clone();
+            event["event"] = "test_started".into();
+            send(event.clone());
+
+            event["event"] = "test_finished".into();
+            event["status"] = match idx {
+                0 => "passed",
+                1 => "failed",
+                _ => "cancelled",
+            }
+            .into();
+            send(event.clone());
+
+            event["event"] = "test_output".into();
+            event["prompt"] = json!([{ "role": "user", "content": "Hi {name}" }]);
+            event["template_args"] = json!({ "{name}": "Bob" });
+            event["raw_output"] = "Hello".into();
+            send(event);
+        }
+    }
+
+    fn test_id(function: &str, r#impl: &str, test: &str) -> TestId {
+        TestId {
+            function: function.into(),
+            r#impl: r#impl.into(),
+            test: test.into(),
+        }
+    }
+
+    /// Runs one of the fake executors above, and handles its events until `done` holds. Returns
+    /// its exit status, and everything printed for the tests.
+    fn run_fake_executor(
+        fake: &str,
+        state: &Arc<Mutex<RunState>>,
+        selected_tests: &[(String, String, String)],
+        env: &[(&str, &str)],
+        done: impl Fn(&RunState, &str) -> bool,
+    ) -> (ExitStatus, String) {
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        rt.block_on(async {
+            let port = listen(Executor::JsonLines, state.clone(), None)
+                .await
+                .unwrap();
+
+            let test_command = format!(
+                "{} test_command::executor::tests::{} --exact --nocapture",
+                std::env::current_exe().unwrap().display(),
+                fake
+            );
+            let cassettes = state.lock().await.cassettes().cloned();
+            let mut cmd = Executor::JsonLines
+                .command(
+                    &test_command,
+                    &PathBuf::from("/project/baml_client"),
+                    selected_tests,
+                    port,
+                    cassettes.as_ref(),
+                )
+                .unwrap();
+            cmd.envs(env.iter().copied());
+            let status = cmd.status().unwrap();
+
+            // Events are handled as they arrive, which may be after the executor exits.
+            let deadline = Instant::now() + Duration::from_secs(10);
+            let mut logs = String::new();
+            loop {
+                let mut state = state.lock().await;
+                if let Some(log) = state.sync() {
+                    logs += &log;
+                }
+                if done(&state, &logs) || Instant::now() > deadline {
+                    break;
+                }
+                drop(state);
+                tokio::time::sleep(Duration::from_millis(20)).await;
+            }
+            (status, logs)
+        })
+    }
+
+    #[test]
+    fn driver_runs_a_json_lines_executor() {
+        let selected_tests = vec![
+            ("Greet".to_string(), "hello".to_string(), "v1".to_string()),
+            ("Greet".to_string(), "hello".to_string(), "v2".to_string()),
+            ("Greet".to_string(), "bye".to_string(), "v1".to_string()),
+        ];
+        let state = Arc::new(Mutex::new(RunState::from_tests(
+            ParserDatabase::new(),
+            &selected_tests,
+        )));
+
+        let (status, _) =
+            run_fake_executor("fake_executor", &state, &selected_tests, &[], |state, _| {
+                [("hello", "v1"), ("hello", "v2"), ("bye", "v1")]
+                    .iter()
+                    .all(|(test, r#impl)| {
+                        !matches!(
+                            state.outcome(&test_id("Greet", r#impl, test)),
+                            Some(TestOutcome::Queued | TestOutcome::Running)
+                        )
+                    })
+            });
+        assert!(status.success());
+
+        let state = state.blocking_lock();
+        assert_eq!(
+            state.outcome(&test_id("Greet", "v1", "hello")),
+            Some(TestOutcome::Passed)
+        );
+        assert_eq!(
+            state.outcome(&test_id("Greet", "v2", "hello")),
+            Some(TestOutcome::Failed)
+        );
+        assert_eq!(
+            state.outcome(&test_id("Greet", "v1", "bye")),
+            Some(TestOutcome::Cancelled)
+        );
+    }
+
+    /// Not a test on its own: a stub for a model call, which the executor
+    /// `responses_are_recorded_and_replayed` spawns. When replaying, it answers with the recorded
+    /// response. The name it greets comes from `FAKE_NAME`.
+    #[test]
+    fn fake_recording_executor() {
+        let Ok(port) = std::env::var("BAML_TEST_PORT") else {
+            return;
+        };
+        let replay = std::env::var("BAML_TEST_CASSETTE_MODE").unwrap() == "replay";
+        let dir = PathBuf::from(std::env::var("BAML_TEST_CASSETTE_DIR").unwrap());
+        let name = std::env::var("FAKE_NAME").unwrap();
+        let selection: Vec<Value> =
+            serde_json::from_str(&std::env::var("BAML_TEST_SELECTION").unwrap()).unwrap();
+
+        let mut stream = TcpStream::connect(format!("127.0.0.1:{}", port)).unwrap();
+        let mut send = |event: Value| writeln!(stream, "{}", event).unwrap();
+        for test in selection {
+            let raw_output = match replay {
+                true => {
+                    let path = dir
+                        .join(test["function"].as_str().unwrap())
+                        .join(test["test"].as_str().unwrap())
+                        .join(format!("{}.cassette", test["impl"].as_str().unwrap()));
+                    let cassette: Value =
+                        serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap();
+                    cassette["raw_output"].as_str().unwrap().to_string()
+                }
+                false => format!("Hi {}, from the model", name),
+            };
+
+            let mut event = test.clone();
+            event["event"] = "test_finished".into();
+            event["status"] = "passed".into();
+            send(event.clone());
+
+            event["event"] = "test_output".into();
+            event["prompt"] = json!([{ "role": "user", "content": "Greet {name}" }]);
+            event["template_args"] = json!({ "{name}": name });
+            event["client"] = json!({ "provider": "stub", "model": "stub-1", "options": {} });
+            event["raw_output"] = raw_output.clone().into();
+            event["parsed_output"] = raw_output.into();
+            send(event);
+        }
+    }
+
+    #[test]
+    fn responses_are_recorded_and_replayed() {
+        let baml_dir = std::env::temp_dir().join(format!("baml-cassettes-{}", std::process::id()));
+        let selected_tests = vec![("Greet".to_string(), "hello".to_string(), "v1".to_string())];
+        let run = |mode: CassetteMode, name: &str| {
+            let state = Arc::new(Mutex::new(
+                RunState::from_tests(greet_schema(), &selected_tests)
+                    .with_cassettes(Cassettes::new(&baml_dir, mode)),
+            ));
+            let (status, logs) = run_fake_executor(
+                "fake_recording_executor",
+                &state,
+                &selected_tests,
+                &[("FAKE_NAME", name)],
+                |_, logs| logs.contains("(impl: v1)"),
+            );
+            assert!(status.success());
+            let outcome = state
+                .blocking_lock()
+                .outcome(&test_id("Greet", "v1", "hello"));
+            (outcome, logs)
+        };
+
+        let cassettes = Cassettes::new(&baml_dir, CassetteMode::Replay);
+        assert_eq!(cassettes.missing(&selected_tests).len(), 1);
+        assert_eq!(
+            run(CassetteMode::Record, "Bob").0,
+            Some(TestOutcome::Passed)
+        );
+        assert!(cassettes.missing(&selected_tests).is_empty());
+
+        let (outcome, logs) = run(CassetteMode::Replay, "Bob");
+        assert_eq!(outcome, Some(TestOutcome::Passed));
+        assert!(logs.contains("Hi Bob, from the model"), "{}", logs);
+
+        // The prompt no longer matches the recording.
+        let (outcome, logs) = run(CassetteMode::Replay, "Ann");
+        assert_eq!(outcome, Some(TestOutcome::Failed));
+        assert!(logs.contains("changed since"), "{}", logs);
+
+        std::fs::remove_dir_all(&baml_dir).unwrap();
+    }
+
+    /// A project with a `Greet` function, and a `hello` test asserting its output starts with "Hi".
+    fn greet_schema() -> ParserDatabase {
+        let schema = baml_lib::validate(
+            &PathBuf::from("/project"),
+            vec![baml_lib::SourceFile::from((
+                PathBuf::from("/project/main.baml"),
+                r#"
+function Greet {
+  input string
+  output string
+}
+
+test hello {
+  function Greet
+  input "Bob"
+  assert [{ matches "^Hi" }]
+}
+"#,
+            ))],
+        );
+        assert!(!schema.diagnostics.has_errors(), "{:?}", schema.diagnostics);
+        schema.db
+    }
+
+    #[test]
+    fn assertions_are_checked_against_the_parsed_output() {
+        let selected_tests = vec![
+            ("Greet".to_string(), "hello".to_string(), "v1".to_string()),
+            ("Greet".to_string(), "hello".to_string(), "v2".to_string()),
+        ];
+        let mut state = RunState::from_tests(greet_schema(), &selected_tests);
+        for (r#impl, output) in [("v1", "Hi Bob"), ("v2", "Bye Bob")] {
+            let test = json!({ "function": "Greet", "impl": r#impl, "test": "hello" });
+            let mut event = test.clone();
+            event["event"] = "test_finished".into();
+            event["status"] = "passed".into();
+            state.add_event(
+                Executor::JsonLines
+                    .parse_line(&event.to_string())
+                    .unwrap()
+                    .unwrap(),
+            );
+
+            let mut event = test.clone();
+            event["event"] = "test_output".into();
+            event["parsed_output"] = output.into();
+            state.add_event(
+                Executor::JsonLines
+                    .parse_line(&event.to_string())
+                    .unwrap()
+                    .unwrap(),
+            );
+
+            // The span of the test itself ends last, and has no output.
+            let mut event = test;
+            event["event"] = "test_output".into();
+            state.add_event(
+                Executor::JsonLines
+                    .parse_line(&event.to_string())
+                    .unwrap()
+                    .unwrap(),
+            );
+        }
+        let log = state.sync().unwrap();
+
+        assert_eq!(
+            state.outcome(&test_id("Greet", "v1", "hello")),
+            Some(TestOutcome::Passed)
+        );
+        assert_eq!(
+            state.outcome(&test_id("Greet", "v2", "hello")),
+            Some(TestOutcome::AssertionFailed)
+        );
+        assert!(log.contains("Expected `$` to match /^Hi/, got \"Bye Bob\"."));
+    }
+
+    #[test]
+    fn json_report_has_every_test() {
+        let selected_tests = ["v1", "v2", "v3", "v4"]
+            .map(|r#impl| ("Greet".to_string(), "hello".to_string(), r#impl.to_string()));
+        let path = std::env::temp_dir().join(format!("baml-report-{}.json", std::process::id()));
+        let mut state = RunState::from_tests(greet_schema(), &selected_tests.to_vec())
+            .with_report(Report::new(TestReporter::Json, path.clone()));
+        let events = [
+            json!({ "event": "test_started", "impl": "v1" }),
+            json!({ "event": "test_finished", "impl": "v1", "status": "passed" }),
+            json!({
+                "event": "test_output",
+                "impl": "v1",
+                "input": "Bob",
+                "prompt": "Greet {name}",
+                "template_args": { "{name}": "Bob" },
+                "raw_output": "Hi Bob",
+                "parsed_output": "Hi Bob",
+            }),
+            json!({ "event": "test_started", "impl": "v2" }),
+            json!({ "event": "test_finished", "impl": "v2", "status": "failed", "error": "Timed out" }),
+            json!({ "event": "test_finished", "impl": "v3", "status": "cancelled" }),
+        ];
+        for mut event in events {
+            event["function"] = "Greet".into();
+            event["test"] = "hello".into();
+            state.add_event(
+                Executor::JsonLines
+                    .parse_line(&event.to_string())
+                    .unwrap()
+                    .unwrap(),
+            );
+        }
+        state.sync();
+        state.write_report().unwrap();
+
+        let report: Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        let tests = report["tests"].as_array().unwrap();
+        assert_eq!(
+            tests.iter().map(|t| &t["status"]).collect::<Vec<_>>(),
+            ["passed", "failed", "cancelled", "queued"]
+        );
+        assert_eq!(tests[0]["input"], "Bob");
+        assert_eq!(tests[0]["prompt"], "Greet Bob");
+        assert_eq!(tests[0]["raw_output"], "Hi Bob");
+        assert!(tests[0]["duration_ms"].is_u64());
+        assert_eq!(tests[1]["errors"], json!(["Timed out"]));
+        assert!(tests[3]["duration_ms"].is_null());
+        assert_eq!(report["summary"]["total"], 4);
+        assert_eq!(report["summary"]["queued"], 1);
+    }
+
+    #[test]
+    fn pytest_messages_are_translated() {
+        let update = |status: &str| {
+            json!({
+                "name": "update_test_case",
+                "data": {
+                    "project_id": "p",
+                    "test_cycle_id": "c",
+                    "test_dataset_name": "Greet",
+                    "test_case_definition_name": "test_hello",
+                    "test_case_arg_name": "test_hello[Greet-v1]",
+                    "status": status,
+                    "error_data": null,
+                },
+            })
+            .to_string()
+                + "<END_MSG>"
+        };
+
+        let mut state = RunState::from_tests(
+            ParserDatabase::new(),
+            &vec![("Greet".to_string(), "hello".to_string(), "v1".to_string())],
+        );
+        let id = test_id("Greet", "v1", "hello");
+
+        state.add_event(
+            Executor::Pytest
+                .parse_line(&update("RUNNING"))
+                .unwrap()
+                .unwrap(),
+        );
+        state.sync();
+        assert_eq!(state.outcome(&id), Some(TestOutcome::Running));
+
+        state.add_event(
+            Executor::Pytest
+                .parse_line(&update("FAILED"))
+                .unwrap()
+                .unwrap(),
+        );
+        state.sync();
+        assert_eq!(state.outcome(&id), Some(TestOutcome::Failed));
+
+        assert!(Executor::Pytest
+            .parse_line(&update("QUEUED"))
+            .unwrap()
+            .is_none());
+        assert!(Executor::Pytest.parse_line("not json<END_MSG>").is_err());
+    }
+}
@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::collections::HashMap;
+use std::{collections::HashMap, str::FromStr};
+
+use super::protocol::{self, Prompt, TestEvent, TestId, TestStatus};
 
 pub(crate) fn handle_message(message: &str) -> Option<MessageData> {
     if message.is_empty() {
@@ -27,6 +29,88 @@ pub(crate) enum MessageData {
     Log(LogSchema),
 }
 
+impl MessageData {
+    /// Translates a message from the pytest plugin into the events of the test protocol.
+    pub(crate) fn into_event(self) -> Option<TestEvent> {
+        match self {
+            MessageData::TestRunMeta(meta) => Some(TestEvent::RunStarted {
+                dashboard_url: Some(meta.dashboard_url),
+            }),
+            MessageData::UpdateTestCase(update) => {
+                let test = parse_test_case_arg_name(&update.test_case_arg_name)?;
+                let error = update.error_data.map(|error| error.to_string());
+                let status = match update.status {
+                    TestCaseStatus::Queued => return None,
+                    TestCaseStatus::Running => return Some(TestEvent::TestStarted { test }),
+                    TestCaseStatus::Passed | TestCaseStatus::ExpectedFailure => TestStatus::Passed,
+                    TestCaseStatus::Failed => TestStatus::Failed,
+                    TestCaseStatus::Cancelled => TestStatus::Cancelled,
+                };
+                Some(TestEvent::TestFinished {
+                    test,
+                    status,
+                    error,
+                })
+            }
+            MessageData::Log(log) => {
+                let test = parse_test_case_arg_name(log.context.tags.get("test_case_arg_name")?)?;
+                let (prompt, template_args, raw_output, client) = match log.metadata {
+                    Some(meta) => (
+                        Some(match meta.input.prompt.template {
+                            Template::Single(prompt) => Prompt::Completion(prompt),
+                            Template::Multiple(chats) => Prompt::Chat(
+                                chats
+                                    .into_iter()
+                                    .map(|chat| protocol::ChatMessage {
+                                        role: chat.role.as_str().to_string(),
+                                        content: chat.content,
+                                    })
+                                    .collect(),
+                            ),
+                        }),
+                        meta.input.prompt.template_args,
+                        meta.output.map(|output| output.raw_text),
+                        Some(serde_json::json!({
+                            "provider": meta.provider,
+                            "model": meta.model_name,
+                            "options": meta.input.invocation_params,
+                        })),
+                    ),
+                    None => (None, HashMap::new(), None, None),
+                };
+                let input = log.io.input.and_then(IOValue::into_json);
+                let parsed_output = log.io.output.and_then(IOValue::into_json);
+                let error = log.error.map(|error| match error.traceback {
+                    Some(traceback) => format!("{}\n{}", error.message, traceback),
+                    None => error.message,
+                });
+                Some(TestEvent::TestOutput {
+                    test,
+                    input,
+                    prompt,
+                    template_args,
+                    raw_output,
+                    parsed_output,
+                    client,
+                    error,
+                })
+            }
+        }
+    }
+}
+
+/// pytest names each test case "test_<test>[<function>-<impl>]".
+fn parse_test_case_arg_name(name: &str) -> Option<TestId> {
+    let (test, rest) = name.split_once('[')?;
+    let test = test.strip_prefix("test_")?;
+    let (function, r#impl) = rest.strip_suffix(']')?.split_once('-')?;
+    Some(TestId {
+        function: function.into(),
+        r#impl: r#impl.into(),
+        test: test.into(),
+    })
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub(crate) struct TestRunMeta {
     pub dashboard_url: String,
@@ -68,6 +152,20 @@ pub(crate) struct IOValue {
     r#override: Option<HashMap<String, Value>>,
 }
 
+impl IOValue {
+    fn into_json(self) -> Option<Value> {
+        match self.value {
+            ValueType::String(s) => Value::from_str(&s).ok(),
+            ValueType::List(l) => l
+                .iter()
+                .map(|v| Value::from_str(v))
+                .collect::<Result<Vec<_>, _>>()
+                .map(Value::Array)
+                .ok(),
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(untagged)]
 pub(crate) enum ValueType {
@@ -181,13 +279,13 @@ impl Role {
 #[derive(Serialize, Deserialize, Debug)]
 pub(crate) struct LLMEventInput {
     pub prompt: LLMEventInputPrompt,
-    invocation_params: HashMap<String, Value>,
+    pub invocation_params: HashMap<String, Value>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 pub(crate) struct LLMEventSchema {
     pub model_name: String,
-    provider: String,
+    pub provider: String,
     pub input: LLMEventInput,
     pub output: Option<LLMOutputModel>,
 }
@@ -0,0 +1,190 @@
+//! The protocol `baml test` uses to drive a generator's `test_command`.
+//!
+//! Any test runner (pytest, Jest, Vitest, cargo test, ...) can report into `baml test` by
+//! implementing this protocol in a small adapter.
+//!
+//! # Starting a run
+//!
+//! `baml test run` starts a TCP server on `127.0.0.1` and spawns the generator's `test_command`
+//! with these environment variables:
+//!
+//! | Variable | Value |
+//! |----------|-------|
+//! | `BAML_TEST_PROTOCOL` | The protocol version, currently `jsonl-v1`. |
+//! | `BAML_TEST_PORT` | The port of the server to report to. |
+//! | `BAML_TEST_DIR` | The directory of the generated client. |
+//! | `BAML_TEST_SELECTION` | A JSON array of the tests to run, e.g. `[{"function": "Greet", "impl": "v1", "test": "hello"}]`. |
+//!
+//! The executor runs every selected test, once per impl, and skips the rest.
+//!
+//! # Reporting
+//!
+//! The executor connects to the server and writes events as UTF-8 JSON objects, one per line.
+//! It may use a single connection for the whole run, or a new connection per event. Every event
+//! has an `event` field naming it:
+//!
+//! ```json
+//! {"event": "run_started", "dashboard_url": "https://..."}
+//! {"event": "test_started", "function": "Greet", "impl": "v1", "test": "hello"}
+//! {"event": "test_finished", "function": "Greet", "impl": "v1", "test": "hello", "status": "failed", "error": "..."}
+//! {"event": "test_output", "function": "Greet", "impl": "v1", "test": "hello", "prompt": "...", "raw_output": "...", "parsed_output": {"name": "..."}}
+//! ```
+//!
+//! - `run_started` is optional. `dashboard_url` may be `null`.
+//! - `test_finished` has a `status` of `passed`, `failed` or `cancelled`. `error` is optional.
+//! - `test_output` is optional and comes after `test_finished`. Every field besides the test's is
+//!   optional. `input` is the input the function was called with. `prompt` is either a string or a list of chat messages, e.g.
+//!   `[{"role": "system", "content": "..."}]`. `template_args` may map placeholders in the prompt
+//!   to their values, which are highlighted when the prompt is printed. `parsed_output` is any
+//!   JSON value, and is what the test's `assert` expectations are checked against: a test that
+//!   passed but whose output fails them is reported as such. `client` describes the client that
+//!   was called, e.g. `{"provider": "openai", "model": "gpt-4", "options": {...}}`, and is part of
+//!   what recorded responses are keyed by.
+//!
+//! # Recording and replaying
+//!
+//! With `--record` or `--replay`, the executor also gets:
+//!
+//! | Variable | Value |
+//! |----------|-------|
+//! | `BAML_TEST_CASSETTE_MODE` | `record` or `replay`. |
+//! | `BAML_TEST_CASSETTE_DIR` | The directory of the recorded responses. |
+//!
+//! When recording, the executor runs as usual, and `baml test` saves the `raw_output` of each
+//! `test_output`. When replaying, the executor must not call the model: it reads the `raw_output`
+//! of `<dir>/<function>/<test>/<impl>.cassette`, a JSON object, and uses it as the model's
+//! response. It still reports the `prompt`, `template_args` and `client` it would have used.
+//!
+//! Lines that can't be parsed are logged and skipped, and unknown events are ignored, so newer
+//! executors keep working with older CLIs.
+//!
+//! # Finishing a run
+//!
+//! The executor exits with `0` if every test passed and `1` if any failed. Any other exit code is
+//! reported as a failure of the executor itself, along with its logs.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// The value of `BAML_TEST_PROTOCOL`.
+pub(crate) const PROTOCOL_VERSION: &str = "jsonl-v1";
+
+#[derive(Serialize, Deserialize, Debug, Clone, Hash, PartialEq, Eq)]
+pub(crate) struct TestId {
+    pub function: String,
+    #[serde(rename = "impl")]
+    pub r#impl: String,
+    pub test: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum TestStatus {
+    Passed,
+    Failed,
+    Cancelled,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub(crate) struct ChatMessage {
+    pub role: String,
+    pub content: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(untagged)]
+pub(crate) enum Prompt {
+    Completion(String),
+    Chat(Vec<ChatMessage>),
+}
+
+impl Prompt {
+    /// The prompt with its template args filled in.
+    pub(crate) fn render(&self, template_args: &HashMap<String, String>) -> Prompt {
+        // Longest first, so an arg is never replaced inside a longer one.
+        let mut args = template_args.iter().collect::<Vec<_>>();
+        args.sort_by(|(a, _), (b, _)| b.len().cmp(&a.len()).then(a.cmp(b)));
+        let render = |text: &str| {
+            args.iter()
+                .fold(text.to_string(), |text, (k, v)| text.replace(*k, v))
+        };
+
+        match self {
+            Prompt::Completion(text) => Prompt::Completion(render(text)),
+            Prompt::Chat(messages) => Prompt::Chat(
+                messages
+                    .iter()
+                    .map(|message| ChatMessage {
+                        role: message.role.clone(),
+                        content: render(&message.content),
+                    })
+                    .collect(),
+            ),
+        }
+    }
+}
+
+// Events are handled one at a time, so boxing `TestOutput` wouldn't save anything.
+#[allow(clippy::large_enum_variant)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub(crate) enum TestEvent {
+    RunStarted {
+        #[serde(default)]
+        dashboard_url: Option<String>,
+    },
+    TestStarted {
+        #[serde(flatten)]
+        test: TestId,
+    },
+    TestFinished {
+        #[serde(flatten)]
+        test: TestId,
+        status: TestStatus,
+        #[serde(default)]
+        error: Option<String>,
+    },
+    TestOutput {
+        #[serde(flatten)]
+        test: TestId,
+        #[serde(default)]
+        input: Option<Value>,
+        #[serde(default)]
+        prompt: Option<Prompt>,
+        #[serde(default)]
+        template_args: HashMap<String, String>,
+        #[serde(default)]
+        raw_output: Option<String>,
+        #[serde(default)]
+        parsed_output: Option<Value>,
+        #[serde(default)]
+        client: Option<Value>,
+        #[serde(default)]
+        error: Option<String>,
+    },
+}
+
+/// Parses one line written by an executor. Returns `None` for blank lines and unknown events.
+pub(crate) fn parse_line(line: &str) -> Result<Option<TestEvent>, serde_json::Error> {
+    let line = line.trim();
+    if line.is_empty() {
+        return Ok(None);
+    }
+
+    let value: Value = serde_json::from_str(line)?;
+    match serde_json::from_value::<TestEvent>(value.clone()) {
+        Ok(event) => Ok(Some(event)),
+        // Unknown events are skipped, but known events must be well-formed.
+        Err(_) if !is_known_event(&value) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+fn is_known_event(value: &Value) -> bool {
+    matches!(
+        value.get("event").and_then(Value::as_str),
+        Some("run_started" | "test_started" | "test_finished" | "test_output")
+    )
+}
@@ -0,0 +1,299 @@
+//! Machine-readable reports of `baml test run`, for CI.
+//!
+//! `--reporter junit` writes JUnit XML, with one `<testsuite>` per function and one `<testcase>`
+//! per test and impl. `--reporter json` writes every result, along with the input, prompt and
+//! output of the test.
+
+use std::{
+    collections::HashMap,
+    fs,
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use super::{
+    protocol::{Prompt, TestId},
+    test_state::TestOutcome,
+};
+use crate::TestReporter;
+
+/// Where to write the report of a run, and in which format.
+#[derive(Debug, Clone)]
+pub(crate) struct Report {
+    reporter: TestReporter,
+    path: PathBuf,
+}
+
+/// What was reported about a test, collected as its events come in.
+#[derive(Debug, Default)]
+pub(crate) struct TestRecord {
+    pub started_at: Option<Instant>,
+    pub duration: Option<Duration>,
+    pub errors: Vec<String>,
+    pub input: Option<Value>,
+    pub prompt: Option<Prompt>,
+    pub template_args: HashMap<String, String>,
+    pub raw_output: Option<String>,
+    pub parsed_output: Option<Value>,
+}
+
+/// The result of one test, as written to the report.
+#[derive(Serialize, Debug)]
+pub(crate) struct TestResult<'a> {
+    #[serde(flatten)]
+    pub test: &'a TestId,
+    pub status: TestOutcome,
+    pub duration_ms: Option<u128>,
+    pub errors: &'a [String],
+    pub failed_assertions: &'a [String],
+    pub input: Option<&'a Value>,
+    /// The prompt, with its template args filled in.
+    pub prompt: Option<Prompt>,
+    pub raw_output: Option<&'a str>,
+    pub parsed_output: Option<&'a Value>,
+}
+
+impl Report {
+    pub(crate) fn new(reporter: TestReporter, path: PathBuf) -> Self {
+        Self { reporter, path }
+    }
+
+    pub(crate) fn write(&self, results: &[TestResult], duration: Duration) -> Result<(), String> {
+        let content = match self.reporter {
+            TestReporter::Junit => junit(results, duration),
+            TestReporter::Json => {
+                let report = json!({
+                    "summary": summary(results, duration),
+                    "tests": results,
+                });
+                serde_json::to_string_pretty(&report).map_err(|e| e.to_string())? + "\n"
+            }
+        };
+        self.path
+            .parent()
+            .filter(|dir| !dir.as_os_str().is_empty())
+            .map(fs::create_dir_all)
+            .transpose()
+            .and_then(|_| fs::write(&self.path, content))
+            .map_err(|e| format!("Failed to write {}: {}", self.path.display(), e))
+    }
+}
+
+fn summary(results: &[TestResult], duration: Duration) -> Value {
+    let count = |status| results.iter().filter(|r| r.status == status).count();
+    json!({
+        "total": results.len(),
+        "passed": count(TestOutcome::Passed),
+        "failed": count(TestOutcome::Failed),
+        "assertion_failed": count(TestOutcome::AssertionFailed),
+        "cancelled": count(TestOutcome::Cancelled),
+        "queued": count(TestOutcome::Queued),
+        "running": count(TestOutcome::Running),
+        "duration_ms": duration.as_millis(),
+    })
+}
+
+fn junit(results: &[TestResult], duration: Duration) -> String {
+    let mut suites: Vec<(&str, Vec<&TestResult>)> = vec![];
+    for result in results {
+        match suites.last_mut() {
+            Some((function, tests)) if *function == result.test.function => tests.push(result),
+            _ => suites.push((&result.test.function, vec![result])),
+        }
+    }
+
+    let all = results.iter().collect::<Vec<_>>();
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml += &format!(
+        "<testsuites name=\"baml\" {} time=\"{:.3}\">\n",
+        junit_counts(&all),
+        duration.as_secs_f64()
+    );
+    for (function, tests) in suites {
+        let time = tests.iter().filter_map(|t| t.duration_ms).sum::<u128>() as f64 / 1000.0;
+        xml += &format!(
+            "  <testsuite name=\"{}\" {} time=\"{:.3}\">\n",
+            escape(function),
+            junit_counts(&tests),
+            time
+        );
+        for test in tests {
+            xml += &junit_testcase(test);
+        }
+        xml += "  </testsuite>\n";
+    }
+    xml += "</testsuites>\n";
+    xml
+}
+
+fn junit_counts(results: &[&TestResult]) -> String {
+    let count = |f: fn(TestOutcome) -> bool| results.iter().filter(|r| f(r.status)).count();
+    format!(
+        "tests=\"{}\" failures=\"{}\" errors=\"{}\" skipped=\"{}\"",
+        results.len(),
+        count(|s| matches!(s, TestOutcome::Failed | TestOutcome::AssertionFailed)),
+        count(|s| matches!(s, TestOutcome::Running)),
+        count(|s| matches!(s, TestOutcome::Cancelled | TestOutcome::Queued)),
+    )
+}
+
+fn junit_testcase(result: &TestResult) -> String {
+    let TestId {
+        function,
+        r#impl,
+        test,
+    } = result.test;
+    let mut xml = format!(
+        "    <testcase name=\"{}[{}]\" classname=\"{}\" time=\"{:.3}\"",
+        escape(test),
+        escape(r#impl),
+        escape(function),
+        result.duration_ms.unwrap_or_default() as f64 / 1000.0
+    );
+
+    let errors = result.errors.join("\n\n");
+    let status = match result.status {
+        TestOutcome::Passed => None,
+        TestOutcome::Failed => Some(format!(
+            "<failure message=\"{}\">{}</failure>",
+            escape(
+                result
+                    .errors
+                    .first()
+                    .map_or("Failed", |e| e.lines().next().unwrap_or(e))
+            ),
+            escape(&errors)
+        )),
+        TestOutcome::AssertionFailed => Some(format!(
+            "<failure type=\"assertion\" message=\"{} assertion(s) failed\">{}</failure>",
+            result.failed_assertions.len(),
+            escape(&result.failed_assertions.join("\n"))
+        )),
+        TestOutcome::Cancelled => Some("<skipped message=\"Cancelled\"/>".to_string()),
+        TestOutcome::Queued => Some("<skipped message=\"Never started\"/>".to_string()),
+        TestOutcome::Running => Some(format!(
+            "<error message=\"Never finished\">{}</error>",
+            escape(&errors)
+        )),
+    };
+    let output = system_out(result);
+    if status.is_none() && output.is_empty() {
+        return xml + "/>\n";
+    }
+
+    xml += ">\n";
+    if let Some(status) = status {
+        xml += &format!("      {}\n", status);
+    }
+    if !output.is_empty() {
+        xml += &format!("      <system-out>{}</system-out>\n", escape(&output));
+    }
+    xml + "    </testcase>\n"
+}
+
+/// The input, prompt and output of a test, as plain text.
+fn system_out(result: &TestResult) -> String {
+    let pretty = |value: &Value| serde_json::to_string_pretty(value).unwrap_or_default();
+    let mut sections = vec![];
+    if let Some(input) = result.input {
+        sections.push(("Input", pretty(input)));
+    }
+    if let Some(prompt) = &result.prompt {
+        let prompt = match prompt {
+            Prompt::Completion(text) => text.clone(),
+            Prompt::Chat(messages) => messages
+                .iter()
+                .map(|message| format!("{}:\n{}", message.role, message.content))
+                .collect::<Vec<_>>()
+                .join("\n"),
+        };
+        sections.push(("Prompt", prompt));
+    }
+    if let Some(raw_output) = result.raw_output {
+        sections.push(("Raw Response", raw_output.to_string()));
+    }
+    if let Some(parsed_output) = result.parsed_output {
+        sections.push(("Parsed Response", pretty(parsed_output)));
+    }
+    sections
+        .into_iter()
+        .map(|(title, content)| format!("---- {} ----\n{}\n", title, content))
+        .collect()
+}
+
+/// Escapes text for XML, and drops the control characters XML 1.0 doesn't allow.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped += "&amp;",
+            '<' => escaped += "&lt;",
+            '>' => escaped += "&gt;",
+            '"' => escaped += "&quot;",
+            '\'' => escaped += "&apos;",
+            '\t' | '\n' | '\r' => escaped.push(c),
+            c if c < ' ' => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result<'a>(test: &'a TestId, status: TestOutcome, errors: &'a [String]) -> TestResult<'a> {
+        TestResult {
+            test,
+            status,
+            duration_ms: Some(1500),
+            errors,
+            failed_assertions: &[],
+            input: None,
+            prompt: None,
+            raw_output: None,
+            parsed_output: None,
+        }
+    }
+
+    #[test]
+    fn junit_reports_every_state() {
+        let id = |test: &str| TestId {
+            function: "Greet".into(),
+            r#impl: "v1".into(),
+            test: test.into(),
+        };
+        let (passed, failed, cancelled, queued) = (id("a"), id("b"), id("c"), id("d"));
+        let errors = vec!["Expected <Hi> & \"Bye\"\u{1b}".to_string()];
+        let mut results = vec![
+            result(&passed, TestOutcome::Passed, &[]),
+            result(&failed, TestOutcome::Failed, &errors),
+            result(&cancelled, TestOutcome::Cancelled, &[]),
+            result(&queued, TestOutcome::Queued, &[]),
+        ];
+        results[0].raw_output = Some("Hi");
+
+        let xml = junit(&results, Duration::from_secs(3));
+        assert!(xml.contains(
+            "<testsuites name=\"baml\" tests=\"4\" failures=\"1\" errors=\"0\" skipped=\"2\" time=\"3.000\">"
+        ), "{}", xml);
+        assert!(xml.contains(
+            "<testcase name=\"a[v1]\" classname=\"Greet\" time=\"1.500\">\n      <system-out>---- Raw Response ----\nHi\n</system-out>"
+        ), "{}", xml);
+        assert!(
+            xml.contains("<failure message=\"Expected &lt;Hi&gt; &amp; &quot;Bye&quot;\">"),
+            "{}",
+            xml
+        );
+        assert!(xml.contains("<skipped message=\"Cancelled\"/>"), "{}", xml);
+        assert!(
+            xml.contains("<skipped message=\"Never started\"/>"),
+            "{}",
+            xml
+        );
+    }
+}
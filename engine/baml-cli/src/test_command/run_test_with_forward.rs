@@ -1,88 +1,29 @@
 use colored::*;
 use std::{
     fs::{self, File},
-    process::Stdio,
+    process::{Command, Stdio},
     sync::Arc,
 };
 
-use tokio::{
-    io::{AsyncReadExt, AsyncWriteExt},
-    net::{TcpListener, TcpStream},
-    sync::Mutex,
-    time,
-};
-
-use crate::{errors::CliError, shell::build_shell_command};
-
-use super::{ipc_comms, test_state::RunState};
+use tokio::{sync::Mutex, time};
 
-async fn handle_connection(
-    mut stream: TcpStream,
-    state: Arc<Mutex<RunState>>,
-    forward_port: u16,
-) -> tokio::io::Result<()> {
-    let mut buffer = String::new();
-    // Read message from Python test
-    let n = stream.read_to_string(&mut buffer).await?;
-    if n == 0 {
-        return Ok(());
-    }
-    let promise = forward_to_port(forward_port, &buffer);
-
-    // buffer may have multiple messages in it, so we need to split it
-    // on the message separator <END_MSG>\n
-    let messages = buffer.split("<END_MSG>\n");
-    let mut state = state.lock().await;
-    for message in messages {
-        if message.is_empty() {
-            continue;
-        }
-        if let Some(message) = ipc_comms::handle_message(message) {
-            state.add_message(message);
-        } else {
-            _ = promise.await;
-            return Err(tokio::io::Error::new(
-                tokio::io::ErrorKind::InvalidData,
-                format!("Failed to parse message: {}", message),
-            ));
-        }
-    }
-    _ = promise.await;
+use crate::errors::CliError;
 
-    Ok(())
-}
-
-async fn start_server() -> std::io::Result<(TcpListener, u16)> {
-    let listener = TcpListener::bind("127.0.0.1:0").await?;
-    let port = listener.local_addr()?.port();
-    Ok((listener, port))
-}
+use super::{
+    executor::{self, Executor},
+    test_state::RunState,
+};
 
-async fn run_pytest_and_update_state(
+async fn run_executor_and_update_state(
     state: Arc<Mutex<RunState>>,
-    mut shell_command: Vec<String>,
+    executor: Executor,
+    build_command: impl FnOnce(u16) -> Result<Command, CliError>,
     forward_port: u16,
 ) -> tokio::io::Result<()> {
-    let (listener, port) = start_server().await?;
-
-    // Spawn a separate task for the TCP server
-    let server_state = state.clone();
-    tokio::spawn(async move {
-        loop {
-            let (socket, _) = listener.accept().await.unwrap();
-            let state = server_state.clone();
-            tokio::spawn(handle_connection(socket, state, forward_port));
-        }
-    });
-
-    // Append args to pytest to enable IPC
-    // println!("Running pytest with args: {:?}", cmd);
-    // cmd.arg("--pytest-baml-ipc");
-    // cmd.arg(format!("{}", port));
-    shell_command.push("--pytest-baml-ipc".into());
-    shell_command.push(format!("{}", port));
+    let port = executor::listen(executor, state.clone(), Some(forward_port)).await?;
 
-    let mut cmd = build_shell_command(shell_command);
+    let mut cmd = build_command(port)
+        .map_err(|e| tokio::io::Error::new(tokio::io::ErrorKind::InvalidInput, e.to_string()))?;
 
     // We don't need this - too noisy. We can append to stdout logs later or print it if there was an error.
     // println!(
@@ -115,9 +56,9 @@ async fn run_pytest_and_update_state(
         .stdout(Stdio::from(stdout_file))
         .stderr(Stdio::from(stderr_file))
         .spawn()
-        .expect("failed to spawn pytest");
+        .expect("failed to spawn the test command");
 
-    // Print state every 2 seconds while pytest is running
+    // Print state every 2 seconds while the tests are running
     let mut interval = time::interval(time::Duration::from_millis(500));
     while child.try_wait()?.is_none() {
         interval.tick().await;
@@ -132,7 +73,8 @@ async fn run_pytest_and_update_state(
         state.sync();
 
         // Create a symlink to the baml directory
-        println!("{}", state.to_string())
+        println!("{}", state.to_string());
+        state.write_report().map_err(std::io::Error::other)?;
     }
 
     // exit also with the same status only if the exit codes are
@@ -166,24 +108,19 @@ async fn run_pytest_and_update_state(
 
 pub(crate) fn run_test_with_forward(
     state: RunState,
-    shell_command: Vec<String>,
+    executor: Executor,
+    build_command: impl FnOnce(u16) -> Result<Command, CliError>,
     forward_port: u16,
 ) -> Result<(), CliError> {
     let rt = tokio::runtime::Runtime::new().unwrap();
 
     let state = Arc::new(Mutex::new(state));
 
-    rt.block_on(run_pytest_and_update_state(
+    rt.block_on(run_executor_and_update_state(
         state,
-        shell_command,
+        executor,
+        build_command,
         forward_port,
     ))
-    .map_err(|e| format!("Failed to run pytest: {}", e).into())
-}
-
-async fn forward_to_port(port: u16, message: &String) -> tokio::io::Result<()> {
-    const HOST: &str = "127.0.0.1";
-    // Forward message to the port.
-    let mut stream = TcpStream::connect(format!("{}:{}", HOST, port)).await?;
-    stream.write_all(message.as_bytes()).await
+    .map_err(|e| format!("Failed to run tests: {}", e).into())
 }
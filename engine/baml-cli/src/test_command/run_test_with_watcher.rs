@@ -2,79 +2,30 @@ use colored::*;
 use std::{
     fs::{self, File},
     io::{self, Write},
-    process::Stdio,
+    process::{Command, Stdio},
     sync::Arc,
 };
 
-use tokio::{
-    io::AsyncReadExt,
-    net::{TcpListener, TcpStream},
-    sync::Mutex,
-    time,
-};
-
-use crate::{errors::CliError, shell::build_shell_command};
-
-use super::{ipc_comms, test_state::RunState};
+use tokio::{sync::Mutex, time};
 
-async fn handle_connection(
-    mut stream: TcpStream,
-    state: Arc<Mutex<RunState>>,
-) -> tokio::io::Result<()> {
-    let mut buffer = String::new();
-    // Read message from Python test
-    let n = stream.read_to_string(&mut buffer).await?;
-    if n == 0 {
-        return Ok(());
-    }
-    // buffer may have multiple messages in it, so we need to split it
-    // on the message separator <END_MSG>\n
-    let messages = buffer.split("<END_MSG>\n");
-    let mut state = state.lock().await;
-    for message in messages {
-        if message.is_empty() {
-            continue;
-        }
-        if let Some(message) = ipc_comms::handle_message(message) {
-            state.add_message(message);
-        } else {
-            return Err(tokio::io::Error::new(
-                tokio::io::ErrorKind::InvalidData,
-                format!("Failed to parse message: {}", message),
-            ));
-        }
-    }
-    Ok(())
-}
+use crate::errors::CliError;
 
-async fn start_server() -> std::io::Result<(TcpListener, u16)> {
-    let listener = TcpListener::bind("127.0.0.1:0").await?;
-    let port = listener.local_addr()?.port();
-    Ok((listener, port))
-}
+use super::{
+    executor::{self, Executor},
+    test_state::RunState,
+};
 
-async fn run_pytest_and_update_state(
+async fn run_executor_and_update_state(
     state: Arc<Mutex<RunState>>,
-    mut shell_command: Vec<String>,
+    executor: Executor,
+    build_command: impl FnOnce(u16) -> Result<Command, CliError>,
 ) -> tokio::io::Result<()> {
-    let (listener, port) = start_server().await?;
-
-    // Spawn a separate task for the TCP server
-    let server_state = state.clone();
-    tokio::spawn(async move {
-        loop {
-            let (socket, _) = listener.accept().await.unwrap();
-            let state = server_state.clone();
-            tokio::spawn(handle_connection(socket, state));
-        }
-    });
+    let port = executor::listen(executor, state.clone(), None).await?;
 
-    shell_command.push("--pytest-baml-ipc".into());
-    shell_command.push(format!("{}", port));
+    let mut cmd = build_command(port)
+        .map_err(|e| tokio::io::Error::new(tokio::io::ErrorKind::InvalidInput, e.to_string()))?;
 
-    let mut cmd = build_shell_command(shell_command);
-
-    println!("Running pytest with args: {:?}", cmd);
+    println!("Running tests with: {:?}", cmd);
 
     // Create a directory in the temp folder
     // Load from environment variable (BAML_TEST_LOGS) if set or use temp_dir
@@ -98,12 +49,14 @@ async fn run_pytest_and_update_state(
         .stdout(Stdio::from(stdout_file))
         .stderr(Stdio::from(stderr_file))
         .spawn()
-        .expect("failed to spawn pytest");
+        .expect("failed to spawn the test command");
 
-    // Print state every 2 seconds while pytest is running
+    // Print state every 2 seconds while the tests are running
     let mut interval = time::interval(time::Duration::from_millis(500));
     let mut last_print_lines = 0;
-    while child.try_wait()?.is_none() {
+    loop {
+        // Print once more after the tests finish, for the events that came in since.
+        let finished = child.try_wait()?.is_some();
         interval.tick().await;
         let (state_string, extra_message) = {
             let mut state = state.lock().await;
@@ -126,8 +79,18 @@ async fn run_pytest_and_update_state(
 
         // Flush stdout to ensure immediate output
         let _ = io::stdout().flush();
+
+        if finished {
+            break;
+        }
     }
 
+    state
+        .lock()
+        .await
+        .write_report()
+        .map_err(io::Error::other)?;
+
     // Optionally, you can handle the output after the subprocess has finished
     let output = child.wait_with_output()?;
     if let Some(code) = output.status.code() {
@@ -154,11 +117,16 @@ async fn run_pytest_and_update_state(
 
 pub(crate) fn run_test_with_watcher(
     state: RunState,
-    shell_command: Vec<String>,
+    executor: Executor,
+    build_command: impl FnOnce(u16) -> Result<Command, CliError>,
 ) -> Result<(), CliError> {
     let rt = tokio::runtime::Runtime::new().unwrap();
 
     let state = Arc::new(Mutex::new(state));
-    rt.block_on(run_pytest_and_update_state(state, shell_command))
-        .map_err(|e| format!("Failed to run tests: {}", e).into())
+    rt.block_on(run_executor_and_update_state(
+        state,
+        executor,
+        build_command,
+    ))
+    .map_err(|e| format!("Failed to run tests: {}", e).into())
 }
@@ -1,8 +1,15 @@
-use baml_lib::internal_baml_parser_database::ParserDatabase;
+use baml_lib::{
+    internal_baml_parser_database::ParserDatabase, internal_baml_schema_ast::ast::WithName,
+};
 use colored::*;
-use std::{collections::HashMap, ops::Deref, str::FromStr};
+use serde::Serialize;
+use std::{collections::HashMap, ops::Deref, time::Instant};
 
-use super::ipc_comms::{LogSchema, MessageData, Template, TestCaseStatus, ValueType};
+use super::{
+    cassette::{CassetteMode, Cassettes},
+    protocol::{Prompt, TestEvent, TestId, TestStatus},
+    report::{Report, TestRecord, TestResult},
+};
 
 #[derive(Debug)]
 enum TestState {
@@ -16,7 +23,21 @@ enum TestState {
 #[derive(Debug)]
 struct FinishedState {
     passed: bool,
-    log: Option<LogSchema>,
+    // Assertions on the parsed output that failed, checked once the output comes in.
+    failed_assertions: Vec<String>,
+}
+
+/// Where a test is at, as seen by the driver.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum TestOutcome {
+    Queued,
+    Running,
+    Cancelled,
+    Passed,
+    Failed,
+    /// The test ran, but its output failed some of its assertions.
+    AssertionFailed,
 }
 
 enum ExecutorStage {
@@ -28,25 +49,24 @@ enum ExecutorStage {
     Finished,
 }
 
-#[derive(Debug, Clone, Hash, PartialEq, Eq)]
-pub(crate) struct TestSpec {
-    function: String,
-    test: String,
-    r#impl: String,
-}
-
 pub(crate) struct RunState {
     schema: ParserDatabase,
-    messages: Vec<MessageData>,
+    messages: Vec<TestEvent>,
     dashboard_url: Option<String>,
     // If pytest got past the parsing stage
     executor_stage: ExecutorStage,
     // Test state for each test
     // Function, Test, Impl
-    tests: HashMap<TestSpec, TestState>,
+    tests: HashMap<TestId, TestState>,
 
     // Collection summary (used for simplified printing)
     collected: Vec<(String, Vec<(String, Vec<String>)>, Vec<String>)>,
+    // Where to record responses to, or replay them from
+    cassettes: Option<Cassettes>,
+    // Everything reported about each test, for the report
+    records: HashMap<TestId, TestRecord>,
+    started_at: Instant,
+    report: Option<Report>,
 }
 
 impl RunState {
@@ -101,7 +121,7 @@ impl RunState {
                 .iter()
                 .map(|(function, test, r#impl)| {
                     (
-                        TestSpec {
+                        TestId {
                             function: function.clone(),
                             test: test.clone(),
                             r#impl: r#impl.clone(),
@@ -111,9 +131,70 @@ impl RunState {
                 })
                 .collect(),
             collected,
+            cassettes: None,
+            records: HashMap::new(),
+            started_at: Instant::now(),
+            report: None,
         }
     }
 
+    pub(crate) fn with_report(mut self, report: Report) -> Self {
+        self.report = Some(report);
+        self
+    }
+
+    /// Writes the report of the run, if one was asked for.
+    pub(crate) fn write_report(&self) -> Result<(), String> {
+        let Some(report) = &self.report else {
+            return Ok(());
+        };
+        let no_record = TestRecord::default();
+        let results = self
+            .collected
+            .iter()
+            .flat_map(|(function, tests, _)| {
+                tests.iter().flat_map(move |(test, impls)| {
+                    impls.iter().map(move |r#impl| (function, test, r#impl))
+                })
+            })
+            .filter_map(|(function, test, r#impl)| {
+                let (id, state) = self.tests.get_key_value(&TestId {
+                    function: function.clone(),
+                    r#impl: r#impl.clone(),
+                    test: test.clone(),
+                })?;
+                let record = self.records.get(id).unwrap_or(&no_record);
+                Some(TestResult {
+                    test: id,
+                    status: self.outcome(id)?,
+                    duration_ms: record.duration.map(|d| d.as_millis()),
+                    errors: &record.errors,
+                    failed_assertions: match state {
+                        TestState::Finished(finished) => &finished.failed_assertions,
+                        _ => &[],
+                    },
+                    input: record.input.as_ref(),
+                    prompt: record
+                        .prompt
+                        .as_ref()
+                        .map(|prompt| prompt.render(&record.template_args)),
+                    raw_output: record.raw_output.as_deref(),
+                    parsed_output: record.parsed_output.as_ref(),
+                })
+            })
+            .collect::<Vec<_>>();
+        report.write(&results, self.started_at.elapsed())
+    }
+
+    pub(crate) fn with_cassettes(mut self, cassettes: Cassettes) -> Self {
+        self.cassettes = Some(cassettes);
+        self
+    }
+
+    pub(crate) fn cassettes(&self) -> Option<&Cassettes> {
+        self.cassettes.as_ref()
+    }
+
     pub(crate) fn sync(&mut self) -> Option<String> {
         let mut additional = HashMap::new();
         let messages = std::mem::take(&mut self.messages);
@@ -157,28 +238,30 @@ impl RunState {
                 for (test, impls) in tests {
                     output += &format!("  {} {}", test, "(impls:".dimmed());
                     for impl_ in impls {
-                        let state = self.tests.get(&TestSpec {
+                        let outcome = self.outcome(&TestId {
                             function: function.clone(),
                             test: test.clone(),
                             r#impl: impl_.clone(),
                         });
 
-                        match state {
-                            Some(TestState::Queued) => {
+                        match outcome {
+                            Some(TestOutcome::Queued) => {
                                 output += &format!(" {}{}", "○".dimmed(), impl_.dimmed());
                             }
-                            Some(TestState::Running) => {
+                            Some(TestOutcome::Running) => {
                                 output += &format!(" {}{}", "●".dimmed(), impl_.dimmed());
                             }
-                            Some(TestState::Cancelled) => {
+                            Some(TestOutcome::Cancelled) => {
                                 output += &format!(" {}{}", "✕".dimmed(), impl_.dimmed());
                             }
-                            Some(TestState::Finished(state)) => {
-                                if state.passed {
-                                    output += &format!(" {}{}", "✔".green(), impl_.dimmed());
-                                } else {
-                                    output += &format!(" {}{}", "✖".red(), impl_.dimmed());
-                                }
+                            Some(TestOutcome::Passed) => {
+                                output += &format!(" {}{}", "✔".green(), impl_.dimmed());
+                            }
+                            Some(TestOutcome::Failed) => {
+                                output += &format!(" {}{}", "✖".red(), impl_.dimmed());
+                            }
+                            Some(TestOutcome::AssertionFailed) => {
+                                output += &format!(" {}{}", "✖".yellow(), impl_.dimmed());
                             }
                             None => {
                                 output += &format!(" {}", impl_.dimmed());
@@ -202,27 +285,29 @@ impl RunState {
                 for (test, impls) in tests {
                     output += &format!("  {}", test);
                     for impl_ in impls {
-                        let state = self.tests.get(&TestSpec {
+                        let outcome = self.outcome(&TestId {
                             function: function.clone(),
                             test: test.clone(),
                             r#impl: impl_.clone(),
                         });
-                        match state {
-                            Some(TestState::Queued) => {
+                        match outcome {
+                            Some(TestOutcome::Queued) => {
                                 output += &format!(" {}", "○".dimmed());
                             }
-                            Some(TestState::Running) => {
+                            Some(TestOutcome::Running) => {
                                 output += &format!(" {}", "●".dimmed());
                             }
-                            Some(TestState::Cancelled) => {
+                            Some(TestOutcome::Cancelled) => {
                                 output += &format!(" {}", "✕".dimmed());
                             }
-                            Some(TestState::Finished(state)) => {
-                                if state.passed {
-                                    output += &format!(" {}", "✔".green());
-                                } else {
-                                    output += &format!(" {}", "✖".red());
-                                }
+                            Some(TestOutcome::Passed) => {
+                                output += &format!(" {}", "✔".green());
+                            }
+                            Some(TestOutcome::Failed) => {
+                                output += &format!(" {}", "✖".red());
+                            }
+                            Some(TestOutcome::AssertionFailed) => {
+                                output += &format!(" {}", "✖".yellow());
                             }
                             None => {}
                         }
@@ -248,214 +333,238 @@ impl RunState {
         }
     }
 
-    pub(crate) fn add_message(&mut self, message: MessageData) {
-        self.messages.push(message);
+    pub(crate) fn add_event(&mut self, event: TestEvent) {
+        self.messages.push(event);
+    }
+
+    pub(crate) fn outcome(&self, test: &TestId) -> Option<TestOutcome> {
+        self.tests.get(test).map(|state| match state {
+            TestState::Queued => TestOutcome::Queued,
+            TestState::Running => TestOutcome::Running,
+            TestState::Cancelled => TestOutcome::Cancelled,
+            TestState::Finished(FinishedState { passed: false, .. }) => TestOutcome::Failed,
+            TestState::Finished(FinishedState {
+                failed_assertions, ..
+            }) if !failed_assertions.is_empty() => TestOutcome::AssertionFailed,
+            TestState::Finished(_) => TestOutcome::Passed,
+        })
     }
 
-    fn update_test_state(&mut self, message: MessageData) -> Option<(TestSpec, String)> {
-        match message {
-            MessageData::TestRunMeta(meta) => {
-                self.dashboard_url = Some(meta.dashboard_url);
+    /// Checks the assertions of a test against its parsed output, and returns the ones that
+    /// failed.
+    fn failed_assertions(&self, test: &TestId, output: &serde_json::Value) -> Vec<String> {
+        self.schema
+            .walk_test_cases()
+            .find(|t| t.test_case().function.0 == test.function && t.name() == test.test)
+            .map(|t| {
+                t.test_case()
+                    .assertions
+                    .iter()
+                    .filter_map(|assertion| assertion.evaluate(output).err())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    fn update_test_state(&mut self, event: TestEvent) -> Option<(TestId, String)> {
+        match event {
+            TestEvent::RunStarted { dashboard_url } => {
+                self.dashboard_url = dashboard_url;
                 self.executor_stage = ExecutorStage::Parsed;
                 None
             }
-            MessageData::UpdateTestCase(update) => {
-                // update.test_case_arg_name is of form "<test>[<function>-<impl>]"
-                let mut parts = update.test_case_arg_name.split('[');
-                let test = parts.next().unwrap();
-                // Strip leading "test_"
-                let test = &test[5..];
-
-                let mut parts = parts.next().unwrap().split(']');
-                let mut parts = parts.next().unwrap().split('-');
-                let function = parts.next().unwrap();
-                let r#impl = parts.next().unwrap();
-
-                let key = TestSpec {
-                    function: function.into(),
-                    test: test.into(),
-                    r#impl: r#impl.into(),
-                };
-
-                let new_state = match update.status {
-                    TestCaseStatus::Queued => TestState::Queued,
-                    TestCaseStatus::Running => TestState::Running,
-                    TestCaseStatus::Cancelled => TestState::Cancelled,
-                    TestCaseStatus::ExpectedFailure => TestState::Finished(FinishedState {
-                        passed: true,
-                        log: None,
-                    }),
-                    TestCaseStatus::Passed => TestState::Finished(FinishedState {
+            TestEvent::TestStarted { test } => {
+                let state = self.tests.get_mut(&test)?;
+                *state = TestState::Running;
+                self.records.entry(test).or_default().started_at = Some(Instant::now());
+                None
+            }
+            TestEvent::TestFinished {
+                test,
+                status,
+                error,
+            } => {
+                let state = self.tests.get_mut(&test)?;
+                *state = match status {
+                    TestStatus::Passed => TestState::Finished(FinishedState {
                         passed: true,
-                        log: None,
+                        failed_assertions: vec![],
                     }),
-                    TestCaseStatus::Failed => TestState::Finished(FinishedState {
+                    TestStatus::Failed => TestState::Finished(FinishedState {
                         passed: false,
-                        log: None,
+                        failed_assertions: vec![],
                     }),
+                    TestStatus::Cancelled => TestState::Cancelled,
                 };
+                let record = self.records.entry(test.clone()).or_default();
+                record.duration = record.started_at.map(|started_at| started_at.elapsed());
+                record.errors.extend(error.clone());
 
-                let state = self.tests.get_mut(&key);
-                let state = state.unwrap();
-                *state = new_state;
-
-                match update.error_data {
-                    Some(error) => Some((key, format!("{}", error.to_string().red()))),
-                    None => None,
-                }
+                error.map(|error| (test, format!("{}", error.red())))
             }
-            MessageData::Log(log) => {
-                // Log messages always come after the test case update
-                {
-                    let test_name = log.context.tags.get("test_case_arg_name");
-                    if let Some(test_name) = test_name {
-                        let mut parts = test_name.split('[');
-                        let test = parts.next().unwrap();
-                        // Strip leading "test_"
-                        let test = &test[5..];
-
-                        let mut parts = parts.next().unwrap().split(']');
-                        let mut parts = parts.next().unwrap().split('-');
-                        let function = parts.next().unwrap();
-                        let r#impl = parts.next().unwrap();
-
-                        Some(TestSpec {
-                            function: function.into(),
-                            test: test.into(),
-                            r#impl: r#impl.into(),
-                        })
-                    } else {
-                        None
-                    }
+            TestEvent::TestOutput {
+                test,
+                input,
+                prompt,
+                template_args,
+                raw_output: llm_raw_output,
+                parsed_output,
+                client,
+                error: err,
+            } => {
+                // Output is only printed once the test has finished.
+                if !matches!(self.tests.get(&test), Some(TestState::Finished(_))) {
+                    return None;
                 }
-                .and_then(|spec| {
-                    let state = self.tests.get_mut(&spec);
-                    if let Some(state) = state {
-                        if let TestState::Finished(state) = state {
-                            let (llm_prompt, llm_raw_output) =
-                                match log.metadata.as_ref().and_then(|meta| {
-                                    // TODO: Swap out template vars
-                                    let input = match &meta.input.prompt.template {
-                                        Template::Single(o) => o.clone(),
-                                        Template::Multiple(chats) => chats
-                                            .iter()
-                                            .map(|c| {
-                                                format!(
-                                                    "{}:\n{}",
-                                                    c.role.as_str().yellow().bold(),
-                                                    c.content.white()
-                                                )
-                                            })
-                                            .collect::<Vec<_>>()
-                                            .join("\n"),
-                                    };
-
-                                    let mut colored_input = input.clone();
-                                    meta.input.prompt.template_args.iter().for_each(|(k, v)| {
-                                        let replacement = format!("{}", v.blue()); // Colorize the replacement text in magenta
-                                        colored_input = colored_input.replace(k, &replacement);
-                                    });
-
-                                    let raw_output = match &meta.output {
-                                        Some(output) => Some(output.raw_text.clone()),
-                                        None => None,
-                                    };
-
-                                    Some((colored_input, raw_output))
-                                }) {
-                                    Some((llm_prompt, llm_raw_output)) => {
-                                        (Some(llm_prompt), llm_raw_output)
-                                    }
-                                    None => (None, None),
-                                };
-
-                            let err = log.error.as_ref().and_then(|error| match &error.traceback {
-                                Some(traceback) => {
-                                    Some(format!("{}\n{}", error.message, traceback))
-                                }
-                                None => Some(error.message.clone()),
-                            });
-
-                            let parsed_output = match log.io.output.as_ref().and_then(|output| {
-                                let output = match &output.value {
-                                    ValueType::String(s) => serde_json::Value::from_str(s),
-                                    ValueType::List(l) => l
-                                        .iter()
-                                        .map(|v| serde_json::Value::from_str(v))
-                                        .collect::<Result<Vec<_>, _>>()
-                                        .map(|v| serde_json::Value::Array(v)),
-                                }
-                                .map(|v| {
-                                    serde_json::to_string_pretty(&v).unwrap_or_else(|_| {
-                                        format!("Failed to serialize output: {:?}", v)
-                                    })
-                                })
-                                .ok();
-                                let r#type = self
-                                    .schema
-                                    .find_function_by_name(&spec.function)
-                                    .and_then(|f| {
-                                        Some(
-                                            f.walk_output_args()
-                                                .map(|w| w.ast_arg().1.field_type.to_string())
-                                                .collect::<Vec<_>>()
-                                                .join(", "),
-                                        )
-                                    });
-                                Some((output, r#type))
-                            }) {
-                                Some((Some(output), Some(r#type))) => Some((output, r#type)),
-                                _ => None,
-                            };
-
-                            state.log = Some(log);
-
-                            let res = match (llm_prompt, llm_raw_output, err, parsed_output) {
-                                (Some(llm_prompt), Some(llm_raw_output), Some(err), _) => vec![
-                                    format!("\n{}", "---- Prompt ---------".dimmed()),
-                                    format!("{}", llm_prompt),
-                                    format!("\n{}", "---- Raw Response ---".dimmed()),
-                                    format!("{}", llm_raw_output.white()),
-                                    format!("{}", "----- Error -----".dimmed()),
-                                    format!("{}", err.red()),
-                                ],
-                                (Some(llm_prompt), None, Some(err), _) => vec![
-                                    format!("\n{}", "---- Prompt ---------".dimmed()),
-                                    format!("{}", llm_prompt),
-                                    format!("{}", "----- Error -----".dimmed()),
-                                    format!("{}", err.red()),
-                                ],
-                                (
-                                    Some(llm_prompt),
-                                    Some(llm_raw_output),
-                                    None,
-                                    Some((output, output_type)),
-                                ) => {
-                                    vec![
-                                        format!("\n{}", "------- Prompt ------".yellow()),
-                                        format!("{}", llm_prompt),
-                                        format!("\n{}", "---- Raw Response ---".dimmed()),
-                                        format!("{}", llm_raw_output.dimmed()),
-                                        format!(
-                                            "\n{}{}{}",
-                                            "----- Parsed Response (".green(),
-                                            output_type.green(),
-                                            ") -----".green()
-                                        ),
-                                        format!("{}", output.green()),
-                                    ]
-                                }
-                                _ => vec![],
-                            }
-                            .join("\n");
 
-                            if !res.is_empty() {
-                                return Some((spec, res));
+                // A test may log several outputs, e.g. one per traced span, and only some of them
+                // carry the parsed output. Failures found on any of them stand. Assertions only
+                // matter if the test itself passed.
+                if let Some(output) = &parsed_output {
+                    let failed_assertions = self.failed_assertions(&test, output);
+                    if let Some(TestState::Finished(finished)) = self.tests.get_mut(&test) {
+                        if finished.passed {
+                            for failure in failed_assertions {
+                                if !finished.failed_assertions.contains(&failure) {
+                                    finished.failed_assertions.push(failure);
+                                }
                             }
                         }
                     }
+                }
+
+                let cassette_error = match (&self.cassettes, &prompt) {
+                    (Some(cassettes), Some(prompt)) => match cassettes.mode() {
+                        CassetteMode::Record => llm_raw_output.as_ref().and_then(|raw_output| {
+                            cassettes
+                                .record(&test, prompt, &template_args, client.as_ref(), raw_output)
+                                .err()
+                        }),
+                        CassetteMode::Replay => cassettes
+                            .check(&test, prompt, &template_args, client.as_ref())
+                            .err(),
+                    },
+                    _ => None,
+                };
+                // A replayed response is only valid for the prompt it was recorded with.
+                if let (Some(_), Some(CassetteMode::Replay)) = (
+                    &cassette_error,
+                    self.cassettes.as_ref().map(Cassettes::mode),
+                ) {
+                    if let Some(TestState::Finished(finished)) = self.tests.get_mut(&test) {
+                        finished.passed = false;
+                    }
+                }
+
+                let record = self.records.entry(test.clone()).or_default();
+                // Keep what earlier outputs of the test logged if this one lacks it.
+                record.input = input.or(record.input.take());
+                if prompt.is_some() {
+                    record.prompt = prompt.clone();
+                    record.template_args = template_args.clone();
+                }
+                record.raw_output = llm_raw_output.clone().or(record.raw_output.take());
+                record.parsed_output = parsed_output.clone().or(record.parsed_output.take());
+                record.errors.extend(err.clone());
+                record.errors.extend(cassette_error.clone());
+
+                let llm_prompt = prompt.map(|prompt| {
+                    // TODO: Swap out template vars
+                    let input = match prompt {
+                        Prompt::Completion(o) => o,
+                        Prompt::Chat(chats) => chats
+                            .iter()
+                            .map(|c| format!("{}:\n{}", c.role.yellow().bold(), c.content.white()))
+                            .collect::<Vec<_>>()
+                            .join("\n"),
+                    };
+
+                    let mut colored_input = input.clone();
+                    template_args.iter().for_each(|(k, v)| {
+                        let replacement = format!("{}", v.blue()); // Colorize the replacement text in magenta
+                        colored_input = colored_input.replace(k, &replacement);
+                    });
+                    colored_input
+                });
+
+                let parsed_output = parsed_output.and_then(|output| {
+                    let output = serde_json::to_string_pretty(&output)
+                        .unwrap_or_else(|_| format!("Failed to serialize output: {:?}", output));
+                    let r#type = self.schema.find_function_by_name(&test.function).map(|f| {
+                        f.walk_output_args()
+                            .map(|w| w.ast_arg().1.field_type.to_string())
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    })?;
+                    Some((output, r#type))
+                });
+
+                let res = match (llm_prompt, llm_raw_output, err, parsed_output) {
+                    (Some(llm_prompt), Some(llm_raw_output), Some(err), _) => vec![
+                        format!("\n{}", "---- Prompt ---------".dimmed()),
+                        format!("{}", llm_prompt),
+                        format!("\n{}", "---- Raw Response ---".dimmed()),
+                        format!("{}", llm_raw_output.white()),
+                        format!("{}", "----- Error -----".dimmed()),
+                        format!("{}", err.red()),
+                    ],
+                    (Some(llm_prompt), None, Some(err), _) => vec![
+                        format!("\n{}", "---- Prompt ---------".dimmed()),
+                        format!("{}", llm_prompt),
+                        format!("{}", "----- Error -----".dimmed()),
+                        format!("{}", err.red()),
+                    ],
+                    (Some(llm_prompt), Some(llm_raw_output), None, Some((output, output_type))) => {
+                        vec![
+                            format!("\n{}", "------- Prompt ------".yellow()),
+                            format!("{}", llm_prompt),
+                            format!("\n{}", "---- Raw Response ---".dimmed()),
+                            format!("{}", llm_raw_output.dimmed()),
+                            format!(
+                                "\n{}{}{}",
+                                "----- Parsed Response (".green(),
+                                output_type.green(),
+                                ") -----".green()
+                            ),
+                            format!("{}", output.green()),
+                        ]
+                    }
+                    _ => vec![],
+                }
+                .join("\n");
+                let res = match self.tests.get(&test) {
+                    Some(TestState::Finished(FinishedState {
+                        failed_assertions, ..
+                    })) if !failed_assertions.is_empty() => {
+                        let failures = failed_assertions
+                            .iter()
+                            .map(|failure| format!("{}", failure.yellow()))
+                            .collect::<Vec<_>>()
+                            .join("\n");
+                        format!(
+                            "{}\n{}\n{}",
+                            res,
+                            "---- Failed Assertions ----".dimmed(),
+                            failures
+                        )
+                    }
+                    _ => res,
+                };
+                let res = match cassette_error {
+                    Some(error) => format!(
+                        "{}\n{}\n{}",
+                        res,
+                        "----- Cassette -----".dimmed(),
+                        error.red()
+                    ),
+                    None => res,
+                };
+
+                if res.is_empty() {
                     None
-                })
+                } else {
+                    Some((test, res))
+                }
             }
         }
     }
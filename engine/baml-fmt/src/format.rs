@@ -0,0 +1,28 @@
+use std::{
+    fs,
+    io::{self, Read},
+};
+
+use baml_lib::internal_baml_schema_ast::{ast::IndentationType, reformat};
+
+use crate::FormatOpts;
+
+pub fn run(opts: FormatOpts) {
+    let schema = match opts.input {
+        Some(file_name) => fs::read_to_string(&file_name).expect("Unable to open file"),
+        None => {
+            let mut buf = String::new();
+            io::stdin()
+                .read_to_string(&mut buf)
+                .expect("Unable to read from stdin.");
+            buf
+        }
+    };
+
+    let reformatted = reformat(&schema, IndentationType::Spaces(opts.tabwidth)).unwrap_or(schema);
+
+    match opts.output {
+        Some(file_name) => fs::write(file_name, reformatted).expect("Unable to write file"),
+        None => print!("{}", reformatted),
+    }
+}
@@ -7,11 +7,18 @@
 mod generate_test_file;
 mod lint;
 // mod native;
+mod parse_output;
 // mod preview;
+mod rename;
+mod render_prompt;
 // mod text_document_completion;
 mod validate;
 
-use baml_lib::{internal_baml_parser_database::ast, SourceFile};
+use baml_lib::{
+    internal_baml_parser_database::ast,
+    internal_baml_schema_ast::{ast::IndentationType, reformat},
+    SourceFile,
+};
 
 use lsp_types::{Position, Range};
 
@@ -46,24 +53,31 @@ use lsp_types::{Position, Range};
 // }
 
 /// The two parameters are:
-/// - The Prisma schema to reformat, as a string.
+/// - The BAML schema to reformat, as a string.
 /// - An LSP
 /// [DocumentFormattingParams](https://github.com/microsoft/language-server-protocol/blob/gh-pages/_specifications/specification-3-16.md#textDocument_formatting) object, as JSON.
 ///
-/// The function returns the formatted schema, as a string.
+/// The function returns the formatted schema, as a string. If the schema does not parse, it is
+/// returned unchanged.
 ///
-/// Of the DocumentFormattingParams, we only take into account tabSize, at the moment.
-// pub fn format(schema: &str, params: &str) -> String {
-//     let params: lsp_types::DocumentFormattingParams = match serde_json::from_str(params) {
-//         Ok(params) => params,
-//         Err(err) => {
-//             warn!("Error parsing DocumentFormattingParams params: {}", err);
-//             return schema.to_owned();
-//         }
-//     };
+/// Of the DocumentFormattingParams, we only take into account tabSize and insertSpaces, at the moment.
+pub fn format(schema: &str, params: &str) -> String {
+    let params: lsp_types::DocumentFormattingParams = match serde_json::from_str(params) {
+        Ok(params) => params,
+        Err(err) => {
+            log::warn!("Error parsing DocumentFormattingParams params: {}", err);
+            return schema.to_owned();
+        }
+    };
 
-//     psl::reformat(schema, params.options.tab_size as usize).unwrap_or_else(|| schema.to_owned())
-// }
+    let indentation = if params.options.insert_spaces {
+        IndentationType::Spaces(params.options.tab_size as usize)
+    } else {
+        IndentationType::Tabs
+    };
+
+    reformat(schema, indentation).unwrap_or_else(|| schema.to_owned())
+}
 
 pub fn lint(schema: String) -> String {
     lint::run(&schema)
@@ -73,6 +87,47 @@ pub fn generate_test_file(schema: String) -> String {
     generate_test_file::run(&schema)
 }
 
+/// Renames the class, enum, enum value, field, type alias, function, impl or client at a byte
+/// offset, updating every reference to it, including `override` blocks and prompt variables.
+///
+/// The input is the JSON accepted by `lint()`, with the `path` of the file the cursor is in, the
+/// `offset` of the cursor and the `new_name`. The response holds the `edits` to apply:
+///
+/// ```ignore
+/// { start: number, end: number, source_file: string, new_text: string }[]
+/// ```
+pub fn rename(input: String) -> String {
+    rename::run(&input)
+}
+
+/// Renders the prompt an impl sends to the model, with the input of a test or a given input
+/// filled in, along with the output of its `{#print_type(..)}` and `{#print_enum(..)}` blocks.
+///
+/// The input is the JSON accepted by `lint()`, with the `function` and `impl` to render, and
+/// either the name of a `test` of the function or the `input` itself. The response holds the
+/// `prompt`, which is a string, or the messages of a chat prompt:
+///
+/// ```ignore
+/// { role: string, content: string }[]
+/// ```
+pub fn render_prompt(input: String) -> String {
+    render_prompt::run(&input)
+}
+
+/// Parses what a model returned for a function into its output type, the same way every
+/// generated client does.
+///
+/// The input is the JSON accepted by `lint()`, with the `function`, optionally the `impl` whose
+/// aliases and output adapter to use, and the model's `output`. The response holds the parsed
+/// `value` and the `fixes` it took to parse it:
+///
+/// ```ignore
+/// { path: string, kind: string, ... }[]
+/// ```
+pub fn parse_output(input: String) -> String {
+    parse_output::run(&input)
+}
+
 /// Function that throws a human-friendly error message when the schema is invalid, following the JSON formatting
 /// historically used by the Query Engine's `user_facing_errors::common::SchemaParserError`.
 /// When the schema is valid, nothing happens.
@@ -252,4 +307,136 @@ mod tests {
 
         assert_eq!(found_offset, expected_offset);
     }
+
+    fn format_params(tab_size: u32, insert_spaces: bool) -> String {
+        serde_json::json!({
+            "textDocument": { "uri": "file:///main.baml" },
+            "options": { "tabSize": tab_size, "insertSpaces": insert_spaces },
+        })
+        .to_string()
+    }
+
+    #[test]
+    fn format_normalizes_blocks() {
+        let schema = indoc::indoc! {r##"
+            // Leading comment
+            class   Foo {
+                  name    string   @alias(n)   // trailing
+              tags string[]?
+
+
+              age int
+                @description(#"
+                      The age.
+                "#)
+            }
+            enum Color {
+            RED
+            }
+            impl<llm,   Fn> v1 {
+                client   GPT4
+              prompt #"
+                      Hello {#input}
+
+                        Indented
+                    "#
+            }
+        "##};
+
+        let expected = indoc::indoc! {r##"
+            // Leading comment
+            class Foo {
+              name string @alias(n) // trailing
+              tags string[]?
+
+              age int
+                @description(#"
+                  The age.
+                "#)
+            }
+
+            enum Color {
+              RED
+            }
+
+            impl<llm, Fn> v1 {
+              client GPT4
+              prompt #"
+                Hello {#input}
+
+                  Indented
+              "#
+            }
+        "##};
+
+        let formatted = super::format(schema, &format_params(2, true));
+        assert_eq!(formatted, expected);
+        assert_eq!(
+            super::format(&formatted, &format_params(2, true)),
+            formatted
+        );
+    }
+
+    #[test]
+    fn format_honors_indentation_options() {
+        let schema = "client<llm> GPT4 {\nprovider openai\noptions {\nmodel gpt-4\n}\n}\n";
+
+        assert_eq!(
+            super::format(schema, &format_params(4, true)),
+            "client<llm> GPT4 {\n    provider openai\n    options {\n        model gpt-4\n    }\n}\n"
+        );
+        assert_eq!(
+            super::format(schema, &format_params(2, false)),
+            "client<llm> GPT4 {\n\tprovider openai\n\toptions {\n\t\tmodel gpt-4\n\t}\n}\n"
+        );
+    }
+
+    #[test]
+    fn format_type_alias() {
+        let schema = "type   Foo =   string[] |   Bar   // trailing\n";
+        assert_eq!(
+            super::format(schema, &format_params(2, true)),
+            "type Foo = string[] | Bar // trailing\n"
+        );
+    }
+
+    #[test]
+    fn format_array_of_maps() {
+        let schema = indoc::indoc! {r##"
+            client<llm> Resilient {
+              provider baml-fallback
+              options {
+                strategy [GPT4, { client Claude, retry_policy Quick }]
+              }
+            }
+        "##};
+
+        let expected = indoc::indoc! {r##"
+            client<llm> Resilient {
+              provider baml-fallback
+              options {
+                strategy [
+                  GPT4
+                  {
+                    client Claude
+                    retry_policy Quick
+                  }
+                ]
+              }
+            }
+        "##};
+
+        let formatted = super::format(schema, &format_params(2, true));
+        assert_eq!(formatted, expected);
+        assert_eq!(
+            super::format(&formatted, &format_params(2, true)),
+            formatted
+        );
+    }
+
+    #[test]
+    fn format_leaves_unparsable_schema_untouched() {
+        let schema = "class Foo {\n  name string\n";
+        assert_eq!(super::format(schema, &format_params(2, true)), schema);
+    }
 }
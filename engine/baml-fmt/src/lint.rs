@@ -8,8 +8,9 @@ mod jsonschema;
 use jsonschema::WithJsonSchema;
 
 use baml_lib::{
+    internal_baml_core::tokenizer::prompt_tokens,
     internal_baml_diagnostics::{DatamodelError, DatamodelWarning, Span},
-    internal_baml_parser_database::PromptRepr,
+    internal_baml_parser_database::{walkers::VariantWalker, PromptRepr},
     internal_baml_schema_ast::ast::{self, WithIdentifier, WithName, WithSpan},
     SourceFile,
 };
@@ -35,11 +36,11 @@ impl StringSpan {
 
 #[derive(serde::Serialize)]
 pub struct MiniError {
-    start: usize,
-    end: usize,
-    text: String,
-    is_warning: bool,
-    source_file: String,
+    pub(crate) start: usize,
+    pub(crate) end: usize,
+    pub(crate) text: String,
+    pub(crate) is_warning: bool,
+    pub(crate) source_file: String,
 }
 
 #[derive(Deserialize)]
@@ -154,7 +155,7 @@ pub(crate) fn run(input: &str) -> String {
                         let props = t.test_case();
                         json!({
                             "name": StringSpan::new(t.name(), &t.identifier().span()),
-                            "content": props.content.value(),
+                            "content": props.input.to_json().to_string(),
                         })
                     }
                 ).collect::<Vec<_>>(),
@@ -207,6 +208,7 @@ pub(crate) fn run(input: &str) -> String {
                                 })
                             ).collect::<Vec<_>>(),
                             "client": schema.db.find_client(&props.client.value).map(|c| StringSpan::new(c.name(), &c.identifier().span())).unwrap_or_else(|| StringSpan::new(&props.client.value, &props.client.span)),
+                            "tokens": token_counts(i),
 
                         })
                     }
@@ -219,7 +221,23 @@ pub(crate) fn run(input: &str) -> String {
     print_diagnostics(mini_errors, Some(response))
 }
 
-fn print_diagnostics(diagnostics: Vec<MiniError>, response: Option<Value>) -> String {
+/// How many tokens the prompt of an impl takes up before its inputs are added, and how much of
+/// its model's context window that leaves.
+fn token_counts(variant: VariantWalker<'_>) -> Value {
+    let tokens = prompt_tokens(variant);
+    json!({
+        "encoding": tokens.encoding.name(),
+        "exact": tokens.exact,
+        "prompt": tokens.prompt,
+        "output_schema": tokens.output_schema,
+        "model": tokens.model,
+        "context_size": tokens.context_size,
+        "max_tokens": tokens.max_tokens,
+        "remaining": tokens.remaining(),
+    })
+}
+
+pub(crate) fn print_diagnostics(diagnostics: Vec<MiniError>, response: Option<Value>) -> String {
     return json!({
         "ok": response.is_some(),
         "diagnostics": diagnostics,
@@ -1,6 +1,6 @@
 use baml_lib::{
     internal_baml_parser_database::walkers::{ClassWalker, EnumWalker},
-    internal_baml_schema_ast::ast::{FieldType, Identifier, TypeValue, WithName},
+    internal_baml_schema_ast::ast::{FieldType, Identifier, LiteralValue, TypeValue, WithName},
 };
 use serde_json::{json, Value};
 
@@ -56,6 +56,14 @@ impl WithJsonSchema for FieldType {
                 }),
                 _ => panic!("Not implemented"),
             },
+            FieldType::Literal(_, value, _) => json!({
+                "type": match value {
+                    LiteralValue::String(_) => "string",
+                    LiteralValue::Int(_) => "integer",
+                    LiteralValue::Bool(_) => "boolean",
+                },
+                "const": value,
+            }),
             FieldType::List(item, dims, _) => {
                 let mut inner = json!({
                     "type": "array",
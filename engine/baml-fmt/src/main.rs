@@ -1,5 +1,6 @@
 #![allow(dead_code)]
 
+mod format;
 mod generate_test_file;
 mod lint;
 
@@ -34,7 +35,7 @@ pub enum FmtOpts {
     /// Specifiers the generate test code mode (this is temporary until baml cli is ready)
     GenerateTestCode,
     /// Specifies format mode
-    // Format(FormatOpts),
+    Format(FormatOpts),
     /// Specifies Native Types mode
     // NativeTypes,
     /// List of available referential actions
@@ -50,7 +51,7 @@ fn main() {
         FmtOpts::DebugPanic => panic!("This is the debugPanic artificial panic"),
         FmtOpts::Lint => plug(lint::run),
         FmtOpts::GenerateTestCode => plug(generate_test_file::run),
-        // FmtOpts::Format(opts) => format::run(opts),
+        FmtOpts::Format(opts) => format::run(opts),
         // FmtOpts::NativeTypes => plug(native::run),
         // FmtOpts::ReferentialActions => plug(actions::run),
         // FmtOpts::PreviewFeatures => plug(|_s| preview::run()),
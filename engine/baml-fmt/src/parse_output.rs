@@ -0,0 +1,156 @@
+use serde::Deserialize;
+use serde_json::json;
+use std::{path::PathBuf, sync::Arc};
+
+use baml_lib::{internal_baml_core::ir, internal_baml_jsonish::Deserializer, SourceFile};
+
+use crate::{
+    lint::print_diagnostics,
+    render_prompt::{mini_error, request_error},
+};
+
+#[derive(Deserialize)]
+struct File {
+    path: String,
+    content: String,
+}
+
+#[derive(Deserialize)]
+struct Input {
+    root_path: String,
+    files: Vec<File>,
+    function: String,
+    /// The impl whose aliases and output adapter to use.
+    r#impl: Option<String>,
+    /// What the model returned.
+    output: String,
+}
+
+/// Parses what a model returned for a function into its output type. The schema must be valid.
+pub(crate) fn run(input: &str) -> String {
+    let input: Input = serde_json::from_str(input).expect("Failed to parse input");
+
+    let files: Vec<SourceFile> = input
+        .files
+        .into_iter()
+        .map(|file| SourceFile::new_allocated(file.path.into(), Arc::from(file.content)))
+        .collect();
+
+    let schema = baml_lib::validate(&PathBuf::from(input.root_path), files);
+    if schema.diagnostics.has_errors() {
+        let errors = schema.diagnostics.errors().iter().map(mini_error).collect();
+        return print_diagnostics(errors, None);
+    }
+
+    let ir = match ir::to_ir(&schema.db) {
+        Ok(ir) => ir,
+        Err(err) => return request_error(err.to_string()),
+    };
+    let Some(function) = ir.find_function(&input.function) else {
+        return request_error(format!("Unknown function `{}`.", input.function));
+    };
+
+    let mut deserializer = Deserializer::new(&ir);
+    let mut target = &function.elem().output.elem;
+    if let Some(name) = &input.r#impl {
+        let Some(r#impl) = function.walk_impls().find(|i| i.elem().name == *name) else {
+            return request_error(format!(
+                "Function `{}` has no impl `{}`.",
+                input.function, name
+            ));
+        };
+        // The model returns what the output adapter takes.
+        if let Some(adapter) = &r#impl.elem().output_adapter {
+            target = &adapter.from;
+        }
+        deserializer = deserializer.for_impl(&input.function, name);
+    }
+
+    match deserializer.from_str(&input.output, target) {
+        Ok(parsed) => print_diagnostics(vec![], Some(json!(parsed))),
+        Err(err) => request_error(err.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+
+    const SCHEMA: &str = r##"
+enum Mood {
+  Happy
+  Sad
+}
+
+class Reply {
+  mood Mood
+  text string @alias("message")
+}
+
+function Respond {
+  input string
+  output Reply
+}
+
+impl<llm, Respond> v1 {
+  client GPT4
+  override Mood {
+    Sad @alias("down")
+  }
+  prompt #"
+    Reply to {#input}.
+  "#
+}
+
+client<llm> GPT4 {
+  provider baml-openai-chat
+  options {
+    model gpt-4
+  }
+}
+"##;
+
+    fn parse(request: Value) -> Value {
+        let mut input = json!({
+            "root_path": "/project/baml_src",
+            "files": [{ "path": "/project/baml_src/main.baml", "content": SCHEMA }],
+            "function": "Respond",
+        });
+        input
+            .as_object_mut()
+            .unwrap()
+            .extend(request.as_object().unwrap().clone());
+        serde_json::from_str(&super::run(&input.to_string())).unwrap()
+    }
+
+    #[test]
+    fn parse_output_with_impl_aliases() {
+        let result = parse(json!({
+            "impl": "v1",
+            "output": "```json\n{\"mood\": \"down\", \"message\": \"Sorry!\"}\n```",
+        }));
+        assert_eq!(
+            result["response"],
+            json!({
+                "value": { "mood": "Sad", "text": "Sorry!" },
+                "fixes": [{ "path": "", "kind": "stripped_markdown" }],
+            })
+        );
+    }
+
+    #[test]
+    fn parse_output_reports_errors() {
+        let result = parse(json!({ "output": "{\"mood\": \"down\"}" }));
+        assert_eq!(result["ok"], false);
+        assert_eq!(
+            result["diagnostics"][0]["text"],
+            "mood: `down` is not a value of `Mood`. Expected one of: Happy, Sad"
+        );
+
+        let result = parse(json!({ "impl": "v2", "output": "" }));
+        assert_eq!(
+            result["diagnostics"][0]["text"],
+            "Function `Respond` has no impl `v2`."
+        );
+    }
+}
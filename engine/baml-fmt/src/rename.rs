@@ -0,0 +1,327 @@
+use serde::Deserialize;
+use serde_json::json;
+use std::{path::PathBuf, sync::Arc};
+
+use baml_lib::{internal_baml_diagnostics::DatamodelError, SourceFile};
+
+use crate::lint::{print_diagnostics, MiniError};
+
+#[derive(Deserialize)]
+struct File {
+    path: String,
+    content: String,
+}
+
+#[derive(Deserialize)]
+struct Input {
+    root_path: String,
+    files: Vec<File>,
+    /// The file the cursor is in.
+    path: String,
+    /// The byte offset of the cursor in that file.
+    offset: usize,
+    new_name: String,
+}
+
+fn mini_error(err: &DatamodelError) -> MiniError {
+    MiniError {
+        start: err.span().start,
+        end: err.span().end,
+        text: err.message().to_string(),
+        is_warning: false,
+        source_file: err.span().file.path(),
+    }
+}
+
+/// Renames the item at the cursor. The response holds the edits to apply, as byte ranges of
+/// each file; it is empty if there is nothing to rename at the cursor. The schema must be valid.
+pub(crate) fn run(input: &str) -> String {
+    let input: Input = serde_json::from_str(input).expect("Failed to parse input");
+
+    let files: Vec<SourceFile> = input
+        .files
+        .into_iter()
+        .map(|file| SourceFile::new_allocated(file.path.into(), Arc::from(file.content)))
+        .collect();
+
+    let schema = baml_lib::validate(&PathBuf::from(input.root_path), files);
+    if schema.diagnostics.has_errors() {
+        let errors = schema.diagnostics.errors().iter().map(mini_error).collect();
+        return print_diagnostics(errors, None);
+    }
+
+    let db = &schema.db;
+    let edits = match db.rename_target_at(&PathBuf::from(input.path), input.offset) {
+        Some(target) => match db.rename(target, &input.new_name) {
+            Ok(edits) => edits,
+            Err(err) => return print_diagnostics(vec![mini_error(&err)], None),
+        },
+        None => vec![],
+    };
+
+    let response = json!({
+        "edits": edits.iter().map(|edit| json!({
+            "start": edit.span.start,
+            "end": edit.span.end,
+            "source_file": edit.span.file.path(),
+            "new_text": edit.new_text,
+        })).collect::<Vec<_>>(),
+    });
+
+    print_diagnostics(vec![], Some(response))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+
+    const TYPES: &str = r##"
+class Address {
+  city string
+}
+
+class Person {
+  name string
+  home Address?
+}
+
+enum Mood {
+  Happy
+  Sad
+}
+
+type People = Person[]
+"##;
+
+    const FUNCTIONS: &str = r##"
+function Greet {
+  input (person: Person, mood: Mood)
+  output string
+  default_impl v1
+}
+
+impl<llm, Greet> v1 {
+  client GPT4
+  override Person {
+    name @alias(full_name)
+  }
+  override Mood {
+    Happy @alias(glad)
+  }
+  prompt #"
+    Say hi to {#input.person.name} from {#input.person.home.city}.
+    {#print_enum(Mood)}
+  "#
+}
+
+test greet_bob {
+  function Greet
+  impls [v1]
+  input {
+    person {
+      name Bob
+    }
+    mood Sad
+  }
+}
+
+function Count {
+  input People
+  output Person
+}
+
+impl<llm, Count> v1 {
+  client "GPT4"
+  prompt #"
+    How many of {#input} are there?
+    {#print_type(Person)}
+  "#
+}
+
+function Guess {
+  input string
+  output Mood
+}
+
+test guess_mood {
+  function Guess
+  input "I won!"
+  assert [
+    {
+      path "$"
+      one_of [Happy]
+    }
+  ]
+}
+
+client<llm> GPT4 {
+  provider baml-openai-chat
+  options {
+    model gpt-4
+  }
+}
+"##;
+
+    const TESTS: &str = r##"{
+  "input": {
+    "person": { "name": "Ann", "home": { "city": "Oslo" } },
+    "mood": "Happy"
+  }
+}
+"##;
+
+    /// Renames what is at the `nth` occurrence of `needle` and returns the edited files, or
+    /// the first error.
+    fn rename(file: &str, needle: &str, nth: usize, new_name: &str) -> Result<Vec<String>, String> {
+        let contents = match file {
+            "types.baml" => TYPES,
+            "functions.baml" => FUNCTIONS,
+            _ => TESTS,
+        };
+        let (offset, _) = contents.match_indices(needle).nth(nth).unwrap();
+        let input = json!({
+            "root_path": "/project/baml_src",
+            "files": [
+                { "path": "/project/baml_src/types.baml", "content": TYPES },
+                { "path": "/project/baml_src/functions.baml", "content": FUNCTIONS },
+                { "path": "/project/baml_src/__tests__/Greet/ann.json", "content": TESTS },
+            ],
+            "path": format!("/project/baml_src/{}", file),
+            "offset": offset + 1,
+            "new_name": new_name,
+        });
+        let result: Value = serde_json::from_str(&super::run(&input.to_string())).unwrap();
+        if result["ok"] != json!(true) {
+            return Err(result["diagnostics"][0]["text"]
+                .as_str()
+                .unwrap()
+                .to_string());
+        }
+
+        let mut files = vec![TYPES.to_string(), FUNCTIONS.to_string(), TESTS.to_string()];
+        let mut edits = result["response"]["edits"].as_array().unwrap().clone();
+        // Apply from the end so earlier offsets stay valid.
+        edits.reverse();
+        for edit in edits {
+            let index = match edit["source_file"].as_str().unwrap() {
+                "/project/baml_src/types.baml" => 0,
+                "/project/baml_src/functions.baml" => 1,
+                _ => 2,
+            };
+            let range =
+                edit["start"].as_u64().unwrap() as usize..edit["end"].as_u64().unwrap() as usize;
+            files[index].replace_range(range, edit["new_text"].as_str().unwrap());
+        }
+        Ok(files)
+    }
+
+    #[test]
+    fn rename_class_updates_types_overrides_and_printers() {
+        let files = rename("types.baml", "Person {", 0, "Human").unwrap();
+        assert_eq!(files[0], TYPES.replace("Person", "Human"));
+        assert_eq!(files[1], FUNCTIONS.replace("Person", "Human"));
+    }
+
+    #[test]
+    fn rename_field_updates_overrides_and_prompt_variables() {
+        let files = rename("types.baml", "name", 0, "full").unwrap();
+        assert_eq!(files[0], TYPES.replace("name string", "full string"));
+        assert_eq!(
+            files[1],
+            FUNCTIONS
+                .replace("    name @alias", "    full @alias")
+                .replace("person.name", "person.full")
+                .replace("name Bob", "full Bob")
+        );
+        assert_eq!(files[2], TESTS.replace("\"name\"", "\"full\""));
+
+        // From a prompt variable, through an optional field.
+        let files = rename("functions.baml", "city", 0, "town").unwrap();
+        assert_eq!(files[0], TYPES.replace("city", "town"));
+        assert_eq!(files[1], FUNCTIONS.replace("city", "town"));
+        assert_eq!(files[2], TESTS.replace("city", "town"));
+
+        // From the input of a test.
+        let files = rename("__tests__/Greet/ann.json", "home", 0, "house").unwrap();
+        assert_eq!(files[0], TYPES.replace("home", "house"));
+        assert_eq!(files[1], FUNCTIONS.replace("home", "house"));
+        assert_eq!(files[2], TESTS.replace("home", "house"));
+    }
+
+    #[test]
+    fn rename_enum_and_enum_value() {
+        let files = rename("functions.baml", "Mood {", 0, "Feeling").unwrap();
+        assert_eq!(files[0], TYPES.replace("Mood", "Feeling"));
+        assert_eq!(files[1], FUNCTIONS.replace("Mood", "Feeling"));
+
+        let files = rename("types.baml", "Happy", 0, "Joyful").unwrap();
+        assert_eq!(files[0], TYPES.replace("Happy", "Joyful"));
+        assert_eq!(files[1], FUNCTIONS.replace("Happy", "Joyful"));
+        assert_eq!(files[2], TESTS.replace("Happy", "Joyful"));
+
+        let files = rename("functions.baml", "Sad", 0, "Down").unwrap();
+        assert_eq!(files[0], TYPES.replace("Sad", "Down"));
+        assert_eq!(files[1], FUNCTIONS.replace("Sad", "Down"));
+        assert_eq!(files[2], TESTS);
+    }
+
+    #[test]
+    fn rename_function_impl_and_client() {
+        let files = rename("functions.baml", "Greet", 0, "Welcome").unwrap();
+        assert_eq!(files[0], TYPES);
+        assert_eq!(files[1], FUNCTIONS.replace("Greet", "Welcome"));
+
+        // Only the impl of `Greet`, and its `default_impl`.
+        let files = rename("functions.baml", "v1", 0, "v2").unwrap();
+        assert_eq!(
+            files[1],
+            FUNCTIONS
+                .replacen("default_impl v1", "default_impl v2", 1)
+                .replacen("Greet> v1", "Greet> v2", 1)
+                .replacen("impls [v1]", "impls [v2]", 1)
+        );
+
+        // Quoted and unquoted references.
+        let files = rename("functions.baml", "GPT4", 2, "Claude").unwrap();
+        assert_eq!(files[1], FUNCTIONS.replace("GPT4", "Claude"));
+    }
+
+    #[test]
+    fn rename_type_alias() {
+        let files = rename("functions.baml", "People", 0, "Crowd").unwrap();
+        assert_eq!(files[0], TYPES.replace("People", "Crowd"));
+        assert_eq!(files[1], FUNCTIONS.replace("People", "Crowd"));
+    }
+
+    #[test]
+    fn rename_rejects_invalid_and_taken_names() {
+        assert_eq!(
+            rename("types.baml", "Person {", 0, "Address"),
+            Err("Invalid name for `class`: `Address` is already taken.".into())
+        );
+        assert_eq!(
+            rename("types.baml", "Person {", 0, "human"),
+            Err("Invalid name for `class`: Must start with an uppercase letter.".into())
+        );
+        assert_eq!(
+            rename("types.baml", "name", 0, "home"),
+            Err("Invalid name for `class field`: `home` is already taken.".into())
+        );
+        assert_eq!(
+            rename("types.baml", "name", 0, "full name"),
+            Err("Invalid name for `class field`: Only letters, numbers, and _ are allowed, and it must not start with a number.".into())
+        );
+        assert_eq!(
+            rename("types.baml", "name", 0, "string"),
+            Err("Invalid name for `class field`: string is a primitive type.".into())
+        );
+    }
+
+    #[test]
+    fn nothing_to_rename_outside_names() {
+        assert_eq!(
+            rename("types.baml", "string", 0, "Foo").unwrap(),
+            [TYPES.to_string(), FUNCTIONS.to_string(), TESTS.to_string()]
+        );
+    }
+}
@@ -0,0 +1,265 @@
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::{path::PathBuf, sync::Arc};
+
+use baml_lib::{internal_baml_diagnostics::DatamodelError, SourceFile};
+
+use crate::lint::{print_diagnostics, MiniError};
+
+#[derive(Deserialize)]
+struct File {
+    path: String,
+    content: String,
+}
+
+#[derive(Deserialize)]
+struct Input {
+    root_path: String,
+    files: Vec<File>,
+    function: String,
+    r#impl: String,
+    /// The test whose input to render the prompt with.
+    test: Option<String>,
+    /// The input to render the prompt with, if there is no `test`.
+    input: Option<Value>,
+}
+
+pub(crate) fn mini_error(err: &DatamodelError) -> MiniError {
+    MiniError {
+        start: err.span().start,
+        end: err.span().end,
+        text: err.message().to_string(),
+        is_warning: false,
+        source_file: err.span().file.path(),
+    }
+}
+
+/// An error that isn't about a place in the schema, e.g. an unknown test.
+pub(crate) fn request_error(text: String) -> String {
+    let error = MiniError {
+        start: 0,
+        end: 0,
+        text,
+        is_warning: false,
+        source_file: String::new(),
+    };
+    print_diagnostics(vec![error], None)
+}
+
+/// Renders the prompt an impl sends for the input of a test, or for a given input. The schema
+/// must be valid.
+pub(crate) fn run(input: &str) -> String {
+    let input: Input = serde_json::from_str(input).expect("Failed to parse input");
+
+    let files: Vec<SourceFile> = input
+        .files
+        .into_iter()
+        .map(|file| SourceFile::new_allocated(file.path.into(), Arc::from(file.content)))
+        .collect();
+
+    let schema = baml_lib::validate(&PathBuf::from(input.root_path), files);
+    if schema.diagnostics.has_errors() {
+        let errors = schema.diagnostics.errors().iter().map(mini_error).collect();
+        return print_diagnostics(errors, None);
+    }
+
+    let db = &schema.db;
+    let variant = match db.find_variant_by_name(&input.function, &input.r#impl) {
+        Ok(variant) => variant,
+        Err(err) => return request_error(err),
+    };
+    let value = match (input.test, input.input) {
+        (Some(_), _) if variant.properties().input_adapter.is_some() => {
+            return request_error(format!(
+                "Impl `{}` has an input adapter, which only the generated clients can run. Pass what it returns as `input` instead.",
+                input.r#impl
+            ))
+        }
+        (Some(test), _) => match db.find_test_input(&input.function, &test) {
+            Ok(value) => value,
+            Err(err) => return request_error(err),
+        },
+        (None, Some(value)) => value,
+        (None, None) => return request_error("Either `test` or `input` is required.".into()),
+    };
+
+    let response = json!({ "prompt": variant.render_prompt(&value) });
+    print_diagnostics(vec![], Some(response))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+
+    const SCHEMA: &str = r##"
+class Doc {
+  title string
+  tags string[]
+}
+
+enum Mood {
+  Happy
+  Sad
+}
+
+function Summarize {
+  input (docs: Doc[], hint: string?)
+  output Mood
+}
+
+impl<llm, Summarize> v1 {
+  client GPT4
+  prompt #"
+    {#chat(system)}
+    Summarize the docs.
+    {#print_enum(Mood)}
+    {#chat(user)}
+    {#for doc in input.docs}
+    - {#doc.title} ({#doc.tags[*]})
+    {#end}
+    {#if input.hint}
+    Hint: {#input.hint}
+    {#else}
+    No hint.
+    {#end}
+  "#
+}
+
+function Echo {
+  input string
+  output string
+}
+
+impl<llm, Echo> v1 {
+  client GPT4
+  prompt #"
+    Repeat {#input} and {{this}}.
+  "#
+}
+
+function Describe {
+  input (count: int, ready: bool, tags: string[], doc: Doc, hint: string?)
+  output string
+}
+
+impl<llm, Describe> v1 {
+  client GPT4
+  prompt #"
+    {#input.count} {#input.ready} {#input.tags} {#input.doc} [{#input.hint}]
+  "#
+}
+
+test two_docs {
+  function Summarize
+  input {
+    docs [
+      {
+        title A
+        tags [x]
+      }
+      {
+        title B
+        tags [y, z]
+      }
+    ]
+  }
+}
+
+client<llm> GPT4 {
+  provider baml-openai-chat
+  options {
+    model gpt-4
+  }
+}
+"##;
+
+    fn render(request: Value) -> Value {
+        let mut input = json!({
+            "root_path": "/project/baml_src",
+            "files": [{ "path": "/project/baml_src/main.baml", "content": SCHEMA }],
+        });
+        input
+            .as_object_mut()
+            .unwrap()
+            .extend(request.as_object().unwrap().clone());
+        serde_json::from_str(&super::run(&input.to_string())).unwrap()
+    }
+
+    #[test]
+    fn render_prompt_for_test() {
+        let result = render(json!({ "function": "Summarize", "impl": "v1", "test": "two_docs" }));
+        let messages = result["response"]["prompt"].as_array().unwrap();
+        assert_eq!(messages[0]["role"], "system");
+        assert!(
+            messages[0]["content"]
+                .as_str()
+                .unwrap()
+                .starts_with("Summarize the docs.\nMood\n---\nHappy\nSad"),
+            "{}",
+            messages[0]["content"]
+        );
+        assert_eq!(
+            messages[1],
+            json!({ "role": "user", "content": "- A (x)\n- B (y\nz)\nNo hint.\n" })
+        );
+    }
+
+    #[test]
+    fn render_prompt_for_input() {
+        let result = render(json!({
+            "function": "Summarize",
+            "impl": "v1",
+            "input": { "docs": [], "hint": "Be brief." },
+        }));
+        assert_eq!(
+            result["response"]["prompt"][1]["content"],
+            "Hint: Be brief.\n"
+        );
+
+        let result = render(json!({ "function": "Echo", "impl": "v1", "input": "hello" }));
+        assert_eq!(result["response"]["prompt"], "Repeat hello and {{this}}.");
+    }
+
+    #[test]
+    fn render_prompt_for_non_string_values() {
+        let result = render(json!({
+            "function": "Describe",
+            "impl": "v1",
+            "input": {
+                "count": 2,
+                "ready": true,
+                "tags": ["a", "b"],
+                "doc": { "title": "A", "tags": [] },
+                "hint": null,
+            },
+        }));
+        assert_eq!(
+            result["response"]["prompt"],
+            r#"2 true ["a","b"] {"title":"A","tags":[]} [null]"#
+        );
+
+        // A missing value renders as nothing.
+        let result = render(json!({
+            "function": "Describe",
+            "impl": "v1",
+            "input": { "count": 2.5, "ready": false, "tags": [], "doc": {} },
+        }));
+        assert_eq!(result["response"]["prompt"], "2.5 false [] {} []");
+    }
+
+    #[test]
+    fn render_prompt_reports_unknown_names() {
+        let result = render(json!({ "function": "Summarize", "impl": "v2", "test": "two_docs" }));
+        assert_eq!(result["ok"], false);
+        assert_eq!(
+            result["diagnostics"][0]["text"],
+            "Function `Summarize` has no impl `v2`. Could be one of: v1"
+        );
+
+        let result = render(json!({ "function": "Echo", "impl": "v1", "test": "two_docs" }));
+        assert_eq!(
+            result["diagnostics"][0]["text"],
+            "Function `Echo` has no test `two_docs`. Could be one of: (none)"
+        );
+    }
+}
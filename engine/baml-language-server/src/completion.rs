@@ -0,0 +1,68 @@
+use std::path::Path;
+
+use baml_lib::internal_baml_schema_ast::ast::{Top, WithName};
+use lsp_types::{CompletionItem, CompletionItemKind, CompletionResponse, Position};
+
+use crate::{position::position_to_offset, project::Project};
+
+const PRIMITIVES: [&str; 5] = ["string", "int", "float", "bool", "char"];
+
+/// Completes client names after `client` in an impl, and type names wherever a type is
+/// expected. The context comes from the text rather than the syntax tree, as the file being
+/// edited usually does not parse.
+pub(crate) fn completion(
+    project: &Project,
+    path: &Path,
+    position: Position,
+) -> Option<CompletionResponse> {
+    let source = project.source(path)?;
+    let offset = position_to_offset(source, position);
+    let before = &source[..offset];
+    let line = before.rsplit('\n').next().unwrap_or_default();
+
+    // The block the cursor is in starts on the closest line that is not indented.
+    let block = before
+        .lines()
+        .rev()
+        .map(|line| line.trim_end())
+        .find(|line| !line.is_empty() && !line.starts_with(char::is_whitespace))
+        .filter(|line| !line.starts_with('}'))?;
+    let keyword = block
+        .split(|c: char| !c.is_alphanumeric() && c != '_')
+        .next()
+        .unwrap_or_default();
+
+    let mut words = line.split_whitespace();
+    let items = match (keyword, words.next()) {
+        ("impl", Some("client")) if words.count() <= 1 => project
+            .tops()
+            .filter_map(|top| top.as_client())
+            .map(|client| item(client.name(), CompletionItemKind::MODULE))
+            .collect(),
+        ("class" | "type" | "function", _) => project
+            .tops()
+            .filter_map(|top| match top {
+                Top::Class(_) => Some(item(top.name(), CompletionItemKind::CLASS)),
+                Top::Enum(_) => Some(item(top.name(), CompletionItemKind::ENUM)),
+                Top::TypeAlias(_) => Some(item(top.name(), CompletionItemKind::TYPE_PARAMETER)),
+                _ => None,
+            })
+            .chain(
+                PRIMITIVES
+                    .iter()
+                    .map(|name| item(name, CompletionItemKind::KEYWORD)),
+            )
+            .collect(),
+        _ => return None,
+    };
+
+    Some(CompletionResponse::Array(items))
+}
+
+fn item(label: &str, kind: CompletionItemKind) -> CompletionItem {
+    CompletionItem {
+        label: label.to_string(),
+        kind: Some(kind),
+        ..Default::default()
+    }
+}
@@ -0,0 +1,123 @@
+use std::path::Path;
+
+use baml_lib::internal_baml_schema_ast::ast::{FunctionArgs, Span, Top, WithName};
+use lsp_types::{Hover, HoverContents, MarkupContent, MarkupKind, Position};
+
+use crate::{
+    position::position_to_offset,
+    project::{Project, Symbol},
+};
+
+/// The declaration of the item under the cursor, followed by its description.
+pub(crate) fn hover(project: &Project, path: &Path, position: Position) -> Option<Hover> {
+    let offset = position_to_offset(project.source(path)?, position);
+    let symbol = project.symbol_at(path, offset)?;
+
+    let mut value = format!("```baml\n{}\n```", signature(&symbol));
+    if let Some(description) = symbol.description() {
+        value.push_str("\n\n");
+        value.push_str(description.trim());
+    }
+
+    Some(Hover {
+        contents: HoverContents::Markup(MarkupContent {
+            kind: MarkupKind::Markdown,
+            value,
+        }),
+        range: None,
+    })
+}
+
+/// The text of `span`, as written in the source.
+fn source(span: &Span) -> &str {
+    span.file
+        .as_str()
+        .get(span.start..span.end)
+        .unwrap_or_default()
+}
+
+fn signature(symbol: &Symbol<'_>) -> String {
+    match symbol {
+        Symbol::Top(Top::Class(class)) => {
+            let fields = class
+                .fields()
+                .iter()
+                .map(|field| format!("  {} {}\n", field.name(), source(field.field_type.span())))
+                .collect::<String>();
+            format!("class {} {{\n{}}}", class.name(), fields)
+        }
+        Symbol::Top(Top::Enum(r#enum)) => {
+            let values = r#enum
+                .iter_values()
+                .map(|(_, value)| format!("  {}\n", value.name()))
+                .collect::<String>();
+            format!("enum {} {{\n{}}}", r#enum.name(), values)
+        }
+        Symbol::Top(Top::TypeAlias(alias)) => {
+            format!("type {} = {}", alias.name(), source(alias.value().span()))
+        }
+        Symbol::Top(Top::Function(function)) => {
+            let args = |args: &FunctionArgs| match args {
+                FunctionArgs::Unnamed(arg) => source(arg.field_type.span()).to_string(),
+                FunctionArgs::Named(list) => format!(
+                    "({})",
+                    list.args
+                        .iter()
+                        .map(|(name, arg)| format!(
+                            "{}: {}",
+                            name.name(),
+                            source(arg.field_type.span())
+                        ))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+            };
+            format!(
+                "function {} {{\n  input {}\n  output {}\n}}",
+                function.name(),
+                args(function.input()),
+                args(function.output())
+            )
+        }
+        Symbol::Top(Top::Client(client)) => {
+            let provider = client
+                .fields()
+                .iter()
+                .find(|field| field.name() == "provider")
+                .and_then(|field| field.value.as_ref())
+                .map(|value| format!("\n  provider {}", source(value.span())))
+                .unwrap_or_default();
+            format!(
+                "client<{}> {} {{{}\n}}",
+                client.client_type,
+                client.name(),
+                provider
+            )
+        }
+        Symbol::Top(Top::Variant(variant)) => format!(
+            "impl<{}, {}> {}",
+            if variant.is_llm() { "llm" } else { "?" },
+            variant.function_name().name(),
+            variant.name()
+        ),
+        Symbol::Top(top) => format!("{} {}", top.get_type(), top.name()),
+        Symbol::Field(class, field) => format!(
+            "{} {}\n// in class {}",
+            field.name(),
+            source(field.field_type.span()),
+            class.name()
+        ),
+        Symbol::EnumValue(r#enum, value) => format!("{}.{}", r#enum.name(), value.name()),
+        Symbol::Argument(function, name, field_type) => format!(
+            "{}: {}\n// argument of {}",
+            name.name(),
+            source(field_type.span()),
+            function.name()
+        ),
+        Symbol::Input(function, field_type) => format!(
+            "input: {}\n// argument of {}",
+            source(field_type.span()),
+            function.name()
+        ),
+    }
+}
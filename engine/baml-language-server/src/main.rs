@@ -0,0 +1,18 @@
+mod completion;
+mod hover;
+mod navigation;
+mod position;
+mod project;
+mod server;
+#[cfg(test)]
+mod tests;
+
+fn main() {
+    // Logs go to stderr; stdout carries the protocol.
+    env_logger::init();
+
+    if let Err(err) = server::run() {
+        log::error!("{}", err);
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,45 @@
+use std::path::Path;
+
+use lsp_types::{GotoDefinitionResponse, Location, Position};
+
+use crate::{
+    position::{position_to_offset, span_to_location},
+    project::Project,
+};
+
+/// Where the item under the cursor is declared. Works on type references, on the function and
+/// client of an impl, and on each segment of a `{#input.a.b}` prompt variable.
+pub(crate) fn definition(
+    project: &Project,
+    path: &Path,
+    position: Position,
+) -> Option<GotoDefinitionResponse> {
+    let offset = position_to_offset(project.source(path)?, position);
+    let symbol = project.symbol_at(path, offset)?;
+    span_to_location(symbol.declaration()).map(GotoDefinitionResponse::Scalar)
+}
+
+/// Every place that refers to the item under the cursor.
+pub(crate) fn references(
+    project: &Project,
+    path: &Path,
+    position: Position,
+    include_declaration: bool,
+) -> Option<Vec<Location>> {
+    let offset = position_to_offset(project.source(path)?, position);
+    let declaration = project.symbol_at(path, offset)?.declaration();
+
+    let references = project
+        .references()
+        .into_iter()
+        .filter(|(_, symbol)| symbol.declaration() == declaration)
+        .map(|(span, _)| span);
+    let locations = include_declaration
+        .then(|| declaration.clone())
+        .into_iter()
+        .chain(references)
+        .filter_map(|span| span_to_location(&span))
+        .collect();
+
+    Some(locations)
+}
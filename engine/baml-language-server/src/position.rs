@@ -0,0 +1,88 @@
+use baml_lib::internal_baml_schema_ast::ast::Span;
+use lsp_types::{Location, Position, Range, Url};
+
+/// Converts an LSP position, counted in UTF-16 code units as the protocol requires, to a byte
+/// offset into `document`. Positions past the end of a line or of the document are clamped.
+pub(crate) fn position_to_offset(document: &str, position: Position) -> usize {
+    let mut line_start = 0;
+    for _ in 0..position.line {
+        match document[line_start..].find('\n') {
+            Some(idx) => line_start += idx + 1,
+            None => return document.len(),
+        }
+    }
+
+    let mut character = 0;
+    for (idx, chr) in document[line_start..].char_indices() {
+        if chr == '\n' || character >= position.character {
+            return line_start + idx;
+        }
+        character += chr.len_utf16() as u32;
+    }
+
+    document.len()
+}
+
+/// Converts a byte offset into `document` to an LSP position.
+pub(crate) fn offset_to_position(document: &str, offset: usize) -> Position {
+    let mut position = Position::default();
+
+    for (idx, chr) in document.char_indices() {
+        if idx >= offset {
+            break;
+        }
+        if chr == '\n' {
+            position.line += 1;
+            position.character = 0;
+        } else {
+            position.character += chr.len_utf16() as u32;
+        }
+    }
+
+    position
+}
+
+pub(crate) fn span_to_range(span: &Span) -> Range {
+    let document = span.file.as_str();
+    Range::new(
+        offset_to_position(document, span.start),
+        offset_to_position(document, span.end),
+    )
+}
+
+pub(crate) fn span_to_location(span: &Span) -> Option<Location> {
+    let uri = Url::from_file_path(span.file.path_buf()).ok()?;
+    Some(Location::new(uri, span_to_range(span)))
+}
+
+#[cfg(test)]
+mod tests {
+    use lsp_types::Position;
+
+    use super::{offset_to_position, position_to_offset};
+
+    #[test]
+    fn round_trips_through_multibyte_characters() {
+        let document = "class Foo {\r\n  // héllo 🙂\r\n  bar string\r\n}";
+        let offset = document.find("bar").unwrap();
+        let position = offset_to_position(document, offset);
+
+        assert_eq!(position, Position::new(2, 2));
+        assert_eq!(position_to_offset(document, position), offset);
+
+        let offset = document.find("llo").unwrap();
+        assert_eq!(offset_to_position(document, offset), Position::new(1, 7));
+        let offset = document.find("\r\n  bar").unwrap();
+        assert_eq!(offset_to_position(document, offset), Position::new(1, 13));
+    }
+
+    #[test]
+    fn clamps_positions_past_the_end() {
+        let document = "enum A {\n  B\n}";
+        assert_eq!(position_to_offset(document, Position::new(1, 40)), 12);
+        assert_eq!(
+            position_to_offset(document, Position::new(9, 0)),
+            document.len()
+        );
+    }
+}
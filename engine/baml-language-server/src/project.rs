@@ -0,0 +1,475 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::{Path, PathBuf},
+};
+
+use baml_lib::{
+    internal_baml_schema_ast::{
+        self,
+        ast::{
+            Class, ClassPosition, Client, ClientPosition, Enum, EnumPosition, EnumValue, Field,
+            FieldPosition, FieldType, Function, FunctionArgs, FunctionPosition, Identifier,
+            SchemaAst, SchemaPosition, Span, Top, TopId, TypeAliasPosition, Variant,
+            VariantPosition, WithDocumentation, WithIdentifier, WithName, WithSpan,
+        },
+    },
+    parse_and_validate_schema, SourceFile,
+};
+use lsp_types::{Diagnostic, DiagnosticSeverity};
+
+use crate::position::span_to_range;
+
+/// All the BAML files of one `baml_src` directory, with the unsaved contents of open editors
+/// taking precedence over what is on disk.
+pub(crate) struct Project {
+    root: PathBuf,
+    files: BTreeMap<PathBuf, SourceFile>,
+    asts: BTreeMap<PathBuf, SchemaAst>,
+}
+
+/// The `baml_src` directory `path` belongs to. Files outside of one form a project with their
+/// siblings.
+pub(crate) fn project_root(path: &Path) -> PathBuf {
+    path.ancestors()
+        .find(|dir| dir.file_name().is_some_and(|name| name == "baml_src"))
+        .or_else(|| path.parent())
+        .unwrap_or(path)
+        .to_path_buf()
+}
+
+fn collect_files(dir: &Path, files: &mut BTreeMap<PathBuf, String>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for path in entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+    {
+        if path.is_dir() {
+            collect_files(&path, files);
+        } else if matches!(
+            path.extension().and_then(|ext| ext.to_str()),
+            Some("baml" | "json")
+        ) {
+            if let Ok(contents) = std::fs::read_to_string(&path) {
+                files.insert(path, contents);
+            }
+        }
+    }
+}
+
+impl Project {
+    /// Reads every `.baml` and `.json` file under `root`, then applies `open_documents`.
+    pub(crate) fn load(root: PathBuf, open_documents: &HashMap<PathBuf, String>) -> Self {
+        let mut files = BTreeMap::new();
+        collect_files(&root, &mut files);
+        for (path, contents) in open_documents {
+            if path.starts_with(&root) {
+                files.insert(path.clone(), contents.clone());
+            }
+        }
+        Project::new(root, files)
+    }
+
+    pub(crate) fn new(root: PathBuf, files: BTreeMap<PathBuf, String>) -> Self {
+        let files = files
+            .into_iter()
+            .map(|(path, contents)| (path.clone(), SourceFile::from((path, contents))))
+            .collect::<BTreeMap<_, _>>();
+
+        // Navigation only needs the syntax tree, which is still available for the files that
+        // parse when validation of the whole project fails.
+        let asts = files
+            .iter()
+            .filter_map(|(path, file)| {
+                let (ast, _) = internal_baml_schema_ast::parse_schema(&root, file).ok()?;
+                Some((path.clone(), ast))
+            })
+            .collect();
+
+        Project { root, files, asts }
+    }
+
+    pub(crate) fn source(&self, path: &Path) -> Option<&str> {
+        self.files.get(path).map(|file| file.as_str())
+    }
+
+    /// Validates the project and groups the errors and warnings by file. Every file of the
+    /// project has an entry, so that editors clear the diagnostics of fixed files.
+    pub(crate) fn diagnostics(&self) -> BTreeMap<PathBuf, Vec<Diagnostic>> {
+        let mut by_file = self
+            .files
+            .keys()
+            .map(|path| (path.clone(), Vec::new()))
+            .collect::<BTreeMap<_, _>>();
+
+        let diagnostics = match parse_and_validate_schema(
+            &self.root,
+            self.files.values().cloned().collect::<Vec<_>>(),
+        ) {
+            Ok(schema) => schema.diagnostics,
+            Err(diagnostics) => diagnostics,
+        };
+
+        let errors = diagnostics
+            .errors()
+            .iter()
+            .map(|e| (e.span(), e.message(), DiagnosticSeverity::ERROR));
+        let warnings = diagnostics
+            .warnings()
+            .iter()
+            .map(|w| (w.span(), w.message(), DiagnosticSeverity::WARNING));
+        for (span, message, severity) in errors.chain(warnings) {
+            by_file
+                .entry(span.file.path_buf().clone())
+                .or_default()
+                .push(Diagnostic {
+                    range: span_to_range(span),
+                    severity: Some(severity),
+                    source: Some("baml".into()),
+                    message: message.to_string(),
+                    ..Default::default()
+                });
+        }
+
+        by_file
+    }
+
+    pub(crate) fn tops(&self) -> impl Iterator<Item = &Top> {
+        self.asts.values().flat_map(|ast| ast.tops.iter())
+    }
+
+    /// The class, enum or type alias called `name`.
+    pub(crate) fn find_type(&self, name: &str) -> Option<&Top> {
+        self.tops().find(|top| {
+            matches!(top, Top::Class(_) | Top::Enum(_) | Top::TypeAlias(_))
+                && top.identifier().name() == name
+        })
+    }
+
+    pub(crate) fn find_function(&self, name: &str) -> Option<&Function> {
+        self.tops()
+            .filter_map(|top| top.as_function())
+            .find(|function| function.name() == name)
+    }
+
+    pub(crate) fn find_client(&self, name: &str) -> Option<&Client> {
+        self.tops()
+            .filter_map(|top| top.as_client())
+            .find(|client| client.name() == name)
+    }
+
+    /// What the cursor at byte `offset` of `path` is on.
+    pub(crate) fn symbol_at(&self, path: &Path, offset: usize) -> Option<Symbol<'_>> {
+        let ast = self.asts.get(path)?;
+
+        match ast.find_at_position(offset) {
+            SchemaPosition::TopLevel => None,
+            SchemaPosition::Enum(id, position) => {
+                let r#enum = &ast[id];
+                match position {
+                    EnumPosition::Value(value_id, _) => {
+                        Some(Symbol::EnumValue(r#enum, &r#enum[value_id]))
+                    }
+                    EnumPosition::Name => Some(Symbol::Top(&ast[TopId::Enum(id)])),
+                    EnumPosition::Enum => None,
+                }
+            }
+            SchemaPosition::Class(id, position) => {
+                let class = &ast[id];
+                match position {
+                    ClassPosition::Field(_, FieldPosition::Type(idn)) => self.type_symbol(idn),
+                    ClassPosition::Field(field_id, _) => {
+                        Some(Symbol::Field(class, &class[field_id]))
+                    }
+                    ClassPosition::Name => Some(Symbol::Top(&ast[TopId::Class(id)])),
+                    ClassPosition::Class => None,
+                }
+            }
+            SchemaPosition::TypeAlias(id, position) => match position {
+                TypeAliasPosition::Type(idn) => self.type_symbol(idn),
+                TypeAliasPosition::Name => Some(Symbol::Top(&ast[TopId::TypeAlias(id)])),
+                TypeAliasPosition::TypeAlias => None,
+            },
+            SchemaPosition::Function(id, position) => {
+                let function = &ast[id];
+                match position {
+                    FunctionPosition::Type(idn) => self.type_symbol(idn),
+                    FunctionPosition::Argument(name) => match function.input() {
+                        FunctionArgs::Named(list) => {
+                            list.args.iter().find(|(arg_name, _)| arg_name == name).map(
+                                |(arg_name, arg)| {
+                                    Symbol::Argument(function, arg_name, &arg.field_type)
+                                },
+                            )
+                        }
+                        FunctionArgs::Unnamed(_) => None,
+                    },
+                    FunctionPosition::Name => Some(Symbol::Top(&ast[TopId::Function(id)])),
+                    FunctionPosition::Function => None,
+                }
+            }
+            SchemaPosition::Variant(id, position) => {
+                let variant = &ast[id];
+                match position {
+                    VariantPosition::FunctionName(idn) => self
+                        .find_function(idn.name())
+                        .and_then(|function| self.top_of(function)),
+                    VariantPosition::Client(name, _) => self
+                        .find_client(name)
+                        .and_then(|client| self.top_of(client)),
+                    VariantPosition::PromptVariable(variable) => {
+                        let path = variable
+                            .path
+                            .iter()
+                            .map(|(part, _)| *part)
+                            .collect::<Vec<_>>();
+                        self.resolve_prompt_variable(variant, &path[..=variable.segment])
+                    }
+                    VariantPosition::Name => Some(Symbol::Top(&ast[TopId::Variant(id)])),
+                    VariantPosition::Variant => None,
+                }
+            }
+            SchemaPosition::Client(id, position) => match position {
+                ClientPosition::Name => Some(Symbol::Top(&ast[TopId::Client(id)])),
+                ClientPosition::Client => None,
+            },
+        }
+    }
+
+    fn type_symbol(&self, idn: &Identifier) -> Option<Symbol<'_>> {
+        match idn {
+            Identifier::Primitive(..) => None,
+            _ => self.find_type(idn.name()).map(Symbol::Top),
+        }
+    }
+
+    fn top_of<T: WithSpan>(&self, item: &T) -> Option<Symbol<'_>> {
+        self.tops()
+            .find(|top| top.span() == item.span())
+            .map(Symbol::Top)
+    }
+
+    /// Resolves `input.a.b` in a prompt of `variant` to the argument or class field it names.
+    pub(crate) fn resolve_prompt_variable(
+        &self,
+        variant: &Variant,
+        path: &[&str],
+    ) -> Option<Symbol<'_>> {
+        let function = self.find_function(variant.function_name().name())?;
+        let (first, rest) = path.split_first()?;
+        if *first != "input" {
+            return None;
+        }
+
+        let (mut symbol, mut field_type, rest) = match function.input() {
+            FunctionArgs::Unnamed(arg) => (
+                Symbol::Input(function, &arg.field_type),
+                &arg.field_type,
+                rest,
+            ),
+            FunctionArgs::Named(list) => {
+                let Some((name, rest)) = rest.split_first() else {
+                    return self.top_of(function);
+                };
+                let (arg_name, arg) = list
+                    .args
+                    .iter()
+                    .find(|(arg_name, _)| arg_name.name() == *name)?;
+                (
+                    Symbol::Argument(function, arg_name, &arg.field_type),
+                    &arg.field_type,
+                    rest,
+                )
+            }
+        };
+
+        for name in rest {
+            let (class, field) = self.find_field(field_type, name, 0)?;
+            symbol = Symbol::Field(class, field);
+            field_type = &field.field_type;
+        }
+
+        Some(symbol)
+    }
+
+    /// Looks for a field called `name` in the classes `field_type` may hold, looking through
+    /// optionals, unions, lists and type aliases.
+    fn find_field(
+        &self,
+        field_type: &FieldType,
+        name: &str,
+        depth: usize,
+    ) -> Option<(&Class, &Field)> {
+        // Aliases are not allowed to be recursive, but the project may not be valid yet.
+        if depth > 16 {
+            return None;
+        }
+        field_type
+            .flat_idns()
+            .into_iter()
+            .find_map(|idn| match self.find_type(idn.name())? {
+                Top::Class(class) => class
+                    .fields()
+                    .iter()
+                    .find(|field| field.name() == name)
+                    .map(|field| (class, field)),
+                Top::TypeAlias(alias) => self.find_field(alias.value(), name, depth + 1),
+                _ => None,
+            })
+    }
+
+    /// Every place in the project that refers to another item, with the item it refers to.
+    pub(crate) fn references(&self) -> Vec<(Span, Symbol<'_>)> {
+        let mut references = Vec::new();
+
+        for ast in self.asts.values() {
+            for top in &ast.tops {
+                match top {
+                    Top::Class(class) => {
+                        for field in class.fields() {
+                            self.type_references(&field.field_type, &mut references);
+                        }
+                    }
+                    Top::TypeAlias(alias) => self.type_references(alias.value(), &mut references),
+                    Top::Function(function) => {
+                        for args in [function.input(), function.output()] {
+                            match args {
+                                FunctionArgs::Unnamed(arg) => {
+                                    self.type_references(&arg.field_type, &mut references)
+                                }
+                                FunctionArgs::Named(list) => {
+                                    for (_, arg) in &list.args {
+                                        self.type_references(&arg.field_type, &mut references);
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    Top::Variant(variant) => {
+                        let function_name = variant.function_name();
+                        if let Some(symbol) = self
+                            .find_function(function_name.name())
+                            .and_then(|function| self.top_of(function))
+                        {
+                            references.push((function_name.span().clone(), symbol));
+                        }
+                        for field in &variant.fields {
+                            match (field.name(), &field.value) {
+                                ("client", Some(value)) => {
+                                    if let Some(symbol) = value
+                                        .as_string_value()
+                                        .and_then(|(name, _)| self.find_client(name))
+                                        .and_then(|client| self.top_of(client))
+                                    {
+                                        references.push((value.span().clone(), symbol));
+                                    }
+                                }
+                                ("prompt", Some(value)) => self.prompt_references(
+                                    ast,
+                                    variant,
+                                    value.span(),
+                                    &mut references,
+                                ),
+                                _ => {}
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        references
+    }
+
+    fn type_references<'a>(
+        &'a self,
+        field_type: &FieldType,
+        references: &mut Vec<(Span, Symbol<'a>)>,
+    ) {
+        for idn in field_type.flat_idns() {
+            if let Some(symbol) = self.type_symbol(idn) {
+                references.push((idn.span().clone(), symbol));
+            }
+        }
+    }
+
+    fn prompt_references<'a>(
+        &'a self,
+        ast: &SchemaAst,
+        variant: &Variant,
+        prompt: &Span,
+        references: &mut Vec<(Span, Symbol<'a>)>,
+    ) {
+        let source = &prompt.file.as_str()[prompt.start..prompt.end];
+        for (idx, _) in source.match_indices("{#") {
+            let position = ast.find_at_position(prompt.start + idx + 2);
+            let SchemaPosition::Variant(_, VariantPosition::PromptVariable(variable)) = position
+            else {
+                continue;
+            };
+            let path = variable
+                .path
+                .iter()
+                .map(|(part, _)| *part)
+                .collect::<Vec<_>>();
+            for (segment, (_, span)) in variable.path.iter().enumerate() {
+                if let Some(symbol) = self.resolve_prompt_variable(variant, &path[..=segment]) {
+                    references.push((span.clone(), symbol));
+                }
+            }
+        }
+    }
+}
+
+/// An item of the project, as found under the cursor.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Symbol<'a> {
+    /// A declaration: class, enum, type alias, function, impl, client, ...
+    Top(&'a Top),
+    Field(&'a Class, &'a Field),
+    EnumValue(&'a Enum, &'a EnumValue),
+    /// A named argument of a function.
+    Argument(&'a Function, &'a Identifier, &'a FieldType),
+    /// The unnamed argument of a function, `input` in its prompts.
+    Input(&'a Function, &'a FieldType),
+}
+
+impl<'a> Symbol<'a> {
+    /// The span of the name the item is declared with.
+    pub(crate) fn declaration(&self) -> &'a Span {
+        match self {
+            Symbol::Top(top) => top.identifier().span(),
+            Symbol::Field(_, field) => field.identifier().span(),
+            Symbol::EnumValue(_, value) => value.identifier().span(),
+            Symbol::Argument(_, name, _) => name.span(),
+            Symbol::Input(_, field_type) => field_type.span(),
+        }
+    }
+
+    /// The documentation of the item: its `@description`, or else its doc comment.
+    pub(crate) fn description(&self) -> Option<String> {
+        let (attributes, documentation) = match self {
+            Symbol::Field(_, field) => (field.attributes.as_slice(), field.documentation()),
+            Symbol::EnumValue(_, value) => (value.attributes.as_slice(), value.documentation()),
+            Symbol::Top(Top::Class(class)) => (class.attributes.as_slice(), class.documentation()),
+            Symbol::Top(Top::Enum(r#enum)) => {
+                (r#enum.attributes.as_slice(), r#enum.documentation())
+            }
+            Symbol::Top(Top::TypeAlias(alias)) => (&[][..], alias.documentation()),
+            Symbol::Top(Top::Function(function)) => (&[][..], function.documentation()),
+            Symbol::Top(Top::Client(client)) => (&[][..], client.documentation()),
+            Symbol::Top(Top::Variant(variant)) => (&[][..], variant.documentation()),
+            _ => (&[][..], None),
+        };
+
+        attributes
+            .iter()
+            .filter(|attr| attr.name() == "description")
+            .find_map(|attr| attr.arguments.iter().next())
+            .and_then(|(_, arg)| arg.value.as_string_value())
+            .map(|(description, _)| description.to_string())
+            .or_else(|| documentation.map(str::to_string))
+    }
+}
@@ -0,0 +1,205 @@
+use std::{
+    collections::HashMap,
+    error::Error,
+    path::{Path, PathBuf},
+};
+
+use lsp_server::{Connection, ErrorCode, Message, Notification, Request, Response};
+use lsp_types::{
+    notification::{
+        DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument, DidSaveTextDocument,
+        Notification as _, PublishDiagnostics,
+    },
+    request::{Completion, GotoDefinition, HoverRequest, References, Request as _},
+    CompletionOptions, CompletionParams, DidChangeTextDocumentParams, DidCloseTextDocumentParams,
+    DidOpenTextDocumentParams, DidSaveTextDocumentParams, GotoDefinitionParams, HoverParams,
+    HoverProviderCapability, OneOf, PublishDiagnosticsParams, ReferenceParams, ServerCapabilities,
+    TextDocumentSyncCapability, TextDocumentSyncKind, Url,
+};
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{
+    completion::completion,
+    hover::hover,
+    navigation::{definition, references},
+    project::{project_root, Project},
+};
+
+/// Serves the language server protocol over stdin and stdout until the client shuts it down.
+pub(crate) fn run() -> Result<(), Box<dyn Error + Sync + Send>> {
+    let (connection, io_threads) = Connection::stdio();
+
+    let capabilities = ServerCapabilities {
+        text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL)),
+        hover_provider: Some(HoverProviderCapability::Simple(true)),
+        definition_provider: Some(OneOf::Left(true)),
+        references_provider: Some(OneOf::Left(true)),
+        completion_provider: Some(CompletionOptions {
+            trigger_characters: Some(vec![" ".into()]),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    connection.initialize(serde_json::to_value(capabilities)?)?;
+
+    let mut server = Server::default();
+    for message in &connection.receiver {
+        match message {
+            Message::Request(request) => {
+                if connection.handle_shutdown(&request)? {
+                    break;
+                }
+                let response = server.handle_request(request);
+                connection.sender.send(Message::Response(response))?;
+            }
+            Message::Notification(notification) => {
+                for notification in server.handle_notification(notification) {
+                    connection
+                        .sender
+                        .send(Message::Notification(notification))?;
+                }
+            }
+            Message::Response(_) => {}
+        }
+    }
+
+    io_threads.join()?;
+    Ok(())
+}
+
+#[derive(Default)]
+struct Server {
+    /// The contents of the documents open in the editor, which may not be saved yet.
+    open_documents: HashMap<PathBuf, String>,
+    /// The projects of the open documents, keyed by their `baml_src` directory.
+    projects: HashMap<PathBuf, Project>,
+}
+
+fn parse_params<P: DeserializeOwned>(request: &Request) -> Result<P, (ErrorCode, String)> {
+    serde_json::from_value(request.params.clone())
+        .map_err(|err| (ErrorCode::InvalidParams, err.to_string()))
+}
+
+impl Server {
+    /// The project of the document at `uri`, and the path of the document.
+    fn project(&self, uri: &Url) -> Option<(&Project, PathBuf)> {
+        let path = uri.to_file_path().ok()?;
+        let project = self.projects.get(&project_root(&path))?;
+        Some((project, path))
+    }
+
+    fn respond<T: Serialize>(
+        &self,
+        uri: &Url,
+        f: impl FnOnce(&Project, &Path) -> Option<T>,
+    ) -> Result<serde_json::Value, (ErrorCode, String)> {
+        let result = self
+            .project(uri)
+            .and_then(|(project, path)| f(project, &path));
+        serde_json::to_value(result).map_err(|err| (ErrorCode::InternalError, err.to_string()))
+    }
+
+    fn handle_request(&self, request: Request) -> Response {
+        let result = match request.method.as_str() {
+            HoverRequest::METHOD => parse_params::<HoverParams>(&request).and_then(|params| {
+                let params = params.text_document_position_params;
+                self.respond(&params.text_document.uri, |project, path| {
+                    hover(project, path, params.position)
+                })
+            }),
+            GotoDefinition::METHOD => {
+                parse_params::<GotoDefinitionParams>(&request).and_then(|params| {
+                    let params = params.text_document_position_params;
+                    self.respond(&params.text_document.uri, |project, path| {
+                        definition(project, path, params.position)
+                    })
+                })
+            }
+            References::METHOD => parse_params::<ReferenceParams>(&request).and_then(|params| {
+                let include_declaration = params.context.include_declaration;
+                let params = params.text_document_position;
+                self.respond(&params.text_document.uri, |project, path| {
+                    references(project, path, params.position, include_declaration)
+                })
+            }),
+            Completion::METHOD => parse_params::<CompletionParams>(&request).and_then(|params| {
+                let params = params.text_document_position;
+                self.respond(&params.text_document.uri, |project, path| {
+                    completion(project, path, params.position)
+                })
+            }),
+            method => Err((
+                ErrorCode::MethodNotFound,
+                format!("Unsupported request: {}", method),
+            )),
+        };
+
+        match result {
+            Ok(result) => Response::new_ok(request.id, result),
+            Err((code, message)) => Response::new_err(request.id, code as i32, message),
+        }
+    }
+
+    /// Tracks the open documents. Any change revalidates the document's project and returns
+    /// the diagnostics to publish for each of its files.
+    fn handle_notification(&mut self, notification: Notification) -> Vec<Notification> {
+        let uri = match notification.method.as_str() {
+            DidOpenTextDocument::METHOD => {
+                serde_json::from_value::<DidOpenTextDocumentParams>(notification.params)
+                    .ok()
+                    .and_then(|params| {
+                        let path = params.text_document.uri.to_file_path().ok()?;
+                        self.open_documents.insert(path, params.text_document.text);
+                        Some(params.text_document.uri)
+                    })
+            }
+            DidChangeTextDocument::METHOD => {
+                serde_json::from_value::<DidChangeTextDocumentParams>(notification.params)
+                    .ok()
+                    .and_then(|params| {
+                        let path = params.text_document.uri.to_file_path().ok()?;
+                        // Documents are synced in full, so the last change holds the whole text.
+                        let change = params.content_changes.into_iter().last()?;
+                        self.open_documents.insert(path, change.text);
+                        Some(params.text_document.uri)
+                    })
+            }
+            DidSaveTextDocument::METHOD => {
+                serde_json::from_value::<DidSaveTextDocumentParams>(notification.params)
+                    .ok()
+                    .map(|params| params.text_document.uri)
+            }
+            DidCloseTextDocument::METHOD => {
+                serde_json::from_value::<DidCloseTextDocumentParams>(notification.params)
+                    .ok()
+                    .and_then(|params| {
+                        let path = params.text_document.uri.to_file_path().ok()?;
+                        self.open_documents.remove(&path);
+                        Some(params.text_document.uri)
+                    })
+            }
+            _ => None,
+        };
+
+        let Some(path) = uri.and_then(|uri| uri.to_file_path().ok()) else {
+            return vec![];
+        };
+        let root = project_root(&path);
+        let project = Project::load(root.clone(), &self.open_documents);
+        let notifications = project
+            .diagnostics()
+            .into_iter()
+            .filter_map(|(path, diagnostics)| {
+                let params = PublishDiagnosticsParams {
+                    uri: Url::from_file_path(path).ok()?,
+                    diagnostics,
+                    version: None,
+                };
+                Some(Notification::new(PublishDiagnostics::METHOD.into(), params))
+            })
+            .collect();
+        self.projects.insert(root, project);
+
+        notifications
+    }
+}
@@ -0,0 +1,328 @@
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+use lsp_types::{
+    CompletionResponse, GotoDefinitionResponse, HoverContents, Location, Position, Range,
+};
+
+use crate::{
+    completion::completion,
+    hover::hover,
+    navigation::{definition, references},
+    position::offset_to_position,
+    project::Project,
+};
+
+const TYPES: &str = r##"
+/// Who sent a message.
+enum Role {
+  User
+  Assistant @description("The model")
+}
+
+class Message {
+  role Role
+  body string @description(#"
+    What was said.
+  "#)
+}
+
+type Thread = Message[]
+
+class Conversation {
+  messages Thread
+  latest Message?
+}
+"##;
+
+const FUNCTIONS: &str = r##"
+function Reply {
+  input (conversation: Conversation, tone: string)
+  output Message
+}
+
+impl<llm, Reply> v1 {
+  client GPT4
+  prompt #"
+    Reply to {#input.conversation.latest.body} in a {#input.tone} tone.
+  "#
+}
+
+client<llm> GPT4 {
+  provider baml-openai-chat
+  options {
+    model gpt-4
+  }
+}
+"##;
+
+const CURSOR: &str = "<|>";
+
+fn root() -> PathBuf {
+    PathBuf::from("/project/baml_src")
+}
+
+/// Builds a project from `types.baml` and `functions.baml`, where one of the two contains the
+/// `<|>` cursor marker. Returns the project, the path of the file with the cursor and the
+/// cursor position.
+fn project_with_cursor(types: &str, functions: &str) -> (Project, PathBuf, Position) {
+    let mut files = BTreeMap::new();
+    let mut cursor = None;
+    for (name, contents) in [("types.baml", types), ("functions.baml", functions)] {
+        let path = root().join(name);
+        if let Some(offset) = contents.find(CURSOR) {
+            let contents = contents.replacen(CURSOR, "", 1);
+            cursor = Some((path.clone(), offset_to_position(&contents, offset)));
+            files.insert(path, contents);
+        } else {
+            files.insert(path, contents.to_string());
+        }
+    }
+
+    let (path, position) = cursor.expect("a file must contain the cursor marker");
+    (Project::new(root(), files), path, position)
+}
+
+/// Inserts the cursor marker before the `nth` occurrence of `needle`.
+fn at(contents: &str, needle: &str, nth: usize) -> String {
+    let (offset, _) = contents
+        .match_indices(needle)
+        .nth(nth)
+        .unwrap_or_else(|| panic!("`{}` not found", needle));
+    format!("{}{}{}", &contents[..offset], CURSOR, &contents[offset..])
+}
+
+fn hover_text(types: &str, functions: &str) -> Option<String> {
+    let (project, path, position) = project_with_cursor(types, functions);
+    hover(&project, &path, position).map(|hover| match hover.contents {
+        HoverContents::Markup(markup) => markup.value,
+        other => panic!("unexpected hover contents: {:?}", other),
+    })
+}
+
+/// The file name and the text of `location`.
+fn describe(project_files: (&str, &str), location: &Location) -> (String, String) {
+    let path = location.uri.to_file_path().unwrap();
+    let name = path.file_name().unwrap().to_string_lossy().to_string();
+    let contents = if name == "types.baml" {
+        project_files.0
+    } else {
+        project_files.1
+    };
+    (name, text_of(contents, location.range))
+}
+
+fn text_of(contents: &str, range: Range) -> String {
+    let lines = contents.lines().collect::<Vec<_>>();
+    assert_eq!(range.start.line, range.end.line);
+    let line = lines[range.start.line as usize];
+    line[range.start.character as usize..range.end.character as usize].to_string()
+}
+
+fn definition_of(types: &str, functions: &str) -> Option<(String, String)> {
+    let (project, path, position) = project_with_cursor(types, functions);
+    match definition(&project, &path, position)? {
+        GotoDefinitionResponse::Scalar(location) => Some(describe((TYPES, FUNCTIONS), &location)),
+        other => panic!("unexpected definition: {:?}", other),
+    }
+}
+
+#[test]
+fn hover_on_type_reference_shows_declaration() {
+    let text = hover_text(&at(TYPES, "Message?", 0), FUNCTIONS).unwrap();
+    assert_eq!(
+        text,
+        "```baml\nclass Message {\n  role Role\n  body string\n}\n```"
+    );
+}
+
+#[test]
+fn hover_shows_descriptions_and_doc_comments() {
+    let text = hover_text(&at(TYPES, "role Role", 0), FUNCTIONS).unwrap();
+    assert_eq!(text, "```baml\nrole Role\n// in class Message\n```");
+
+    let text = hover_text(&at(TYPES, "body", 0), FUNCTIONS).unwrap();
+    assert_eq!(
+        text,
+        "```baml\nbody string\n// in class Message\n```\n\nWhat was said."
+    );
+
+    let text = hover_text(&at(TYPES, "Assistant", 0), FUNCTIONS).unwrap();
+    assert_eq!(text, "```baml\nRole.Assistant\n```\n\nThe model");
+
+    let text = hover_text(&at(TYPES, "Role {", 0), FUNCTIONS).unwrap();
+    assert_eq!(
+        text,
+        "```baml\nenum Role {\n  User\n  Assistant\n}\n```\n\nWho sent a message."
+    );
+}
+
+#[test]
+fn hover_on_prompt_variable_shows_field() {
+    let text = hover_text(TYPES, &at(FUNCTIONS, "body}", 0)).unwrap();
+    assert_eq!(
+        text,
+        "```baml\nbody string\n// in class Message\n```\n\nWhat was said."
+    );
+
+    let text = hover_text(TYPES, &at(FUNCTIONS, "tone}", 0)).unwrap();
+    assert_eq!(text, "```baml\ntone: string\n// argument of Reply\n```");
+}
+
+#[test]
+fn hover_on_function_and_client() {
+    let text = hover_text(TYPES, &at(FUNCTIONS, "Reply {", 0)).unwrap();
+    assert_eq!(
+        text,
+        "```baml\nfunction Reply {\n  input (conversation: Conversation, tone: string)\n  output Message\n}\n```"
+    );
+
+    let text = hover_text(TYPES, &at(FUNCTIONS, "GPT4", 0)).unwrap();
+    assert_eq!(
+        text,
+        "```baml\nclient<llm> GPT4 {\n  provider baml-openai-chat\n}\n```"
+    );
+}
+
+#[test]
+fn hover_on_primitive_is_empty() {
+    assert_eq!(hover_text(&at(TYPES, "string @", 0), FUNCTIONS), None);
+}
+
+#[test]
+fn definition_of_type_references() {
+    assert_eq!(
+        definition_of(&at(TYPES, "Thread\n", 0), FUNCTIONS),
+        Some(("types.baml".into(), "Thread".into()))
+    );
+    assert_eq!(
+        definition_of(TYPES, &at(FUNCTIONS, "Conversation,", 0)),
+        Some(("types.baml".into(), "Conversation".into()))
+    );
+}
+
+#[test]
+fn definition_of_impl_function_and_client() {
+    let (name, text) = definition_of(TYPES, &at(FUNCTIONS, "Reply>", 0)).unwrap();
+    assert_eq!((name.as_str(), text.as_str()), ("functions.baml", "Reply"));
+    let location = {
+        let (project, path, position) = project_with_cursor(TYPES, &at(FUNCTIONS, "GPT4\n", 0));
+        match definition(&project, &path, position).unwrap() {
+            GotoDefinitionResponse::Scalar(location) => location,
+            other => panic!("unexpected definition: {:?}", other),
+        }
+    };
+    // The declaration, not the reference the cursor is on.
+    assert_eq!(location.range.start.line, 13);
+}
+
+#[test]
+fn definition_of_prompt_variable_segments() {
+    assert_eq!(
+        definition_of(TYPES, &at(FUNCTIONS, "conversation.latest", 0)),
+        Some(("functions.baml".into(), "conversation".into()))
+    );
+    assert_eq!(
+        definition_of(TYPES, &at(FUNCTIONS, "latest.body", 0)),
+        Some(("types.baml".into(), "latest".into()))
+    );
+    assert_eq!(
+        definition_of(TYPES, &at(FUNCTIONS, "body}", 0)),
+        Some(("types.baml".into(), "body".into()))
+    );
+    assert_eq!(definition_of(TYPES, &at(FUNCTIONS, "Reply to", 0)), None);
+}
+
+#[test]
+fn references_to_a_class_and_a_field() {
+    let (project, path, position) = project_with_cursor(&at(TYPES, "Message {", 0), FUNCTIONS);
+    let mut found = references(&project, &path, position, true)
+        .unwrap()
+        .iter()
+        .map(|location| describe((TYPES, FUNCTIONS), location))
+        .map(|(name, text)| format!("{}:{}", name, text))
+        .collect::<Vec<_>>();
+    found.sort();
+    assert_eq!(
+        found,
+        [
+            "functions.baml:Message",
+            "types.baml:Message",
+            "types.baml:Message",
+            "types.baml:Message",
+        ]
+    );
+
+    let (project, path, position) = project_with_cursor(&at(TYPES, "body", 0), FUNCTIONS);
+    let found = references(&project, &path, position, false).unwrap();
+    assert_eq!(found.len(), 1);
+    assert_eq!(
+        describe((TYPES, FUNCTIONS), &found[0]),
+        ("functions.baml".into(), "body".into())
+    );
+}
+
+fn completion_labels(types: &str, functions: &str) -> Option<Vec<String>> {
+    let (project, path, position) = project_with_cursor(types, functions);
+    match completion(&project, &path, position)? {
+        CompletionResponse::Array(items) => {
+            Some(items.into_iter().map(|item| item.label).collect())
+        }
+        other => panic!("unexpected completion: {:?}", other),
+    }
+}
+
+#[test]
+fn completes_type_names_in_classes() {
+    let types = TYPES.replace("  latest Message?\n", "  latest <|>\n");
+    assert_eq!(
+        completion_labels(&types, FUNCTIONS).unwrap(),
+        [
+            "Role",
+            "Message",
+            "Thread",
+            "Conversation",
+            "string",
+            "int",
+            "float",
+            "bool",
+            "char"
+        ]
+    );
+}
+
+#[test]
+fn completes_client_names_in_impls() {
+    let functions = FUNCTIONS.replace("  client GPT4\n", "  client <|>\n");
+    assert_eq!(completion_labels(TYPES, &functions).unwrap(), ["GPT4"]);
+    assert_eq!(
+        completion_labels(TYPES, &at(FUNCTIONS, "  prompt", 0)),
+        None
+    );
+}
+
+#[test]
+fn diagnostics_are_reported_per_file() {
+    let functions = FUNCTIONS.replace("output Message", "output Missing");
+    let (project, _, _) = project_with_cursor(&format!("{}{}", CURSOR, TYPES), &functions);
+    let diagnostics = project.diagnostics();
+
+    assert_eq!(
+        diagnostics.keys().map(PathBuf::as_path).collect::<Vec<_>>(),
+        [
+            Path::new("/project/baml_src/functions.baml"),
+            Path::new("/project/baml_src/types.baml")
+        ]
+    );
+    assert!(diagnostics[&root().join("types.baml")].is_empty());
+    let errors = &diagnostics[&root().join("functions.baml")];
+    assert_eq!(errors.len(), 1, "{:?}", errors);
+    assert!(
+        errors[0].message.contains("Missing"),
+        "{}",
+        errors[0].message
+    );
+    assert_eq!(text_of(&functions, errors[0].range), "Missing");
+}
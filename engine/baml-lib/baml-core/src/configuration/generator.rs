@@ -43,6 +43,8 @@ pub enum GeneratorLanguage {
     Python,
     #[serde(rename = "typescript")]
     TypeScript,
+    #[serde(rename = "rust")]
+    Rust,
 }
 
 impl GeneratorLanguage {
@@ -50,6 +52,7 @@ impl GeneratorLanguage {
         match self {
             Self::Python => "python",
             Self::TypeScript => "typescript",
+            Self::Rust => "rust",
         }
     }
 
@@ -57,6 +60,7 @@ impl GeneratorLanguage {
         match self {
             Self::Python => "baml",
             Self::TypeScript => "@boundaryml/baml_client",
+            Self::Rust => "baml",
         }
     }
 
@@ -86,7 +90,7 @@ impl GeneratorLanguage {
                                 })
                                 .map(|line| line.split_whitespace().last().unwrap().to_string())
                         }
-                        Self::TypeScript => None,
+                        Self::TypeScript | Self::Rust => None,
                     })
                     .flatten()
             })
@@ -1,8 +1,11 @@
 mod dir_writer;
 mod generate_pipeline;
 mod generate_python_client;
+mod generate_rust_client;
 mod generate_ts_client;
-mod ir;
+#[cfg(test)]
+mod golden;
+pub mod ir;
 mod test_request;
 
 pub(crate) use generate_pipeline::generate_pipeline;
@@ -92,15 +92,25 @@ impl<L: LanguageFeatures> FileCollector<L> {
         })
     }
 
-    pub(super) fn commit(&self, dir: &PathBuf) -> std::io::Result<()> {
+    /// Every collected file and its formatted content, sorted by path.
+    pub(super) fn rendered_files(&self) -> Vec<(&PathBuf, String)> {
         // Sort the files by path so that we always write to the same file
         let mut files = self.files.iter().collect::<Vec<_>>();
-        files.sort_by(|(a, _), (b, _)| a.cmp(b));
+        files.sort_by_key(|(path, _)| *path);
+
+        files
+            .into_iter()
+            .map(|(path, file)| (path, self.format_file(file)))
+            .collect()
+    }
+
+    pub(super) fn commit(&self, dir: &PathBuf) -> std::io::Result<()> {
+        let files = self.rendered_files();
 
-        for (path, file) in &files {
+        for (path, content) in &files {
             let path = dir.join(path);
             std::fs::create_dir_all(path.parent().unwrap())?;
-            std::fs::write(&path, &self.format_file(file))?;
+            std::fs::write(&path, content)?;
         }
 
         info!("Wrote {} files to {}", files.len(), dir.display());
@@ -6,6 +6,7 @@ use crate::{
 };
 
 use super::generate_python_client::generate_py;
+use super::generate_rust_client::generate_rust;
 use super::generate_ts_client::generate_ts;
 use super::ir;
 
@@ -23,7 +24,8 @@ pub(crate) fn generate_pipeline(
         )
     })?;
     match gen.language {
-        GeneratorLanguage::Python => generate_py(db, gen, lock),
+        GeneratorLanguage::Python => generate_py(&ir, gen, lock),
         GeneratorLanguage::TypeScript => generate_ts(&ir, gen),
+        GeneratorLanguage::Rust => generate_rust(&ir, gen),
     }
 }
@@ -1,12 +1,12 @@
 use std::path::PathBuf;
 
-use internal_baml_parser_database::ParserDatabase;
-use internal_baml_schema_ast::ast::WithName;
-use log::{debug, info};
+use log::info;
 use serde_json::json;
 
 use crate::{configuration::Generator, lockfile::LockFileWrapper};
 
+use super::ir::IntermediateRepr;
+
 use self::traits::WithWritePythonString;
 
 mod r#class;
@@ -14,54 +14,54 @@ mod client;
 mod configuration;
 mod r#enum;
 mod field;
+mod field_type;
 mod r#file;
 mod function;
 mod template;
 mod traits;
-mod types;
-mod types_partial;
+mod type_alias;
 mod value;
 mod variants;
 pub(super) use r#file::{File, FileCollector};
+pub(super) use field_type::RequiredTypes;
 pub(super) use traits::WithToCode;
 
 fn generate_py_file<'a>(obj: &impl WithWritePythonString, fc: &'a mut FileCollector) {
     obj.write_py_file(fc);
 }
 
-pub(crate) fn generate_py(
-    db: &ParserDatabase,
-    gen: &Generator,
-    lock: &LockFileWrapper,
-) -> std::io::Result<()> {
+fn collect_py_files(ir: &IntermediateRepr) -> FileCollector {
     let mut fc = Default::default();
-    db.walk_enums().for_each(|e| generate_py_file(&e, &mut fc));
-    db.walk_classes()
+    ir.walk_enums().for_each(|e| generate_py_file(&e, &mut fc));
+    ir.walk_classes()
         .for_each(|c| generate_py_file(&c, &mut fc));
-    db.walk_functions()
+    ir.walk_type_aliases()
+        .for_each(|a| generate_py_file(&a, &mut fc));
+    ir.walk_functions()
         .for_each(|f| generate_py_file(&f, &mut fc));
-    let mut variants = db.walk_variants().collect::<Vec<_>>();
-    variants.sort_by(|a, b| {
-        match a
-            .function_identifier()
-            .name()
-            .cmp(b.function_identifier().name())
-        {
-            std::cmp::Ordering::Equal => a.name().cmp(b.name()),
-            x => x,
-        }
+    ir.walk_functions().for_each(|f| {
+        let mut impls = f.walk_impls().collect::<Vec<_>>();
+        impls.sort_by(|a, b| a.elem().name.cmp(&b.elem().name));
+        impls.iter().for_each(|i| generate_py_file(i, &mut fc));
     });
-    variants.iter().for_each(|f| generate_py_file(f, &mut fc));
 
-    db.walk_clients()
-        .for_each(|f| generate_py_file(&f, &mut fc));
-    db.walk_retry_policies()
-        .for_each(|f| generate_py_file(&f, &mut fc));
-    let mut test_cases = db.walk_test_cases().collect::<Vec<_>>();
-    test_cases.sort_by(|a, b| a.name().cmp(b.name()));
-    test_cases.iter().for_each(|f| generate_py_file(f, &mut fc));
+    ir.walk_clients()
+        .for_each(|c| generate_py_file(&c, &mut fc));
+    ir.walk_retry_policies()
+        .for_each(|r| generate_py_file(&r, &mut fc));
+    ir.walk_test_cases()
+        .for_each(|t| generate_py_file(&t, &mut fc));
+
+    generate_py_file(ir, &mut fc);
+    fc
+}
 
-    generate_py_file(db, &mut fc);
+pub(crate) fn generate_py(
+    ir: &IntermediateRepr,
+    gen: &Generator,
+    lock: &LockFileWrapper,
+) -> std::io::Result<()> {
+    let fc = collect_py_files(ir);
 
     let output_path = &gen.output_path;
     info!("Writing files to {}", output_path.to_string_lossy());
@@ -80,7 +80,7 @@ pub(crate) fn generate_py(
     }
 }
 
-impl WithWritePythonString for ParserDatabase {
+impl WithWritePythonString for IntermediateRepr {
     fn write_py_file<'a>(&'a self, fc: &'a mut FileCollector) {
         fc.start_py_file(".", "__init__");
         fc.last_file().add_line("from . import impls");
@@ -107,24 +107,31 @@ impl WithWritePythonString for ParserDatabase {
         self.walk_functions().for_each(|f| {
             fc.last_file().add_import(
                 &format!("..__do_not_import.functions.{}", f.file_name()),
-                &format!("I{}", f.name()),
+                &format!("I{}", f.elem().name),
             );
             fc.last_file().add_import(
                 &format!("..__do_not_import.functions.{}", f.file_name()),
-                &format!("I{}Output", f.name()),
+                &format!("I{}Output", f.elem().name),
             )
         });
         self.walk_enums().for_each(|e| {
             fc.last_file().add_import(
                 &format!("..__do_not_import.types.enums.{}", e.file_name()),
-                e.name(),
+                &e.elem().name,
             )
         });
 
         self.walk_classes().for_each(|c| {
             fc.last_file().add_import(
                 &format!("..__do_not_import.types.classes.{}", c.file_name()),
-                c.name(),
+                &c.elem().name,
+            )
+        });
+
+        self.walk_type_aliases().for_each(|a| {
+            fc.last_file().add_import(
+                &format!("..__do_not_import.types.aliases.{}", a.file_name()),
+                &a.elem().name,
             )
         });
         fc.complete_file();
@@ -134,7 +141,7 @@ impl WithWritePythonString for ParserDatabase {
         self.walk_classes().for_each(|c| {
             fc.last_file().add_import_and_reexport(
                 &format!("..__do_not_import.types.partial.classes.{}", c.file_name()),
-                &format!("Partial{}", c.name()),
+                &format!("Partial{}", c.elem().name),
             )
         });
         fc.complete_file();
@@ -153,9 +160,9 @@ impl WithWritePythonString for ParserDatabase {
             .map(|f| {
                 fc.last_file().add_import(
                     &format!(".functions.{}", f.file_name()),
-                    &format!("BAML{}", f.name()),
+                    &format!("BAML{}", f.elem().name),
                 );
-                f.name()
+                f.elem().name.as_str()
             })
             .collect::<Vec<_>>();
         fxs.sort();
@@ -165,9 +172,9 @@ impl WithWritePythonString for ParserDatabase {
             .map(|f| {
                 fc.last_file().add_import(
                     &format!(".clients.{}", f.file_name()),
-                    &format!("{}", f.name()),
+                    &f.elem().name,
                 );
-                f.name().to_string()
+                f.elem().name.as_str()
             })
             .collect::<Vec<_>>();
         clients.sort();
@@ -184,3 +191,6 @@ impl WithWritePythonString for ParserDatabase {
         "generated_baml_client".to_string()
     }
 }
+
+#[cfg(test)]
+mod tests;
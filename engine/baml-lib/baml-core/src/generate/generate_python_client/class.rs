@@ -1,43 +1,71 @@
-use internal_baml_parser_database::{
-    walkers::{ClassWalker, FieldWalker},
-    WithStaticRenames,
-};
-use internal_baml_schema_ast::ast::WithName;
 use serde_json::json;
 
-use crate::generate::generate_python_client::file::clean_file_name;
+use crate::generate::{
+    generate_python_client::file::clean_file_name,
+    ir::{Class, FieldType, Walker},
+};
 
 use super::{
+    field_type::{can_be_null, is_nullable, RequiredTypes},
     file::{File, FileCollector},
     template::render_template,
     traits::{JsonHelper, WithPartial, WithToCode, WithWritePythonString},
 };
 
-impl WithWritePythonString for ClassWalker<'_> {
+impl WithWritePythonString for Walker<'_, &Class> {
     fn file_name(&self) -> String {
-        format!("cls_{}", clean_file_name(self.name()))
+        format!("cls_{}", clean_file_name(&self.elem().name))
     }
 
     fn write_py_file(&self, fc: &mut FileCollector) {
+        let name = &self.elem().name;
+        let required = RequiredTypes::transitive(self.db, field_types(self));
+        // The classes that refer back to this one, itself included if it contains itself. Their
+        // modules import this one in turn, so they are imported after the class is defined.
+        let mut recursive = required
+            .classes()
+            .filter(|f| RequiredTypes::transitive(self.db, field_types(f)).has_class(name))
+            .collect::<Vec<_>>();
+        recursive.sort_by(|a, b| a.elem().name.cmp(&b.elem().name));
+        let is_recursive =
+            |f: &Walker<'_, &Class>| recursive.iter().any(|r| r.elem().name == f.elem().name);
+
         fc.start_py_file("types/classes", "__init__");
         fc.complete_file();
         fc.start_py_file("types", "__init__");
         fc.last_file()
-            .add_import(&format!(".classes.{}", self.file_name()), self.name());
+            .add_import(&format!(".classes.{}", self.file_name()), name);
         fc.complete_file();
 
         fc.start_py_file("types/classes", self.file_name());
 
-        self.required_classes().for_each(|f| {
+        if !recursive.is_empty() {
+            fc.last_file().add_import("__future__", "annotations");
+        }
+        required
+            .classes()
+            .filter(|f| !is_recursive(f))
+            .for_each(|f| {
+                fc.last_file()
+                    .add_import(&format!(".{}", f.file_name()), &f.elem().name);
+            });
+        required.enums().for_each(|f| {
             fc.last_file()
-                .add_import(&format!(".{}", f.file_name()), f.name());
+                .add_import(&format!("..enums.{}", f.file_name()), &f.elem().name);
         });
-        self.required_enums().for_each(|f| {
+        required.type_aliases().for_each(|f| {
             fc.last_file()
-                .add_import(&format!("..enums.{}", f.file_name()), f.name());
+                .add_import(&format!("..aliases.{}", f.file_name()), &f.elem().name);
         });
         let json = self.json(fc.last_file());
         render_template(super::template::HSTemplate::Class, fc.last_file(), json);
+        if !recursive.is_empty() {
+            let imports = recursive
+                .iter()
+                .filter(|f| f.elem().name != *name)
+                .map(|f| (format!(".{}", f.file_name()), f.elem().name.clone()));
+            resolve_recursive_fields(fc.last_file(), name, imports);
+        }
         fc.complete_file();
 
         // Do the same thing, but now write the "Partial" types, which have all fields optional.
@@ -46,22 +74,34 @@ impl WithWritePythonString for ClassWalker<'_> {
         fc.start_py_file("types/partial", "__init__");
         fc.last_file().add_import(
             &format!(".classes.{}", self.file_name()),
-            &format!("Partial{}", self.name()),
+            &format!("Partial{}", name),
         );
         fc.complete_file();
 
         fc.start_py_file("types/partial/classes", self.file_name());
 
-        self.required_classes().for_each(|f| {
-            fc.last_file().add_import(
-                &format!("...classes.{}", f.file_name()),
-                &format!("{}", f.name()),
-            );
+        if !recursive.is_empty() {
+            fc.last_file().add_import("__future__", "annotations");
+        }
+        required.classes().for_each(|f| {
+            fc.last_file()
+                .add_import(&format!("...classes.{}", f.file_name()), &f.elem().name);
+            // Nested classes are partial too.
+            if f.elem().name != *name && !is_recursive(&f) {
+                fc.last_file().add_import(
+                    &format!(".{}", f.file_name()),
+                    &format!("Partial{}", f.elem().name),
+                );
+            }
         });
         // Still import the regular enums, as the partials are not different
-        self.required_enums().for_each(|f| {
+        required.enums().for_each(|f| {
+            fc.last_file()
+                .add_import(&format!("...enums.{}", f.file_name()), &f.elem().name);
+        });
+        required.type_aliases().for_each(|f| {
             fc.last_file()
-                .add_import(&format!("...enums.{}", f.file_name()), f.name());
+                .add_import(&format!("...aliases.{}", f.file_name()), &f.elem().name);
         });
 
         let json = self.json(fc.last_file());
@@ -70,44 +110,76 @@ impl WithWritePythonString for ClassWalker<'_> {
             fc.last_file(),
             json,
         );
+        if !recursive.is_empty() {
+            let imports = recursive
+                .iter()
+                .filter(|f| f.elem().name != *name)
+                .map(|f| {
+                    (
+                        format!(".{}", f.file_name()),
+                        format!("Partial{}", f.elem().name),
+                    )
+                });
+            resolve_recursive_fields(fc.last_file(), &format!("Partial{}", name), imports);
+        }
         fc.complete_file();
     }
 }
 
-impl JsonHelper for ClassWalker<'_> {
-    fn json(&self, f: &mut File) -> serde_json::Value {
-        json!({
-            "name": self.name(),
-            "name_partial": "Partial".to_string() + &self.name(),
-            "fields": self.static_fields().map(|field|
-                field.json(f)
-                ).collect::<Vec<_>>(),
-            "properties": self.dynamic_fields().map(|field|
-                field.json(f)
-            ).collect::<Vec<_>>(),
-            "num_fields": self.ast_class().fields().len(),
-        })
+/// The types of the fields of `cls`.
+fn field_types<'a>(cls: &Walker<'a, &'a Class>) -> impl Iterator<Item = &'a FieldType> {
+    let cls = cls.elem();
+    cls.static_fields
+        .iter()
+        .chain(cls.dynamic_fields.iter())
+        .map(|f| &f.elem.r#type.elem)
+}
+
+/// Imports the classes that refer back to `model` once it is defined, then resolves the fields
+/// of `model` that use them.
+fn resolve_recursive_fields(
+    file: &mut File,
+    model: &str,
+    imports: impl Iterator<Item = (String, String)>,
+) {
+    file.add_empty_line();
+    for (module, name) in imports {
+        file.add_line(format!("from {} import {}", module, name));
     }
+    file.add_line(format!("{}.model_rebuild()", model));
 }
 
-impl JsonHelper for FieldWalker<'_> {
+impl JsonHelper for Walker<'_, &Class> {
     fn json(&self, f: &mut File) -> serde_json::Value {
-        //log::info!("FieldWalker::json {} {}", self.name(), self.is_dynamic());
-        match self.is_dynamic() {
-            true => json!({
-                "name": self.name(),
-                "type": self.r#type().to_py_string(f),
-                "type_partial": self.r#type().to_partial_py_string(f),
-                "code": self.code_for_language("python").unwrap_or("raise NotImplementedError()"),
-            }),
-            false => json!({
-                "name": self.name(),
-                "type": self.r#type().to_py_string(f),
-                "type_partial": self.r#type().to_partial_py_string(f),
-                "optional": self.r#type().is_nullable(),
-                "can_be_null": self.r#type().can_be_null(),
-                "alias": self.maybe_alias(self.db),
-            }),
-        }
+        let cls = self.elem();
+        json!({
+            "name": cls.name,
+            "name_partial": format!("Partial{}", cls.name),
+            "fields": cls.static_fields.iter().map(|field| {
+                let r#type = &field.elem.r#type.elem;
+                json!({
+                    "name": field.elem.name,
+                    "type": r#type.to_py_string(f),
+                    "type_partial": r#type.to_partial_py_string(self.db, f),
+                    "optional": is_nullable(r#type),
+                    "can_be_null": can_be_null(r#type),
+                    "alias": field.attributes.get("alias").and_then(|a| a.as_string_value()),
+                })
+            }).collect::<Vec<_>>(),
+            "properties": cls.dynamic_fields.iter().map(|field| {
+                let r#type = &field.elem.r#type.elem;
+                json!({
+                    "name": field.elem.name,
+                    "type": r#type.to_py_string(f),
+                    "type_partial": r#type.to_partial_py_string(self.db, f),
+                    "code": field
+                        .attributes
+                        .get("get/python")
+                        .and_then(|code| code.as_string_value())
+                        .unwrap_or("raise NotImplementedError()"),
+                })
+            }).collect::<Vec<_>>(),
+            "num_fields": cls.static_fields.len() + cls.dynamic_fields.len(),
+        })
     }
 }
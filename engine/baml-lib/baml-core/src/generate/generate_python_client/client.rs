@@ -1,11 +1,9 @@
-use internal_baml_parser_database::walkers::{ClientWalker, Walker};
-use internal_baml_schema_ast::ast::{
-    ClientId, Expression, Identifier, WithDocumentation, WithName,
-};
-
 use serde_json::json;
 
-use crate::generate::generate_python_client::file::clean_file_name;
+use crate::generate::{
+    generate_python_client::file::clean_file_name,
+    ir::{Client, Expression, Identifier, TypeValue, Walker},
+};
 
 use super::{
     file::File,
@@ -18,14 +16,27 @@ fn escaped_string(s: &str, quotes: (&'static str, &'static str)) -> String {
     s.replace("\\", "\\\\").replace(quotes.0, quotes.1)
 }
 
+pub(super) fn quoted(s: &str) -> String {
+    format!("\"{}\"", escaped_string(s, ("\"", "\\\"")))
+}
+
 impl ToPyObject for Identifier {
     fn to_py_object(&self, f: &mut File) -> String {
         match self {
-            Identifier::ENV(s, _) => {
+            Identifier::ENV(s) => {
                 f.add_import("os", "environ");
                 format!("environ['{}']", s.clone())
             }
-            _ => format!("\"{}\"", escaped_string(self.name(), ("\"", "\\\""))),
+            Identifier::Local(s) | Identifier::String(s) => quoted(s),
+            Identifier::Ref(path) => quoted(&path.join(".")),
+            Identifier::Primitive(p) => quoted(match p {
+                TypeValue::String => "string",
+                TypeValue::Int => "int",
+                TypeValue::Float => "float",
+                TypeValue::Bool => "bool",
+                TypeValue::Char => "char",
+                TypeValue::Null => "null",
+            }),
         }
     }
 }
@@ -33,20 +44,18 @@ impl ToPyObject for Identifier {
 impl ToPyObject for Expression {
     fn to_py_object(&self, f: &mut File) -> String {
         match self {
-            Expression::NumericValue(val, _) => val.clone(),
-            Expression::StringValue(val, _) => {
-                format!("\"{}\"", escaped_string(val, ("\"", "\\\"")))
-            }
-            Expression::RawStringValue(val) => format!(
+            Expression::Numeric(val) => val.clone(),
+            Expression::String(val) => quoted(val),
+            Expression::RawString(val) => format!(
                 "\"\"\"\\\n{}\\\n\"\"\"",
-                escaped_string(val.value(), ("\"\"\"", "\\\"\\\"\\\""))
+                escaped_string(val, ("\"\"\"", "\\\"\\\"\\\""))
             ),
             Expression::Identifier(idn) => idn.to_py_object(f),
-            Expression::Array(arr, _) => {
+            Expression::List(arr) => {
                 let json_arr: Vec<_> = arr.iter().map(|x| x.to_py_object(f)).collect();
                 format!("[{}]", json_arr.join(", "))
             }
-            Expression::Map(map, _) => {
+            Expression::Map(map) => {
                 let kvs = map
                     .iter()
                     .map(|(k, v)| {
@@ -63,57 +72,54 @@ impl ToPyObject for Expression {
     }
 }
 
-impl JsonHelper for ClientWalker<'_> {
+impl JsonHelper for Walker<'_, &Client> {
     fn json(&self, f: &mut File) -> serde_json::Value {
-        let props = self.properties();
-        let opts = props
+        let client = self.elem();
+        let opts = client
             .options
             .iter()
             .map(|(k, v)| {
                 json!({
-                            "key": k.clone(),
-                            "value": v.to_py_object(f),
+                    "key": k.clone(),
+                    "value": v.to_py_object(f),
                 })
             })
             .collect::<Vec<_>>();
 
-        let retry_policy = props
+        let retry_policy = client
             .retry_policy
             .as_ref()
-            .map(|(policy, _)| {
-                f.add_import(" ..configs.retry_policy", &policy);
+            .map(|policy| {
+                f.add_import(" ..configs.retry_policy", policy);
                 policy.as_str()
             })
             .unwrap_or("None");
 
-        let redactions = props
+        let redactions = client
             .options
             .iter()
-            .filter_map(|(k, v)| {
-                if v.is_env_expression() {
-                    return Some(format!("\"{}\"", k));
-                }
-                None
+            .filter_map(|(k, v)| match v {
+                Expression::Identifier(Identifier::ENV(_)) => Some(format!("\"{}\"", k)),
+                _ => None,
             })
             .collect::<Vec<_>>()
             .join(", ");
 
         json!({
-            "name": self.name(),
+            "name": client.name,
             "kwargs": {
-                "provider": serde_json::to_string(&props.provider.0).unwrap(),
+                "provider": serde_json::to_string(&client.provider).unwrap(),
                 "retry_policy": retry_policy,
                 "redactions": format!("[{}]", redactions),
             },
             "options": opts,
-            "doc_string": self.ast_client().documentation(),
         })
     }
 }
 
-impl WithWritePythonString for Walker<'_, ClientId> {
+impl WithWritePythonString for Walker<'_, &Client> {
     fn file_name(&self) -> String {
-        format!("client_{}", clean_file_name(self.name()))
+        format!("client_{}", clean_file_name(&self.elem().name))
     }
 
     fn write_py_file(&self, fc: &mut FileCollector) {
@@ -1,165 +1,144 @@
-use internal_baml_parser_database::walkers::ConfigurationWalker;
 use internal_baml_parser_database::RetryPolicyStrategy;
-use internal_baml_schema_ast::ast::{Configuration, FunctionArgs, WithName};
 use serde_json::{json, Value};
 
-use crate::generate::generate_python_client::file::clean_file_name;
+use crate::generate::ir::{FunctionArgs, RetryPolicy, TestCase, Walker};
 
 use super::{
-    file::FileCollector,
+    field_type::RequiredTypes,
+    file::{File, FileCollector},
     template::{render_template, HSTemplate},
     traits::{JsonHelper, WithWritePythonString},
     value::to_py_value,
     WithToCode,
 };
 
-impl WithWritePythonString for ConfigurationWalker<'_> {
+impl WithWritePythonString for Walker<'_, &RetryPolicy> {
     fn file_name(&self) -> String {
-        clean_file_name(match self.id.1 {
-            "retry_policy" => "retry_policy",
-            "test_case" => "test_baml_client",
-            "printer" => "printer",
-            _ => unreachable!("Invalid configuration type"),
-        })
+        "retry_policy".to_string()
     }
 
-    fn write_py_file<'a>(&'a self, fc: &'a mut FileCollector) {
-        if self.id.1 == "printer" {
-            // Printers aren't generated.
-            return;
-        }
+    fn write_py_file(&self, fc: &mut FileCollector) {
+        fc.start_py_file("configs", "__init__");
+        fc.last_file()
+            .add_import(&format!(".{}", self.file_name()), &self.elem().name.0);
+        fc.complete_file();
 
-        match self.ast_node() {
-            Configuration::RetryPolicy(_) => {
-                fc.start_py_file("configs", "__init__");
-                fc.last_file()
-                    .add_import(&format!(".{}", self.file_name()), self.name());
-                fc.complete_file();
+        fc.start_py_file("configs", self.file_name());
+        let json = self.json(fc.last_file());
+        render_template(HSTemplate::RetryPolicy, fc.last_file(), json);
+        fc.complete_file();
+    }
+}
 
-                fc.start_py_file("configs", self.file_name());
-                let json = self.json(fc.last_file());
-                render_template(
-                    super::template::HSTemplate::RetryPolicy,
-                    fc.last_file(),
-                    json,
+impl JsonHelper for Walker<'_, &RetryPolicy> {
+    fn json(&self, f: &mut File) -> serde_json::Value {
+        let strategy = match &self.elem().strategy {
+            RetryPolicyStrategy::ConstantDelay(strategy) => {
+                f.add_import(
+                    "baml_core.configs.retry_policy",
+                    "create_retry_policy_constant_delay",
+                );
+                json!({
+                    "type": "constant_delay",
+                    "params": {
+                      "delay_ms": strategy.delay_ms,
+                    }
+                })
+            }
+            RetryPolicyStrategy::ExponentialBackoff(strategy) => {
+                f.add_import(
+                    "baml_core.configs.retry_policy",
+                    "create_retry_policy_exponential_backoff",
                 );
-                fc.complete_file();
+                json!({
+                    "type": "exponential_backoff",
+                    "params": {
+                        "delay_ms": strategy.delay_ms,
+                        "max_delay_ms": strategy.max_delay_ms,
+                        "multiplier": strategy.multiplier,
+                    }
+                })
             }
-            Configuration::Printer(_) => {}
-            Configuration::TestCase(tc) => {
-                let func = self.walk_function();
+        };
 
-                fc.start_export_file("tests", "__init__");
-                fc.complete_file();
+        json!({
+            "name": self.elem().name.0,
+            "max_retries": self.elem().max_retries,
+            "strategy": strategy,
+        })
+    }
+}
 
-                fc.start_export_file("tests", format!("test_{}", func.name()));
+impl WithWritePythonString for Walker<'_, &TestCase> {
+    fn file_name(&self) -> String {
+        format!("test_{}", self.elem().function)
+    }
 
-                func.walk_input_args().for_each(|arg| {
-                    arg.required_classes().for_each(|class| {
-                        fc.last_file().add_import("..baml_types", class.name());
-                    });
-                    arg.required_enums().for_each(|enum_| {
-                        fc.last_file().add_import("..baml_types", enum_.name());
-                    });
-                });
-                func.walk_output_args().for_each(|arg| {
-                    arg.required_classes().for_each(|class| {
-                        fc.last_file().add_import("..baml_types", class.name());
-                    });
-                    arg.required_enums().for_each(|enum_| {
-                        fc.last_file().add_import("..baml_types", enum_.name());
-                    });
-                });
-                fc.last_file()
-                    .add_import("..baml_types", &format!("I{}", func.name()));
+    fn write_py_file(&self, fc: &mut FileCollector) {
+        let Some(func) = self.function() else {
+            // Validation reports tests for unknown functions.
+            return;
+        };
+        let func_name = &func.elem().name;
 
-                let test_case_content =
-                    serde_json::from_str::<Value>(self.test_case().content.value())
-                        .map(|v| to_py_value(&v))
-                        .unwrap();
+        fc.start_export_file("tests", "__init__");
+        fc.complete_file();
 
-                match func.ast_function().input() {
-                    FunctionArgs::Unnamed(arg) => {
-                        let data = json!({
-                            "function_name": func.name(),
-                            "test_case_name": tc.name(),
-                            "test_case_input": test_case_content,
-                            "test_case_type": arg.to_py_string(fc.last_file()),
-                        });
-                        render_template(HSTemplate::SingleArgTestSnippet, fc.last_file(), data);
-                    }
-                    FunctionArgs::Named(args) => {
-                        let data = json!({
-                            "function_name": func.name(),
-                            "test_case_name": tc.name(),
-                            "test_case_input": test_case_content,
-                            "test_case_types": args.args.iter().map(|(k, v)| json!({
-                                "name": k.name(),
-                                "type": v.to_py_string(fc.last_file()),
-                             })).collect::<Vec<_>>(),
-                        });
-                        render_template(HSTemplate::MultiArgTestSnippet, fc.last_file(), data);
-                    }
-                }
-                fc.complete_file();
-            }
-        }
-    }
-}
+        fc.start_export_file("tests", self.file_name());
 
-impl JsonHelper for ConfigurationWalker<'_> {
-    fn json(&self, f: &mut super::file::File) -> serde_json::Value {
-        match self.id.1 {
-            "retry_policy" => {
-                let strategy = match &self.retry_policy().strategy {
-                    RetryPolicyStrategy::ConstantDelay(strategy) => {
-                        f.add_import(
-                            "baml_core.configs.retry_policy",
-                            "create_retry_policy_constant_delay",
-                        );
-                        json!({
-                            "type": "constant_delay",
-                            "params": {
-                              "delay_ms": strategy.delay_ms,
-                            }
-                        })
-                    }
-                    RetryPolicyStrategy::ExponentialBackoff(strategy) => {
-                        f.add_import(
-                            "baml_core.configs.retry_policy",
-                            "create_retry_policy_exponential_backoff",
-                        );
-                        json!({
-                            "type": "exponential_backoff",
-                            "params": {
-                                "delay_ms": strategy.delay_ms,
-                                "max_delay_ms": strategy.max_delay_ms,
-                                "multiplier": strategy.multiplier,
-                            }
-                        })
-                    }
-                };
+        let required = RequiredTypes::transitive(
+            self.db,
+            func.input_types()
+                .into_iter()
+                .chain([&func.elem().output.elem]),
+        );
+        required
+            .classes()
+            .map(|c| &c.elem().name)
+            .chain(required.enums().map(|e| &e.elem().name))
+            .chain(required.type_aliases().map(|a| &a.elem().name))
+            .for_each(|name| fc.last_file().add_import("..baml_types", name));
+        fc.last_file()
+            .add_import("..baml_types", &format!("I{}", func_name));
 
-                json!({
-                    "name": self.name(),
-                    "max_retries": self.retry_policy().max_retries,
-                    "strategy": strategy,
-                })
-            }
-            "test_case" => {
-                json!({
-                    "name": self.name(),
-                    "function": self.test_case().function.0,
-                    "test_case": self.test_case().content.value(),
-                })
+        let test_case_content = serde_json::from_str::<Value>(&self.elem().content)
+            .map(|v| to_py_value(&v))
+            .unwrap();
+        // Tests that target some impls skip the others.
+        let exclude_impls = match self.elem().impls.is_empty() {
+            true => vec![],
+            false => func
+                .walk_impls()
+                .map(|i| &i.elem().name)
+                .filter(|name| !self.elem().impls.contains(name))
+                .collect::<Vec<_>>(),
+        };
+
+        match &func.elem().inputs {
+            FunctionArgs::UnnamedArg(arg) => {
+                let data = json!({
+                    "function_name": func_name,
+                    "test_case_name": self.elem().name,
+                    "test_case_input": test_case_content,
+                    "test_case_type": arg.to_py_string(fc.last_file()),
+                    "exclude_impls": exclude_impls,
+                });
+                render_template(HSTemplate::SingleArgTestSnippet, fc.last_file(), data);
             }
-            "printer" => {
-                json!({
-                    "name": self.name(),
-                    "printer": self.printer().template(),
-                })
+            FunctionArgs::NamedArgList(args) => {
+                let data = json!({
+                    "function_name": func_name,
+                    "test_case_name": self.elem().name,
+                    "test_case_input": test_case_content,
+                    "exclude_impls": exclude_impls,
+                    "test_case_types": args.iter().map(|(name, arg)| json!({
+                        "name": name,
+                        "type": arg.to_py_string(fc.last_file()),
+                    })).collect::<Vec<_>>(),
+                });
+                render_template(HSTemplate::MultiArgTestSnippet, fc.last_file(), data);
             }
-            _ => unreachable!("Invalid configuration type"),
         }
+        fc.complete_file();
     }
 }
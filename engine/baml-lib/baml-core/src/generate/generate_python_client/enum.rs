@@ -1,11 +1,9 @@
-use internal_baml_parser_database::{
-    walkers::{EnumValueWalker, EnumWalker},
-    WithStaticRenames,
-};
-use internal_baml_schema_ast::ast::WithName;
 use serde_json::json;
 
-use crate::generate::generate_python_client::file::clean_file_name;
+use crate::generate::{
+    generate_python_client::file::clean_file_name,
+    ir::{Enum, Walker},
+};
 
 use super::{
     file::{File, FileCollector},
@@ -13,27 +11,21 @@ use super::{
     traits::{JsonHelper, WithWritePythonString},
 };
 
-impl JsonHelper for EnumWalker<'_> {
-    fn json(&self, _f: &mut File) -> serde_json::Value {
-        json!({
-            "name": self.name(),
-            "values": self.values().map(|v| v.json(_f)).collect::<Vec<_>>(),
-        })
-    }
-}
-
-impl JsonHelper for EnumValueWalker<'_> {
+impl JsonHelper for Walker<'_, &Enum> {
     fn json(&self, _f: &mut File) -> serde_json::Value {
         json!({
-            "name": self.name(),
-            "alias": self.maybe_alias(self.db),
+            "name": self.elem().name,
+            "values": self.elem().values.iter().map(|v| json!({
+                "name": v.elem.0,
+                "alias": v.attributes.get("alias").and_then(|a| a.as_string_value()),
+            })).collect::<Vec<_>>(),
         })
     }
 }
 
-impl WithWritePythonString for EnumWalker<'_> {
+impl WithWritePythonString for Walker<'_, &Enum> {
     fn file_name(&self) -> String {
-        format!("enm_{}", clean_file_name(self.name()))
+        format!("enm_{}", clean_file_name(&self.elem().name))
     }
 
     fn write_py_file<'a>(&'a self, fc: &'a mut FileCollector) {
@@ -41,7 +33,7 @@ impl WithWritePythonString for EnumWalker<'_> {
         fc.complete_file();
         fc.start_py_file("types", "__init__");
         fc.last_file()
-            .add_import(&format!(".enums.{}", self.file_name()), self.name());
+            .add_import(&format!(".enums.{}", self.file_name()), &self.elem().name);
         fc.complete_file();
 
         fc.start_py_file("types/enums", self.file_name());
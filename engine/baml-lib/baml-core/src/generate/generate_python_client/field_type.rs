@@ -0,0 +1,297 @@
+use std::collections::HashSet;
+
+use crate::generate::ir::{
+    Class, Enum, FieldType, IntermediateRepr, LiteralValue, TypeAlias, TypeValue, Walker,
+};
+
+use super::{
+    file::File,
+    traits::{WithPartial, WithToCode},
+};
+
+impl WithToCode for TypeValue {
+    fn to_py_string(&self, _f: &mut File) -> String {
+        match self {
+            TypeValue::Char | TypeValue::String => "str".to_string(),
+            TypeValue::Int => "int".to_string(),
+            TypeValue::Float => "float".to_string(),
+            TypeValue::Bool => "bool".to_string(),
+            TypeValue::Null => "None".to_string(),
+        }
+    }
+}
+
+impl WithToCode for LiteralValue {
+    fn to_py_string(&self, _f: &mut File) -> String {
+        match self {
+            LiteralValue::String(s) => format!("{:?}", s),
+            LiteralValue::Int(i) => i.to_string(),
+            LiteralValue::Bool(true) => "True".to_string(),
+            LiteralValue::Bool(false) => "False".to_string(),
+        }
+    }
+}
+
+fn join_py_strings(types: &[FieldType], f: &mut File) -> String {
+    types
+        .iter()
+        .map(|t| t.to_py_string(f))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+impl WithToCode for FieldType {
+    fn to_py_string(&self, f: &mut File) -> String {
+        match self {
+            FieldType::Primitive(t) => t.to_py_string(f),
+            FieldType::Literal(value) => {
+                f.add_import("typing", "Literal");
+                format!("Literal[{}]", value.to_py_string(f))
+            }
+            FieldType::Class(name) | FieldType::Enum(name) | FieldType::TypeAlias(name) => {
+                name.clone()
+            }
+            FieldType::List(inner) => {
+                f.add_import("typing", "List");
+                format!("List[{}]", inner.to_py_string(f))
+            }
+            FieldType::Map(key, value) => {
+                f.add_import("typing", "Dict");
+                format!("Dict[{}, {}]", key.to_py_string(f), value.to_py_string(f))
+            }
+            FieldType::Tuple(types) => {
+                f.add_import("typing", "Tuple");
+                format!("Tuple[{}]", join_py_strings(types, f))
+            }
+            // A union of literals is written as a single `Literal`.
+            FieldType::Union(types) if types.iter().all(|t| matches!(t, FieldType::Literal(_))) => {
+                f.add_import("typing", "Literal");
+                let values = types
+                    .iter()
+                    .filter_map(|t| match t {
+                        FieldType::Literal(value) => Some(value.to_py_string(f)),
+                        _ => None,
+                    })
+                    .collect::<Vec<_>>();
+                format!("Literal[{}]", values.join(", "))
+            }
+            FieldType::Union(types) => {
+                f.add_import("typing", "Union");
+                format!("Union[{}]", join_py_strings(types, f))
+            }
+            FieldType::Optional(inner) => {
+                f.add_import("typing", "Optional");
+                format!("Optional[{}]", inner.to_py_string(f))
+            }
+        }
+    }
+}
+
+impl WithPartial for FieldType {
+    fn to_partial_py_string(&self, ir: &IntermediateRepr, f: &mut File) -> String {
+        match self {
+            FieldType::Primitive(_)
+            | FieldType::Literal(_)
+            | FieldType::Class(_)
+            | FieldType::Enum(_)
+            | FieldType::TypeAlias(..) => {
+                // they are all optional
+                f.add_import("typing", "Optional");
+                format!("Optional[{}]", to_partial_arg_type(ir, self, f))
+            }
+            // Only identifiers and tuples can be optional, and their partials already are.
+            FieldType::Optional(inner) => inner.to_partial_py_string(ir, f),
+            // the items cant be optional.
+            FieldType::List(_) => to_partial_arg_type(ir, self, f),
+            FieldType::Map(key, value) => {
+                f.add_import("typing", "Dict");
+                format!(
+                    "Dict[{}, {}]",
+                    key.to_partial_py_string(ir, f),
+                    value.to_partial_py_string(ir, f)
+                )
+            }
+            FieldType::Tuple(types) => {
+                f.add_import("typing", "Tuple");
+                let repr = format!(
+                    "Tuple[{}]",
+                    types
+                        .iter()
+                        .map(|t| t.to_partial_py_string(ir, f))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                );
+                f.add_import("typing", "Optional");
+                format!("Optional[{}]", repr)
+            }
+            // Every member of a partial union would match the same partial value, so union
+            // members aren't made partial.
+            FieldType::Union(_) => self.to_py_string(f),
+        }
+    }
+}
+
+/// The streamed counterpart of a function argument or return type: every class it refers to,
+/// however deeply nested, is swapped for its partial class. Unlike class fields, the type itself
+/// isn't made optional.
+pub(super) fn to_partial_arg_type(
+    ir: &IntermediateRepr,
+    r#type: &FieldType,
+    f: &mut File,
+) -> String {
+    match r#type {
+        FieldType::Class(name) => format!("Partial{}", name),
+        FieldType::Enum(_)
+        | FieldType::Primitive(_)
+        | FieldType::Literal(_)
+        | FieldType::Union(_) => r#type.to_py_string(f),
+        FieldType::TypeAlias(name) => to_partial_arg_type(ir, ir.alias_target(name), f),
+        FieldType::List(inner) => {
+            f.add_import("typing", "List");
+            format!("List[{}]", to_partial_arg_type(ir, inner, f))
+        }
+        FieldType::Map(key, value) => {
+            f.add_import("typing", "Dict");
+            format!(
+                "Dict[{}, {}]",
+                key.to_py_string(f),
+                to_partial_arg_type(ir, value, f)
+            )
+        }
+        FieldType::Tuple(types) => {
+            f.add_import("typing", "Tuple");
+            let types = types
+                .iter()
+                .map(|t| to_partial_arg_type(ir, t, f))
+                .collect::<Vec<_>>()
+                .join(", ");
+            format!("Tuple[{}]", types)
+        }
+        FieldType::Optional(inner) => {
+            f.add_import("typing", "Optional");
+            format!("Optional[{}]", to_partial_arg_type(ir, inner, f))
+        }
+    }
+}
+
+/// Whether a value of this type may be `None`.
+pub(super) fn is_nullable(r#type: &FieldType) -> bool {
+    match r#type {
+        FieldType::Optional(_) => true,
+        FieldType::Primitive(TypeValue::Null) => true,
+        FieldType::Union(types) => types.iter().any(is_nullable),
+        _ => false,
+    }
+}
+
+/// Whether the field could theoretically be made optional.
+pub(super) fn can_be_null(r#type: &FieldType) -> bool {
+    match r#type {
+        FieldType::Optional(inner) => can_be_null(inner),
+        FieldType::Primitive(_)
+        | FieldType::Literal(_)
+        | FieldType::Class(_)
+        | FieldType::Enum(_)
+        | FieldType::TypeAlias(..)
+        | FieldType::Tuple(_) => true,
+        // There's a bug with unions where we cant parse optionals in unions right now
+        FieldType::Union(_) => false,
+        // Lists can't be nullable
+        FieldType::List(_) | FieldType::Map(..) => false,
+    }
+}
+
+/// The classes, enums and type aliases a set of types refers to, which the generated file has to
+/// import.
+pub(crate) struct RequiredTypes<'a> {
+    ir: &'a IntermediateRepr,
+    classes: HashSet<&'a str>,
+    enums: HashSet<&'a str>,
+    type_aliases: HashSet<&'a str>,
+}
+
+impl<'a> RequiredTypes<'a> {
+    /// Only the types named in `types` themselves.
+    pub fn direct(
+        ir: &'a IntermediateRepr,
+        types: impl IntoIterator<Item = &'a FieldType>,
+    ) -> Self {
+        let mut required = Self::new(ir);
+        types.into_iter().for_each(|t| required.visit(t, false));
+        required
+    }
+
+    /// The types named in `types`, along with everything the fields of those classes and the
+    /// targets of those aliases refer to.
+    pub fn transitive(
+        ir: &'a IntermediateRepr,
+        types: impl IntoIterator<Item = &'a FieldType>,
+    ) -> Self {
+        let mut required = Self::new(ir);
+        types.into_iter().for_each(|t| required.visit(t, true));
+        required
+    }
+
+    fn new(ir: &'a IntermediateRepr) -> Self {
+        Self {
+            ir,
+            classes: HashSet::new(),
+            enums: HashSet::new(),
+            type_aliases: HashSet::new(),
+        }
+    }
+
+    fn visit(&mut self, r#type: &'a FieldType, transitive: bool) {
+        match r#type {
+            FieldType::Primitive(_) | FieldType::Literal(_) => {}
+            FieldType::Enum(name) => {
+                self.enums.insert(name);
+            }
+            FieldType::Class(name) => {
+                if !self.classes.insert(name) || !transitive {
+                    return;
+                }
+                if let Some(cls) = self.ir.find_class(name) {
+                    let cls = cls.elem();
+                    cls.static_fields
+                        .iter()
+                        .chain(cls.dynamic_fields.iter())
+                        .for_each(|field| self.visit(&field.elem.r#type.elem, true));
+                }
+            }
+            FieldType::TypeAlias(name) => {
+                if self.type_aliases.insert(name) && transitive {
+                    self.visit(self.ir.alias_target(name), true);
+                }
+            }
+            FieldType::List(inner) | FieldType::Optional(inner) => self.visit(inner, transitive),
+            FieldType::Map(key, value) => {
+                self.visit(key, transitive);
+                self.visit(value, transitive);
+            }
+            FieldType::Union(types) | FieldType::Tuple(types) => {
+                types.iter().for_each(|t| self.visit(t, transitive))
+            }
+        }
+    }
+
+    pub fn has_class(&self, name: &str) -> bool {
+        self.classes.contains(name)
+    }
+
+    pub fn classes(&self) -> impl Iterator<Item = Walker<'a, &'a Class>> + '_ {
+        self.classes
+            .iter()
+            .filter_map(|name| self.ir.find_class(name))
+    }
+
+    pub fn enums(&self) -> impl Iterator<Item = Walker<'a, &'a Enum>> + '_ {
+        self.enums.iter().filter_map(|name| self.ir.find_enum(name))
+    }
+
+    pub fn type_aliases(&self) -> impl Iterator<Item = Walker<'a, &'a TypeAlias>> + '_ {
+        self.type_aliases
+            .iter()
+            .filter_map(|name| self.ir.find_type_alias(name))
+    }
+}
@@ -1,4 +1,3 @@
-use std::io::Write;
 use std::{
     collections::{HashMap, HashSet},
     path::PathBuf,
@@ -83,6 +82,23 @@ impl FileCollector {
             .unwrap()
     }
 
+    /// Every collected file, keyed by its path relative to the output directory.
+    pub fn rendered_files(&self) -> impl Iterator<Item = (PathBuf, String)> + '_ {
+        let comment_prefix = r#"# This file is generated by the BAML compiler.
+# Do not edit this file directly.
+# Instead, edit the BAML files and recompile.
+
+# ruff: noqa: E501,F401
+# flake8: noqa: E501,F401
+# pylint: disable=unused-import,line-too-long
+# fmt: off
+
+"#;
+        self.files
+            .values()
+            .map(move |file| (file.path(), format!("{}{}", comment_prefix, file.content())))
+    }
+
     pub fn write(
         &self,
         output: &PathBuf,
@@ -100,28 +116,14 @@ impl FileCollector {
             )
         })?;
 
-        let comment_prefix = r#"# This file is generated by the BAML compiler.
-# Do not edit this file directly.
-# Instead, edit the BAML files and recompile.
-
-# ruff: noqa: E501,F401
-# flake8: noqa: E501,F401
-# pylint: disable=unused-import,line-too-long
-# fmt: off
-
-"#;
         std::fs::write(
             output.join("baml.lock"),
             serde_json::to_string_pretty(lock).unwrap(),
         )?;
-        for file in self.files.values() {
-            let path = output.join(file.path());
+        for (path, content) in self.rendered_files() {
+            let path = output.join(path);
             std::fs::create_dir_all(path.parent().unwrap())?;
-            std::fs::write(&path, &comment_prefix)?;
-            std::fs::OpenOptions::new()
-                .append(true)
-                .open(&path)?
-                .write_all(file.content().as_bytes())?;
+            std::fs::write(&path, content)?;
         }
 
         Ok(())
@@ -246,7 +248,8 @@ impl File {
 
     pub(crate) fn content(&self) -> String {
         let mut modules = self.imports.keys().collect::<Vec<_>>();
-        modules.sort();
+        // Future imports must come first.
+        modules.sort_by_key(|module| (module.as_str() != "__future__", *module));
 
         let mut exports = self.explicit_exports.clone();
 
@@ -9,6 +9,7 @@ pub(super) enum HSTemplate {
     Enum,
     ClassPartial,
     Class,
+    TypeAlias,
     Client,
     BAMLClient,
     Variant,
@@ -23,6 +24,8 @@ fn init_hs() -> handlebars::Handlebars<'static> {
     let mut reg = handlebars::Handlebars::new();
     reg.register_helper("BLOCK_OPEN", Box::new(BLOCK_OPEN));
     reg.register_helper("BLOCK_CLOSE", Box::new(BLOCK_CLOSE));
+    // Everything is rendered into Python source, so nothing should be HTML-escaped.
+    reg.register_escape_fn(handlebars::no_escape);
     reg.set_strict_mode(true);
     reg
 }
@@ -102,6 +105,10 @@ fn use_partial(
             f.add_import("baml_lib._impl.deserializer", "register_deserializer");
             String::from("class_partial")
         }
+        HSTemplate::TypeAlias => {
+            register_partial_file!(reg, "types", "type_alias");
+            String::from("type_alias")
+        }
         HSTemplate::Enum => {
             register_partial!(reg, "enum_value", r#"{{name}} = "{{name}}""#);
             register_partial_file!(reg, "types", "enum");
@@ -0,0 +1,33 @@
+use std::path::Path;
+
+use crate::generate::golden::{assert_generated, load_ir};
+
+use super::collect_py_files;
+
+const TESTS_ROOT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/generate_python");
+
+/// Generates the Python client for `tests/generate_python/<case>/baml_src` and compares it,
+/// file by file, with `tests/generate_python/<case>/baml_client`.
+fn run_generate_test(case: &str) {
+    let case_dir = Path::new(TESTS_ROOT).join(case);
+    let ir = load_ir(&case_dir.join("baml_src"));
+    assert_generated(
+        &case_dir.join("baml_client"),
+        collect_py_files(&ir).rendered_files(),
+    );
+}
+
+#[test]
+fn kitchen_sink() {
+    run_generate_test("kitchen_sink");
+}
+
+#[test]
+fn recursive() {
+    run_generate_test("recursive");
+}
+
+#[test]
+fn literals() {
+    run_generate_test("literals");
+}
@@ -1,3 +1,5 @@
+use crate::generate::ir::IntermediateRepr;
+
 use super::file::{File, FileCollector};
 
 pub(super) trait WithWritePythonString {
@@ -13,7 +15,7 @@ pub(crate) trait WithToCode {
 // A trait that allows us to specify a "partial" type that is optional
 // used for reading streaming data, where not all data may be completed.
 pub(crate) trait WithPartial {
-    fn to_partial_py_string(&self, f: &mut File) -> String;
+    fn to_partial_py_string(&self, ir: &IntermediateRepr, f: &mut File) -> String;
 }
 
 pub(super) trait JsonHelper {
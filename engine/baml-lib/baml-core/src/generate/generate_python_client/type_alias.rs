@@ -0,0 +1,56 @@
+use serde_json::json;
+
+use crate::generate::{
+    generate_python_client::file::clean_file_name,
+    ir::{TypeAlias, Walker},
+};
+
+use super::{
+    field_type::RequiredTypes,
+    file::{File, FileCollector},
+    template::render_template,
+    traits::{JsonHelper, WithToCode, WithWritePythonString},
+};
+
+impl JsonHelper for Walker<'_, &TypeAlias> {
+    fn json(&self, f: &mut File) -> serde_json::Value {
+        json!({
+            "name": self.elem().name,
+            "type": self.elem().target.to_py_string(f),
+        })
+    }
+}
+
+impl WithWritePythonString for Walker<'_, &TypeAlias> {
+    fn file_name(&self) -> String {
+        format!("als_{}", clean_file_name(&self.elem().name))
+    }
+
+    fn write_py_file(&self, fc: &mut FileCollector) {
+        fc.start_py_file("types/aliases", "__init__");
+        fc.complete_file();
+        fc.start_py_file("types", "__init__");
+        fc.last_file()
+            .add_import(&format!(".aliases.{}", self.file_name()), &self.elem().name);
+        fc.complete_file();
+
+        fc.start_py_file("types/aliases", self.file_name());
+        // Only what the alias names itself; the aliased classes import their own dependencies.
+        let required = RequiredTypes::direct(self.db, [&self.elem().target]);
+        required.classes().for_each(|f| {
+            fc.last_file()
+                .add_import(&format!("..classes.{}", f.file_name()), &f.elem().name);
+        });
+        required.enums().for_each(|f| {
+            fc.last_file()
+                .add_import(&format!("..enums.{}", f.file_name()), &f.elem().name);
+        });
+        required.type_aliases().for_each(|f| {
+            fc.last_file()
+                .add_import(&format!(".{}", f.file_name()), &f.elem().name);
+        });
+        let json = self.json(fc.last_file());
+        render_template(super::template::HSTemplate::TypeAlias, fc.last_file(), json);
+        fc.complete_file();
+    }
+}
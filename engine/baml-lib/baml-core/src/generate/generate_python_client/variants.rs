@@ -1,47 +1,113 @@
-use either::Either;
-use internal_baml_parser_database::{walkers::VariantWalker, PromptRepr, WithStaticRenames};
-use internal_baml_schema_ast::ast::WithName;
-
 use serde_json::json;
 
-use crate::generate::generate_python_client::file::clean_file_name;
+use crate::generate::{
+    generate_python_client::file::clean_file_name,
+    ir::{Expression, Function, Impl, Prompt, Walker},
+};
 
 use super::{
+    client::quoted,
+    field_type::RequiredTypes,
     file::File,
     template::render_template,
     traits::{JsonHelper, WithToCode, WithWritePythonString},
     FileCollector,
 };
 
-impl<'db> JsonHelper for VariantWalker<'db> {
+impl<'a> Walker<'a, (&'a Function, &'a Impl)> {
+    /// The `name @alias(..)` overrides this impl declares, grouped by the class or enum they apply to.
+    fn overrides(&self) -> Vec<(&'a str, Vec<serde_json::Value>)> {
+        let (func, imp) = (&self.item.0.elem.name, &self.item.1.elem.name);
+        let alias = |alias: Option<&Expression>, value: &str| {
+            alias
+                .and_then(|a| a.as_string_value())
+                .map(|alias| json!({ "alias": alias, "value": value }))
+        };
+
+        let classes = self.db.walk_classes().map(|cls| {
+            (
+                cls.elem().name.as_str(),
+                cls.elem()
+                    .static_fields
+                    .iter()
+                    .filter_map(|f| {
+                        alias(f.attributes.get_override(func, imp, "alias"), &f.elem.name)
+                    })
+                    .collect::<Vec<_>>(),
+            )
+        });
+        let enums = self.db.walk_enums().map(|enm| {
+            (
+                enm.elem().name.as_str(),
+                enm.elem()
+                    .values
+                    .iter()
+                    .filter_map(|v| alias(v.attributes.get_override(func, imp, "alias"), &v.elem.0))
+                    .collect::<Vec<_>>(),
+            )
+        });
+
+        let mut overrides = classes
+            .chain(enums)
+            .filter(|(_, aliases)| !aliases.is_empty())
+            .collect::<Vec<_>>();
+        overrides.sort_by(|a, b| a.0.cmp(b.0));
+        overrides
+    }
+}
+
+impl JsonHelper for Walker<'_, (&Function, &Impl)> {
     fn json(&self, f: &mut File) -> serde_json::Value {
-        let func = self.walk_function().unwrap();
-        let client = self.client().unwrap();
+        let func = Walker {
+            db: self.db,
+            item: self.item.0,
+        };
+        let imp = self.elem();
         f.add_import(
             &format!("..functions.{}", func.file_name()),
-            &format!("BAML{}", func.name()),
+            &format!("BAML{}", func.elem().name),
         );
-        f.add_import(&format!("..clients.{}", client.file_name()), client.name());
-
-        let prompt = self.to_prompt();
-
-        let _ = self
-            .output_required_classes()
-            .map(|cls| f.add_import(&format!("..types.classes.{}", cls.file_name()), cls.name()))
-            .collect::<Vec<_>>();
-        let _ = self
-            .output_required_enums()
-            .map(|enm| f.add_import(&format!("..types.enums.{}", enm.file_name()), enm.name()))
-            .collect::<Vec<_>>();
+        if let Some(client) = self.db.find_client(&imp.client) {
+            f.add_import(
+                &format!("..clients.{}", client.file_name()),
+                &client.elem().name,
+            );
+        }
 
-        let inputs = match &prompt {
-            PromptRepr::Chat(_, used_inputs) => used_inputs,
-            PromptRepr::String(_, used_inputs) => used_inputs,
+        // With an adapter, the impl only ever sees the adapter's input type.
+        let required = match &imp.output_adapter {
+            Some(adapter) => RequiredTypes::direct(self.db, [&adapter.from]),
+            None => RequiredTypes::transitive(self.db, [&func.elem().output.elem]),
         };
+        // The input adapter returns what the prompt uses.
+        let adapted = imp
+            .input_adapter
+            .as_ref()
+            .map(|adapter| RequiredTypes::direct(self.db, [&adapter.to]));
+        for required in std::iter::once(&required).chain(&adapted) {
+            required.classes().for_each(|cls| {
+                f.add_import(
+                    &format!("..types.classes.{}", cls.file_name()),
+                    &cls.elem().name,
+                )
+            });
+            required.enums().for_each(|enm| {
+                f.add_import(
+                    &format!("..types.enums.{}", enm.file_name()),
+                    &enm.elem().name,
+                )
+            });
+            required.type_aliases().for_each(|als| {
+                f.add_import(
+                    &format!("..types.aliases.{}", als.file_name()),
+                    &als.elem().name,
+                )
+            });
+        }
 
-        let is_chat = match &prompt {
-            PromptRepr::Chat(..) => true,
-            _ => false,
+        let (is_chat, inputs) = match &imp.rendered_prompt {
+            Prompt::Chat(_, used_inputs) => (true, used_inputs),
+            Prompt::String(_, used_inputs) => (false, used_inputs),
         };
 
         if is_chat {
@@ -53,90 +119,60 @@ impl<'db> JsonHelper for VariantWalker<'db> {
         }
 
         json!({
-            "name": self.name(),
+            "name": imp.name,
             "function": func.json(f),
             "is_chat": is_chat,
-            "prompt": match &prompt {
-                PromptRepr::Chat(parts, _) => {
-                        json!(parts.iter().map(|(ctx, text)| {
-                            json!({
-                                "role": ctx.map(|c| c.role.0.as_str()).unwrap_or("system"),
-                                "content": text,
-                            })
-                        }).collect::<Vec<_>>())
+            "prompt": match &imp.rendered_prompt {
+                Prompt::Chat(parts, _) => {
+                    json!(parts.iter().map(|part| {
+                        json!({
+                            "role": part.role.as_deref().unwrap_or("system"),
+                            "content": part.content,
+                        })
+                    }).collect::<Vec<_>>())
                 },
-                PromptRepr::String(content, _) => {
+                Prompt::String(content, _) => {
                     json!(content)
                 },
             },
-            "client": client.name(),
-            "inputs": inputs,
-            "output_adapter": self.properties().output_adapter.as_ref().map(|(idx, _)| {
-                let adapter = &self.ast_variant()[*idx];
-
+            "client": imp.client,
+            "inputs": inputs.iter().map(|input| quoted(&format!("{{{}}}", input))).collect::<Vec<_>>(),
+            "input_adapter": imp.input_adapter.as_ref().map(|adapter| {
+                json!({
+                    "type": adapter.to.to_py_string(f),
+                    "code": adapter
+                        .code
+                        .get("python")
+                        .map(|code| code.as_str())
+                        .unwrap_or("raise NotImplementedError()"),
+                })
+            }),
+            "output_adapter": imp.output_adapter.as_ref().map(|adapter| {
                 json!({
                     "type": adapter.from.to_py_string(f),
-                    "code": self.properties().output_adapter_for_language("python").unwrap_or("raise NotImplementedError()")
+                    "code": adapter
+                        .code
+                        .get("python")
+                        .map(|code| code.as_str())
+                        .unwrap_or("raise NotImplementedError()"),
                 })
             }),
-            "overrides": self.ast_variant().iter_serializers().filter_map(|(_k, v)| {
-                let matches = match self.db.find_type_by_str(v.name()) {
-                    Some(Either::Left(cls)) => {
-                        cls.static_fields().filter_map(|f| {
-                            let (overrides, _) = f.get_attributes(self);
-                            match overrides.and_then(|o| Some(o.alias())) {
-                                Some(Some(id)) => {
-                                    Some(json!({
-                                        "alias": self.db[*id].to_string(),
-                                        "value": f.name(),
-                                    }))
-                                },
-                                _ => {
-                                    None
-                                }
-                            }
-                        }).collect::<Vec<_>>()
-                    },
-                    Some(Either::Right(enm)) => {
-                        enm.values().filter_map(|f| {
-                            let (overrides, _) = f.get_attributes(self);
-                            match overrides.and_then(|o| Some(o.alias())) {
-                                Some(Some(id)) => {
-                                    Some(json!({
-                                        "alias": self.db[*id].to_string(),
-                                        "value": f.name(),
-                                    }))
-                                },
-                                _ => {
-                                    None
-                                }
-                            }
-                        }).collect::<Vec<_>>()
-                    },
-                    None => {
-                        Vec::new()
-                    }
-                };
-
-                if matches.is_empty() {
-                    None
-                } else {
-                    Some(json!({
-                        "name": v.name(),
-                        "aliases": matches,
-                    }))
-                }
+            "overrides": self.overrides().into_iter().map(|(name, aliases)| {
+                json!({
+                    "name": name,
+                    "aliases": aliases,
+                })
             }).collect::<Vec<_>>(),
         })
     }
 }
 
-impl WithWritePythonString for VariantWalker<'_> {
+impl WithWritePythonString for Walker<'_, (&Function, &Impl)> {
     fn file_name(&self) -> String {
         format!(
             "fx_{}_impl_{}",
-            clean_file_name(self.function_identifier().name()),
-            clean_file_name(self.name())
+            clean_file_name(&self.item.0.elem.name),
+            clean_file_name(&self.elem().name)
         )
     }
 
@@ -145,7 +181,7 @@ impl WithWritePythonString for VariantWalker<'_> {
         fc.last_file().add_line(format!(
             "from .{0} import {1} as unused_{0}",
             self.file_name(),
-            self.name(),
+            self.elem().name,
         ));
         fc.complete_file();
 
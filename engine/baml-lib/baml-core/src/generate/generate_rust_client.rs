@@ -0,0 +1,39 @@
+mod class;
+mod r#enum;
+mod field_type;
+mod function;
+mod intermediate_repr;
+mod rust_language_features;
+mod template;
+mod type_alias;
+
+#[cfg(test)]
+mod tests;
+
+use crate::configuration::Generator;
+
+use super::{dir_writer::WithFileContent, ir::IntermediateRepr};
+use rust_language_features::{get_file_collector, RustFileCollector};
+
+fn collect_rust_files(ir: &IntermediateRepr) -> RustFileCollector {
+    let mut collector = get_file_collector();
+
+    ir.walk_enums().for_each(|e| e.write(&mut collector));
+    ir.walk_classes().for_each(|c| c.write(&mut collector));
+    ir.walk_type_aliases().for_each(|a| a.write(&mut collector));
+
+    let file = collector.start_file(".", "functions", false);
+    file.append(template::render_with_hbs(
+        template::Template::PromptValue,
+        &serde_json::json!({}),
+    ));
+    collector.finish_file();
+    ir.walk_functions().for_each(|f| f.write(&mut collector));
+
+    ir.write(&mut collector);
+    collector
+}
+
+pub(crate) fn generate_rust(ir: &IntermediateRepr, gen: &Generator) -> std::io::Result<()> {
+    collect_rust_files(ir).commit(&gen.output_path)
+}
@@ -0,0 +1,102 @@
+use std::collections::HashSet;
+
+use serde_json::json;
+
+use crate::generate::{
+    dir_writer::WithFileContent,
+    ir::{Class, FieldType, IntermediateRepr, Walker},
+};
+
+use super::{
+    field_type::to_rust,
+    rust_language_features::{
+        serde_rename, to_identifier, to_pascal_case, RustFileCollector, RustLanguageFeatures,
+    },
+    template::{render_with_hbs, Template},
+};
+
+impl WithFileContent<RustLanguageFeatures> for Walker<'_, &Class> {
+    fn file_dir(&self) -> &'static str {
+        "."
+    }
+
+    fn file_name(&self) -> String {
+        "types".into()
+    }
+
+    fn write(&self, collector: &mut RustFileCollector) {
+        let cls = self.elem();
+        let mut unions = vec![];
+        // Dynamic fields are computed by `@get` code, which has no Rust implementation.
+        let fields = cls
+            .static_fields
+            .iter()
+            .map(|f| {
+                let union_name = format!("{}{}", cls.name, to_pascal_case(&f.elem.name));
+                let mut r#type = to_rust(&f.elem.r#type.elem, &union_name, &mut unions);
+                // A struct can only contain itself behind a pointer.
+                if contains_class(self.db, &f.elem.r#type.elem, &cls.name, &mut HashSet::new()) {
+                    r#type = format!("Box<{}>", r#type);
+                }
+                json!({
+                    "name": to_identifier(&f.elem.name),
+                    "type": r#type,
+                    "rename": serde_rename(
+                        &f.elem.name,
+                        f.attributes.get("alias").and_then(|a| a.as_string_value()),
+                    ),
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let file = collector.start_file(
+            WithFileContent::<RustLanguageFeatures>::file_dir(self),
+            WithFileContent::<RustLanguageFeatures>::file_name(self),
+            false,
+        );
+        file.add_import("serde", "Deserialize", None, false);
+        file.add_import("serde", "Serialize", None, false);
+        file.append(render_with_hbs(
+            Template::Class,
+            &json!({
+                "name": cls.name,
+                "fields": fields,
+            }),
+        ));
+        unions
+            .iter()
+            .for_each(|u| file.append(render_with_hbs(Template::Union, u)));
+        collector.finish_file();
+    }
+}
+
+/// Whether a value of `r#type` holds a `class` inline, i.e. not in a list or map.
+fn contains_class<'a>(
+    ir: &'a IntermediateRepr,
+    r#type: &'a FieldType,
+    class: &str,
+    seen: &mut HashSet<&'a str>,
+) -> bool {
+    match r#type {
+        FieldType::Class(name) if name == class => true,
+        FieldType::Class(name) => {
+            seen.insert(name)
+                && ir.find_class(name).is_some_and(|cls| {
+                    cls.elem()
+                        .static_fields
+                        .iter()
+                        .any(|f| contains_class(ir, &f.elem.r#type.elem, class, seen))
+                })
+        }
+        FieldType::TypeAlias(name) => contains_class(ir, ir.alias_target(name), class, seen),
+        FieldType::Optional(inner) => contains_class(ir, inner, class, seen),
+        FieldType::Union(members) | FieldType::Tuple(members) => members
+            .iter()
+            .any(|member| contains_class(ir, member, class, seen)),
+        FieldType::Primitive(_)
+        | FieldType::Literal(_)
+        | FieldType::Enum(_)
+        | FieldType::List(_)
+        | FieldType::Map(..) => false,
+    }
+}
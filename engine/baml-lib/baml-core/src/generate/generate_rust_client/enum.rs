@@ -0,0 +1,47 @@
+use serde_json::json;
+
+use crate::generate::{
+    dir_writer::WithFileContent,
+    ir::{Enum, Walker},
+};
+
+use super::{
+    rust_language_features::{
+        serde_rename, to_identifier, RustFileCollector, RustLanguageFeatures,
+    },
+    template::{render_with_hbs, Template},
+};
+
+impl WithFileContent<RustLanguageFeatures> for Walker<'_, &Enum> {
+    fn file_dir(&self) -> &'static str {
+        "."
+    }
+
+    fn file_name(&self) -> String {
+        "types".into()
+    }
+
+    fn write(&self, collector: &mut RustFileCollector) {
+        let file = collector.start_file(
+            WithFileContent::<RustLanguageFeatures>::file_dir(self),
+            WithFileContent::<RustLanguageFeatures>::file_name(self),
+            false,
+        );
+        file.add_import("serde", "Deserialize", None, false);
+        file.add_import("serde", "Serialize", None, false);
+        file.append(render_with_hbs(
+            Template::Enum,
+            &json!({
+                "name": self.elem().name,
+                "values": self.elem().values.iter().map(|v| json!({
+                    "name": to_identifier(&v.elem.0),
+                    "rename": serde_rename(
+                        &v.elem.0,
+                        v.attributes.get("alias").and_then(|a| a.as_string_value()),
+                    ),
+                })).collect::<Vec<_>>(),
+            }),
+        ));
+        collector.finish_file();
+    }
+}
@@ -0,0 +1,139 @@
+use internal_baml_schema_ast::ast::TypeValue;
+
+use crate::generate::ir::FieldType;
+
+/// A BAML union, which becomes an untagged Rust enum with one variant per member.
+#[derive(serde::Serialize)]
+pub(super) struct UnionDef {
+    pub name: String,
+    pub variants: Vec<UnionVariant>,
+}
+
+#[derive(serde::Serialize)]
+pub(super) struct UnionVariant {
+    pub name: String,
+    pub r#type: String,
+}
+
+fn is_null(r#type: &FieldType) -> bool {
+    matches!(r#type, FieldType::Primitive(TypeValue::Null))
+}
+
+/// The primitive type every member of a union is a literal of, if there is one. Rust has no
+/// literal types, so such a union becomes that primitive.
+fn literal_union_type(members: &[&FieldType]) -> Option<TypeValue> {
+    let mut types = members.iter().map(|t| match t {
+        FieldType::Literal(value) => Some(value.type_value()),
+        _ => None,
+    });
+    let first = types.next()??;
+    types.all(|t| t == Some(first)).then_some(first)
+}
+
+/// Whether `r#type` becomes exactly one Rust enum, rather than e.g. an `Option` of one.
+pub(super) fn is_plain_union(r#type: &FieldType) -> bool {
+    match r#type {
+        FieldType::Union(members) => {
+            !members.iter().any(is_null)
+                && members.len() > 1
+                && literal_union_type(&members.iter().collect::<Vec<_>>()).is_none()
+        }
+        _ => false,
+    }
+}
+
+fn primitive_to_rust(r#type: &TypeValue) -> &'static str {
+    match r#type {
+        TypeValue::String => "String",
+        TypeValue::Int => "i64",
+        TypeValue::Float => "f64",
+        TypeValue::Bool => "bool",
+        TypeValue::Char => "String",
+        TypeValue::Null => "()",
+    }
+}
+
+fn variant_name(r#type: &FieldType) -> String {
+    match r#type {
+        FieldType::Class(name) | FieldType::Enum(name) | FieldType::TypeAlias(name) => name.clone(),
+        FieldType::Primitive(p) => match p {
+            TypeValue::String => "String",
+            TypeValue::Int => "Int",
+            TypeValue::Float => "Float",
+            TypeValue::Bool => "Bool",
+            TypeValue::Char => "Char",
+            TypeValue::Null => "Null",
+        }
+        .to_string(),
+        FieldType::Literal(value) => variant_name(&FieldType::Primitive(value.type_value())),
+        FieldType::List(inner) => format!("{}List", variant_name(inner)),
+        FieldType::Map(..) => "Map".to_string(),
+        FieldType::Tuple(..) => "Tuple".to_string(),
+        FieldType::Union(..) => "Union".to_string(),
+        FieldType::Optional(inner) => variant_name(inner),
+    }
+}
+
+/// The Rust type for `r#type`.
+///
+/// Rust has no anonymous unions, so each union is declared as an enum in `unions`. `name` is
+/// what that enum is called; nested unions get names derived from it.
+pub(super) fn to_rust(r#type: &FieldType, name: &str, unions: &mut Vec<UnionDef>) -> String {
+    match r#type {
+        FieldType::Class(name) | FieldType::Enum(name) | FieldType::TypeAlias(name) => name.clone(),
+        FieldType::Primitive(p) => primitive_to_rust(p).to_string(),
+        FieldType::Literal(value) => primitive_to_rust(&value.type_value()).to_string(),
+        FieldType::List(inner) => {
+            format!("Vec<{}>", to_rust(inner, &format!("{}Item", name), unions))
+        }
+        FieldType::Map(key, value) => format!(
+            "std::collections::HashMap<{}, {}>",
+            to_rust(key, &format!("{}Key", name), unions),
+            to_rust(value, &format!("{}Value", name), unions)
+        ),
+        FieldType::Tuple(items) => {
+            let items = items
+                .iter()
+                .enumerate()
+                .map(|(i, t)| to_rust(t, &format!("{}{}", name, i), unions))
+                .collect::<Vec<_>>();
+            match items.as_slice() {
+                [item] => format!("({},)", item),
+                _ => format!("({})", items.join(", ")),
+            }
+        }
+        FieldType::Optional(inner) => format!("Option<{}>", to_rust(inner, name, unions)),
+        FieldType::Union(members) => {
+            let nullable = members.iter().any(is_null);
+            let members = members.iter().filter(|t| !is_null(t)).collect::<Vec<_>>();
+            let inner = match members.as_slice() {
+                [] => primitive_to_rust(&TypeValue::Null).to_string(),
+                [member] => to_rust(member, name, unions),
+                _ if literal_union_type(&members).is_some() => to_rust(members[0], name, unions),
+                _ => {
+                    let mut variants: Vec<UnionVariant> = vec![];
+                    for member in members {
+                        let mut variant = variant_name(member);
+                        if variants.iter().any(|v| v.name == variant) {
+                            variant = format!("{}{}", variant, variants.len());
+                        }
+                        let r#type = to_rust(member, &format!("{}{}", name, variant), unions);
+                        variants.push(UnionVariant {
+                            name: variant,
+                            r#type,
+                        });
+                    }
+                    unions.push(UnionDef {
+                        name: name.to_string(),
+                        variants,
+                    });
+                    name.to_string()
+                }
+            };
+            match nullable {
+                true => format!("Option<{}>", inner),
+                false => inner,
+            }
+        }
+    }
+}
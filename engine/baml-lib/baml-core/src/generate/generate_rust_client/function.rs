@@ -0,0 +1,341 @@
+use indexmap::IndexMap;
+use internal_baml_parser_database::{split_prompt_blocks, PromptPart};
+use internal_baml_prompt_parser::ast::VariableSegment;
+use serde_json::json;
+
+use crate::generate::{
+    dir_writer::WithFileContent,
+    ir::{FieldType, Function, FunctionArgs, IntermediateRepr, Walker},
+};
+
+use super::{
+    field_type::to_rust,
+    rust_language_features::{
+        to_identifier, to_pascal_case, to_raw_string_literal, to_snake_case, RustFileCollector,
+        RustLanguageFeatures,
+    },
+    template::{render_with_hbs, Template},
+};
+
+/// Finds the field `name` of `r#type`, returning the key it serializes to and its type.
+fn find_field<'a>(
+    ir: &'a IntermediateRepr,
+    r#type: &'a FieldType,
+    name: &str,
+) -> Option<(&'a str, &'a FieldType)> {
+    match r#type {
+        FieldType::Optional(inner) => find_field(ir, inner, name),
+        FieldType::TypeAlias(alias) => find_field(ir, ir.alias_target(alias), name),
+        // Only some members may have the field; use the first that does.
+        FieldType::Union(members) => members.iter().find_map(|m| find_field(ir, m, name)),
+        FieldType::Class(cls) => ir
+            .find_class(cls)?
+            .elem()
+            .static_fields
+            .iter()
+            .find(|f| f.elem.name == name)
+            .map(|f| {
+                let key = f
+                    .attributes
+                    .get("alias")
+                    .and_then(|a| a.as_string_value())
+                    .unwrap_or(&f.elem.name);
+                (key, &f.elem.r#type.elem)
+            }),
+        _ => None,
+    }
+}
+
+/// The type `segment` (`[0]`, `["key"]` or `[*]`) selects from a value of `r#type`.
+fn item_type<'a>(
+    ir: &'a IntermediateRepr,
+    r#type: &'a FieldType,
+    segment: &VariableSegment,
+) -> Option<&'a FieldType> {
+    match (r#type, segment) {
+        (FieldType::Optional(inner), _) => item_type(ir, inner, segment),
+        (FieldType::TypeAlias(name), _) => item_type(ir, ir.alias_target(name), segment),
+        (FieldType::Union(members), _) => members.iter().find_map(|m| item_type(ir, m, segment)),
+        (FieldType::List(item), VariableSegment::Index(_) | VariableSegment::Each) => Some(item),
+        (FieldType::Tuple(items), VariableSegment::Index(idx)) => items.get(*idx),
+        (FieldType::Map(_, value), VariableSegment::Key(_) | VariableSegment::Each) => Some(value),
+        _ => None,
+    }
+}
+
+/// Maps a prompt variable's path onto the steps `prompt_value` takes through the serialized
+/// value, e.g. a field onto the key it serializes to. Also returns the type the path leads to, if
+/// known.
+fn serialized_path<'a>(
+    ir: &'a IntermediateRepr,
+    r#type: Option<&'a FieldType>,
+    path: &[VariableSegment],
+) -> (Vec<String>, Option<&'a FieldType>) {
+    let mut current = r#type;
+    let steps = path
+        .iter()
+        .map(|segment| match segment {
+            VariableSegment::Field(name) => match current.and_then(|t| find_field(ir, t, name)) {
+                Some((key, r#type)) => {
+                    current = Some(r#type);
+                    format!("PromptStep::Key({:?})", key)
+                }
+                None => {
+                    current = None;
+                    format!("PromptStep::Key({:?})", name)
+                }
+            },
+            _ => {
+                current = current.and_then(|t| item_type(ir, t, segment));
+                match segment {
+                    VariableSegment::Key(key) => format!("PromptStep::Key({:?})", key),
+                    VariableSegment::Index(idx) => format!("PromptStep::Index({})", idx),
+                    _ => "PromptStep::Each".to_string(),
+                }
+            }
+        })
+        .collect();
+    (steps, current)
+}
+
+/// What the prompt of an impl can refer to: the function's args, and the items of the `{#for}`
+/// blocks it's in.
+struct PromptScope<'a> {
+    ir: &'a IntermediateRepr,
+    input_replacers: &'a IndexMap<String, String>,
+    /// Each arg, the variable holding its serialized value, and its type.
+    args: Vec<(&'a str, String, &'a FieldType)>,
+    /// Each open `{#for}` block's item, and the type of its items if known.
+    items: Vec<(&'a str, Option<&'a FieldType>)>,
+}
+
+impl<'a> PromptScope<'a> {
+    /// The value the replacer `key` starts from, the steps to take from it, and the type they lead
+    /// to.
+    fn source(&self, key: &str) -> Option<(String, String, Option<&'a FieldType>)> {
+        let path = VariableSegment::parse_path(self.input_replacers.get(key)?)?;
+        let root = path[0].as_field()?;
+        let (value, r#type) = match self.items.iter().rev().find(|(item, _)| *item == root) {
+            Some((item, r#type)) => (to_identifier(item), *r#type),
+            None => {
+                let (_, value, r#type) = self.args.iter().find(|(name, ..)| *name == root)?;
+                (format!("&{}", value), Some(*r#type))
+            }
+        };
+        let (steps, r#type) = serialized_path(self.ir, r#type, &path[1..]);
+        Some((value, steps.join(", "), r#type))
+    }
+
+    /// Renders the statements that append `parts` to `__prompt`.
+    fn statements(&mut self, parts: &[PromptPart<'a>], depth: usize, out: &mut Vec<String>) {
+        let indent = "    ".repeat(depth);
+        for part in parts {
+            match part {
+                PromptPart::Text(text) => {
+                    let mut keys = self
+                        .input_replacers
+                        .keys()
+                        .filter(|k| text.contains(k.as_str()))
+                        .filter_map(|k| Some((k, self.source(k)?)))
+                        .collect::<Vec<_>>();
+                    // The replacers come from a hash map; sort them so the output is stable.
+                    keys.sort_by(|a, b| a.0.cmp(b.0));
+                    if keys.is_empty() {
+                        out.push(format!(
+                            "{}__prompt += {};",
+                            indent,
+                            to_raw_string_literal(text)
+                        ));
+                        continue;
+                    }
+                    out.push(format!(
+                        "{}__prompt += &{}",
+                        indent,
+                        to_raw_string_literal(text)
+                    ));
+                    for (key, (value, steps, _)) in keys {
+                        out.push(format!(
+                            "{}    .replace({:?}, &prompt_value({}, &[{}]))",
+                            indent, key, value, steps
+                        ));
+                    }
+                    out.last_mut().unwrap().push(';');
+                }
+                PromptPart::If {
+                    condition,
+                    then,
+                    otherwise,
+                } => {
+                    let Some((value, steps, _)) = self.source(condition) else {
+                        continue;
+                    };
+                    out.push(format!(
+                        "{}if prompt_is_set(prompt_lookup({}, &[{}])) {{",
+                        indent, value, steps
+                    ));
+                    self.statements(then, depth + 1, out);
+                    if !otherwise.is_empty() {
+                        out.push(format!("{}}} else {{", indent));
+                        self.statements(otherwise, depth + 1, out);
+                    }
+                    out.push(format!("{}}}", indent));
+                }
+                PromptPart::For {
+                    item,
+                    collection,
+                    body,
+                } => {
+                    let Some((value, steps, r#type)) = self.source(collection) else {
+                        continue;
+                    };
+                    out.push(format!(
+                        "{}for {} in prompt_items(prompt_lookup({}, &[{}])) {{",
+                        indent,
+                        to_identifier(item),
+                        value,
+                        steps
+                    ));
+                    let item_type =
+                        r#type.and_then(|t| item_type(self.ir, t, &VariableSegment::Each));
+                    self.items.push((item, item_type));
+                    self.statements(body, depth + 1, out);
+                    self.items.pop();
+                    out.push(format!("{}}}", indent));
+                }
+            }
+        }
+    }
+}
+
+fn indent(code: &str) -> String {
+    code.lines()
+        .map(|line| match line {
+            "" => String::new(),
+            _ => format!("    {}", line),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+impl WithFileContent<RustLanguageFeatures> for Walker<'_, &Function> {
+    fn file_dir(&self) -> &'static str {
+        "."
+    }
+
+    fn file_name(&self) -> String {
+        "functions".into()
+    }
+
+    fn write(&self, collector: &mut RustFileCollector) {
+        let func = self.elem();
+        let mut unions = vec![];
+
+        let args = match &func.inputs {
+            FunctionArgs::UnnamedArg(arg) => vec![("arg", arg, format!("{}Input", func.name))],
+            FunctionArgs::NamedArgList(args) => args
+                .iter()
+                .map(|(name, arg)| {
+                    let union_name = format!("{}{}", func.name, to_pascal_case(name));
+                    (name.as_str(), arg, union_name)
+                })
+                .collect(),
+        };
+        let params = args
+            .iter()
+            .map(|(name, arg, union_name)| {
+                format!(
+                    "{}: &{}",
+                    to_identifier(name),
+                    to_rust(arg, union_name, &mut unions)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        let return_type = to_rust(
+            &func.output.elem,
+            &format!("{}Output", func.name),
+            &mut unions,
+        );
+
+        let impls = func
+            .impls
+            .iter()
+            .map(|i| {
+                let mut prompt = i.elem.prompt.clone();
+                i.elem.output_replacers.iter().for_each(|(k, val)| {
+                    prompt = prompt.replace(k, val);
+                });
+
+                // Replacers are `arg.path[0].to.field` for unnamed args, and
+                // `name.path[0].to.field` for named ones.
+                let mut inputs = i
+                    .elem
+                    .input_replacers
+                    .iter()
+                    .filter(|(k, _)| prompt.contains(k.as_str()))
+                    .filter_map(|(k, val)| {
+                        let path = VariableSegment::parse_path(val)?;
+                        let (arg, r#type, _) = args
+                            .iter()
+                            .find(|(name, ..)| path[0].as_field() == Some(*name))?;
+                        Some(json!({
+                            "key": format!("{:?}", k),
+                            "arg": to_identifier(arg),
+                            "path": serialized_path(self.db, Some(r#type), &path[1..]).0.join(", "),
+                        }))
+                    })
+                    .collect::<Vec<_>>();
+                // The replacers come from a hash map; sort them so the output is stable.
+                inputs.sort_by(|a, b| a["key"].as_str().cmp(&b["key"].as_str()));
+
+                // Prompts with `{#if}` or `{#for}` blocks are built up statement by statement.
+                let body = match split_prompt_blocks(&prompt).as_slice() {
+                    [] | [PromptPart::Text(_)] => None,
+                    parts => {
+                        let mut scope = PromptScope {
+                            ir: self.db,
+                            input_replacers: &i.elem.input_replacers,
+                            args: args
+                                .iter()
+                                .map(|(name, r#type, _)| (*name, to_identifier(name), *r#type))
+                                .collect(),
+                            items: vec![],
+                        };
+                        let mut statements = vec![];
+                        scope.statements(parts, 2, &mut statements);
+                        Some(statements.join("\n"))
+                    }
+                };
+
+                json!({
+                    "name": i.elem.name,
+                    "body": body,
+                    "fn_name": format!("prompt_{}", to_snake_case(&i.elem.name)),
+                    "const_name": format!("PROMPT_{}", to_snake_case(&i.elem.name).to_uppercase()),
+                    "prompt": to_raw_string_literal(&prompt),
+                    "inputs": inputs,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let file = collector.start_file(
+            WithFileContent::<RustLanguageFeatures>::file_dir(self),
+            WithFileContent::<RustLanguageFeatures>::file_name(self),
+            false,
+        );
+        file.append(render_with_hbs(
+            Template::Function,
+            &json!({
+                "name": func.name,
+                "module": to_identifier(&to_snake_case(&func.name)),
+                "params": params,
+                "args": args.iter().map(|(name, ..)| to_identifier(name)).collect::<Vec<_>>(),
+                "return_type": return_type,
+                "unions": unions.iter().map(|u| indent(&render_with_hbs(Template::Union, u))).collect::<Vec<_>>(),
+                "impls": impls,
+                "default_impl": func.default_impl.as_ref().map(|name| format!("prompt_{}", to_snake_case(name))),
+            }),
+        ));
+        collector.finish_file();
+    }
+}
@@ -0,0 +1,28 @@
+use serde_json::json;
+
+use crate::generate::{dir_writer::WithFileContent, ir::IntermediateRepr};
+
+use super::{
+    rust_language_features::{RustFileCollector, RustLanguageFeatures},
+    template::{render_with_hbs, Template},
+};
+
+impl WithFileContent<RustLanguageFeatures> for IntermediateRepr {
+    fn file_dir(&self) -> &'static str {
+        "."
+    }
+
+    fn file_name(&self) -> String {
+        "mod".into()
+    }
+
+    fn write(&self, fc: &mut RustFileCollector) {
+        let file = fc.start_file(
+            WithFileContent::<RustLanguageFeatures>::file_dir(self),
+            WithFileContent::<RustLanguageFeatures>::file_name(self),
+            false,
+        );
+        file.append(render_with_hbs(Template::ExportFile, &json!({})));
+        fc.finish_file();
+    }
+}
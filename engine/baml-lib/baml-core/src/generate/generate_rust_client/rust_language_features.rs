@@ -0,0 +1,136 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::generate::dir_writer::{FileCollector, Import, LanguageFeatures};
+
+pub(super) struct RustLanguageFeatures {}
+
+impl LanguageFeatures for RustLanguageFeatures {
+    fn content_prefix(&self) -> &'static str {
+        r#"
+// This file is auto-generated. Do not edit this file manually.
+//
+// Disable lints for this file, as it is not meant to be read.
+#![allow(
+    clippy::all,
+    dead_code,
+    non_camel_case_types,
+    non_snake_case,
+    unused_imports,
+    unused_variables
+)]
+        "#
+        .trim()
+    }
+
+    fn format_exports(&self, exports: &Vec<String>) -> String {
+        format!("pub use self::{{{}}};", exports.join(", "))
+    }
+
+    fn format_imports(&self, imports: &Vec<Import>) -> String {
+        // group imports by lib
+        let imports_by_lib = imports.iter().fold(BTreeMap::new(), |mut map, import| {
+            let name = match import.as_name {
+                Some(ref as_name) => format!("{} as {}", import.name, as_name),
+                None => import.name.clone(),
+            };
+            map.entry(&import.lib)
+                .or_insert_with(BTreeSet::new)
+                .insert(name);
+            map
+        });
+
+        imports_by_lib
+            .iter()
+            .map(|(lib, names)| match names.len() {
+                1 => format!("use {}::{};", lib, names.iter().next().unwrap()),
+                _ => format!(
+                    "use {}::{{{}}};",
+                    lib,
+                    names.iter().cloned().collect::<Vec<_>>().join(", ")
+                ),
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn to_file_path(&self, path: &str, name: &str) -> std::path::PathBuf {
+        std::path::PathBuf::from(format!("{}/{}.rs", path, name).to_lowercase())
+    }
+}
+
+pub(super) type RustFileCollector = FileCollector<RustLanguageFeatures>;
+
+pub(super) fn get_file_collector() -> RustFileCollector {
+    RustFileCollector::new(RustLanguageFeatures {})
+}
+
+const KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do", "dyn",
+    "else", "enum", "extern", "false", "final", "fn", "for", "if", "impl", "in", "let", "loop",
+    "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return", "static",
+    "struct", "trait", "true", "try", "type", "typeof", "unsafe", "unsized", "use", "virtual",
+    "where", "while", "yield",
+];
+
+/// Turns a BAML name into a Rust identifier, escaping keywords.
+pub(super) fn to_identifier(name: &str) -> String {
+    match name {
+        // These can't be raw identifiers.
+        "self" | "Self" | "super" | "crate" => format!("{}_", name),
+        _ if KEYWORDS.contains(&name) => format!("r#{}", name),
+        _ => name.to_string(),
+    }
+}
+
+pub(super) fn to_snake_case(name: &str) -> String {
+    let mut result = String::new();
+    let chars = name.chars().collect::<Vec<_>>();
+    for (i, c) in chars.iter().enumerate() {
+        if c.is_uppercase() {
+            let after_lower = i > 0 && (chars[i - 1].is_lowercase() || chars[i - 1].is_numeric());
+            let before_lower = i > 0
+                && chars[i - 1].is_uppercase()
+                && chars.get(i + 1).is_some_and(|n| n.is_lowercase());
+            if after_lower || before_lower {
+                result.push('_');
+            }
+            result.extend(c.to_lowercase());
+        } else {
+            result.push(*c);
+        }
+    }
+    result
+}
+
+pub(super) fn to_pascal_case(name: &str) -> String {
+    name.split('_')
+        .map(|part| {
+            let mut chars = part.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect::<String>(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+/// A string literal for `value`, using a raw string so that prompts stay readable.
+pub(super) fn to_raw_string_literal(value: &str) -> String {
+    let mut hashes = 1;
+    while value.contains(&format!("\"{}", "#".repeat(hashes))) {
+        hashes += 1;
+    }
+    let hashes = "#".repeat(hashes);
+    format!("r{hashes}\"{value}\"{hashes}")
+}
+
+/// The `#[serde(rename = ..)]` value, if any, that keeps `name` (or its alias) on the wire.
+pub(super) fn serde_rename(name: &str, alias: Option<&str>) -> Option<String> {
+    let renamed = match alias {
+        Some(alias) => Some(alias),
+        // serde already strips the `r#` of raw identifiers.
+        None if to_identifier(name).trim_start_matches("r#") != name => Some(name),
+        None => None,
+    };
+    renamed.map(|name| format!("{:?}", name))
+}
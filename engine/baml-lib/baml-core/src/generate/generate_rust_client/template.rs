@@ -0,0 +1,70 @@
+macro_rules! include_template {
+    ($type:expr, $file:expr) => {
+        include_str!(concat!("templates/", $type, "/", $file, ".hbs"))
+    };
+}
+
+macro_rules! register_partial_file {
+    ($reg:expr, $type:expr, $file:expr) => {
+        register_partial!($reg, $file, include_template!($type, $file));
+    };
+}
+
+macro_rules! register_partial {
+    ($reg:expr, $name:expr, $template:expr) => {
+        $reg.register_partial($name, $template)
+            .unwrap_or_else(|e| panic!("Failed to register template: {}", e));
+    };
+}
+
+pub(super) enum Template {
+    Enum,
+    Class,
+    Union,
+    TypeAlias,
+    PromptValue,
+    Function,
+    ExportFile,
+}
+
+pub(super) fn render_with_hbs<T: serde::Serialize>(template: Template, data: &T) -> String {
+    let mut reg = handlebars::Handlebars::new();
+    // Everything is rendered into Rust source, so nothing should be HTML-escaped.
+    reg.register_escape_fn(handlebars::no_escape);
+
+    let template = match template {
+        Template::Enum => {
+            register_partial_file!(reg, "types", "enum");
+            "enum"
+        }
+        Template::Class => {
+            register_partial_file!(reg, "types", "class");
+            "class"
+        }
+        Template::Union => {
+            register_partial_file!(reg, "types", "union");
+            "union"
+        }
+        Template::TypeAlias => {
+            register_partial_file!(reg, "types", "type_alias");
+            "type_alias"
+        }
+        Template::PromptValue => {
+            register_partial_file!(reg, "functions", "prompt_value");
+            "prompt_value"
+        }
+        Template::Function => {
+            register_partial_file!(reg, "functions", "function");
+            "function"
+        }
+        Template::ExportFile => {
+            register_partial_file!(reg, "exports", "mod");
+            "mod"
+        }
+    };
+
+    match reg.render_template(&format!("{{{{> {}}}}}", template), &data) {
+        Ok(s) => s,
+        Err(e) => panic!("Failed to render template: {}", e),
+    }
+}
@@ -0,0 +1,39 @@
+use std::path::Path;
+
+use crate::generate::golden::{assert_generated, load_ir};
+
+use super::collect_rust_files;
+
+const TESTS_ROOT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/generate_rust");
+
+/// Generates the Rust client for `tests/generate_rust/<case>/baml_src` and compares it, file by
+/// file, with `tests/generate_rust/<case>/baml_client`.
+///
+/// Each case is also a small crate, so `cargo check` in its directory checks that the expected
+/// client compiles.
+fn run_generate_test(case: &str) {
+    let case_dir = Path::new(TESTS_ROOT).join(case);
+    let ir = load_ir(&case_dir.join("baml_src"));
+    assert_generated(
+        &case_dir.join("baml_client"),
+        collect_rust_files(&ir)
+            .rendered_files()
+            .into_iter()
+            .map(|(path, content)| (path.clone(), content)),
+    );
+}
+
+#[test]
+fn kitchen_sink() {
+    run_generate_test("kitchen_sink");
+}
+
+#[test]
+fn recursive() {
+    run_generate_test("recursive");
+}
+
+#[test]
+fn literals() {
+    run_generate_test("literals");
+}
@@ -0,0 +1,52 @@
+use serde_json::json;
+
+use crate::generate::{
+    dir_writer::WithFileContent,
+    ir::{TypeAlias, Walker},
+};
+
+use super::{
+    field_type::{is_plain_union, to_rust},
+    rust_language_features::{RustFileCollector, RustLanguageFeatures},
+    template::{render_with_hbs, Template},
+};
+
+impl WithFileContent<RustLanguageFeatures> for Walker<'_, &TypeAlias> {
+    fn file_dir(&self) -> &'static str {
+        "."
+    }
+
+    fn file_name(&self) -> String {
+        "types".into()
+    }
+
+    fn write(&self, collector: &mut RustFileCollector) {
+        let name = &self.elem().name;
+        let target = &self.elem().target;
+        let mut unions = vec![];
+
+        let file = collector.start_file(
+            WithFileContent::<RustLanguageFeatures>::file_dir(self),
+            WithFileContent::<RustLanguageFeatures>::file_name(self),
+            false,
+        );
+        file.add_import("serde", "Deserialize", None, false);
+        file.add_import("serde", "Serialize", None, false);
+        if is_plain_union(target) {
+            // The alias names the union's enum itself.
+            to_rust(target, name, &mut unions);
+        } else {
+            file.append(render_with_hbs(
+                Template::TypeAlias,
+                &json!({
+                    "name": name,
+                    "type": to_rust(target, &format!("{}Inner", name), &mut unions),
+                }),
+            ));
+        }
+        unions
+            .iter()
+            .for_each(|u| file.append(render_with_hbs(Template::Union, u)));
+        collector.finish_file();
+    }
+}
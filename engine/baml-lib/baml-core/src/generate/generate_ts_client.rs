@@ -8,20 +8,39 @@ mod r#impl;
 mod intermediate_repr;
 mod template;
 mod ts_language_features;
+mod type_alias;
+
+#[cfg(test)]
+mod tests;
+
+use std::collections::HashSet;
 
 use crate::configuration::Generator;
 
 use super::{
     dir_writer::WithFileContent,
+    generate_python_client::RequiredTypes,
     ir::{IntermediateRepr, WithJsonSchema},
 };
-use ts_language_features::get_file_collector;
+use ts_language_features::{get_file_collector, TSFileCollector};
 
-pub(crate) fn generate_ts(ir: &IntermediateRepr, gen: &Generator) -> std::io::Result<()> {
+fn collect_ts_files(ir: &IntermediateRepr) -> TSFileCollector {
     let mut collector = get_file_collector();
 
     ir.walk_enums().for_each(|e| e.write(&mut collector));
     ir.walk_classes().for_each(|c| c.write(&mut collector));
+    // Streamed results are partial, so every class the functions return gets a partial interface.
+    let streamed =
+        RequiredTypes::transitive(ir, ir.walk_functions().map(|f| &f.elem().output.elem));
+    let streamed = streamed
+        .classes()
+        .map(|c| c.elem().name.as_str())
+        .collect::<HashSet<_>>();
+    ir.walk_classes()
+        .filter(|c| streamed.contains(c.elem().name.as_str()))
+        .for_each(|c| c.write_partial(&mut collector));
+    ir.walk_type_aliases()
+        .for_each(|a| a.write(&mut collector));
     ir.walk_functions().for_each(|f| f.write(&mut collector));
     ir.walk_functions().for_each(|f| {
         f.walk_impls().for_each(|i| {
@@ -63,6 +82,9 @@ pub(crate) fn generate_ts(ir: &IntermediateRepr, gen: &Generator) -> std::io::Re
     file.add_export("schema");
     collector.finish_file();
     ir.write(&mut collector);
+    collector
+}
 
-    collector.commit(&gen.output_path)
+pub(crate) fn generate_ts(ir: &IntermediateRepr, gen: &Generator) -> std::io::Result<()> {
+    collect_ts_files(ir).commit(&gen.output_path)
 }
@@ -3,9 +3,10 @@ use serde_json::json;
 use crate::generate::{
     dir_writer::WithFileContent,
     generate_ts_client::field_type::{
-        to_internal_type, to_internal_type_constructor, to_type_check,
+        to_internal_type, to_internal_type_constructor, to_partial_ts, to_type_check,
+        walk_partial_types,
     },
-    ir::{Class, Expression, Walker},
+    ir::{Class, Expression, FieldType, Walker},
 };
 
 use super::{
@@ -23,7 +24,11 @@ impl WithFileContent<TSLanguageFeatures> for Walker<'_, &Class> {
     }
 
     fn write(&self, collector: &mut TSFileCollector) {
-        let file = collector.start_file(self.file_dir(), self.file_name(), false);
+        let file = collector.start_file(
+            WithFileContent::<TSLanguageFeatures>::file_dir(self),
+            WithFileContent::<TSLanguageFeatures>::file_name(self),
+            false,
+        );
         file.append(render_with_hbs(
             super::template::Template::Class,
             &json!({
@@ -37,7 +42,11 @@ impl WithFileContent<TSLanguageFeatures> for Walker<'_, &Class> {
         file.add_export(self.elem().name.clone());
         collector.finish_file();
 
-        let file = collector.start_file(self.file_dir(), self.file_name() + "_internal", false);
+        let file = collector.start_file(
+            WithFileContent::<TSLanguageFeatures>::file_dir(self),
+            WithFileContent::<TSLanguageFeatures>::file_name(self) + "_internal",
+            false,
+        );
         file.add_import("./types", self.elem().name.clone(), None, false);
         file.add_export(format!("Internal{}", self.elem().name));
         file.append(render_with_hbs(
@@ -47,14 +56,14 @@ impl WithFileContent<TSLanguageFeatures> for Walker<'_, &Class> {
                 "fields": self.elem().static_fields.iter().map(|f| json!({
                     "name": f.elem.name,
                     "type": f.elem.r#type.elem.to_ts(),
-                    "internal_type": to_internal_type(&f.elem.r#type.elem),
-                    "constructor": to_internal_type_constructor(&format!("data.{}", f.elem.name), &f.elem.r#type.elem),
-                    "check": to_type_check(&format!("obj.{}", f.elem.name), &f.elem.r#type.elem),
+                    "internal_type": to_internal_type(self.db, &f.elem.r#type.elem),
+                    "constructor": to_internal_type_constructor(self.db, &format!("data.{}", f.elem.name), &f.elem.r#type.elem),
+                    "check": to_type_check(self.db, &format!("obj.{}", f.elem.name), &f.elem.r#type.elem),
                 })).collect::<Vec<_>>(),
                 "getters": self.elem().dynamic_fields.iter().map(|f| json!({
                     "name": f.elem.name,
                     "type": f.elem.r#type.elem.to_ts(),
-                    "internal_type": to_internal_type(&f.elem.r#type.elem),
+                    "internal_type": to_internal_type(self.db, &f.elem.r#type.elem),
                     "body": f.attributes.get("get/typescript").map_or("throw NotImplemented()".to_string(), |v| match v {
                         Expression::RawString(s) => s.clone(),
                         Expression::String(s) => s.clone(),
@@ -66,3 +75,32 @@ impl WithFileContent<TSLanguageFeatures> for Walker<'_, &Class> {
         collector.finish_file();
     }
 }
+
+impl Walker<'_, &Class> {
+    /// Writes the partial interface of the class, which streamed results use: every field may be
+    /// null, and refers to the partial interfaces of other classes.
+    pub(super) fn write_partial(&self, collector: &mut TSFileCollector) {
+        let file = collector.start_file(".", "types_partial", false);
+        let fields = &self.elem().static_fields;
+        fields
+            .iter()
+            .flat_map(|f| walk_partial_types(self.db, &f.elem.r#type.elem))
+            .filter(|(lib, _)| *lib == "types")
+            .for_each(|(_, t)| file.add_import("./types", t, None, false));
+        file.append(render_with_hbs(
+            super::template::Template::ClassPartial,
+            &json!({
+                "name": self.elem().name,
+                "fields": fields.iter().map(|f| json!({
+                    "name": f.elem.name,
+                    "type": match &f.elem.r#type.elem {
+                        FieldType::Optional(inner) => to_partial_ts(self.db, inner),
+                        other => to_partial_ts(self.db, other),
+                    },
+                })).collect::<Vec<_>>(),
+            }),
+        ));
+        file.add_export(format!("Partial{}", self.elem().name));
+        collector.finish_file();
+    }
+}
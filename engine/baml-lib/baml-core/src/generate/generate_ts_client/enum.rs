@@ -18,12 +18,20 @@ impl WithFileContent<TSLanguageFeatures> for Walker<'_, &Enum> {
     }
 
     fn write(&self, collector: &mut TSFileCollector) {
-        let file = collector.start_file(self.file_dir(), self.file_name(), false);
+        let file = collector.start_file(
+            WithFileContent::<TSLanguageFeatures>::file_dir(self),
+            WithFileContent::<TSLanguageFeatures>::file_name(self),
+            false,
+        );
         file.append(render_with_hbs(super::template::Template::Enum, &self.item));
         file.add_export(&self.elem().name);
         collector.finish_file();
 
-        let file = collector.start_file(self.file_dir(), self.file_name() + "_internal", false);
+        let file = collector.start_file(
+            WithFileContent::<TSLanguageFeatures>::file_dir(self),
+            WithFileContent::<TSLanguageFeatures>::file_name(self) + "_internal",
+            false,
+        );
         file.add_import("./types", self.elem().name.clone(), None, false);
         file.append(render_with_hbs(
             super::template::Template::EnumInternal,
@@ -1,8 +1,12 @@
 use std::collections::HashSet;
 
 use internal_baml_schema_ast::ast::TypeValue;
+use serde_json::json;
 
-use crate::generate::{dir_writer::FileContent, ir::FieldType};
+use crate::generate::{
+    dir_writer::FileContent,
+    ir::{FieldType, IntermediateRepr},
+};
 
 use super::ts_language_features::ToTypeScript;
 
@@ -11,6 +15,7 @@ impl ToTypeScript for FieldType {
         match self {
             FieldType::Class(name) => name.clone(),
             FieldType::Enum(name) => name.clone(),
+            FieldType::TypeAlias(name) => name.clone(),
             FieldType::List(inner) => format!("{}[]", inner.to_ts()),
             FieldType::Map(key, value) => {
                 format!("{{ [key: {}]: {} }}", key.to_ts(), value.to_ts())
@@ -23,6 +28,8 @@ impl ToTypeScript for FieldType {
                 TypeValue::Null => "null".to_string(),
                 TypeValue::Char => "string".to_string(),
             },
+            // JSON literals are TypeScript literals too.
+            FieldType::Literal(value) => json!(value).to_string(),
             FieldType::Union(inner) => inner
                 .iter()
                 .map(|t| t.to_ts())
@@ -41,16 +48,85 @@ impl ToTypeScript for FieldType {
     }
 }
 
-pub(super) fn to_internal_type(r#type: &FieldType) -> String {
+/// The streamed counterpart of a type: every class it refers to, however deeply nested, is
+/// swapped for its partial interface. Every member of a partial union would match the same
+/// partial value, so union members are kept as they are.
+pub(super) fn to_partial_ts(ir: &IntermediateRepr, r#type: &FieldType) -> String {
+    match r#type {
+        FieldType::Class(name) => format!("Partial{}", name),
+        FieldType::Enum(_)
+        | FieldType::Primitive(_)
+        | FieldType::Literal(_)
+        | FieldType::Union(_) => r#type.to_ts(),
+        FieldType::TypeAlias(name) => to_partial_ts(ir, ir.alias_target(name)),
+        FieldType::List(inner) => {
+            let item = to_partial_ts(ir, inner);
+            match item.contains(" | ") {
+                true => format!("({})[]", item),
+                false => format!("{}[]", item),
+            }
+        }
+        FieldType::Map(key, value) => {
+            format!("{{ [key: {}]: {} }}", key.to_ts(), to_partial_ts(ir, value))
+        }
+        FieldType::Tuple(inner) => format!(
+            "[{}]",
+            inner
+                .iter()
+                .map(|t| to_partial_ts(ir, t))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        FieldType::Optional(inner) => format!("{} | null", to_partial_ts(ir, inner)),
+    }
+}
+
+/// The types `to_partial_ts` refers to, with the file that declares each: `types_partial` for
+/// partial interfaces and `types` for everything else.
+pub(super) fn walk_partial_types(
+    ir: &IntermediateRepr,
+    r#type: &FieldType,
+) -> impl Iterator<Item = (&'static str, String)> {
+    let mut results = HashSet::new();
+
+    fn walk(
+        ir: &IntermediateRepr,
+        r#type: &FieldType,
+        results: &mut HashSet<(&'static str, String)>,
+    ) {
+        match r#type {
+            FieldType::Class(name) => {
+                results.insert(("types_partial", format!("Partial{}", name)));
+            }
+            FieldType::TypeAlias(name) => walk(ir, ir.alias_target(name), results),
+            FieldType::Enum(_) | FieldType::Union(_) => {
+                results.extend(walk_custom_types(r#type).map(|t| ("types", t.to_string())))
+            }
+            FieldType::List(inner) | FieldType::Optional(inner) => walk(ir, inner, results),
+            FieldType::Map(key, value) => {
+                results.extend(walk_custom_types(key).map(|t| ("types", t.to_string())));
+                walk(ir, value, results);
+            }
+            FieldType::Tuple(types) => types.iter().for_each(|t| walk(ir, t, results)),
+            FieldType::Primitive(_) | FieldType::Literal(_) => (),
+        }
+    }
+
+    walk(ir, r#type, &mut results);
+    results.into_iter()
+}
+
+pub(super) fn to_internal_type(ir: &IntermediateRepr, r#type: &FieldType) -> String {
     match r#type {
         FieldType::Class(name) => format!("Internal{}", name),
         FieldType::Enum(name) => name.clone(),
-        FieldType::List(inner) => format!("{}[]", to_internal_type(inner)),
+        FieldType::TypeAlias(name) => to_internal_type(ir, ir.alias_target(name)),
+        FieldType::List(inner) => format!("{}[]", to_internal_type(ir, inner)),
         FieldType::Map(key, value) => {
             format!(
                 "{{ [key: {}]: {} }}",
-                to_internal_type(key),
-                to_internal_type(value)
+                to_internal_type(ir, key),
+                to_internal_type(ir, value)
             )
         }
         FieldType::Primitive(r#type) => match r#type {
@@ -61,6 +137,7 @@ pub(super) fn to_internal_type(r#type: &FieldType) -> String {
             TypeValue::Null => "null".to_string(),
             TypeValue::Char => "string".to_string(),
         },
+        FieldType::Literal(_) => r#type.to_ts(),
         FieldType::Union(inner) => inner
             .iter()
             .map(|t| t.to_ts())
@@ -78,30 +155,37 @@ pub(super) fn to_internal_type(r#type: &FieldType) -> String {
     }
 }
 
-pub(super) fn to_internal_type_constructor(variable: &str, r#type: &FieldType) -> String {
+pub(super) fn to_internal_type_constructor(
+    ir: &IntermediateRepr,
+    variable: &str,
+    r#type: &FieldType,
+) -> String {
     match r#type {
-        FieldType::Class(name) => format!("new Internal{name}({variable})"),
+        FieldType::Class(name) => format!("Internal{name}.from({variable})"),
         FieldType::Enum(_) => variable.to_string(),
+        FieldType::TypeAlias(name) => {
+            to_internal_type_constructor(ir, variable, ir.alias_target(name))
+        }
         FieldType::List(inner) => format!(
             "{variable}.map(x => {})",
-            to_internal_type_constructor("x", inner)
+            to_internal_type_constructor(ir, "x", inner)
         ),
         FieldType::Map(_key, _value) => {
             unimplemented!("Map type is not supported in TypeScript")
         }
-        FieldType::Primitive(_) => variable.to_string(),
+        FieldType::Primitive(_) | FieldType::Literal(_) => variable.to_string(),
         FieldType::Union(inner) => {
             let content = inner
                 .iter()
                 .map(|t| {
-                    let response = to_internal_type_constructor("x", t);
+                    let response = to_internal_type_constructor(ir, "x", t);
                     format!(
                         r#"
 if ({type_check}) {{
   return {response};
 }}
                       "#,
-                        type_check = to_type_check("x", t),
+                        type_check = to_type_check(ir, "x", t),
                     )
                     .trim()
                     .to_string()
@@ -123,24 +207,25 @@ if ({type_check}) {{
             inner
                 .iter()
                 .enumerate()
-                .map(|(i, t)| to_internal_type_constructor(&format!("{variable}[{i}]"), t))
+                .map(|(i, t)| to_internal_type_constructor(ir, &format!("{variable}[{i}]"), t))
                 .collect::<Vec<_>>()
                 .join(", ")
         ),
         FieldType::Optional(inner) => format!(
             "({variable} === null || {variable} === undefined) ? null : {}",
-            to_internal_type_constructor(variable, inner)
+            to_internal_type_constructor(ir, variable, inner)
         ),
     }
 }
 
-pub(super) fn to_type_check(variable: &str, r#type: &FieldType) -> String {
+pub(super) fn to_type_check(ir: &IntermediateRepr, variable: &str, r#type: &FieldType) -> String {
     match r#type {
         FieldType::Class(name) => format!("is{name}({variable})"),
         FieldType::Enum(name) => format!("is{name}({variable})"),
+        FieldType::TypeAlias(name) => to_type_check(ir, variable, ir.alias_target(name)),
         FieldType::List(inner) => format!(
             "Array.isArray({variable}) && {variable}.every((x: any) => {})",
-            to_type_check("x", inner)
+            to_type_check(ir, "x", inner)
         ),
         FieldType::Map(_key, _value) => {
             unimplemented!("Map type is not supported in TypeScript")
@@ -153,10 +238,11 @@ pub(super) fn to_type_check(variable: &str, r#type: &FieldType) -> String {
             TypeValue::Null => format!("{variable} === null"),
             TypeValue::Char => format!("typeof {variable} === 'string'"),
         },
+        FieldType::Literal(value) => format!("{variable} === {}", json!(value)),
         FieldType::Union(inner) => inner
             .iter()
             .map(|t| {
-                let response = to_type_check(variable, t);
+                let response = to_type_check(ir, variable, t);
                 format!(r#"({response})"#,)
             })
             .collect::<Vec<_>>()
@@ -167,13 +253,13 @@ pub(super) fn to_type_check(variable: &str, r#type: &FieldType) -> String {
             inner
                 .iter()
                 .enumerate()
-                .map(|(i, t)| to_type_check(&format!("{variable}[{i}]"), t))
+                .map(|(i, t)| to_type_check(ir, &format!("{variable}[{i}]"), t))
                 .collect::<Vec<_>>()
                 .join(", ")
         ),
         FieldType::Optional(inner) => format!(
             "({variable} === null || {variable} === undefined) || {}",
-            to_type_check(variable, inner)
+            to_type_check(ir, variable, inner)
         ),
     }
 }
@@ -189,14 +275,14 @@ pub(super) fn walk_custom_types<'a>(r#type: &'a FieldType) -> impl Iterator<Item
                     walk(t, results);
                 }
             }
-            FieldType::Class(name) | FieldType::Enum(name) => {
+            FieldType::Class(name) | FieldType::Enum(name) | FieldType::TypeAlias(name) => {
                 results.insert(&name);
             }
             FieldType::List(inner) => walk(inner, results),
             FieldType::Map(_key, _value) => {
                 // Handle or ignore the map type as needed
             }
-            FieldType::Primitive(_) => (), // Ignore primitive types
+            FieldType::Primitive(_) | FieldType::Literal(_) => (), // Ignore primitive types
             FieldType::Optional(inner) => walk(inner, results),
         }
     }
@@ -209,6 +295,7 @@ pub(super) fn walk_custom_types<'a>(r#type: &'a FieldType) -> impl Iterator<Item
 }
 
 pub(super) fn to_parse_expression(
+    ir: &IntermediateRepr,
     variable: &String,
     r#type: &FieldType,
     file: &mut FileContent,
@@ -219,21 +306,24 @@ pub(super) fn to_parse_expression(
             format!("Internal{name}.from({variable})")
         }
         FieldType::Enum(name) => format!("{variable} as {name}"),
+        FieldType::TypeAlias(name) => {
+            to_parse_expression(ir, variable, ir.alias_target(name), file)
+        }
         FieldType::List(inner) => {
             format!(
                 "{variable}.map(x => {})",
-                to_parse_expression(&"x".to_string(), inner, file)
+                to_parse_expression(ir, &"x".to_string(), inner, file)
             )
         }
         FieldType::Map(_key, _value) => {
             unimplemented!("Map type is not supported in TypeScript")
         }
-        FieldType::Primitive(_) => variable.to_string(),
+        FieldType::Primitive(_) | FieldType::Literal(_) => variable.to_string(),
         FieldType::Union(inner) => {
             let content = inner
                 .iter()
                 .map(|t| {
-                    let response = to_parse_expression(variable, t, file);
+                    let response = to_parse_expression(ir, variable, t, file);
                     format!(
                         r#"
 if (to_type_check({variable}, t)) {{
@@ -252,7 +342,7 @@ if (to_type_check({variable}, t)) {{
     throw new Error(`Could not parse {variable} as {curr}`);
 }})({variable})
 "#,
-                curr = to_internal_type(r#type),
+                curr = to_internal_type(ir, r#type),
             )
             .trim()
             .to_string()
@@ -262,13 +352,13 @@ if (to_type_check({variable}, t)) {{
             inner
                 .iter()
                 .enumerate()
-                .map(|(i, t)| to_parse_expression(&format!("{variable}[{i}]"), t, file))
+                .map(|(i, t)| to_parse_expression(ir, &format!("{variable}[{i}]"), t, file))
                 .collect::<Vec<_>>()
                 .join(", ")
         ),
         FieldType::Optional(inner) => format!(
             "({variable} === null || {variable} === undefined) ? null : {}",
-            to_parse_expression(variable, inner, file)
+            to_parse_expression(ir, variable, inner, file)
         ),
     }
 }
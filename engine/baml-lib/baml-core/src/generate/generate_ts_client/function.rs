@@ -7,7 +7,7 @@ use crate::generate::{
 };
 
 use super::{
-    field_type::walk_custom_types,
+    field_type::{to_partial_ts, walk_custom_types, walk_partial_types},
     template::render_with_hbs,
     ts_language_features::{TSFileCollector, TSLanguageFeatures},
 };
@@ -22,7 +22,11 @@ impl WithFileContent<TSLanguageFeatures> for Walker<'_, &Function> {
     }
 
     fn write(&self, collector: &mut TSFileCollector) {
-        let file = collector.start_file(self.file_dir(), self.file_name(), false);
+        let file = collector.start_file(
+            WithFileContent::<TSLanguageFeatures>::file_dir(self),
+            WithFileContent::<TSLanguageFeatures>::file_name(self),
+            false,
+        );
 
         match &self.elem().inputs {
             FunctionArgs::UnnamedArg(arg) => {
@@ -41,6 +45,15 @@ impl WithFileContent<TSLanguageFeatures> for Walker<'_, &Function> {
         walk_custom_types(&self.elem().output.elem).for_each(|t| {
             file.add_import("./types", t, None, false);
         });
+        walk_partial_types(self.db, &self.elem().output.elem).for_each(|(lib, t)| {
+            file.add_import(format!("./{}", lib), t, None, false);
+        });
+        file.add_import(
+            "@boundaryml/baml_client/baml_lib/stream/baml_stream",
+            "BamlStream",
+            None,
+            false,
+        );
 
         let function_content = json!({
           "name": self.elem().name.clone(),
@@ -62,6 +75,7 @@ impl WithFileContent<TSLanguageFeatures> for Walker<'_, &Function> {
             }),
           },
           "return_type": self.elem().output.elem.to_ts(),
+          "partial_return_type": to_partial_ts(self.db, &self.elem().output.elem),
           "impls": self.elem().impls.iter().map(|i| i.elem.name.clone()).collect::<Vec<_>>(),
           "default_impl": self.elem().default_impl,
         });
@@ -72,6 +86,7 @@ impl WithFileContent<TSLanguageFeatures> for Walker<'_, &Function> {
         ));
         file.add_export(self.elem().name.clone());
         file.add_export(format!("I{}", self.elem().name));
+        file.add_export(format!("I{}Stream", self.elem().name));
         file.add_export(format!("{}Function", self.elem().name));
         collector.finish_file();
     }
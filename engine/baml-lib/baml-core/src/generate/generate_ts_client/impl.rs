@@ -1,9 +1,15 @@
+use indexmap::IndexMap;
+use internal_baml_parser_database::{split_prompt_blocks, PromptPart};
+use internal_baml_prompt_parser::ast::VariableSegment;
 use serde_json::json;
 
 use crate::generate::{
     dir_writer::WithFileContent,
-    generate_ts_client::{field_type::to_parse_expression, ts_language_features::ToTypeScript},
-    ir::{Function, FunctionArgs, Impl, Walker},
+    generate_ts_client::{
+        field_type::{to_parse_expression, to_partial_ts, walk_custom_types, walk_partial_types},
+        ts_language_features::ToTypeScript,
+    },
+    ir::{Function, FunctionArgs, Impl, Walker, WithJsonSchema},
 };
 
 use super::{
@@ -34,15 +40,39 @@ impl WithFileContent<TSLanguageFeatures> for Walker<'_, (&Function, &Impl)> {
         );
         file.add_import("../json_schema", "schema", None, false);
 
+        // Adapter code may use the function's types and the types it converts to and from.
+        let input_adapter = impl_.elem.input_adapter.as_ref();
+        let output_adapter = impl_.elem.output_adapter.as_ref();
+        let input_types = match &function.elem.inputs {
+            FunctionArgs::UnnamedArg(arg) => vec![arg],
+            FunctionArgs::NamedArgList(args) => args.iter().map(|(_, r#type)| r#type).collect(),
+        };
+        input_types
+            .into_iter()
+            .chain([&function.elem.output.elem])
+            .chain(input_adapter.map(|adapter| &adapter.to))
+            .chain(output_adapter.map(|adapter| &adapter.from))
+            .flat_map(walk_custom_types)
+            .for_each(|t| file.add_import("../types", t, None, false));
+        file.add_import(
+            "@boundaryml/baml_client/baml_lib/stream/baml_stream",
+            "BamlStream",
+            None,
+            false,
+        );
+        walk_partial_types(self.db, &function.elem.output.elem)
+            .for_each(|(lib, t)| file.add_import(format!("../{}", lib), t, None, false));
+
         let function_content = json!({
           "name": function.elem.name.clone(),
           "params": match &function.elem.inputs {
             FunctionArgs::UnnamedArg(arg) => {
               json!({
                 "positional": true,
-                "name": "arg",
+                // The prompt uses what the input adapter returns as `arg`.
+                "name": if input_adapter.is_some() { "input" } else { "arg" },
                 "type": arg.to_ts(),
-                "expr": to_parse_expression(&"arg".to_string(), arg, file),
+                "expr": to_parse_expression(self.db, &"arg".to_string(), arg, file),
               })
             }
             FunctionArgs::NamedArgList(args) => json!({
@@ -51,18 +81,78 @@ impl WithFileContent<TSLanguageFeatures> for Walker<'_, (&Function, &Impl)> {
                 "values": args.iter().map(|(name, r#type)| json!({
                   "name": name.clone(),
                   "type": r#type.to_ts(),
-                  "expr": to_parse_expression(&format!("args.{}", name), r#type, file),
+                  "expr": to_parse_expression(self.db, &format!("args.{}", name), r#type, file),
                 })).collect::<Vec<_>>(),
             }),
           },
           "return_type": function.elem.output.elem.to_ts(),
+          "partial_return_type": to_partial_ts(self.db, &function.elem.output.elem),
         });
 
         let mut prompt = impl_.elem.prompt.clone();
         impl_.elem.output_replacers.iter().for_each(|(k, val)| {
             prompt = prompt.replace(k, &format!("{}", val));
         });
-        prompt = prompt.replace("`", "\\`");
+        // Prompts with `{#if}` or `{#for}` blocks are built when the impl runs.
+        let has_blocks = !matches!(
+            split_prompt_blocks(&prompt).as_slice(),
+            [] | [PromptPart::Text(_)]
+        );
+        let prompt = match has_blocks {
+            true => {
+                file.add_import(
+                    "@boundaryml/baml_client/baml_lib/prompt_blocks",
+                    "promptIsSet",
+                    None,
+                    false,
+                );
+                file.add_import(
+                    "@boundaryml/baml_client/baml_lib/prompt_blocks",
+                    "promptItems",
+                    None,
+                    false,
+                );
+                let mut items = vec![];
+                to_ts_template(
+                    &split_prompt_blocks(&prompt),
+                    &impl_.elem.input_replacers,
+                    &mut items,
+                )
+            }
+            false => prompt.replace("`", "\\`"),
+        };
+
+        // The input adapter takes the same arguments as the function, and is called with the
+        // parsed ones.
+        let input_adapter_content = input_adapter.map(|adapter| {
+            let (params, args) = match &function.elem.inputs {
+                FunctionArgs::UnnamedArg(arg) => {
+                    (format!("arg: {}", arg.to_ts()), "input".to_string())
+                }
+                FunctionArgs::NamedArgList(args) => {
+                    let names = args
+                        .iter()
+                        .map(|(name, _)| name.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    let types = args
+                        .iter()
+                        .map(|(name, r#type)| format!("{}: {}", name, r#type.to_ts()))
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    (
+                        format!("{{ {} }}: {{ {} }}", names, types),
+                        format!("{{ {} }}", names),
+                    )
+                }
+            };
+            json!({
+                "type": adapter.to.to_ts(),
+                "params": params,
+                "args": args,
+                "code": adapter_code(&adapter.code),
+            })
+        });
 
         file.append(render_with_hbs(
             super::template::Template::Impl,
@@ -70,8 +160,20 @@ impl WithFileContent<TSLanguageFeatures> for Walker<'_, (&Function, &Impl)> {
                 "function": function_content,
                 "name": impl_.elem.name.clone(),
                 "prompt": prompt,
+                "has_blocks": has_blocks,
                 "client": impl_.elem.client.clone(),
-                "inputs": impl_.elem.input_replacers,
+                "input_adapter": input_adapter_content,
+                "output_adapter": output_adapter.map(|adapter| json!({
+                    "type": adapter.from.to_ts(),
+                    "schema": adapter.from.json_schema().to_string(),
+                    "code": adapter_code(&adapter.code),
+                })),
+                "inputs": impl_.elem.input_replacers.iter()
+                    .filter(|(key, _)| prompt.contains(key.as_str()))
+                    .map(|(key, path)| json!({
+                        "key": serde_json::to_string(key).unwrap_or_default(),
+                        "expr": to_ts_expression(path),
+                    })).collect::<Vec<_>>(),
             }),
         ));
         collector.finish_file();
@@ -81,3 +183,105 @@ impl WithFileContent<TSLanguageFeatures> for Walker<'_, (&Function, &Impl)> {
         collector.finish_file();
     }
 }
+
+/// The TypeScript code of an adapter, indented for the body of a function.
+fn adapter_code(code: &IndexMap<String, String>) -> String {
+    code.get("typescript")
+        .map(|code| code.as_str())
+        .unwrap_or("throw new Error('This adapter has no typescript code.');")
+        .lines()
+        .map(|line| match line.is_empty() {
+            true => String::new(),
+            false => format!("  {}", line),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Renders prompt parts into the body of a template literal. The blocks become `${..}`
+/// expressions, and so do the replacers that use the item of a `{#for}` block. Other replacers are
+/// left for `run_prompt_template`.
+fn to_ts_template<'a>(
+    parts: &[PromptPart<'a>],
+    input_replacers: &IndexMap<String, String>,
+    items: &mut Vec<&'a str>,
+) -> String {
+    let expression = |key: &str| input_replacers.get(key).map(|path| to_ts_expression(path));
+    parts
+        .iter()
+        .map(|part| match part {
+            PromptPart::Text(text) => {
+                let mut text = text.replace('`', "\\`").replace("${", "\\${");
+                for (key, path) in input_replacers {
+                    let root = VariableSegment::parse_path(path)
+                        .and_then(|p| p[0].as_field().map(str::to_string));
+                    if root.is_some_and(|root| items.contains(&root.as_str())) {
+                        text = text.replace(key, &format!("${{{}}}", to_ts_expression(path)));
+                    }
+                }
+                text
+            }
+            PromptPart::If {
+                condition,
+                then,
+                otherwise,
+            } => match expression(condition) {
+                Some(condition) => format!(
+                    "${{promptIsSet({}) ? `{}` : `{}`}}",
+                    condition,
+                    to_ts_template(then, input_replacers, items),
+                    to_ts_template(otherwise, input_replacers, items)
+                ),
+                None => String::new(),
+            },
+            PromptPart::For {
+                item,
+                collection,
+                body,
+            } => match expression(collection) {
+                Some(collection) => {
+                    items.push(item);
+                    let body = to_ts_template(body, input_replacers, items);
+                    items.pop();
+                    format!(
+                        "${{promptItems({}).map(({}) => `{}`).join(\"\")}}",
+                        collection, item, body
+                    )
+                }
+                None => String::new(),
+            },
+        })
+        .collect()
+}
+
+/// The expression for an input replacer, e.g. `arg.items[0].name`. `[*]` renders every item on
+/// its own line.
+fn to_ts_expression(path: &str) -> String {
+    match VariableSegment::parse_path(path) {
+        Some(segments) => segments_to_ts(String::new(), &segments, 0),
+        None => path.to_string(),
+    }
+}
+
+fn segments_to_ts(mut expr: String, segments: &[VariableSegment], depth: usize) -> String {
+    for (idx, segment) in segments.iter().enumerate() {
+        match segment {
+            VariableSegment::Each => {
+                let item = format!("x{}", depth);
+                return format!(
+                    "Object.values({}).map(({}) => {}).join(\"\\n\")",
+                    expr,
+                    item,
+                    segments_to_ts(item.clone(), &segments[idx + 1..], depth + 1)
+                );
+            }
+            other => {
+                if matches!(other, VariableSegment::Field(_)) && !expr.is_empty() {
+                    expr.push('.');
+                }
+                expr += &VariableSegment::format_path(std::slice::from_ref(other));
+            }
+        }
+    }
+    expr
+}
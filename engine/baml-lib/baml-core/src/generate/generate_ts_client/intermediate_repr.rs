@@ -14,7 +14,11 @@ impl WithFileContent<TSLanguageFeatures> for IntermediateRepr {
     }
 
     fn write(&self, fc: &mut crate::generate::dir_writer::FileCollector<TSLanguageFeatures>) {
-        let file = fc.start_file(self.file_dir(), self.file_name(), false);
+        let file = fc.start_file(
+            WithFileContent::<TSLanguageFeatures>::file_dir(self),
+            WithFileContent::<TSLanguageFeatures>::file_name(self),
+            false,
+        );
         file.append(render_with_hbs(
             super::template::Template::ExportFile,
             &json!({
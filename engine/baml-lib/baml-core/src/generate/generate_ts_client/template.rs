@@ -27,6 +27,8 @@ pub(super) enum Template {
     EnumInternal,
     Class,
     ClassInternal,
+    ClassPartial,
+    TypeAlias,
     Function,
     Impl,
     Client,
@@ -37,6 +39,8 @@ pub(super) fn render_with_hbs<T: serde::Serialize>(template: Template, data: &T)
     let mut reg = handlebars::Handlebars::new();
     reg.register_helper("BLOCK_OPEN", Box::new(BLOCK_OPEN));
     reg.register_helper("BLOCK_CLOSE", Box::new(BLOCK_CLOSE));
+    // Everything is rendered into TypeScript source, so nothing should be HTML-escaped.
+    reg.register_escape_fn(handlebars::no_escape);
 
     let content = serde_json::to_string(&data).unwrap();
 
@@ -57,12 +61,21 @@ pub(super) fn render_with_hbs<T: serde::Serialize>(template: Template, data: &T)
             register_partial_file!(reg, "types", "class_internal");
             "class_internal"
         }
+        Template::ClassPartial => {
+            register_partial_file!(reg, "types", "class_partial");
+            "class_partial"
+        }
+        Template::TypeAlias => {
+            register_partial_file!(reg, "types", "type_alias");
+            "type_alias"
+        }
         Template::Function => {
             info!("Content: {}", content);
             register_partial_file!(reg, "functions", "function");
             "function"
         }
         Template::Impl => {
+            register_partial_file!(reg, "functions", "params");
             register_partial_file!(reg, "functions", "impl");
             "impl"
         }
@@ -0,0 +1,41 @@
+use std::path::Path;
+
+use crate::generate::golden::{assert_generated, load_ir};
+
+use super::collect_ts_files;
+
+const TESTS_ROOT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/generate_ts");
+
+/// Generates the TypeScript client for `tests/generate_ts/<case>/baml_src` and compares it, file
+/// by file, with `tests/generate_ts/<case>/baml_client`.
+fn run_generate_test(case: &str) {
+    let case_dir = Path::new(TESTS_ROOT).join(case);
+    let ir = load_ir(&case_dir.join("baml_src"));
+    assert_generated(
+        &case_dir.join("baml_client"),
+        collect_ts_files(&ir)
+            .rendered_files()
+            .into_iter()
+            .map(|(path, content)| (path.clone(), content)),
+    );
+}
+
+#[test]
+fn adapters() {
+    run_generate_test("adapters");
+}
+
+#[test]
+fn streaming() {
+    run_generate_test("streaming");
+}
+
+#[test]
+fn recursive() {
+    run_generate_test("recursive");
+}
+
+#[test]
+fn literals() {
+    run_generate_test("literals");
+}
@@ -36,6 +36,9 @@ impl LanguageFeatures for TSLanguageFeatures {
         imports_by_lib
             .iter()
             .fold(String::new(), |mut buffer, (lib, imports)| {
+                // Sorted, so the generated files are the same on every run.
+                let mut imports = imports.iter().collect::<Vec<_>>();
+                imports.sort_by(|a, b| (&a.name, &a.as_name).cmp(&(&b.name, &b.as_name)));
                 buffer.push_str(&format!(
                     "import {{ {} }} from '{}';\n",
                     imports
@@ -0,0 +1,38 @@
+use serde_json::json;
+
+use crate::generate::{
+    dir_writer::WithFileContent,
+    ir::{TypeAlias, Walker},
+};
+
+use super::{
+    template::render_with_hbs,
+    ts_language_features::{TSFileCollector, TSLanguageFeatures, ToTypeScript},
+};
+
+impl WithFileContent<TSLanguageFeatures> for Walker<'_, &TypeAlias> {
+    fn file_dir(&self) -> &'static str {
+        "."
+    }
+
+    fn file_name(&self) -> String {
+        "types".into()
+    }
+
+    fn write(&self, collector: &mut TSFileCollector) {
+        let file = collector.start_file(
+            WithFileContent::<TSLanguageFeatures>::file_dir(self),
+            WithFileContent::<TSLanguageFeatures>::file_name(self),
+            false,
+        );
+        file.append(render_with_hbs(
+            super::template::Template::TypeAlias,
+            &json!({
+                "name": self.elem().name,
+                "type": self.elem().target.to_ts(),
+            }),
+        ));
+        file.add_export(self.elem().name.clone());
+        collector.finish_file();
+    }
+}
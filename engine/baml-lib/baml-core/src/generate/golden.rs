@@ -0,0 +1,107 @@
+//! Helpers for the generators' golden-file tests.
+
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Component, Path, PathBuf},
+};
+
+use internal_baml_diagnostics::SourceFile;
+
+use super::ir::{self, IntermediateRepr};
+
+fn walk(dir: &Path, files: &mut Vec<PathBuf>) {
+    for entry in fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.is_dir() {
+            walk(&path, files);
+        } else {
+            files.push(path);
+        }
+    }
+}
+
+/// Validates every `.baml` and `.json` file under `baml_src` and builds the IR from them.
+pub(super) fn load_ir(baml_src: &Path) -> IntermediateRepr {
+    let mut paths = vec![];
+    walk(baml_src, &mut paths);
+    let files = paths
+        .into_iter()
+        .filter(|p| {
+            matches!(
+                p.extension().and_then(|e| e.to_str()),
+                Some("baml" | "json")
+            )
+        })
+        .map(|p| {
+            let contents = fs::read_to_string(&p).unwrap();
+            SourceFile::from((p, contents))
+        })
+        .collect();
+
+    let schema = crate::validate(&baml_src.to_path_buf(), files);
+    assert!(
+        !schema.diagnostics.has_errors(),
+        "{}",
+        schema.diagnostics.to_pretty_string()
+    );
+    ir::to_ir(&schema.db).unwrap()
+}
+
+/// Compares generated files, keyed by their path relative to the output directory, with the
+/// files in `expected_dir`.
+///
+/// Run with UPDATE_EXPECT=1 to rewrite `expected_dir` instead.
+pub(super) fn assert_generated(
+    expected_dir: &Path,
+    actual: impl IntoIterator<Item = (PathBuf, String)>,
+) {
+    // Paths like `./baml_types/__init__.py` are normalized so they match what's read from disk.
+    let actual = actual
+        .into_iter()
+        .map(|(path, content)| {
+            let path = path
+                .components()
+                .filter(|c| !matches!(c, Component::CurDir))
+                .collect::<PathBuf>();
+            (path, content)
+        })
+        .collect::<BTreeMap<_, _>>();
+
+    if std::env::var("UPDATE_EXPECT").is_ok() {
+        let _ = fs::remove_dir_all(expected_dir);
+        for (path, content) in &actual {
+            let path = expected_dir.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        }
+        return;
+    }
+
+    let mut expected_paths = vec![];
+    walk(expected_dir, &mut expected_paths);
+    let expected = expected_paths
+        .into_iter()
+        .map(|p| {
+            let contents = fs::read_to_string(&p).unwrap();
+            (
+                p.strip_prefix(expected_dir).unwrap().to_path_buf(),
+                contents,
+            )
+        })
+        .collect::<BTreeMap<_, _>>();
+
+    assert_eq!(
+        expected.keys().collect::<Vec<_>>(),
+        actual.keys().collect::<Vec<_>>(),
+        "Generated files differ. Run the test again with UPDATE_EXPECT=1 to update them."
+    );
+    for (path, content) in &actual {
+        assert_eq!(
+            &expected[path],
+            content,
+            "{} differs. Run the test again with UPDATE_EXPECT=1 to update it.",
+            path.display()
+        );
+    }
+}
@@ -2,7 +2,10 @@
 
 use serde_json::json;
 
-use super::{Class, Enum, FieldType, Function, FunctionArgs, IntermediateRepr, TypeValue, Walker};
+use super::{
+    Class, Enum, FieldType, Function, FunctionArgs, IntermediateRepr, LiteralValue, TypeAlias,
+    TypeValue, Walker,
+};
 
 pub trait WithJsonSchema {
     fn json_schema(&self) -> serde_json::Value;
@@ -16,6 +19,9 @@ impl WithJsonSchema for IntermediateRepr {
         let classes = self
             .walk_classes()
             .map(|c| (c.elem().name.clone(), c.json_schema()));
+        let type_aliases = self
+            .walk_type_aliases()
+            .map(|a| (a.elem().name.clone(), a.json_schema()));
         let function_inputs = self.walk_functions().map(|f| {
             (
                 format!("{}_input", f.elem().name),
@@ -32,6 +38,7 @@ impl WithJsonSchema for IntermediateRepr {
         // Combine all the definitions into one object of key-value pairs
         let definitions = enums
             .chain(classes)
+            .chain(type_aliases)
             .chain(function_inputs)
             .chain(function_outputs)
             .collect::<serde_json::Map<_, _>>();
@@ -131,12 +138,24 @@ impl WithJsonSchema for Walker<'_, &Class> {
     }
 }
 
+impl WithJsonSchema for Walker<'_, &TypeAlias> {
+    fn json_schema(&self) -> serde_json::Value {
+        let mut res = self.elem().target.json_schema();
+        if let serde_json::Value::Object(r) = &mut res {
+            r.insert("title".to_string(), json!(self.elem().name));
+        }
+        res
+    }
+}
+
 impl<'db> WithJsonSchema for FieldType {
     fn json_schema(&self) -> serde_json::Value {
         match self {
-            FieldType::Class(name) | FieldType::Enum(name) => json!({
-                "$ref": format!("#/definitions/{}", name),
-            }),
+            FieldType::Class(name) | FieldType::Enum(name) | FieldType::TypeAlias(name) => {
+                json!({
+                    "$ref": format!("#/definitions/{}", name),
+                })
+            }
             FieldType::Primitive(t) => match t {
                 TypeValue::Char => json!({
                     "type": "string",
@@ -158,6 +177,10 @@ impl<'db> WithJsonSchema for FieldType {
                     "type": "null",
                 }),
             },
+            FieldType::Literal(value) => json!({
+                "type": literal_type(value),
+                "const": value,
+            }),
             FieldType::List(item) => json!({
                 "type": "array",
                 "items": (*item).json_schema()
@@ -168,6 +191,28 @@ impl<'db> WithJsonSchema for FieldType {
                     "type": v.json_schema(),
                 }
             }),
+            // A union of literals is an enum of their values.
+            FieldType::Union(options)
+                if options.iter().all(|t| matches!(t, FieldType::Literal(_))) =>
+            {
+                let values = options
+                    .iter()
+                    .filter_map(|t| match t {
+                        FieldType::Literal(value) => Some(value),
+                        _ => None,
+                    })
+                    .collect::<Vec<_>>();
+                let mut types = values.iter().map(|v| literal_type(v)).collect::<Vec<_>>();
+                types.sort();
+                types.dedup();
+                json!({
+                    "type": match types.len() {
+                        1 => json!(types[0]),
+                        _ => json!(types),
+                    },
+                    "enum": values,
+                })
+            }
             FieldType::Union(options) => json!({
                 "anyOf": options.iter().map(|t| {
                     let mut res = t.json_schema();
@@ -209,11 +254,20 @@ impl<'db> WithJsonSchema for FieldType {
     }
 }
 
+/// The JSON Schema type of `value`.
+fn literal_type(value: &LiteralValue) -> &'static str {
+    match value {
+        LiteralValue::String(_) => "string",
+        LiteralValue::Int(_) => "integer",
+        LiteralValue::Bool(_) => "boolean",
+    }
+}
+
 // Impl display for FieldType
 impl std::fmt::Display for FieldType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            FieldType::Enum(name) | FieldType::Class(name) => {
+            FieldType::Enum(name) | FieldType::Class(name) | FieldType::TypeAlias(name) => {
                 write!(f, "{}", name)
             }
             FieldType::Primitive(t) => match t {
@@ -224,6 +278,7 @@ impl std::fmt::Display for FieldType {
                 TypeValue::Bool => write!(f, "bool"),
                 TypeValue::Null => write!(f, "null"),
             },
+            FieldType::Literal(value) => write!(f, "{}", value),
             FieldType::Union(choices) => {
                 write!(
                     f,
@@ -6,17 +6,22 @@ mod walker;
 pub use generate::to_ir;
 use internal_baml_schema_ast::ast;
 pub(super) use json_schema::WithJsonSchema;
-pub(super) use repr::IntermediateRepr;
+pub use repr::{IntermediateRepr, NodeAttributes};
 
 // Add aliases for the IR types
-pub(super) type Enum = repr::Node<repr::Enum>;
-pub(super) type Class = repr::Node<repr::Class>;
-pub(super) type FieldType = repr::FieldType;
-pub(super) type Expression = repr::Expression;
+pub type Enum = repr::Node<repr::Enum>;
+pub type Class = repr::Node<repr::Class>;
+pub type TypeAlias = repr::Node<repr::TypeAlias>;
+pub type FieldType = repr::FieldType;
+pub type Expression = repr::Expression;
 pub(super) type Identifier = repr::Identifier;
-pub(super) type TypeValue = ast::TypeValue;
+pub type TypeValue = ast::TypeValue;
+pub type LiteralValue = ast::LiteralValue;
 pub(super) type Function = repr::Node<repr::Function>;
-pub(super) type FunctionArgs = repr::FunctionArgs;
+pub(crate) type FunctionArgs = repr::FunctionArgs;
 pub(super) type Impl = repr::Node<repr::Implementation>;
 pub(super) type Client = repr::Node<repr::Client>;
-pub(super) type Walker<'db, I> = repr::Walker<'db, I>;
+pub(super) type RetryPolicy = repr::Node<repr::RetryPolicy>;
+pub(super) type TestCase = repr::Node<repr::TestCase>;
+pub(super) type Prompt = repr::Prompt;
+pub type Walker<'db, I> = repr::Walker<'db, I>;
@@ -5,9 +5,10 @@ use indexmap::IndexMap;
 use internal_baml_parser_database::{
     walkers::{
         ClassWalker, ClientWalker, ConfigurationWalker, EnumValueWalker, EnumWalker, FieldWalker,
-        FunctionWalker, VariantWalker,
+        FunctionWalker, TypeAliasWalker, VariantWalker,
     },
-    ParserDatabase, RetryPolicyStrategy, ToStringAttributes, WithStaticRenames,
+    ClientStrategyKind, ParserDatabase, PromptRepr, RetryPolicyStrategy, ToStringAttributes,
+    WithStaticRenames,
 };
 use internal_baml_schema_ast::ast::{self, FieldArity, WithName};
 
@@ -19,9 +20,11 @@ use internal_baml_schema_ast::ast::{self, FieldArity, WithName};
 pub struct IntermediateRepr {
     enums: Vec<Node<Enum>>,
     classes: Vec<Node<Class>>,
+    type_aliases: Vec<Node<TypeAlias>>,
     functions: Vec<Node<Function>>,
     clients: Vec<Node<Client>>,
     retry_policies: Vec<Node<RetryPolicy>>,
+    test_cases: Vec<Node<TestCase>>,
 }
 
 /// A generic walker. Only walkers instantiated with a concrete ID type (`I`) are useful.
@@ -42,6 +45,14 @@ impl IntermediateRepr {
         self.classes.iter().map(|e| Walker { db: self, item: e })
     }
 
+    pub fn walk_type_aliases<'a>(
+        &'a self,
+    ) -> impl Iterator<Item = Walker<'a, &'a Node<TypeAlias>>> {
+        self.type_aliases
+            .iter()
+            .map(|e| Walker { db: self, item: e })
+    }
+
     pub fn walk_functions<'a>(&'a self) -> impl Iterator<Item = Walker<'a, &'a Node<Function>>> {
         self.functions.iter().map(|e| Walker { db: self, item: e })
     }
@@ -58,6 +69,39 @@ impl IntermediateRepr {
             .map(|e| Walker { db: self, item: e })
     }
 
+    pub fn walk_test_cases<'a>(&'a self) -> impl Iterator<Item = Walker<'a, &'a Node<TestCase>>> {
+        self.test_cases.iter().map(|e| Walker { db: self, item: e })
+    }
+
+    pub fn find_enum<'a>(&'a self, name: &str) -> Option<Walker<'a, &'a Node<Enum>>> {
+        self.walk_enums().find(|e| e.elem().name == name)
+    }
+
+    pub fn find_class<'a>(&'a self, name: &str) -> Option<Walker<'a, &'a Node<Class>>> {
+        self.walk_classes().find(|c| c.elem().name == name)
+    }
+
+    pub fn find_type_alias<'a>(&'a self, name: &str) -> Option<Walker<'a, &'a Node<TypeAlias>>> {
+        self.walk_type_aliases().find(|a| a.elem().name == name)
+    }
+
+    /// The type the alias named `name` stands for. Every alias a `FieldType::TypeAlias` names is
+    /// part of the IR.
+    pub fn alias_target(&self, name: &str) -> &FieldType {
+        match self.find_type_alias(name) {
+            Some(alias) => &alias.elem().target,
+            None => panic!("Unknown type alias `{}`", name),
+        }
+    }
+
+    pub fn find_function<'a>(&'a self, name: &str) -> Option<Walker<'a, &'a Node<Function>>> {
+        self.walk_functions().find(|f| f.elem().name == name)
+    }
+
+    pub fn find_client<'a>(&'a self, name: &str) -> Option<Walker<'a, &'a Node<Client>>> {
+        self.walk_clients().find(|c| c.elem().name == name)
+    }
+
     pub fn from_parser_database(db: &ParserDatabase) -> Result<IntermediateRepr> {
         let mut repr = IntermediateRepr {
             enums: db
@@ -68,6 +112,10 @@ impl IntermediateRepr {
                 .walk_classes()
                 .map(|e| e.node(db))
                 .collect::<Result<Vec<_>>>()?,
+            type_aliases: db
+                .walk_type_aliases()
+                .map(|e| e.node(db))
+                .collect::<Result<Vec<_>>>()?,
             functions: db
                 .walk_functions()
                 .map(|e| e.node(db))
@@ -80,15 +128,23 @@ impl IntermediateRepr {
                 .walk_retry_policies()
                 .map(|e| WithRepr::<RetryPolicy>::node(&e, db))
                 .collect::<Result<Vec<_>>>()?,
+            test_cases: db
+                .walk_test_cases()
+                .map(|e| WithRepr::<TestCase>::node(&e, db))
+                .collect::<Result<Vec<_>>>()?,
         };
 
         // Sort each item by name.
         repr.enums.sort_by(|a, b| a.elem.name.cmp(&b.elem.name));
         repr.classes.sort_by(|a, b| a.elem.name.cmp(&b.elem.name));
+        repr.type_aliases
+            .sort_by(|a, b| a.elem.name.cmp(&b.elem.name));
         repr.functions.sort_by(|a, b| a.elem.name.cmp(&b.elem.name));
         repr.clients.sort_by(|a, b| a.elem.name.cmp(&b.elem.name));
         repr.retry_policies
             .sort_by(|a, b| a.elem.name.0.cmp(&b.elem.name.0));
+        repr.test_cases
+            .sort_by(|a, b| a.elem.name.cmp(&b.elem.name));
 
         Ok(repr)
     }
@@ -118,6 +174,7 @@ pub struct NodeAttributes {
     ///
     ///   - @skip becomes ("skip", "")
     ///   - @alias(...) becomes ("alias", ...)
+    ///   - @@discriminator(field) becomes ("discriminator", field)
     ///   - @get(python code) becomes ("get/python", python code)
     #[serde(with = "indexmap::map::serde_seq")]
     meta: IndexMap<String, Expression>,
@@ -134,6 +191,13 @@ impl NodeAttributes {
     pub fn get(&self, key: &str) -> Option<&Expression> {
         self.meta.get(key)
     }
+
+    /// Looks up `key` among the attributes the given implementation overrides for this node.
+    pub fn get_override(&self, function: &str, r#impl: &str, key: &str) -> Option<&Expression> {
+        self.overrides
+            .get(&(function.to_string(), r#impl.to_string()))
+            .and_then(|overrides| overrides.get(key))
+    }
 }
 
 fn to_ir_attributes(
@@ -194,11 +258,16 @@ pub trait WithRepr<T> {
 }
 
 /// FieldType represents the type of either a class field or a function arg.
-#[derive(serde::Serialize)]
+#[derive(serde::Serialize, Clone)]
 pub enum FieldType {
     Primitive(ast::TypeValue),
+    /// A single value of a primitive type, e.g. `"ok"`.
+    Literal(ast::LiteralValue),
     Enum(EnumId),
     Class(ClassId),
+    /// A reference to a type alias. [`IntermediateRepr::alias_target`] gives the type it stands
+    /// for.
+    TypeAlias(TypeAliasId),
     List(Box<FieldType>),
     Map(Box<FieldType>, Box<FieldType>),
     Union(Vec<FieldType>),
@@ -223,11 +292,17 @@ impl WithRepr<FieldType> for ast::FieldType {
                 ast::Identifier::Local(name, _) => match db.find_type(idn) {
                     Some(Either::Left(_class_walker)) => Ok(FieldType::Class(name.clone())),
                     Some(Either::Right(_enum_walker)) => Ok(FieldType::Enum(name.clone())),
-                    None => Err(anyhow!("Field type uses unresolvable local identifier")),
+                    None => match db.find_type_alias(idn) {
+                        Some(_) => Ok(FieldType::TypeAlias(name.clone())),
+                        None => Err(anyhow!("Field type uses unresolvable local identifier")),
+                    },
                 }?,
                 _ => bail!("Field type uses unsupported identifier type"),
             })
             .with_arity(arity),
+            ast::FieldType::Literal(arity, value, _) => {
+                FieldType::Literal(value.clone()).with_arity(arity)
+            }
             ast::FieldType::List(ft, dims, _) => {
                 // NB: potential bug: this hands back a 1D list when dims == 0
                 let mut repr = FieldType::List(Box::new(ft.repr(db)?));
@@ -284,6 +359,16 @@ pub enum Expression {
     Map(Vec<(Expression, Expression)>),
 }
 
+impl Expression {
+    /// The contents of a string or raw string expression.
+    pub fn as_string_value(&self) -> Option<&str> {
+        match self {
+            Expression::String(s) | Expression::RawString(s) => Some(s),
+            _ => None,
+        }
+    }
+}
+
 impl WithRepr<Expression> for ast::Expression {
     fn repr(&self, db: &ParserDatabase) -> Result<Expression> {
         Ok(match self {
@@ -298,9 +383,9 @@ impl WithRepr<Expression> for ast::Expression {
                 ast::Identifier::Local(l, _) => {
                     Ok(Expression::Identifier(Identifier::Local(l.clone())))
                 }
-                ast::Identifier::Ref(r, _) => {
-                    Ok(Expression::Identifier(Identifier::Ref(r.path.clone())))
-                }
+                ast::Identifier::Ref(r, _) => Ok(Expression::Identifier(Identifier::Ref(
+                    r.path.iter().chain([&r.name]).cloned().collect(),
+                ))),
                 ast::Identifier::Primitive(p, _) => {
                     Ok(Expression::Identifier(Identifier::Primitive(*p)))
                 }
@@ -440,6 +525,12 @@ impl WithRepr<Class> for ClassWalker<'_> {
         let mut attributes = NodeAttributes::default();
 
         attributes.meta = to_ir_attributes(db, self.get_default_attributes());
+        if let Some(field) = self.discriminator() {
+            attributes.meta.insert(
+                "discriminator".to_string(),
+                Expression::String(field.name().to_string()),
+            );
+        }
 
         for r#fn in db.walk_functions() {
             for r#impl in r#fn.walk_variants() {
@@ -472,6 +563,23 @@ impl WithRepr<Class> for ClassWalker<'_> {
     }
 }
 
+type TypeAliasId = String;
+
+#[derive(serde::Serialize)]
+pub struct TypeAlias {
+    pub name: TypeAliasId,
+    pub target: FieldType,
+}
+
+impl WithRepr<TypeAlias> for TypeAliasWalker<'_> {
+    fn repr(&self, db: &ParserDatabase) -> Result<TypeAlias> {
+        Ok(TypeAlias {
+            name: self.name().to_string(),
+            target: self.target().repr(db)?,
+        })
+    }
+}
+
 #[derive(serde::Serialize)]
 pub enum OracleType {
     LLM,
@@ -492,9 +600,49 @@ pub struct Implementation {
     #[serde(with = "indexmap::map::serde_seq")]
     pub output_replacers: IndexMap<String, String>,
 
+    /// The prompt with all replacers applied, split into messages for chat prompts.
+    pub rendered_prompt: Prompt,
+
+    pub input_adapter: Option<InputAdapter>,
+
+    pub output_adapter: Option<OutputAdapter>,
+
     pub client: ClientId,
 }
 
+#[derive(serde::Serialize)]
+pub enum Prompt {
+    /// A single string prompt, along with the input replacers it uses.
+    String(String, Vec<String>),
+    /// A prompt made of several messages, along with the input replacers it uses.
+    Chat(Vec<ChatMessage>, Vec<String>),
+}
+
+#[derive(serde::Serialize)]
+pub struct ChatMessage {
+    /// `None` for any text before the first `{#chat(..)}` block.
+    pub role: Option<String>,
+    pub content: String,
+}
+
+/// Converts the function's input into what the prompt uses (`to`).
+#[derive(serde::Serialize)]
+pub struct InputAdapter {
+    pub to: FieldType,
+    /// The adapter's code, keyed by language.
+    #[serde(with = "indexmap::map::serde_seq")]
+    pub code: IndexMap<String, String>,
+}
+
+/// Converts what the LLM returns (`from`) into the function's output.
+#[derive(serde::Serialize)]
+pub struct OutputAdapter {
+    pub from: FieldType,
+    /// The adapter's code, keyed by language.
+    #[serde(with = "indexmap::map::serde_seq")]
+    pub code: IndexMap<String, String>,
+}
+
 /// BAML does not allow UnnamedArgList nor a lone NamedArg
 #[derive(serde::Serialize)]
 pub enum FunctionArgs {
@@ -518,7 +666,7 @@ impl WithRepr<Implementation> for VariantWalker<'_> {
         NodeAttributes::default()
     }
 
-    fn repr(&self, _db: &ParserDatabase) -> Result<Implementation> {
+    fn repr(&self, db: &ParserDatabase) -> Result<Implementation> {
         Ok(Implementation {
             r#type: OracleType::LLM,
             name: self.name().to_string(),
@@ -539,11 +687,49 @@ impl WithRepr<Implementation> for VariantWalker<'_> {
                 .iter()
                 .map(|r| (r.0.key(), r.1.clone()))
                 .collect(),
+            rendered_prompt: match self.to_prompt() {
+                PromptRepr::String(content, used_inputs) => Prompt::String(content, used_inputs),
+                PromptRepr::Chat(parts, used_inputs) => Prompt::Chat(
+                    parts
+                        .into_iter()
+                        .map(|(chat, content)| ChatMessage {
+                            role: chat.map(|c| c.role.0.clone()),
+                            content,
+                        })
+                        .collect(),
+                    used_inputs,
+                ),
+            },
+            input_adapter: match &self.properties().input_adapter {
+                Some((idx, code)) => Some(InputAdapter {
+                    to: self.ast_variant()[*idx].to.repr(db)?,
+                    code: adapter_code(code),
+                }),
+                None => None,
+            },
+            output_adapter: match &self.properties().output_adapter {
+                Some((idx, code)) => Some(OutputAdapter {
+                    from: self.ast_variant()[*idx].from.repr(db)?,
+                    code: adapter_code(code),
+                }),
+                None => None,
+            },
             client: self.properties().client.value.clone(),
         })
     }
 }
 
+/// The code of an adapter, keyed by language. Code without a language is left out.
+fn adapter_code(code: &[ast::RawString]) -> IndexMap<String, String> {
+    code.iter()
+        .filter_map(|raw| {
+            raw.language
+                .as_ref()
+                .map(|(lang, _)| (lang.clone(), raw.value().to_string()))
+        })
+        .collect()
+}
+
 impl WithRepr<Function> for FunctionWalker<'_> {
     fn repr(&self, db: &ParserDatabase) -> Result<Function> {
         Ok(Function {
@@ -579,9 +765,18 @@ type ClientId = String;
 pub struct Client {
     pub name: ClientId,
     pub provider: String,
+    pub retry_policy: Option<String>,
+    /// Set for composite clients, which delegate to other clients.
+    pub strategy: Option<ClientStrategy>,
     pub options: Vec<(String, Expression)>,
 }
 
+#[derive(serde::Serialize)]
+pub struct ClientStrategy {
+    pub kind: ClientStrategyKind,
+    pub clients: Vec<ClientId>,
+}
+
 impl WithRepr<Client> for ClientWalker<'_> {
     fn attributes(&self, _: &ParserDatabase) -> NodeAttributes {
         NodeAttributes::default()
@@ -591,6 +786,23 @@ impl WithRepr<Client> for ClientWalker<'_> {
         Ok(Client {
             name: self.name().to_string(),
             provider: self.properties().provider.0.clone(),
+            retry_policy: self
+                .properties()
+                .retry_policy
+                .as_ref()
+                .map(|(policy, _)| policy.clone()),
+            strategy: self
+                .properties()
+                .strategy
+                .as_ref()
+                .map(|strategy| ClientStrategy {
+                    kind: strategy.kind,
+                    clients: strategy
+                        .clients
+                        .iter()
+                        .map(|(name, _)| name.clone())
+                        .collect(),
+                }),
             options: self
                 .properties()
                 .options
@@ -602,16 +814,16 @@ impl WithRepr<Client> for ClientWalker<'_> {
 }
 
 #[derive(serde::Serialize)]
-pub struct RetryPolicyId(String);
+pub struct RetryPolicyId(pub String);
 
 #[derive(serde::Serialize)]
 pub struct RetryPolicy {
-    name: RetryPolicyId,
-    max_retries: u32,
-    strategy: RetryPolicyStrategy,
+    pub name: RetryPolicyId,
+    pub max_retries: u32,
+    pub strategy: RetryPolicyStrategy,
     // NB: the parser DB has a notion of "empty options" vs "no options"; we collapse
     // those here into an empty vec
-    options: Vec<(String, Expression)>,
+    pub options: Vec<(String, Expression)>,
 }
 
 impl WithRepr<RetryPolicy> for ConfigurationWalker<'_> {
@@ -634,3 +846,33 @@ impl WithRepr<RetryPolicy> for ConfigurationWalker<'_> {
         })
     }
 }
+
+#[derive(serde::Serialize)]
+pub struct TestCase {
+    pub name: String,
+    pub function: FunctionId,
+    /// The test's input, as JSON.
+    pub content: String,
+    /// The impls to run the test against. Empty means every impl.
+    pub impls: Vec<ImplementationId>,
+}
+
+impl WithRepr<TestCase> for ConfigurationWalker<'_> {
+    fn attributes(&self, _db: &ParserDatabase) -> NodeAttributes {
+        NodeAttributes::default()
+    }
+
+    fn repr(&self, _db: &ParserDatabase) -> Result<TestCase> {
+        Ok(TestCase {
+            name: self.name().to_string(),
+            function: self.test_case().function.0.clone(),
+            content: self.test_case().input.to_json().to_string(),
+            impls: self
+                .test_case()
+                .impls
+                .iter()
+                .map(|(name, _)| name.clone())
+                .collect(),
+        })
+    }
+}
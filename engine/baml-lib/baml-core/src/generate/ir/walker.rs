@@ -1,6 +1,6 @@
 use super::{
     repr::{self},
-    Class, Client, Enum, Function, Impl, Walker,
+    Class, Client, Enum, Function, Impl, RetryPolicy, TestCase, TypeAlias, Walker,
 };
 
 impl<'a> Walker<'a, &'a Function> {
@@ -49,8 +49,30 @@ impl<'a> Walker<'a, &'a Class> {
     }
 }
 
+impl<'a> Walker<'a, &'a TypeAlias> {
+    pub fn elem(&self) -> &'a repr::TypeAlias {
+        &self.item.elem
+    }
+}
+
 impl<'a> Walker<'a, &'a Client> {
     pub fn elem(&self) -> &'a repr::Client {
         &self.item.elem
     }
 }
+
+impl<'a> Walker<'a, &'a RetryPolicy> {
+    pub fn elem(&self) -> &'a repr::RetryPolicy {
+        &self.item.elem
+    }
+}
+
+impl<'a> Walker<'a, &'a TestCase> {
+    pub fn function(&self) -> Option<Walker<'a, &'a Function>> {
+        self.db.find_function(&self.item.elem.function)
+    }
+
+    pub fn elem(&self) -> &'a repr::TestCase {
+        &self.item.elem
+    }
+}
@@ -1,8 +1,10 @@
 use internal_baml_parser_database::ParserDatabase;
-use internal_baml_schema_ast::ast::WithName;
 use serde_json::json;
 
-use crate::generate::generate_python_client::{FileCollector, WithToCode};
+use crate::generate::{
+    generate_python_client::{FileCollector, RequiredTypes, WithToCode},
+    ir::{self, FunctionArgs},
+};
 
 use super::{
     template::{render_template, HSTemplate},
@@ -11,6 +13,7 @@ use super::{
 
 impl TestRequest {
     pub fn generate_python(self, db: &ParserDatabase) -> Result<String, Vec<String>> {
+        let ir = ir::to_ir(db).map_err(|e| vec![format!("Failed to generate IR: {}", e)])?;
         let mut diagnostics = vec![];
         let mut fc = FileCollector::default();
 
@@ -18,50 +21,55 @@ impl TestRequest {
 
         let content = fc.last_file();
         self.functions.iter().for_each(|f| {
-            if let Some(func) = db.find_function_by_name(&f.name) {
-                content.add_import("baml_client.baml_types", &format!("I{}", func.name()));
-                func
-                    .walk_input_args()
-                    .for_each(|a| {
-                        a.required_classes().for_each(|c| {
-                            content.add_import("baml_client.baml_types", &c.name());
-                        });
-                        a.required_enums()
-                            .for_each(|e| content.add_import("baml_client.baml_types", &e.name()));
-                    });
-
+            if let Some(func) = ir.find_function(&f.name) {
+                let name = &func.elem().name;
+                content.add_import("baml_client.baml_types", &format!("I{}", name));
+                let required = RequiredTypes::transitive(&ir, func.input_types());
+                required.classes().for_each(|c| {
+                    content.add_import("baml_client.baml_types", &c.elem().name);
+                });
+                required
+                    .enums()
+                    .for_each(|e| content.add_import("baml_client.baml_types", &e.elem().name));
+                required
+                    .type_aliases()
+                    .for_each(|t| content.add_import("baml_client.baml_types", &t.elem().name));
 
-                f.tests.iter().for_each(|t| {
-                     match &t.params {
-                        super::TestParam::Positional(p) => {
-                            let input_args = func.find_input_arg_by_position(0).map(|a| a.ast_arg().1.to_py_string(content)).unwrap_or("str".into());
-                            render_template(
-                                HSTemplate::SingleArgTestSnippet,
-                                content,
-                                json!({
-                                    "function_name": func.name(),
-                                    "test_case_name": t.name,
-                                    "test_case_input": p,
-                                    "test_case_type": input_args,
-                                }),
-                            );
-                        }
-                        super::TestParam::Keyword(args) => {
-                            let data = json!({
-                                "function_name": func.name(),
+                f.tests.iter().for_each(|t| match &t.params {
+                    super::TestParam::Positional(p) => {
+                        let input_args = match &func.elem().inputs {
+                            FunctionArgs::UnnamedArg(arg) => arg.to_py_string(content),
+                            FunctionArgs::NamedArgList(_) => "str".into(),
+                        };
+                        render_template(
+                            HSTemplate::SingleArgTestSnippet,
+                            content,
+                            json!({
+                                "function_name": name,
                                 "test_case_name": t.name,
-                                "test_case_input": args.iter().map(|(k, v)| json!({ 
-                                    "name": k, "value": v,
-                                    "type": func.find_input_arg_by_name(k).map(|a| a.ast_arg().1.to_py_string(content)).unwrap_or("str".into()),
-                                 })).collect::<Vec<_>>(),
-                            });
-                            render_template(
-                                HSTemplate::LiveMultiArgTestSnippet,
-                                content,
-                                data,
-                            );
-                        }
-                     }
+                                "test_case_input": p,
+                                "test_case_type": input_args,
+                            }),
+                        );
+                    }
+                    super::TestParam::Keyword(args) => {
+                        let data = json!({
+                            "function_name": name,
+                            "test_case_name": t.name,
+                            "test_case_input": args.iter().map(|(k, v)| json!({
+                                "name": k, "value": v,
+                                "type": match &func.elem().inputs {
+                                    FunctionArgs::NamedArgList(inputs) => inputs
+                                        .iter()
+                                        .find(|(name, _)| name == k)
+                                        .map(|(_, arg)| arg.to_py_string(content))
+                                        .unwrap_or("str".into()),
+                                    FunctionArgs::UnnamedArg(_) => "str".into(),
+                                },
+                             })).collect::<Vec<_>>(),
+                        });
+                        render_template(HSTemplate::LiveMultiArgTestSnippet, content, data);
+                    }
                 });
             } else {
                 diagnostics.push(format!("Function {} not found", f.name));
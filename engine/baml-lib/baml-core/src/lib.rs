@@ -17,16 +17,17 @@ mod common;
 mod configuration;
 mod generate;
 mod lockfile;
+pub mod tokenizer;
 mod validate;
 
 use self::validate::generator_loader;
 
 pub use crate::{
     common::{PreviewFeature, PreviewFeatures, ALL_PREVIEW_FEATURES},
-    configuration::Configuration,
+    configuration::{Configuration, GeneratorLanguage},
 };
 
-pub use generate::TestRequest;
+pub use generate::{ir, TestRequest};
 pub use lockfile::LockFileWrapper;
 
 pub struct ValidatedSchema {
@@ -88,7 +89,7 @@ pub fn validate(root_path: &PathBuf, files: Vec<SourceFile>) -> ValidatedSchema
     }
 
     // actually run the validation pipeline
-    validate::validate(&db, configuration.preview_features(), &mut diagnostics);
+    validate::validate(&db, &configuration, &mut diagnostics);
 
     if diagnostics.has_errors() {
         return ValidatedSchema { db, diagnostics };
@@ -97,6 +98,11 @@ pub fn validate(root_path: &PathBuf, files: Vec<SourceFile>) -> ValidatedSchema
     // Some last linker stuff can only happen post validation.
     db.finalize(&mut diagnostics);
 
+    // Token counts need the finalized prompts.
+    if !diagnostics.has_errors() {
+        validate::context_window::validate(&db, &mut diagnostics);
+    }
+
     ValidatedSchema { db, diagnostics }
 }
 
@@ -0,0 +1,284 @@
+//! Estimates how many tokens the prompt of an impl takes up, so we can tell when it leaves little
+//! room in the model's context window.
+//!
+//! The BPE tables of OpenAI's encodings are bundled (through `tiktoken-rs`), so counting works
+//! offline. Anthropic doesn't publish the tokenizer of its current models, so their prompts are
+//! counted with `cl100k_base`, which is close but not exact.
+
+use internal_baml_parser_database::{
+    split_prompt_blocks,
+    walkers::{ClientWalker, VariantWalker},
+    PromptPart, PromptRepr,
+};
+use tiktoken_rs::{
+    cl100k_base_singleton, o200k_base_singleton, p50k_base_singleton, r50k_base_singleton,
+    tokenizer::{get_tokenizer, Tokenizer},
+};
+
+/// Chat APIs wrap every message in a few tokens for its role and delimiters.
+const TOKENS_PER_MESSAGE: usize = 4;
+/// Chat APIs prime the reply of the assistant with a few tokens.
+const TOKENS_PER_REPLY: usize = 3;
+/// Composite clients can delegate to other composite clients; this bounds how far we follow them.
+const MAX_CLIENT_DEPTH: usize = 8;
+
+/// A BPE encoding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Encoding {
+    /// GPT-4o.
+    O200kBase,
+    /// GPT-4 and GPT-3.5, and our estimate for Anthropic's models.
+    Cl100kBase,
+    /// Codex and `text-davinci-00[23]`.
+    P50kBase,
+    /// GPT-3.
+    R50kBase,
+}
+
+impl Encoding {
+    /// The name of the encoding, e.g. `cl100k_base`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Encoding::O200kBase => "o200k_base",
+            Encoding::Cl100kBase => "cl100k_base",
+            Encoding::P50kBase => "p50k_base",
+            Encoding::R50kBase => "r50k_base",
+        }
+    }
+
+    /// The number of tokens `text` encodes to.
+    pub fn count(self, text: &str) -> usize {
+        let bpe = match self {
+            Encoding::O200kBase => o200k_base_singleton(),
+            Encoding::Cl100kBase => cl100k_base_singleton(),
+            Encoding::P50kBase => p50k_base_singleton(),
+            Encoding::R50kBase => r50k_base_singleton(),
+        };
+        let bpe = bpe.lock();
+        bpe.encode_ordinary(text).len()
+    }
+}
+
+/// What we know about a model.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ModelInfo {
+    /// The encoding its prompts are counted with.
+    pub encoding: Encoding,
+    /// How many tokens the prompt and the response can take up together.
+    pub context_size: usize,
+    /// Whether `encoding` is the one the model uses, rather than an estimate.
+    pub exact: bool,
+}
+
+// Longest prefix first.
+const OPENAI_CONTEXT_SIZES: &[(&str, usize)] = &[
+    ("gpt-4o", 128_000),
+    ("gpt-4-turbo", 128_000),
+    ("gpt-4-0125", 128_000),
+    ("gpt-4-1106", 128_000),
+    ("gpt-4-vision", 128_000),
+    ("gpt-4-32k", 32_768),
+    ("gpt-4", 8_192),
+    ("gpt-3.5-turbo-instruct", 4_096),
+    ("gpt-3.5-turbo", 16_385),
+    ("davinci-002", 16_384),
+    ("babbage-002", 16_384),
+    ("text-davinci-00", 4_097),
+    ("code-davinci-002", 8_001),
+];
+
+const ANTHROPIC_CONTEXT_SIZES: &[(&str, usize)] = &[
+    ("claude-3", 200_000),
+    ("claude-2.1", 200_000),
+    ("claude-2", 100_000),
+    ("claude-instant", 100_000),
+];
+
+/// What we know about `model` of `provider`, if anything.
+pub fn model_info(provider: &str, model: &str) -> Option<ModelInfo> {
+    let context_size = |sizes: &[(&str, usize)]| {
+        sizes
+            .iter()
+            .find(|(prefix, _)| model.starts_with(prefix))
+            .map(|(_, size)| *size)
+    };
+
+    if provider.contains("anthropic") {
+        return Some(ModelInfo {
+            encoding: Encoding::Cl100kBase,
+            context_size: context_size(ANTHROPIC_CONTEXT_SIZES)?,
+            exact: false,
+        });
+    }
+    if !provider.contains("openai") && !provider.contains("azure") {
+        return None;
+    }
+    let encoding = match get_tokenizer(model)? {
+        Tokenizer::O200kBase => Encoding::O200kBase,
+        Tokenizer::Cl100kBase => Encoding::Cl100kBase,
+        Tokenizer::P50kBase | Tokenizer::P50kEdit => Encoding::P50kBase,
+        Tokenizer::R50kBase | Tokenizer::Gpt2 => Encoding::R50kBase,
+    };
+    Some(ModelInfo {
+        encoding,
+        context_size: context_size(OPENAI_CONTEXT_SIZES)?,
+        exact: true,
+    })
+}
+
+/// How many tokens the prompt of an impl takes up before its inputs are filled in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PromptTokens {
+    /// The encoding the tokens were counted with.
+    pub encoding: Encoding,
+    /// The text of the prompt, including the output schema but not the inputs. Every branch of
+    /// an `{#if}` block counts, and the body of a `{#for}` block counts once.
+    pub prompt: usize,
+    /// The part of `prompt` the output of `{#print_type(..)}` and `{#print_enum(..)}` takes up.
+    pub output_schema: usize,
+    /// The model of the impl's client, if the client says.
+    pub model: Option<String>,
+    /// The context window of the model, if it's one we know.
+    pub context_size: Option<usize>,
+    /// The tokens the client's `max_tokens` option reserves for the response.
+    pub max_tokens: Option<usize>,
+    /// Whether `encoding` is the one the model uses, rather than an estimate.
+    pub exact: bool,
+}
+
+impl PromptTokens {
+    /// The tokens of the context window left for the inputs, once the prompt and `max_tokens`
+    /// are taken out. Negative if the prompt alone doesn't fit.
+    pub fn remaining(&self) -> Option<i64> {
+        self.context_size.map(|size| {
+            size as i64 - self.max_tokens.unwrap_or_default() as i64 - self.prompt as i64
+        })
+    }
+}
+
+/// The model of the client of an impl, and how much of it the client uses.
+pub(crate) struct ClientModel {
+    pub(crate) model: Option<String>,
+    pub(crate) max_tokens: Option<usize>,
+    pub(crate) info: Option<ModelInfo>,
+}
+
+impl ClientModel {
+    pub(crate) fn of(variant: VariantWalker<'_>) -> Self {
+        let Some(client) = variant.client().and_then(model_client) else {
+            return ClientModel {
+                model: None,
+                max_tokens: None,
+                info: None,
+            };
+        };
+        let option = |keys: &[&str]| {
+            client
+                .properties()
+                .options
+                .iter()
+                .find(|(key, _)| keys.contains(&key.as_str()))
+                .map(|(_, value)| value)
+        };
+        let model = option(&["model"])
+            .and_then(|value| value.as_path_value())
+            .map(|(model, _)| model.to_string());
+        let max_tokens = option(&[
+            "max_tokens",
+            "maxTokens",
+            "max_tokens_to_sample",
+            "maxTokensToSample",
+        ])
+        .and_then(|value| value.as_numeric_value())
+        .and_then(|(num, _)| num.parse().ok());
+        let info = model
+            .as_deref()
+            .and_then(|model| model_info(&client.properties().provider.0, model));
+        ClientModel {
+            model,
+            max_tokens,
+            info,
+        }
+    }
+}
+
+/// Counts the tokens of the prompt of `variant`, with the encoding of its client's model. Prompts
+/// of unknown models are counted with `cl100k_base`.
+pub fn prompt_tokens(variant: VariantWalker<'_>) -> PromptTokens {
+    let ClientModel {
+        model,
+        max_tokens,
+        info,
+    } = ClientModel::of(variant);
+    let encoding = info.map_or(Encoding::Cl100kBase, |info| info.encoding);
+
+    let properties = variant.properties();
+    let placeholders = properties
+        .replacers
+        .0
+        .values()
+        .map(|path| format!("{{{}}}", path))
+        .collect::<Vec<_>>();
+    let count = |text: &str| {
+        let mut static_text = String::new();
+        block_text(&split_prompt_blocks(text), &mut static_text);
+        let static_text = placeholders.iter().fold(static_text, |text, placeholder| {
+            text.replace(placeholder, "")
+        });
+        encoding.count(&static_text)
+    };
+    let prompt = match variant.to_prompt() {
+        PromptRepr::String(prompt, _) => count(&prompt),
+        PromptRepr::Chat(parts, _) => {
+            parts
+                .iter()
+                .map(|(_, content)| count(content) + TOKENS_PER_MESSAGE)
+                .sum::<usize>()
+                + TOKENS_PER_REPLY
+        }
+    };
+    let output_schema = properties
+        .replacers
+        .1
+        .values()
+        .map(|schema| encoding.count(schema))
+        .sum();
+
+    PromptTokens {
+        encoding,
+        prompt,
+        output_schema,
+        model,
+        context_size: info.map(|info| info.context_size),
+        max_tokens,
+        exact: info.is_some_and(|info| info.exact),
+    }
+}
+
+/// The client that sends the request to the model: the first client of a composite client.
+fn model_client(client: ClientWalker<'_>) -> Option<ClientWalker<'_>> {
+    let mut client = client;
+    for _ in 0..MAX_CLIENT_DEPTH {
+        match &client.properties().strategy {
+            Some(strategy) => client = client.db.find_client(&strategy.clients.first()?.0)?,
+            None => return Some(client),
+        }
+    }
+    None
+}
+
+/// The text of every part of a prompt, without its block markers.
+fn block_text(parts: &[PromptPart<'_>], text: &mut String) {
+    for part in parts {
+        match part {
+            PromptPart::Text(part) => text.push_str(part),
+            PromptPart::If {
+                then, otherwise, ..
+            } => {
+                block_text(then, text);
+                block_text(otherwise, text);
+            }
+            PromptPart::For { body, .. } => block_text(body, text),
+        }
+    }
+}
@@ -1,3 +1,4 @@
+pub(crate) mod context_window;
 pub(crate) mod generator_loader;
 
 mod validation_pipeline;
@@ -0,0 +1,35 @@
+use internal_baml_diagnostics::{DatamodelWarning, Diagnostics};
+use internal_baml_parser_database::ParserDatabase;
+
+use crate::tokenizer::{prompt_tokens, ClientModel};
+
+/// Prompts that take up more than this share of the context window left after `max_tokens` are
+/// reported, as their inputs need the rest.
+const WARNING_RATIO: f64 = 0.8;
+
+/// Warns about impls whose prompt, before its inputs are added, takes up most of the context
+/// window of its model. Only runs once the prompts are finalized.
+pub(crate) fn validate(db: &ParserDatabase, diagnostics: &mut Diagnostics) {
+    for variant in db.walk_variants() {
+        // Counting is only worth it for models we know the context window of.
+        if ClientModel::of(variant).info.is_none() {
+            continue;
+        }
+        let tokens = prompt_tokens(variant);
+        let (Some(model), Some(context_size)) = (&tokens.model, tokens.context_size) else {
+            continue;
+        };
+        let available = context_size.saturating_sub(tokens.max_tokens.unwrap_or_default());
+        if (tokens.prompt as f64) < available as f64 * WARNING_RATIO {
+            continue;
+        }
+        diagnostics.push_warning(DatamodelWarning::prompt_near_context_limit(
+            tokens.prompt,
+            tokens.output_schema,
+            model,
+            context_size,
+            tokens.max_tokens,
+            variant.properties().prompt.key_span.clone(),
+        ));
+    }
+}
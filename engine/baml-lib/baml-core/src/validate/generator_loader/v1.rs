@@ -102,6 +102,7 @@ pub(crate) fn parse_generator(
     let language = match parse_required_key(&args, "language", ast_generator.span()) {
         Ok("python") => Some(GeneratorLanguage::Python),
         Ok("typescript") => Some(GeneratorLanguage::TypeScript),
+        Ok("rust") => Some(GeneratorLanguage::Rust),
         Ok(name) => {
             errors.push(DatamodelError::new_validation_error(
                 &format!("The language '{}' is not supported.", name),
@@ -160,15 +161,29 @@ pub(crate) fn parse_generator(
     let install_command = install_command.unwrap();
     let package_version_command = package_version_command.unwrap();
 
+    let project_root = ast_generator
+        .span()
+        .file
+        .path_buf()
+        .parent()
+        .unwrap()
+        .join(project_root);
+
+    // There is no filesystem to check against in the playground.
+    #[cfg(not(target_arch = "wasm32"))]
+    if matches!(language, GeneratorLanguage::Rust) && !project_root.join("Cargo.toml").exists() {
+        return Err(vec![DatamodelError::new_validation_error(
+            &format!(
+                "The project_root of a rust generator must contain a Cargo.toml: {}",
+                project_root.display()
+            ),
+            ast_generator.span().clone(),
+        )]);
+    }
+
     Generator::new(
         generator_name.to_string(),
-        ast_generator
-            .span()
-            .file
-            .path_buf()
-            .parent()
-            .unwrap()
-            .join(project_root),
+        project_root,
         language,
         test_command.into(),
         install_command.into(),
@@ -1,21 +1,25 @@
 mod context;
 mod validations;
 
-use crate::{internal_baml_diagnostics::Diagnostics, PreviewFeature};
-use enumflags2::BitFlags;
+use crate::{internal_baml_diagnostics::Diagnostics, Configuration};
 use internal_baml_parser_database::ParserDatabase;
 
 /// Validate a Prisma schema.
 pub(crate) fn validate(
     db: &ParserDatabase,
-    preview_features: BitFlags<PreviewFeature>,
+    configuration: &Configuration,
     mut diagnostics: &mut Diagnostics,
 ) {
     // Early return so that the validator does not have to deal with invalid schemas
 
     let mut context = context::Context {
         db: &db,
-        preview_features,
+        preview_features: configuration.preview_features(),
+        generators: configuration
+            .generators
+            .iter()
+            .map(|(generator, _)| generator)
+            .collect(),
         diagnostics: &mut diagnostics,
     };
 
@@ -1,4 +1,4 @@
-use crate::PreviewFeature;
+use crate::{configuration::Generator, PreviewFeature};
 use enumflags2::BitFlags;
 use internal_baml_diagnostics::{DatamodelError, DatamodelWarning, Diagnostics};
 
@@ -9,6 +9,8 @@ pub(crate) struct Context<'a> {
     pub(super) db: &'a internal_baml_parser_database::ParserDatabase,
     #[allow(dead_code)]
     pub(super) preview_features: BitFlags<PreviewFeature>,
+    /// The generators of the project, which decide the languages adapters need code for.
+    pub(super) generators: Vec<&'a Generator>,
     pub(super) diagnostics: &'a mut Diagnostics,
 }
 
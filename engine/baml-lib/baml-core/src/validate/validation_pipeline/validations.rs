@@ -5,6 +5,9 @@ mod configurations;
 mod cycle;
 mod enums;
 mod functions;
+mod prompt_variables;
+mod test_inputs;
+mod type_aliases;
 mod variants;
 
 use super::context::Context;
@@ -12,6 +15,7 @@ use super::context::Context;
 pub(super) fn validate(ctx: &mut Context<'_>) {
     enums::validate(ctx);
     classes::validate(ctx);
+    type_aliases::validate(ctx);
     variants::validate(ctx);
     functions::validate(ctx);
     clients::validate(ctx);
@@ -20,4 +24,10 @@ pub(super) fn validate(ctx: &mut Context<'_>) {
     if !ctx.diagnostics.has_errors() {
         cycle::validate(ctx);
     }
+
+    // Type-checking prompt variables and test inputs needs the IR, which can only be built from a valid schema.
+    if !ctx.diagnostics.has_errors() {
+        prompt_variables::validate(ctx);
+        test_inputs::validate(ctx);
+    }
 }
@@ -1,3 +1,9 @@
+use either::Either;
+use internal_baml_diagnostics::DatamodelError;
+use internal_baml_schema_ast::ast::{
+    FieldType, Identifier, LiteralValue, TypeValue, WithName, WithSpan,
+};
+
 use crate::validate::validation_pipeline::context::Context;
 
 use super::common::validate_type_exists;
@@ -14,5 +20,27 @@ pub(super) fn validate(ctx: &mut Context<'_>) {
             let field = c.ast_field();
             validate_type_exists(ctx, &field.field_type);
         }
+
+        // The value of the discriminator names the class, so it has to be a string.
+        if let Some(field) = cls.discriminator() {
+            let is_string = match &field.ast_field().field_type {
+                FieldType::Identifier(_, Identifier::Primitive(TypeValue::String, _))
+                | FieldType::Literal(_, LiteralValue::String(_), _) => true,
+                FieldType::Identifier(_, idn @ Identifier::Local(..)) => {
+                    matches!(ctx.db.find_type(idn), Some(Either::Right(_)))
+                }
+                _ => false,
+            };
+            if !is_string {
+                ctx.push_error(DatamodelError::new_validation_error(
+                    &format!(
+                        "The discriminator `{}` of `{}` must be a string or an enum.",
+                        field.name(),
+                        cls.name()
+                    ),
+                    field.ast_field().span().clone(),
+                ));
+            }
+        }
     }
 }
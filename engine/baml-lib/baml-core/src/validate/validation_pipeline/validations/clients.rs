@@ -1,5 +1,10 @@
-use internal_baml_diagnostics::{DatamodelError, DatamodelWarning};
+use std::collections::HashSet;
 
+use internal_baml_diagnostics::{DatamodelError, DatamodelWarning};
+use internal_baml_parser_database::walkers::ClientWalker;
+use internal_baml_schema_ast::ast::{
+    ClientId, Expression, Identifier, WithIdentifier, WithName, WithSpan,
+};
 
 use crate::validate::validation_pipeline::context::Context;
 
@@ -9,7 +14,7 @@ pub(super) fn validate(ctx: &mut Context<'_>) {
         let (provider, span) = &f.properties().provider;
         if !provider.starts_with("baml") {
             ctx.push_warning(DatamodelWarning::new(
-                "Baml Providers are: baml-openai-[chat,completion] or baml-anthropic.".into(),
+                "Baml Providers are: baml-openai-[chat,completion], baml-anthropic, baml-fallback or baml-round-robin.".into(),
                 span.clone(),
             ))
         }
@@ -23,5 +28,342 @@ pub(super) fn validate(ctx: &mut Context<'_>) {
                 ))
             }
         }
-    })
+
+        validate_options(ctx, &f);
+
+        if let Some(strategy) = &f.properties().strategy {
+            for (client, span) in &strategy.clients {
+                if ctx.db.find_client(client).is_none() {
+                    ctx.push_error(DatamodelError::new_type_not_found_error(
+                        client,
+                        ctx.db.valid_client_names(),
+                        span.clone(),
+                    ))
+                }
+            }
+            for (retry_policy, span) in &strategy.retry_policies {
+                if ctx.db.find_retry_policy(retry_policy).is_none() {
+                    ctx.push_error(DatamodelError::new_type_not_found_error(
+                        retry_policy,
+                        ctx.db.valid_retry_policy_names(),
+                        span.clone(),
+                    ))
+                }
+            }
+        }
+    });
+
+    if !ctx.diagnostics.has_errors() {
+        validate_strategy_cycles(ctx);
+    }
+}
+
+/// The type of value a client option takes.
+#[derive(Clone, Copy)]
+enum OptionType {
+    String,
+    Int,
+    Float,
+    Bool,
+    List,
+    StringOrList,
+    Map,
+    Any,
+}
+
+impl OptionType {
+    fn description(self) -> &'static str {
+        match self {
+            OptionType::String => "a string",
+            OptionType::Int => "an int",
+            OptionType::Float => "a number",
+            OptionType::Bool => "true or false",
+            OptionType::List => "a list",
+            OptionType::StringOrList => "a string or a list",
+            OptionType::Map => "a map",
+            OptionType::Any => "a value",
+        }
+    }
+
+    fn matches(self, value: &Expression) -> bool {
+        // Environment variables are only known at runtime.
+        if let Expression::Identifier(Identifier::ENV(..)) = value {
+            return true;
+        }
+
+        match self {
+            OptionType::String => value.as_path_value().is_some(),
+            OptionType::Int => value
+                .as_numeric_value()
+                .is_some_and(|(num, _)| num.parse::<i64>().is_ok()),
+            OptionType::Float => value
+                .as_numeric_value()
+                .is_some_and(|(num, _)| num.parse::<f64>().is_ok()),
+            OptionType::Bool => value
+                .as_constant_value()
+                .is_some_and(|(val, _)| val.parse::<bool>().is_ok()),
+            OptionType::List => value.as_array().is_some(),
+            OptionType::StringOrList => {
+                value.as_path_value().is_some() || value.as_array().is_some()
+            }
+            OptionType::Map => value.as_map().is_some(),
+            OptionType::Any => true,
+        }
+    }
+}
+
+type OptionSchema = &'static [(&'static str, OptionType)];
+
+/// The options a provider's runtime implementations accept.
+struct ProviderSchema {
+    providers: &'static [&'static str],
+    options: &'static [OptionSchema],
+    /// Each entry lists keys of which at least one must be set.
+    required: &'static [&'static [&'static str]],
+}
+
+// The OpenAI SDK constructor, including the spellings the TypeScript runtime reads.
+const OPENAI_CLIENT_OPTIONS: OptionSchema = &[
+    ("api_key", OptionType::String),
+    ("apiKey", OptionType::String),
+    ("organization", OptionType::String),
+    ("base_url", OptionType::String),
+    ("baseURL", OptionType::String),
+    ("api_base", OptionType::String),
+    ("api_type", OptionType::String),
+    ("api_version", OptionType::String),
+    ("azure_endpoint", OptionType::String),
+    ("deployment_id", OptionType::String),
+    ("timeout", OptionType::Float),
+    ("request_timeout", OptionType::Float),
+    ("max_retries", OptionType::Int),
+    ("maxRetries", OptionType::Int),
+    ("default_headers", OptionType::Map),
+];
+
+const OPENAI_REQUEST_OPTIONS: OptionSchema = &[
+    ("model", OptionType::String),
+    ("engine", OptionType::String),
+    ("temperature", OptionType::Float),
+    ("top_p", OptionType::Float),
+    ("topP", OptionType::Float),
+    ("n", OptionType::Int),
+    ("stop", OptionType::StringOrList),
+    ("max_tokens", OptionType::Int),
+    ("maxTokens", OptionType::Int),
+    ("presence_penalty", OptionType::Float),
+    ("presencePenalty", OptionType::Float),
+    ("frequency_penalty", OptionType::Float),
+    ("frequencyPenalty", OptionType::Float),
+    ("logit_bias", OptionType::Map),
+    ("logitBias", OptionType::Map),
+    ("user", OptionType::String),
+    ("seed", OptionType::Int),
+];
+
+const OPENAI_CHAT_OPTIONS: OptionSchema = &[
+    ("logprobs", OptionType::Bool),
+    ("top_logprobs", OptionType::Int),
+    ("response_format", OptionType::Map),
+    ("responseFormat", OptionType::Map),
+    ("tools", OptionType::List),
+    ("tool_choice", OptionType::Any),
+    ("functions", OptionType::List),
+    ("function_call", OptionType::Any),
+];
+
+const OPENAI_COMPLETION_OPTIONS: OptionSchema = &[
+    ("logprobs", OptionType::Int),
+    ("echo", OptionType::Bool),
+    ("best_of", OptionType::Int),
+    ("suffix", OptionType::String),
+];
+
+const ANTHROPIC_OPTIONS: OptionSchema = &[
+    ("api_key", OptionType::String),
+    ("apiKey", OptionType::String),
+    ("auth_token", OptionType::String),
+    ("base_url", OptionType::String),
+    ("baseURL", OptionType::String),
+    ("timeout", OptionType::Float),
+    ("max_retries", OptionType::Int),
+    ("maxRetries", OptionType::Int),
+    ("default_headers", OptionType::Map),
+    ("model", OptionType::String),
+    ("max_tokens", OptionType::Int),
+    ("max_tokens_to_sample", OptionType::Int),
+    ("maxTokensToSample", OptionType::Int),
+    ("stop_sequences", OptionType::List),
+    ("stopSequences", OptionType::List),
+    ("temperature", OptionType::Float),
+    ("top_k", OptionType::Int),
+    ("topK", OptionType::Int),
+    ("top_p", OptionType::Float),
+    ("topP", OptionType::Float),
+    ("metadata", OptionType::Map),
+    ("system", OptionType::String),
+];
+
+const PROVIDER_SCHEMAS: &[ProviderSchema] = &[
+    ProviderSchema {
+        providers: &["baml-openai-chat"],
+        options: &[
+            OPENAI_CLIENT_OPTIONS,
+            OPENAI_REQUEST_OPTIONS,
+            OPENAI_CHAT_OPTIONS,
+        ],
+        required: &[&["model", "engine"]],
+    },
+    ProviderSchema {
+        providers: &["baml-openai-completion"],
+        options: &[
+            OPENAI_CLIENT_OPTIONS,
+            OPENAI_REQUEST_OPTIONS,
+            OPENAI_COMPLETION_OPTIONS,
+        ],
+        required: &[&["model", "engine"]],
+    },
+    ProviderSchema {
+        providers: &["baml-azure-chat"],
+        options: &[
+            OPENAI_CLIENT_OPTIONS,
+            OPENAI_REQUEST_OPTIONS,
+            OPENAI_CHAT_OPTIONS,
+        ],
+        required: &[&["model", "engine"], &["api_key", "apiKey"]],
+    },
+    ProviderSchema {
+        providers: &["baml-azure-completion"],
+        options: &[
+            OPENAI_CLIENT_OPTIONS,
+            OPENAI_REQUEST_OPTIONS,
+            OPENAI_COMPLETION_OPTIONS,
+        ],
+        required: &[&["model", "engine"], &["api_key", "apiKey"]],
+    },
+    ProviderSchema {
+        providers: &["baml-anthropic"],
+        options: &[ANTHROPIC_OPTIONS],
+        required: &[&["model"]],
+    },
+];
+
+/// Checks the options of a client against its provider's schema. Providers without a schema,
+/// e.g. custom ones, are not checked. Keys listed in `unchecked_options` are let through as is.
+fn validate_options(ctx: &mut Context<'_>, client: &ClientWalker<'_>) {
+    let properties = client.properties();
+    let (provider, provider_span) = &properties.provider;
+    let Some(schema) = PROVIDER_SCHEMAS
+        .iter()
+        .find(|schema| schema.providers.contains(&provider.as_str()))
+    else {
+        return;
+    };
+
+    let known_options = || schema.options.iter().flat_map(|options| options.iter());
+    for (key, value) in &properties.options {
+        if properties
+            .unchecked_options
+            .iter()
+            .any(|(unchecked, _)| unchecked == key)
+        {
+            continue;
+        }
+
+        match known_options().find(|(name, _)| name == key) {
+            Some((_, option_type)) => {
+                if !option_type.matches(value) {
+                    ctx.push_error(DatamodelError::new_value_parser_error(
+                        &format!("{} for `{}`", option_type.description(), key),
+                        &value.to_string(),
+                        value.span().clone(),
+                    ))
+                }
+            }
+            None => ctx.push_error(DatamodelError::new_client_option_not_known_error(
+                key,
+                provider,
+                known_options().map(|(name, _)| name.to_string()).collect(),
+                value.span().clone(),
+            )),
+        }
+    }
+
+    for keys in schema.required {
+        if !properties
+            .options
+            .iter()
+            .any(|(key, _)| keys.contains(&key.as_str()))
+        {
+            ctx.push_error(DatamodelError::new_validation_error(
+                &format!(
+                    "Missing `{}` option in `{}` client.",
+                    keys.join("` or `"),
+                    provider
+                ),
+                provider_span.clone(),
+            ))
+        }
+    }
+}
+
+/// A composite client may not end up delegating to itself.
+fn validate_strategy_cycles(ctx: &mut Context<'_>) {
+    let mut reported = HashSet::new();
+    let mut done = HashSet::new();
+
+    for client in ctx.db.walk_clients() {
+        let mut path = vec![client.id];
+        if let Some(cycle) = find_strategy_cycle(ctx, &mut path, &mut done) {
+            if cycle.iter().any(|id| reported.contains(id)) {
+                continue;
+            }
+            reported.extend(cycle.iter().cloned());
+
+            let names = cycle
+                .iter()
+                .chain(cycle.first())
+                .map(|&id| ctx.db.ast()[id].name())
+                .collect::<Vec<_>>()
+                .join(" -> ");
+            let span = ctx.db.ast()[cycle[0]].identifier().span().clone();
+            ctx.push_error(DatamodelError::new_validation_error(
+                &format!("These clients form a cycle: {}", names),
+                span,
+            ));
+        }
+    }
+}
+
+/// Looks for a cycle of strategies through the last client of `path`. `done` holds the clients
+/// already known not to lead to any cycle, so that each is only explored once.
+fn find_strategy_cycle(
+    ctx: &Context<'_>,
+    path: &mut Vec<ClientId>,
+    done: &mut HashSet<ClientId>,
+) -> Option<Vec<ClientId>> {
+    let current = *path.last().unwrap();
+    if done.contains(&current) {
+        return None;
+    }
+    let Some(strategy) = &ctx.db.walk(current).properties().strategy else {
+        return None;
+    };
+
+    for (name, _) in &strategy.clients {
+        let Some(dep) = ctx.db.find_client(name) else {
+            continue;
+        };
+        if let Some(start) = path.iter().position(|&id| id == dep.id) {
+            return Some(path[start..].to_vec());
+        }
+        path.push(dep.id);
+        if let Some(cycle) = find_strategy_cycle(ctx, path, done) {
+            return Some(cycle);
+        }
+        path.pop();
+    }
+
+    done.insert(current);
+    None
 }
@@ -20,6 +20,7 @@ pub(crate) fn validate_type_exists(ctx: &mut Context<'_>, field_type: &FieldType
             Some(_) => {}
             None => match f {
                 Identifier::Primitive(..) => {}
+                _ if ctx.db.find_type_alias(f).is_some() => {}
                 _ => errors_with_names(ctx, f),
             },
         });
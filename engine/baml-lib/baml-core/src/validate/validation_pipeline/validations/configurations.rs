@@ -1,4 +1,5 @@
 use internal_baml_diagnostics::DatamodelError;
+use internal_baml_schema_ast::ast::WithName;
 
 use crate::validate::validation_pipeline::context::Context;
 
@@ -10,14 +11,36 @@ pub(super) fn validate(ctx: &mut Context<'_>) {
         // Nothing to validate.
     }
     for config in ctx.db.walk_test_cases() {
-        // Ensure that the test case name is valid.
         let case = config.test_case();
-        if ctx.db.find_function_by_name(&case.function.0).is_none() {
-            ctx.push_error(DatamodelError::new_type_not_found_error(
+        let Some(function) = ctx.db.find_function_by_name(&case.function.0) else {
+            // `__tests__` files name their function with their directory.
+            let error = if case.function.1.file.path().ends_with(".json") {
+                DatamodelError::new_test_function_not_found_error
+            } else {
+                DatamodelError::new_type_not_found_error
+            };
+            ctx.push_error(error(
                 &case.function.0,
                 ctx.db.valid_function_names(),
                 case.function.1.clone(),
             ));
+            continue;
+        };
+
+        for (name, span) in &case.impls {
+            if !function
+                .walk_variants()
+                .any(|variant| variant.name() == name)
+            {
+                ctx.push_error(DatamodelError::new_impl_not_found_error(
+                    name,
+                    function
+                        .walk_variants()
+                        .map(|variant| variant.name().to_string())
+                        .collect(),
+                    span.clone(),
+                ));
+            }
         }
     }
 }
@@ -1,89 +1,196 @@
-use std::collections::HashSet;
+use std::collections::{BTreeSet, HashMap, HashSet};
 
+use either::Either;
 use internal_baml_diagnostics::DatamodelError;
-use internal_baml_schema_ast::ast::{ClassId, WithIdentifier, WithName, WithSpan};
+use internal_baml_schema_ast::ast::{
+    ClassId, FieldType, TypeAliasId, WithIdentifier, WithName, WithSpan,
+};
 
 use crate::validate::validation_pipeline::context::Context;
 
 pub(super) fn validate(ctx: &mut Context<'_>) {
-    // Validates if there's a cycle in any dependency graph.
-    let mut deps_list = ctx
-        .db
-        .walk_classes()
-        .map(|f| {
-            (
-                f.id,
-                f.dependencies()
-                    .into_iter()
-                    .filter(|f| match ctx.db.find_type_by_str(f) {
-                        Some(either::Either::Left(_cls)) => true,
-                        // Don't worry about enum dependencies, they can't form cycles.
-                        Some(either::Either::Right(_enm)) => false,
-                        None => panic!("Unknown class `{}`", f),
-                    })
-                    .collect::<HashSet<_>>(),
-            )
-        })
-        .collect::<Vec<_>>();
+    validate_type_aliases(ctx);
 
-    // Now we can check for cycles using topological sort.
-    let mut stack: Vec<(ClassId, Vec<ClassId>)> = Vec::new(); // This stack now also keeps track of the path
-    let mut visited = HashSet::new();
-    let mut in_stack = HashSet::new();
-
-    // Find all items with 0 dependencies
-    for (id, deps) in &deps_list {
-        if deps.is_empty() {
-            stack.push((*id, vec![*id]));
-        }
+    // Finding class cycles looks through aliases, which must not loop on their own.
+    if !ctx.diagnostics.has_errors() {
+        validate_classes(ctx);
     }
+}
 
-    while let Some((current, path)) = stack.pop() {
-        let name = ctx.db.ast()[current].name().to_string();
-        let span = ctx.db.ast()[current].span();
+fn validate_type_aliases(ctx: &mut Context<'_>) {
+    // An alias may only refer to itself through a class, never directly.
+    let mut reported = HashSet::new();
+    let mut done = HashSet::new();
 
-        if in_stack.contains(&current) {
-            // If current is in in_stack, then we're revisiting a node, which means there's a cycle
-            let cycle_start_index = path.iter().position(|&x| x == current).unwrap();
-            let cycle = path[cycle_start_index..]
+    for alias in ctx.db.walk_type_aliases() {
+        let mut path = vec![alias.id];
+        if let Some(cycle) = find_type_alias_cycle(ctx, &mut path, &mut done) {
+            if cycle.iter().any(|id| reported.contains(id)) {
+                continue;
+            }
+            reported.extend(cycle.iter().cloned());
+
+            let names = cycle
                 .iter()
-                .map(|&x| ctx.db.ast()[x].name())
+                .chain(cycle.first())
+                .map(|&id| ctx.db.ast()[id].name())
                 .collect::<Vec<_>>()
                 .join(" -> ");
+            let span = ctx.db.ast()[cycle[0]].identifier().span().clone();
             ctx.push_error(DatamodelError::new_validation_error(
-                &format!("These classes form a dependency cycle: {}", cycle),
-                span.clone(),
+                &format!("These type aliases form a dependency cycle: {}", names),
+                span,
             ));
-            return;
         }
+    }
+}
 
-        in_stack.insert(current);
-        visited.insert(current);
+/// Looks for a cycle through the last alias of `path`. `done` holds the aliases already known
+/// not to lead to any cycle, so that each is only explored once.
+fn find_type_alias_cycle(
+    ctx: &Context<'_>,
+    path: &mut Vec<TypeAliasId>,
+    done: &mut HashSet<TypeAliasId>,
+) -> Option<Vec<TypeAliasId>> {
+    let current = *path.last().unwrap();
+    if done.contains(&current) {
+        return None;
+    }
 
-        deps_list.iter_mut().for_each(|(id, deps)| {
-            if deps.remove(&name) {
-                // If this item has now 0 dependencies, add it to the stack
-                if deps.is_empty() {
-                    let mut new_path = path.clone();
-                    new_path.push(*id);
-                    stack.push((*id, new_path));
-                }
+    let deps = ctx
+        .db
+        .walk(current)
+        .required_type_aliases()
+        .map(|dep| dep.id)
+        .collect::<BTreeSet<_>>();
+    for dep in deps {
+        if let Some(start) = path.iter().position(|&id| id == dep) {
+            return Some(path[start..].to_vec());
+        }
+        path.push(dep);
+        if let Some(cycle) = find_type_alias_cycle(ctx, path, done) {
+            return Some(cycle);
+        }
+        path.pop();
+    }
+
+    done.insert(current);
+    None
+}
+
+fn validate_classes(ctx: &mut Context<'_>) {
+    // A class may contain itself through an optional, list or map, which can end the recursion.
+    // A cycle of required fields would need a value of infinite size.
+    let mut alias_classes = HashMap::new();
+    let deps = ctx
+        .db
+        .walk_classes()
+        .map(|cls| {
+            let required = cls
+                .static_fields()
+                .flat_map(|f| required_classes(ctx, f.r#type(), &mut alias_classes))
+                .collect::<BTreeSet<_>>();
+            (cls.id, required)
+        })
+        .collect::<HashMap<_, _>>();
+
+    let mut reported = HashSet::new();
+    let mut done = HashSet::new();
+
+    for cls in ctx.db.walk_classes() {
+        let mut path = vec![cls.id];
+        if let Some(cycle) = find_class_cycle(&deps, &mut path, &mut done) {
+            if cycle.iter().any(|id| reported.contains(id)) {
+                continue;
             }
-        });
+            reported.extend(cycle.iter().cloned());
+
+            let names = cycle
+                .iter()
+                .chain(cycle.first())
+                .map(|&id| ctx.db.ast()[id].name())
+                .collect::<Vec<_>>()
+                .join(" -> ");
+            let span = ctx.db.ast()[cycle[0]].identifier().span().clone();
+            ctx.push_error(DatamodelError::new_validation_error(
+                &format!(
+                    "These classes form a dependency cycle: {}. Make one of the fields optional, a list or a map so that the recursion can end.",
+                    names
+                ),
+                span,
+            ));
+        }
+    }
+}
 
-        in_stack.remove(&current);
+/// Looks for a cycle in `deps`, the classes each class requires, through the last class of
+/// `path`. `done` holds the classes already known not to lead to any cycle, so that each is only
+/// explored once.
+fn find_class_cycle(
+    deps: &HashMap<ClassId, BTreeSet<ClassId>>,
+    path: &mut Vec<ClassId>,
+    done: &mut HashSet<ClassId>,
+) -> Option<Vec<ClassId>> {
+    let current = *path.last().unwrap();
+    if done.contains(&current) {
+        return None;
     }
 
-    // If there are still items left in deps_list after the above steps, there's a cycle
-    if visited.len() != deps_list.len() {
-        for (id, _) in &deps_list {
-            if !visited.contains(id) {
-                let cls = &ctx.db.ast()[*id];
-                ctx.push_error(DatamodelError::new_validation_error(
-                    &format!("These classes form a dependency cycle: {}", cls.name()),
-                    cls.identifier().span().clone(),
-                ));
-            }
+    for &dep in &deps[&current] {
+        if let Some(start) = path.iter().position(|&id| id == dep) {
+            return Some(path[start..].to_vec());
+        }
+        path.push(dep);
+        if let Some(cycle) = find_class_cycle(deps, path, done) {
+            return Some(cycle);
+        }
+        path.pop();
+    }
+
+    done.insert(current);
+    None
+}
+
+/// The classes every value of `field_type` contains. Optionals, lists and maps may be empty, so
+/// what they hold is not required. `aliases` caches what each type alias requires.
+fn required_classes(
+    ctx: &Context<'_>,
+    field_type: &FieldType,
+    aliases: &mut HashMap<TypeAliasId, HashSet<ClassId>>,
+) -> HashSet<ClassId> {
+    match field_type {
+        FieldType::Identifier(arity, _)
+        | FieldType::Tuple(arity, ..)
+        | FieldType::Union(arity, ..)
+            if arity.is_optional() =>
+        {
+            HashSet::new()
         }
+        FieldType::Identifier(_, idn) => match ctx.db.find_type(idn) {
+            Some(Either::Left(cls)) => HashSet::from([cls.id]),
+            Some(Either::Right(_)) => HashSet::new(),
+            None => match ctx.db.find_type_alias(idn) {
+                Some(alias) => {
+                    if let Some(required) = aliases.get(&alias.id) {
+                        return required.clone();
+                    }
+                    let required = required_classes(ctx, alias.target(), aliases);
+                    aliases.insert(alias.id, required.clone());
+                    required
+                }
+                None => HashSet::new(),
+            },
+        },
+        FieldType::Literal(..) | FieldType::List(..) | FieldType::Dictionary(..) => HashSet::new(),
+        FieldType::Tuple(_, items, _) => items
+            .iter()
+            .flat_map(|item| required_classes(ctx, item, aliases))
+            .collect(),
+        // A union holds one of its members, so it only requires what all of them do.
+        FieldType::Union(_, members, _) => members
+            .iter()
+            .map(|member| required_classes(ctx, member, aliases))
+            .reduce(|all, required| all.intersection(&required).cloned().collect())
+            .unwrap_or_default(),
     }
 }
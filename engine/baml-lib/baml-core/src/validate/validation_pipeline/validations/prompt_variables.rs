@@ -0,0 +1,308 @@
+use internal_baml_diagnostics::{DatamodelError, Span};
+use internal_baml_parser_database::PromptVariable;
+use internal_baml_prompt_parser::ast::{Variable, VariableSegment};
+use internal_baml_schema_ast::ast::{TypeValue, WithName};
+
+use crate::{
+    generate::ir::{FieldType, FunctionArgs, IntermediateRepr},
+    validate::validation_pipeline::context::Context,
+};
+
+/// Checks every segment of the `{#input...}` variables of each impl against the input type of its
+/// function, or what its input adapter returns, e.g. that `{#input.items[0].name}` indexes a list
+/// of a class with a `name` field.
+///
+/// Variables that start with the item of a `{#for}` block are checked against the items of its
+/// collection.
+pub(super) fn validate(ctx: &mut Context<'_>) {
+    let Ok(ir) = IntermediateRepr::from_parser_database(ctx.db) else {
+        return;
+    };
+
+    let mut errors = vec![];
+    for variant in ctx.db.walk_variants() {
+        let Some(function) = variant
+            .walk_function()
+            .and_then(|f| ir.find_function(f.name()))
+        else {
+            continue;
+        };
+        // With an input adapter, `input` is whatever the adapter returns.
+        let adapted = function
+            .walk_impls()
+            .find(|i| i.elem().name == variant.name())
+            .and_then(|i| i.elem().input_adapter.as_ref())
+            .map(|adapter| FunctionArgs::UnnamedArg(adapter.to.clone()));
+        let inputs = adapted.as_ref().unwrap_or(&function.elem().inputs);
+        let checker = PathChecker { ir: &ir };
+        // The item of each open `{#for}` block and its type, or `None` for `{#if}` blocks.
+        let mut open_blocks: Vec<Option<(&str, Option<FieldType>)>> = vec![];
+
+        for prompt_variable in &variant.properties().prompt_replacements {
+            let (variable, is_block) = match prompt_variable {
+                PromptVariable::Input(variable) => (variable, false),
+                PromptVariable::If(variable) => (variable, true),
+                PromptVariable::For(block) => (&block.collection, true),
+                PromptVariable::End(_) => {
+                    open_blocks.pop();
+                    continue;
+                }
+                PromptVariable::Enum(_)
+                | PromptVariable::Type(_)
+                | PromptVariable::Chat(_)
+                | PromptVariable::Else(_) => continue,
+            };
+
+            let checked = match resolve(&checker, inputs, &open_blocks, variable) {
+                Some(Err((_, err))) => Err(err),
+                Some(Ok(field_type)) if is_block => {
+                    match variable.path.iter().position(|s| s == &VariableSegment::Each) {
+                        Some(idx) => Err(checker.error(
+                            variable,
+                            idx,
+                            "`[*]` can only be printed, so it can't be used by `{#if}` or `{#for}` blocks.".to_string(),
+                        )),
+                        None => Ok(Some(field_type)),
+                    }
+                }
+                _ => Ok(None),
+            };
+            let field_type = match checked {
+                Ok(field_type) => field_type,
+                Err(err) => {
+                    errors.push(err);
+                    None
+                }
+            };
+
+            match prompt_variable {
+                PromptVariable::If(_) => open_blocks.push(None),
+                PromptVariable::For(block) => {
+                    let items = match field_type.map(|t| checker.items(&t)) {
+                        Some(Some(items)) => Some(items),
+                        Some(None) => {
+                            errors.push(DatamodelError::new_validation_error(
+                                &format!(
+                                    "`{}` isn't a list or a map, so a `{{#for}}` block can't loop over it.",
+                                    variable.text
+                                ),
+                                variable.span.clone(),
+                            ));
+                            None
+                        }
+                        None => None,
+                    };
+                    open_blocks.push(Some((&block.item.0, items)));
+                }
+                _ => {}
+            }
+        }
+    }
+    errors.into_iter().for_each(|e| ctx.push_error(e));
+}
+
+/// Checks a variable against the inputs of the function, or the item of the `{#for}` block it
+/// starts with. `None` if there's nothing to check it against.
+fn resolve(
+    checker: &PathChecker<'_>,
+    inputs: &FunctionArgs,
+    open_blocks: &[Option<(&str, Option<FieldType>)>],
+    variable: &Variable,
+) -> Option<Result<FieldType, Failure>> {
+    let root = variable.path[0].as_field();
+    if let Some((_, item)) = open_blocks
+        .iter()
+        .rev()
+        .flatten()
+        .find(|(item, _)| Some(*item) == root)
+    {
+        return item.as_ref().map(|item| checker.check(item, variable, 1));
+    }
+    // The parser database reports variables that don't start with `input`.
+    if root != Some("input") {
+        return None;
+    }
+    match inputs {
+        FunctionArgs::UnnamedArg(arg) => Some(checker.check(arg, variable, 1)),
+        FunctionArgs::NamedArgList(args) => {
+            // The parser database already reports unknown args.
+            let (_, arg) = variable
+                .path
+                .get(1)
+                .and_then(VariableSegment::as_field)
+                .and_then(|name| args.iter().find(|(arg, _)| arg == name))?;
+            Some(checker.check(arg, variable, 2))
+        }
+    }
+}
+
+struct PathChecker<'a> {
+    ir: &'a IntermediateRepr,
+}
+
+/// How far into the path a check got before failing, and why it failed.
+type Failure = (usize, DatamodelError);
+
+impl PathChecker<'_> {
+    /// Checks `variable.path[idx..]` against a value of `field_type`, and returns the type of the
+    /// value it leads to.
+    fn check(
+        &self,
+        field_type: &FieldType,
+        variable: &Variable,
+        idx: usize,
+    ) -> Result<FieldType, Failure> {
+        let Some(segment) = variable.path.get(idx) else {
+            return Ok(field_type.clone());
+        };
+        let error = |message: String| Err((idx, self.error(variable, idx, message)));
+        let at = VariableSegment::format_path(&variable.path[..idx]);
+
+        match (field_type, segment) {
+            (FieldType::Optional(inner), _) => self.check(inner, variable, idx),
+            (FieldType::TypeAlias(name), _) => self.check(self.ir.alias_target(name), variable, idx),
+            (FieldType::Union(options), _) => {
+                let (mut matches, mut failures) = (vec![], vec![]);
+                for option in options {
+                    match self.check(option, variable, idx) {
+                        Ok(field_type) => matches.push(field_type),
+                        Err(failure) => failures.push(failure),
+                    }
+                }
+                match matches.len() {
+                    0 => {}
+                    1 => return Ok(matches.remove(0)),
+                    _ => return Ok(FieldType::Union(matches)),
+                }
+                // Report the type that matched the most of the path, if any got past this segment.
+                match failures.into_iter().max_by_key(|(reached, _)| *reached) {
+                    Some(failure) if failure.0 > idx => Err(failure),
+                    _ => error(format!(
+                        "`{}` is `{}`, and none of its types can be followed by `{}`.",
+                        at,
+                        field_type,
+                        written(segment)
+                    )),
+                }
+            }
+            (FieldType::Class(name), _) => {
+                let Some(class) = self.ir.find_class(name) else {
+                    return Ok(field_type.clone());
+                };
+                let class = class.elem();
+                let fields = class
+                    .static_fields
+                    .iter()
+                    .chain(&class.dynamic_fields)
+                    .map(|f| f.elem.name.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                let VariableSegment::Field(field) = segment else {
+                    return error(format!(
+                        "`{}` is class `{}`, which can't be indexed with `{}`. Could be one of: {}",
+                        at,
+                        name,
+                        written(segment),
+                        fields
+                    ));
+                };
+                if let Some(f) = class.static_fields.iter().find(|f| &f.elem.name == field) {
+                    return self.check(&f.elem.r#type.elem, variable, idx + 1);
+                }
+                if class.dynamic_fields.iter().any(|f| &f.elem.name == field) {
+                    if idx + 1 < variable.path.len() {
+                        return error(format!(
+                            "`{}` is a computed field of class `{}`, so it must be the last part of the path.",
+                            field, name
+                        ));
+                    }
+                    return Ok(FieldType::Primitive(TypeValue::String));
+                }
+                error(format!(
+                    "Unknown field `{}` in class `{}`. Could be one of: {}",
+                    field, name, fields
+                ))
+            }
+            (FieldType::List(item), VariableSegment::Index(_) | VariableSegment::Each) => {
+                self.check(item, variable, idx + 1)
+            }
+            (FieldType::List(_), _) => error(format!(
+                "`{}` is a list (`{}`), so it can only be followed by `[0]` or `[*]`, not `{}`.",
+                at,
+                field_type,
+                written(segment)
+            )),
+            (FieldType::Map(_, value), VariableSegment::Key(_) | VariableSegment::Each) => {
+                self.check(value, variable, idx + 1)
+            }
+            (FieldType::Map(..), _) => error(format!(
+                "`{}` is a map (`{}`), so it can only be followed by `[\"key\"]` or `[*]`, not `{}`.",
+                at,
+                field_type,
+                written(segment)
+            )),
+            (FieldType::Tuple(items), VariableSegment::Index(item_idx)) => {
+                match items.get(*item_idx) {
+                    Some(item) => self.check(item, variable, idx + 1),
+                    None => error(format!(
+                        "`{}` is a tuple (`{}`) of {} items, so it has no item `{}`.",
+                        at,
+                        field_type,
+                        items.len(),
+                        written(segment)
+                    )),
+                }
+            }
+            (FieldType::Tuple(_), _) => error(format!(
+                "`{}` is a tuple (`{}`), so it can only be followed by an index like `[0]`, not `{}`.",
+                at,
+                field_type,
+                written(segment)
+            )),
+            (FieldType::Enum(_) | FieldType::Primitive(_) | FieldType::Literal(_), _) => error(format!(
+                "`{}` is `{}`, which has no fields or items, so it can't be followed by `{}`.",
+                at,
+                field_type,
+                written(segment)
+            )),
+        }
+    }
+
+    /// The type of the items of a list or map, or `None` if `field_type` is neither.
+    fn items(&self, field_type: &FieldType) -> Option<FieldType> {
+        match field_type {
+            FieldType::Optional(inner) => self.items(inner),
+            FieldType::TypeAlias(name) => self.items(self.ir.alias_target(name)),
+            FieldType::List(item) | FieldType::Map(_, item) => Some(item.as_ref().clone()),
+            FieldType::Union(options) => {
+                let mut items = options
+                    .iter()
+                    .map(|option| self.items(option))
+                    .collect::<Option<Vec<_>>>()?;
+                match items.len() {
+                    1 => Some(items.remove(0)),
+                    _ => Some(FieldType::Union(items)),
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// An error pointing at the segment the check failed at.
+    fn error(&self, variable: &Variable, idx: usize, message: String) -> DatamodelError {
+        let span: Span = variable
+            .segment_spans()
+            .get(idx)
+            .cloned()
+            .unwrap_or_else(|| variable.span.clone());
+        DatamodelError::new_validation_error(&message, span)
+    }
+}
+
+/// A segment the way it follows the rest of the path, e.g. `.name` or `[0]`.
+fn written(segment: &VariableSegment) -> String {
+    match segment {
+        VariableSegment::Field(name) => format!(".{}", name),
+        _ => VariableSegment::format_path(std::slice::from_ref(segment)),
+    }
+}
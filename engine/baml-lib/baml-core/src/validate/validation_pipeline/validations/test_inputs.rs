@@ -0,0 +1,308 @@
+use internal_baml_diagnostics::DatamodelError;
+use internal_baml_parser_database::{AssertionCheck, PathSegment, TestAssertion};
+use internal_baml_schema_ast::json::{JsonKind, JsonValue};
+
+use crate::{
+    generate::ir::{FieldType, FunctionArgs, IntermediateRepr, LiteralValue, TypeValue},
+    validate::validation_pipeline::context::Context,
+};
+
+/// Checks the input of every test against the input type of its function, and its assertions
+/// against the output type.
+pub(super) fn validate(ctx: &mut Context<'_>) {
+    let Ok(ir) = IntermediateRepr::from_parser_database(ctx.db) else {
+        return;
+    };
+
+    for config in ctx.db.walk_test_cases() {
+        let case = config.test_case();
+        let Some(function) = ir.find_function(&case.function.0) else {
+            continue;
+        };
+        let input = &case.input;
+
+        let checker = InputChecker { ir: &ir };
+        let mut errors = vec![];
+        match &function.elem().inputs {
+            FunctionArgs::UnnamedArg(arg) => checker.check(arg, input, "input", &mut errors),
+            FunctionArgs::NamedArgList(args) => checker.check_fields(
+                &format!("function `{}`", function.elem().name),
+                args.iter().map(|(name, arg)| (name.as_str(), arg)),
+                input,
+                "input",
+                &mut errors,
+            ),
+        }
+        for assertion in &case.assertions {
+            checker.check_assertion(&function.elem().output.elem, assertion, &mut errors);
+        }
+        errors.into_iter().for_each(|e| ctx.push_error(e));
+    }
+}
+
+struct InputChecker<'a> {
+    ir: &'a IntermediateRepr,
+}
+
+impl InputChecker<'_> {
+    fn mismatch(&self, expected: &str, value: &JsonValue, path: &str) -> DatamodelError {
+        DatamodelError::new_validation_error(
+            &format!(
+                "Expected {} at `{}`, got {}.",
+                expected,
+                path,
+                value.description()
+            ),
+            value.span.clone(),
+        )
+    }
+
+    fn check(
+        &self,
+        field_type: &FieldType,
+        value: &JsonValue,
+        path: &str,
+        errors: &mut Vec<DatamodelError>,
+    ) {
+        match (field_type, &value.kind) {
+            (FieldType::Optional(_), JsonKind::Null) => {}
+            (FieldType::Optional(inner), _) => self.check(inner, value, path, errors),
+            (FieldType::TypeAlias(name), _) => {
+                self.check(self.ir.alias_target(name), value, path, errors)
+            }
+            (FieldType::Primitive(TypeValue::String | TypeValue::Char), JsonKind::String(_))
+            | (FieldType::Primitive(TypeValue::Float), JsonKind::Number(_))
+            | (FieldType::Primitive(TypeValue::Bool), JsonKind::Bool(_))
+            | (FieldType::Primitive(TypeValue::Null), JsonKind::Null) => {}
+            (FieldType::Primitive(TypeValue::Int), JsonKind::Number(num))
+                if num.parse::<i64>().is_ok() => {}
+            (FieldType::Literal(LiteralValue::String(expected)), JsonKind::String(s))
+                if s == expected => {}
+            (FieldType::Literal(LiteralValue::Int(expected)), JsonKind::Number(num))
+                if num.parse::<i64>() == Ok(*expected) => {}
+            (FieldType::Literal(LiteralValue::Bool(expected)), JsonKind::Bool(b))
+                if b == expected => {}
+            (FieldType::Enum(name), JsonKind::String(variant)) => {
+                let Some(e) = self.ir.find_enum(name) else {
+                    return;
+                };
+                let values = e.walk_values().map(|v| v.0.as_str()).collect::<Vec<_>>();
+                if !values.contains(&variant.as_str()) {
+                    errors.push(DatamodelError::new_validation_error(
+                        &format!(
+                            "`{}` is not a value of enum `{}` at `{}`. Expected one of: `{}`.",
+                            variant,
+                            name,
+                            path,
+                            values.join("`, `")
+                        ),
+                        value.span.clone(),
+                    ));
+                }
+            }
+            (FieldType::Class(name), JsonKind::Object(_)) => {
+                let Some(class) = self.ir.find_class(name) else {
+                    return;
+                };
+                self.check_fields(
+                    &format!("class `{}`", name),
+                    class
+                        .walk_fields()
+                        .map(|field| (field.name.as_str(), &field.r#type.elem)),
+                    value,
+                    path,
+                    errors,
+                );
+            }
+            (FieldType::List(item_type), JsonKind::Array(items)) => {
+                for (idx, item) in items.iter().enumerate() {
+                    self.check(item_type, item, &format!("{}[{}]", path, idx), errors);
+                }
+            }
+            (FieldType::Tuple(item_types), JsonKind::Array(items)) => {
+                if item_types.len() != items.len() {
+                    errors.push(self.mismatch(
+                        &format!("`{}` with {} items", field_type, item_types.len()),
+                        value,
+                        path,
+                    ));
+                    return;
+                }
+                for (idx, (item_type, item)) in item_types.iter().zip(items).enumerate() {
+                    self.check(item_type, item, &format!("{}[{}]", path, idx), errors);
+                }
+            }
+            (FieldType::Map(key_type, value_type), JsonKind::Object(entries)) => {
+                for (key, key_span, entry) in entries {
+                    let key_value = JsonValue {
+                        kind: JsonKind::String(key.clone()),
+                        span: key_span.clone(),
+                    };
+                    self.check(key_type, &key_value, path, errors);
+                    self.check(value_type, entry, &format!("{}.{}", path, key), errors);
+                }
+            }
+            (FieldType::Union(options), _) => {
+                let matches = options.iter().any(|option| {
+                    let mut option_errors = vec![];
+                    self.check(option, value, path, &mut option_errors);
+                    option_errors.is_empty()
+                });
+                if !matches {
+                    errors.push(self.mismatch(&format!("`{}`", field_type), value, path));
+                }
+            }
+            _ => errors.push(self.mismatch(&format!("`{}`", field_type), value, path)),
+        }
+    }
+
+    fn check_assertion(
+        &self,
+        output_type: &FieldType,
+        assertion: &TestAssertion,
+        errors: &mut Vec<DatamodelError>,
+    ) {
+        let path = assertion.path.to_string();
+        let field_type = match self.resolve_path(output_type, &assertion.path.segments) {
+            Ok(field_type) => field_type,
+            Err(reason) => {
+                errors.push(DatamodelError::new_validation_error(
+                    &format!("Invalid path `{}`: {}.", path, reason),
+                    assertion.path.span.clone(),
+                ));
+                return;
+            }
+        };
+
+        match &assertion.check {
+            AssertionCheck::Equals(expected) => self.check(&field_type, expected, &path, errors),
+            AssertionCheck::OneOf(options) => options
+                .iter()
+                .for_each(|option| self.check(&field_type, option, &path, errors)),
+            AssertionCheck::Matches(_, span) => {
+                let string = JsonValue {
+                    kind: JsonKind::String(String::new()),
+                    span: span.clone(),
+                };
+                let mut string_errors = vec![];
+                self.check(&field_type, &string, &path, &mut string_errors);
+                if !string_errors.is_empty() {
+                    errors.push(DatamodelError::new_validation_error(
+                        &format!(
+                            "`matches` needs a string, but `{}` is `{}`.",
+                            path, field_type
+                        ),
+                        span.clone(),
+                    ));
+                }
+            }
+            AssertionCheck::NotNull => {}
+        }
+    }
+
+    /// The type of the value at `segments` in a value of `field_type`. Values reached through an
+    /// optional may be null.
+    fn resolve_path(
+        &self,
+        field_type: &FieldType,
+        segments: &[PathSegment],
+    ) -> Result<FieldType, String> {
+        let Some((segment, rest)) = segments.split_first() else {
+            return Ok(field_type.clone());
+        };
+        match (field_type, segment) {
+            (FieldType::Optional(inner), _) => self
+                .resolve_path(inner, segments)
+                .map(|inner| FieldType::Optional(Box::new(inner))),
+            (FieldType::TypeAlias(name), _) => {
+                self.resolve_path(self.ir.alias_target(name), segments)
+            }
+            (FieldType::Class(name), PathSegment::Field(field)) => {
+                let Some(class) = self.ir.find_class(name) else {
+                    return Err(format!("class `{}` does not exist", name));
+                };
+                let resolved = match class.walk_fields().find(|f| &f.name == field) {
+                    Some(f) => self.resolve_path(&f.r#type.elem, rest),
+                    None => Err(format!("class `{}` has no field `{}`", name, field)),
+                };
+                resolved
+            }
+            (FieldType::Map(_, value_type), PathSegment::Field(_)) => self
+                .resolve_path(value_type, rest)
+                .map(|value_type| FieldType::Optional(Box::new(value_type))),
+            (FieldType::List(item_type), PathSegment::Index(_)) => self
+                .resolve_path(item_type, rest)
+                .map(|item_type| FieldType::Optional(Box::new(item_type))),
+            (FieldType::Tuple(item_types), PathSegment::Index(idx)) => match item_types.get(*idx) {
+                Some(item_type) => self.resolve_path(item_type, rest),
+                None => Err(format!("`{}` has no item {}", field_type, idx)),
+            },
+            (FieldType::Union(options), _) => {
+                let mut resolved = options
+                    .iter()
+                    .filter_map(|option| self.resolve_path(option, segments).ok())
+                    .collect::<Vec<_>>();
+                match resolved.len() {
+                    0 => Err(format!("no type in `{}` has it", field_type)),
+                    1 => Ok(resolved.remove(0)),
+                    _ => Ok(FieldType::Union(resolved)),
+                }
+            }
+            (_, PathSegment::Field(field)) => {
+                Err(format!("`{}` has no field `{}`", field_type, field))
+            }
+            (_, PathSegment::Index(_)) => Err(format!("`{}` is not a list", field_type)),
+        }
+    }
+
+    fn accepts_null(&self, field_type: &FieldType, at: &JsonValue) -> bool {
+        let null = JsonValue {
+            kind: JsonKind::Null,
+            span: at.span.clone(),
+        };
+        let mut errors = vec![];
+        self.check(field_type, &null, "", &mut errors);
+        errors.is_empty()
+    }
+
+    /// Checks an object against named fields, e.g. of a class or a function's arguments.
+    fn check_fields<'f>(
+        &self,
+        owner: &str,
+        fields: impl Iterator<Item = (&'f str, &'f FieldType)>,
+        value: &JsonValue,
+        path: &str,
+        errors: &mut Vec<DatamodelError>,
+    ) {
+        let JsonKind::Object(entries) = &value.kind else {
+            errors.push(self.mismatch("an object", value, path));
+            return;
+        };
+
+        let fields = fields.collect::<Vec<_>>();
+        for (name, field_type) in &fields {
+            match value.get(name) {
+                Some(field_value) => self.check(
+                    field_type,
+                    field_value,
+                    &format!("{}.{}", path, name),
+                    errors,
+                ),
+                // Optional fields may be left out.
+                None if self.accepts_null(field_type, value) => {}
+                None => errors.push(DatamodelError::new_validation_error(
+                    &format!("Missing `{}` of {} at `{}`.", name, owner, path),
+                    value.span.clone(),
+                )),
+            }
+        }
+        for (key, key_span, _) in entries {
+            if !fields.iter().any(|(name, _)| name == key) {
+                errors.push(DatamodelError::new_validation_error(
+                    &format!("Unknown field `{}` of {} at `{}`.", key, owner, path),
+                    key_span.clone(),
+                ));
+            }
+        }
+    }
+}
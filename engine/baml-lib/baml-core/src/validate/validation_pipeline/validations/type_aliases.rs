@@ -0,0 +1,9 @@
+use crate::validate::validation_pipeline::context::Context;
+
+use super::common::validate_type_exists;
+
+pub(super) fn validate(ctx: &mut Context<'_>) {
+    for alias in ctx.db.walk_type_aliases() {
+        validate_type_exists(ctx, alias.target());
+    }
+}
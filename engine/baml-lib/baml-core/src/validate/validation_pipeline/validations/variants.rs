@@ -3,7 +3,7 @@ use internal_baml_diagnostics::DatamodelError;
 use internal_baml_parser_database::{PrinterType, PromptVariable};
 use internal_baml_schema_ast::ast::{Identifier, WithIdentifier, WithName, WithSpan};
 
-use crate::validate::validation_pipeline::context::Context;
+use crate::{configuration::GeneratorLanguage, validate::validation_pipeline::context::Context};
 
 pub(super) fn validate(ctx: &mut Context<'_>) {
     for variant in ctx.db.walk_variants() {
@@ -30,12 +30,21 @@ pub(super) fn validate(ctx: &mut Context<'_>) {
                 }
             });
 
-            if let Some((idx, _)) = variant.properties().output_adapter {
-                // Ensure that all input types exist.
-                let adapter = &variant.ast_variant()[idx];
-
-                adapter
-                    .from
+            // Ensure that the types the adapters convert to and from exist.
+            let adapter_types = [
+                variant
+                    .properties()
+                    .input_adapter
+                    .as_ref()
+                    .map(|(idx, _)| &variant.ast_variant()[*idx].to),
+                variant
+                    .properties()
+                    .output_adapter
+                    .as_ref()
+                    .map(|(idx, _)| &variant.ast_variant()[*idx].from),
+            ];
+            for field_type in adapter_types.into_iter().flatten() {
+                field_type
                     .flat_idns()
                     .iter()
                     .filter(|f| match f {
@@ -55,6 +64,42 @@ pub(super) fn validate(ctx: &mut Context<'_>) {
                     });
             }
 
+            // Ensure that every generator has code to run the adapters with.
+            let adapters = [
+                &variant.properties().input_adapter,
+                &variant.properties().output_adapter,
+            ];
+            for (idx, code) in adapters.into_iter().flatten() {
+                let adapter = &variant.ast_variant()[*idx];
+                let mut languages = vec![];
+                for generator in ctx.generators.clone() {
+                    let language = generator.language.to_string();
+                    if languages.contains(&language) {
+                        continue;
+                    }
+                    languages.push(language);
+
+                    let has_code = code.iter().any(|raw| {
+                        raw.language.as_ref().map(|(l, _)| l.as_str()) == Some(language)
+                    });
+                    let message = match generator.language {
+                        GeneratorLanguage::Rust => format!(
+                            "The Rust client doesn't support adapters yet, so generator `{}` can't use this one.",
+                            generator.name
+                        ),
+                        _ if has_code => continue,
+                        _ => format!(
+                            "This adapter has no {0} code, which generator `{1}` needs. Add it with {0}#\"...\"#.",
+                            language, generator.name
+                        ),
+                    };
+                    ctx.push_error(DatamodelError::new_validation_error(
+                        &message,
+                        adapter.span().clone(),
+                    ));
+                }
+            }
+
             // Ensure that all blocks are valid.
             variant
                 .properties()
@@ -63,8 +108,12 @@ pub(super) fn validate(ctx: &mut Context<'_>) {
                 .filter_map(|p| match p {
                     PromptVariable::Enum(e) => e.printer.as_ref().map(|f| (f, "enum")),
                     PromptVariable::Type(t) => t.printer.as_ref().map(|f| (f, "type")),
-                    PromptVariable::Input(_) => None,
-                    PromptVariable::Chat(_) => None,
+                    PromptVariable::Input(_)
+                    | PromptVariable::Chat(_)
+                    | PromptVariable::If(_)
+                    | PromptVariable::Else(_)
+                    | PromptVariable::For(_)
+                    | PromptVariable::End(_) => None,
                 })
                 .for_each(|(printer, t)| {
                     match ctx.db.find_printer(&printer.0) {
@@ -0,0 +1,12 @@
+# This file is generated by the BAML compiler.
+# Do not edit this file directly.
+# Instead, edit the BAML files and recompile.
+
+# ruff: noqa: E501,F401
+# flake8: noqa: E501,F401
+# pylint: disable=unused-import,line-too-long
+# fmt: off
+
+
+
+from . import impls
@@ -0,0 +1,23 @@
+# This file is generated by the BAML compiler.
+# Do not edit this file directly.
+# Instead, edit the BAML files and recompile.
+
+# ruff: noqa: E501,F401
+# flake8: noqa: E501,F401
+# pylint: disable=unused-import,line-too-long
+# fmt: off
+
+from baml_core.provider_manager import LLMManager
+from os import environ
+
+
+Claude = LLMManager.add_llm(
+    name="Claude",
+    provider="baml-anthropic",
+    retry_policy=None,
+    redactions=["api_key"],
+    options=dict(
+        model="claude-2",
+        api_key=environ['ANTHROPIC_API_KEY'],
+    ),
+)
@@ -0,0 +1,22 @@
+# This file is generated by the BAML compiler.
+# Do not edit this file directly.
+# Instead, edit the BAML files and recompile.
+
+# ruff: noqa: E501,F401
+# flake8: noqa: E501,F401
+# pylint: disable=unused-import,line-too-long
+# fmt: off
+
+from  ..configs.retry_policy import Backoff
+from baml_core.provider_manager import LLMManager
+
+
+Fallback = LLMManager.add_llm(
+    name="Fallback",
+    provider="baml-fallback",
+    retry_policy=Backoff,
+    redactions=[],
+    options=dict(
+        strategy=["GPT4", "Claude"],
+    ),
+)
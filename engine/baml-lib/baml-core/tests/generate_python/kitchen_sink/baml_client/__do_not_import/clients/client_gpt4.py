@@ -0,0 +1,27 @@
+# This file is generated by the BAML compiler.
+# Do not edit this file directly.
+# Instead, edit the BAML files and recompile.
+
+# ruff: noqa: E501,F401
+# flake8: noqa: E501,F401
+# pylint: disable=unused-import,line-too-long
+# fmt: off
+
+from  ..configs.retry_policy import Constant
+from baml_core.provider_manager import LLMManager
+from os import environ
+
+
+GPT4 = LLMManager.add_llm(
+    name="GPT4",
+    provider="baml-openai-chat",
+    retry_policy=Constant,
+    redactions=["api_key"],
+    options=dict(
+        model="gpt-4",
+        api_key=environ['OPENAI_API_KEY'],
+        temperature=0.5,
+        stop=["END", "STOP"],
+        headers={"x-title": "BAML test"},
+    ),
+)
@@ -0,0 +1,22 @@
+# This file is generated by the BAML compiler.
+# Do not edit this file directly.
+# Instead, edit the BAML files and recompile.
+
+# ruff: noqa: E501,F401
+# flake8: noqa: E501,F401
+# pylint: disable=unused-import,line-too-long
+# fmt: off
+
+from baml_core.configs.retry_policy import create_retry_policy_constant_delay, create_retry_policy_exponential_backoff
+
+
+Backoff = create_retry_policy_exponential_backoff(
+  max_retries=2,
+  delay_ms=10,
+  max_delay_ms=1000,
+  multiplier=2.0
+)
+Constant = create_retry_policy_constant_delay(
+  max_retries=3,
+  delay_ms=100
+)
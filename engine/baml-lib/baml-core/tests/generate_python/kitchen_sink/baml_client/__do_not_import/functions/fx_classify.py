@@ -0,0 +1,80 @@
+# This file is generated by the BAML compiler.
+# Do not edit this file directly.
+# Instead, edit the BAML files and recompile.
+
+# ruff: noqa: E501,F401
+# flake8: noqa: E501,F401
+# pylint: disable=unused-import,line-too-long
+# fmt: off
+
+from ..types.aliases.als_reply import Reply
+from ..types.aliases.als_thread import Thread
+from ..types.classes.cls_classification import Classification
+from ..types.classes.cls_conversation import Conversation
+from ..types.classes.cls_message import Message
+from ..types.enums.enm_sentiment import Sentiment
+from ..types.enums.enm_tool import Tool
+from ..types.partial.classes.cls_classification import PartialClassification
+from ..types.partial.classes.cls_conversation import PartialConversation
+from ..types.partial.classes.cls_message import PartialMessage
+from baml_core.stream import AsyncStream
+from baml_lib._impl.functions import BaseBAMLFunction
+from typing import AsyncIterator, Callable, List, Optional, Protocol, runtime_checkable
+
+
+IClassifyOutput = Classification
+
+@runtime_checkable
+class IClassify(Protocol):
+    """
+    This is the interface for a function.
+
+    Args:
+        conversation: Conversation
+        thread: Thread
+        hint: Optional[str]
+
+    Returns:
+        Classification
+    """
+
+    async def __call__(self, *, conversation: Conversation, thread: Thread, hint: Optional[str] = None) -> Classification:
+        ...
+
+   
+
+@runtime_checkable
+class IClassifyStream(Protocol):
+    """
+    This is the interface for a stream function.
+
+    Args:
+        conversation: Conversation
+        thread: Thread
+        hint: Optional[str]
+
+    Returns:
+        AsyncStream[Classification, PartialClassification]
+    """
+
+    def __call__(self, *, conversation: Conversation, thread: Thread, hint: Optional[str] = None
+) -> AsyncStream[Classification, PartialClassification]:
+        ...
+class IBAMLClassify(BaseBAMLFunction[Classification, PartialClassification]):
+    def __init__(self) -> None:
+        super().__init__(
+            "Classify",
+            IClassify,
+            ["v1", "v2"],
+        )
+
+    async def __call__(self, *args, **kwargs) -> Classification:
+        return await self.get_impl("v1").run(*args, **kwargs)
+    
+    def stream(self, *args, **kwargs) -> AsyncStream[Classification, PartialClassification]:
+        res = self.get_impl("v1").stream(*args, **kwargs)
+        return res
+
+BAMLClassify = IBAMLClassify()
+
+__all__ = [ "BAMLClassify" ]
@@ -0,0 +1,182 @@
+# This file is generated by the BAML compiler.
+# Do not edit this file directly.
+# Instead, edit the BAML files and recompile.
+
+# ruff: noqa: E501,F401
+# flake8: noqa: E501,F401
+# pylint: disable=unused-import,line-too-long
+# fmt: off
+
+from ..types.aliases.als_reply import Reply
+from ..types.aliases.als_thread import Thread
+from ..types.classes.cls_classification import Classification
+from ..types.classes.cls_conversation import Conversation
+from ..types.classes.cls_message import Message
+from ..types.enums.enm_sentiment import Sentiment
+from ..types.enums.enm_tool import Tool
+from ..types.partial.classes.cls_classification import PartialClassification
+from ..types.partial.classes.cls_conversation import PartialConversation
+from ..types.partial.classes.cls_message import PartialMessage
+from baml_core.stream import AsyncStream
+from typing import Callable, List, Optional, Protocol, runtime_checkable
+
+
+import typing
+
+import pytest
+from contextlib import contextmanager
+from unittest import mock
+
+ImplName = typing.Literal["v1", "v2"]
+
+T = typing.TypeVar("T", bound=typing.Callable[..., typing.Any])
+CLS = typing.TypeVar("CLS", bound=type)
+
+
+IClassifyOutput = Classification
+
+@runtime_checkable
+class IClassify(Protocol):
+    """
+    This is the interface for a function.
+
+    Args:
+        conversation: Conversation
+        thread: Thread
+        hint: Optional[str]
+
+    Returns:
+        Classification
+    """
+
+    async def __call__(self, *, conversation: Conversation, thread: Thread, hint: Optional[str] = None) -> Classification:
+        ...
+
+   
+
+@runtime_checkable
+class IClassifyStream(Protocol):
+    """
+    This is the interface for a stream function.
+
+    Args:
+        conversation: Conversation
+        thread: Thread
+        hint: Optional[str]
+
+    Returns:
+        AsyncStream[Classification, PartialClassification]
+    """
+
+    def __call__(self, *, conversation: Conversation, thread: Thread, hint: Optional[str] = None
+) -> AsyncStream[Classification, PartialClassification]:
+        ...
+class BAMLClassifyImpl:
+    async def run(self, *, conversation: Conversation, thread: Thread, hint: Optional[str] = None) -> Classification:
+        ...
+    
+    def stream(self, *, conversation: Conversation, thread: Thread, hint: Optional[str] = None
+) -> AsyncStream[Classification, PartialClassification]:
+        ...
+
+class IBAMLClassify:
+    def register_impl(
+        self, name: ImplName
+    ) -> typing.Callable[[IClassify, IClassifyStream], None]:
+        ...
+
+    async def __call__(self, *, conversation: Conversation, thread: Thread, hint: Optional[str] = None) -> Classification:
+        ...
+
+    def stream(self, *, conversation: Conversation, thread: Thread, hint: Optional[str] = None
+) -> AsyncStream[Classification, PartialClassification]:
+        ...
+
+    def get_impl(self, name: ImplName) -> BAMLClassifyImpl:
+        ...
+
+    @contextmanager
+    def mock(self) -> typing.Generator[mock.AsyncMock, None, None]:
+        """
+        Utility for mocking the ClassifyInterface.
+
+        Usage:
+            ```python
+            # All implementations are mocked.
+
+            async def test_logic() -> None:
+                with baml.Classify.mock() as mocked:
+                    mocked.return_value = ...
+                    result = await ClassifyImpl(...)
+                    assert mocked.called
+            ```
+        """
+        ...
+
+    @typing.overload
+    def test(self, test_function: T) -> T:
+        """
+        Provides a pytest.mark.parametrize decorator to facilitate testing different implementations of
+        the ClassifyInterface.
+
+        Args:
+            test_function : T
+                The test function to be decorated.
+
+        Usage:
+            ```python
+            # All implementations will be tested.
+
+            @baml.Classify.test
+            async def test_logic(ClassifyImpl: IClassify) -> None:
+                result = await ClassifyImpl(...)
+            ```
+        """
+        ...
+
+    @typing.overload
+    def test(self, *, exclude_impl: typing.Iterable[ImplName]) -> pytest.MarkDecorator:
+        """
+        Provides a pytest.mark.parametrize decorator to facilitate testing different implementations of
+        the ClassifyInterface.
+
+        Args:
+            exclude_impl : Iterable[ImplName]
+                The names of the implementations to exclude from testing.
+
+        Usage:
+            ```python
+            # All implementations except the given impl will be tested.
+
+            @baml.Classify.test(exclude_impl=["implname"])
+            async def test_logic(ClassifyImpl: IClassify) -> None:
+                result = await ClassifyImpl(...)
+            ```
+        """
+        ...
+
+    @typing.overload
+    def test(self, test_class: typing.Type[CLS]) -> typing.Type[CLS]:
+        """
+        Provides a pytest.mark.parametrize decorator to facilitate testing different implementations of
+        the ClassifyInterface.
+
+        Args:
+            test_class : Type[CLS]
+                The test class to be decorated.
+
+        Usage:
+        ```python
+        # All implementations will be tested in every test method.
+
+        @baml.Classify.test
+        class TestClass:
+            def test_a(self, ClassifyImpl: IClassify) -> None:
+                ...
+            def test_b(self, ClassifyImpl: IClassify) -> None:
+                ...
+        ```
+        """
+        ...
+
+BAMLClassify: IBAMLClassify
@@ -0,0 +1,68 @@
+# This file is generated by the BAML compiler.
+# Do not edit this file directly.
+# Instead, edit the BAML files and recompile.
+
+# ruff: noqa: E501,F401
+# flake8: noqa: E501,F401
+# pylint: disable=unused-import,line-too-long
+# fmt: off
+
+from ..types.classes.cls_message import Message
+from ..types.enums.enm_sentiment import Sentiment
+from ..types.partial.classes.cls_message import PartialMessage
+from baml_core.stream import AsyncStream
+from baml_lib._impl.functions import BaseBAMLFunction
+from typing import AsyncIterator, Callable, List, Protocol, runtime_checkable
+
+
+ISentimentsOutput = List[Sentiment]
+
+@runtime_checkable
+class ISentiments(Protocol):
+    """
+    This is the interface for a function.
+
+    Args:
+        arg: List[Message]
+
+    Returns:
+        List[Sentiment]
+    """
+
+    async def __call__(self, arg: List[Message], /) -> List[Sentiment]:
+        ...
+
+   
+
+@runtime_checkable
+class ISentimentsStream(Protocol):
+    """
+    This is the interface for a stream function.
+
+    Args:
+        arg: List[Message]
+
+    Returns:
+        AsyncStream[List[Sentiment], List[Sentiment]]
+    """
+
+    def __call__(self, arg: List[Message], /) -> AsyncStream[List[Sentiment], List[Sentiment]]:
+        ...
+class IBAMLSentiments(BaseBAMLFunction[List[Sentiment], List[Sentiment]]):
+    def __init__(self) -> None:
+        super().__init__(
+            "Sentiments",
+            ISentiments,
+            ["bodies"],
+        )
+
+    async def __call__(self, *args, **kwargs) -> List[Sentiment]:
+        return await self.get_impl("bodies").run(*args, **kwargs)
+    
+    def stream(self, *args, **kwargs) -> AsyncStream[List[Sentiment], List[Sentiment]]:
+        res = self.get_impl("bodies").stream(*args, **kwargs)
+        return res
+
+BAMLSentiments = IBAMLSentiments()
+
+__all__ = [ "BAMLSentiments" ]
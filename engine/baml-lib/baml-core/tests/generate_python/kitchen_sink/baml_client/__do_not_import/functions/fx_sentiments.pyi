@@ -0,0 +1,168 @@
+# This file is generated by the BAML compiler.
+# Do not edit this file directly.
+# Instead, edit the BAML files and recompile.
+
+# ruff: noqa: E501,F401
+# flake8: noqa: E501,F401
+# pylint: disable=unused-import,line-too-long
+# fmt: off
+
+from ..types.classes.cls_message import Message
+from ..types.enums.enm_sentiment import Sentiment
+from ..types.partial.classes.cls_message import PartialMessage
+from baml_core.stream import AsyncStream
+from typing import Callable, List, Protocol, runtime_checkable
+
+
+import typing
+
+import pytest
+from contextlib import contextmanager
+from unittest import mock
+
+ImplName = typing.Literal["bodies"]
+
+T = typing.TypeVar("T", bound=typing.Callable[..., typing.Any])
+CLS = typing.TypeVar("CLS", bound=type)
+
+
+ISentimentsOutput = List[Sentiment]
+
+@runtime_checkable
+class ISentiments(Protocol):
+    """
+    This is the interface for a function.
+
+    Args:
+        arg: List[Message]
+
+    Returns:
+        List[Sentiment]
+    """
+
+    async def __call__(self, arg: List[Message], /) -> List[Sentiment]:
+        ...
+
+   
+
+@runtime_checkable
+class ISentimentsStream(Protocol):
+    """
+    This is the interface for a stream function.
+
+    Args:
+        arg: List[Message]
+
+    Returns:
+        AsyncStream[List[Sentiment], List[Sentiment]]
+    """
+
+    def __call__(self, arg: List[Message], /) -> AsyncStream[List[Sentiment], List[Sentiment]]:
+        ...
+class BAMLSentimentsImpl:
+    async def run(self, arg: List[Message], /) -> List[Sentiment]:
+        ...
+    
+    def stream(self, arg: List[Message], /) -> AsyncStream[List[Sentiment], List[Sentiment]]:
+        ...
+
+class IBAMLSentiments:
+    def register_impl(
+        self, name: ImplName
+    ) -> typing.Callable[[ISentiments, ISentimentsStream], None]:
+        ...
+
+    async def __call__(self, arg: List[Message], /) -> List[Sentiment]:
+        ...
+
+    def stream(self, arg: List[Message], /) -> AsyncStream[List[Sentiment], List[Sentiment]]:
+        ...
+
+    def get_impl(self, name: ImplName) -> BAMLSentimentsImpl:
+        ...
+
+    @contextmanager
+    def mock(self) -> typing.Generator[mock.AsyncMock, None, None]:
+        """
+        Utility for mocking the SentimentsInterface.
+
+        Usage:
+            ```python
+            # All implementations are mocked.
+
+            async def test_logic() -> None:
+                with baml.Sentiments.mock() as mocked:
+                    mocked.return_value = ...
+                    result = await SentimentsImpl(...)
+                    assert mocked.called
+            ```
+        """
+        ...
+
+    @typing.overload
+    def test(self, test_function: T) -> T:
+        """
+        Provides a pytest.mark.parametrize decorator to facilitate testing different implementations of
+        the SentimentsInterface.
+
+        Args:
+            test_function : T
+                The test function to be decorated.
+
+        Usage:
+            ```python
+            # All implementations will be tested.
+
+            @baml.Sentiments.test
+            async def test_logic(SentimentsImpl: ISentiments) -> None:
+                result = await SentimentsImpl(...)
+            ```
+        """
+        ...
+
+    @typing.overload
+    def test(self, *, exclude_impl: typing.Iterable[ImplName]) -> pytest.MarkDecorator:
+        """
+        Provides a pytest.mark.parametrize decorator to facilitate testing different implementations of
+        the SentimentsInterface.
+
+        Args:
+            exclude_impl : Iterable[ImplName]
+                The names of the implementations to exclude from testing.
+
+        Usage:
+            ```python
+            # All implementations except the given impl will be tested.
+
+            @baml.Sentiments.test(exclude_impl=["implname"])
+            async def test_logic(SentimentsImpl: ISentiments) -> None:
+                result = await SentimentsImpl(...)
+            ```
+        """
+        ...
+
+    @typing.overload
+    def test(self, test_class: typing.Type[CLS]) -> typing.Type[CLS]:
+        """
+        Provides a pytest.mark.parametrize decorator to facilitate testing different implementations of
+        the SentimentsInterface.
+
+        Args:
+            test_class : Type[CLS]
+                The test class to be decorated.
+
+        Usage:
+        ```python
+        # All implementations will be tested in every test method.
+
+        @baml.Sentiments.test
+        class TestClass:
+            def test_a(self, SentimentsImpl: ISentiments) -> None:
+                ...
+            def test_b(self, SentimentsImpl: ISentiments) -> None:
+                ...
+        ```
+        """
+        ...
+
+BAMLSentiments: IBAMLSentiments
@@ -0,0 +1,65 @@
+# This file is generated by the BAML compiler.
+# Do not edit this file directly.
+# Instead, edit the BAML files and recompile.
+
+# ruff: noqa: E501,F401
+# flake8: noqa: E501,F401
+# pylint: disable=unused-import,line-too-long
+# fmt: off
+
+from baml_core.stream import AsyncStream
+from baml_lib._impl.functions import BaseBAMLFunction
+from typing import AsyncIterator, Callable, Optional, Protocol, runtime_checkable
+
+
+ISummarizeOutput = Optional[str]
+
+@runtime_checkable
+class ISummarize(Protocol):
+    """
+    This is the interface for a function.
+
+    Args:
+        arg: str
+
+    Returns:
+        Optional[str]
+    """
+
+    async def __call__(self, arg: str, /) -> Optional[str]:
+        ...
+
+   
+
+@runtime_checkable
+class ISummarizeStream(Protocol):
+    """
+    This is the interface for a stream function.
+
+    Args:
+        arg: str
+
+    Returns:
+        AsyncStream[Optional[str], Optional[str]]
+    """
+
+    def __call__(self, arg: str, /) -> AsyncStream[Optional[str], Optional[str]]:
+        ...
+class IBAMLSummarize(BaseBAMLFunction[Optional[str], Optional[str]]):
+    def __init__(self) -> None:
+        super().__init__(
+            "Summarize",
+            ISummarize,
+            ["simple"],
+        )
+
+    async def __call__(self, *args, **kwargs) -> Optional[str]:
+        return await self.get_impl("simple").run(*args, **kwargs)
+    
+    def stream(self, *args, **kwargs) -> AsyncStream[Optional[str], Optional[str]]:
+        res = self.get_impl("simple").stream(*args, **kwargs)
+        return res
+
+BAMLSummarize = IBAMLSummarize()
+
+__all__ = [ "BAMLSummarize" ]
@@ -0,0 +1,15 @@
+# This file is generated by the BAML compiler.
+# Do not edit this file directly.
+# Instead, edit the BAML files and recompile.
+
+# ruff: noqa: E501,F401
+# flake8: noqa: E501,F401
+# pylint: disable=unused-import,line-too-long
+# fmt: off
+
+
+
+from .fx_classify_impl_v1 import v1 as unused_fx_classify_impl_v1
+from .fx_classify_impl_v2 import v2 as unused_fx_classify_impl_v2
+from .fx_sentiments_impl_bodies import bodies as unused_fx_sentiments_impl_bodies
+from .fx_summarize_impl_simple import simple as unused_fx_summarize_impl_simple
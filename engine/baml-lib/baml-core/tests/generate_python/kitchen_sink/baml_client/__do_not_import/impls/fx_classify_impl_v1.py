@@ -0,0 +1,110 @@
+# This file is generated by the BAML compiler.
+# Do not edit this file directly.
+# Instead, edit the BAML files and recompile.
+
+# ruff: noqa: E501,F401
+# flake8: noqa: E501,F401
+# pylint: disable=unused-import,line-too-long
+# fmt: off
+
+from ..clients.client_gpt4 import GPT4
+from ..functions.fx_classify import BAMLClassify
+from ..types.aliases.als_reply import Reply
+from ..types.aliases.als_thread import Thread
+from ..types.classes.cls_classification import Classification
+from ..types.classes.cls_conversation import Conversation
+from ..types.classes.cls_message import Message
+from ..types.enums.enm_sentiment import Sentiment
+from ..types.enums.enm_tool import Tool
+from ..types.partial.classes.cls_classification import PartialClassification
+from ..types.partial.classes.cls_conversation import PartialConversation
+from ..types.partial.classes.cls_message import PartialMessage
+from baml_core.provider_manager.llm_provider_chat import LLMChatMessage
+from baml_core.provider_manager.llm_response import LLMResponse
+from baml_core.stream import AsyncStream
+from baml_lib._impl.deserializer import Deserializer
+from typing import List, Optional
+
+
+import typing
+# Impl: v1
+# Client: GPT4
+# An implementation of Classify.
+
+__prompt_template: List[LLMChatMessage] = [
+{
+    "role": "system",
+    "content": """\
+Classify this conversation.
+
+Tool
+---
+code: Run some code
+k2
+
+Use this output format:
+{
+  "tools": "Tool as string"[],
+  "sentiment": "Sentiment as string",
+  "answer": string | {
+    "from": string,
+    "body": string,
+    "sentiment": "Sentiment as string" | null
+  },
+  "confidence": float | null
+}\
+"""
+}
+,
+{
+    "role": "user",
+    "content": """\
+{conversation}
+{#if hint}Hint: {hint}
+{#end}{#for message in thread}- {message.sender}: {message.body}
+{#end}\
+"""
+}
+
+]
+
+__input_replacers = {
+    "{conversation}",
+    "{hint}",
+    "{message.body}",
+    "{message.sender}"
+}
+
+
+# We ignore the type here because baml does some type magic to make this work
+# for inline SpecialForms like Optional, Union, List.
+__deserializer = Deserializer[Classification](Classification)  # type: ignore
+__deserializer.overload("Classification", {"answer": "reply"})
+__deserializer.overload("Tool", {"code": "CodeInterpreter"})
+
+# Add a deserializer that handles stream responses, which are all Partial types
+__partial_deserializer = Deserializer[PartialClassification](PartialClassification)  # type: ignore
+__partial_deserializer.overload("Classification", {"answer": "reply"})
+__partial_deserializer.overload("Tool", {"code": "CodeInterpreter"})
+
+
+
+
+
+
+
+async def v1(*, conversation: Conversation, thread: Thread, hint: Optional[str] = None) -> Classification:
+    response = await GPT4.run_chat_template(__prompt_template, replacers=__input_replacers, params=dict(conversation=conversation, thread=thread, hint=hint))
+    deserialized = __deserializer.from_string(response.generated)
+    return deserialized
+
+
+def v1_stream(*, conversation: Conversation, thread: Thread, hint: Optional[str] = None
+) -> AsyncStream[Classification, PartialClassification]:
+    def run_prompt() -> typing.AsyncIterator[LLMResponse]:
+        raw_stream = GPT4.run_chat_template_stream(__prompt_template, replacers=__input_replacers, params=dict(conversation=conversation, thread=thread, hint=hint))
+        return raw_stream
+    stream = AsyncStream(stream_cb=run_prompt, partial_deserializer=__partial_deserializer, final_deserializer=__deserializer)
+    return stream
+
+BAMLClassify.register_impl("v1")(v1, v1_stream)
\ No newline at end of file
@@ -0,0 +1,87 @@
+# This file is generated by the BAML compiler.
+# Do not edit this file directly.
+# Instead, edit the BAML files and recompile.
+
+# ruff: noqa: E501,F401
+# flake8: noqa: E501,F401
+# pylint: disable=unused-import,line-too-long
+# fmt: off
+
+from ..clients.client_fallback import Fallback
+from ..functions.fx_classify import BAMLClassify
+from ..types.aliases.als_reply import Reply
+from ..types.aliases.als_thread import Thread
+from ..types.classes.cls_classification import Classification
+from ..types.classes.cls_conversation import Conversation
+from ..types.classes.cls_message import Message
+from ..types.enums.enm_sentiment import Sentiment
+from ..types.enums.enm_tool import Tool
+from ..types.partial.classes.cls_classification import PartialClassification
+from ..types.partial.classes.cls_conversation import PartialConversation
+from ..types.partial.classes.cls_message import PartialMessage
+from baml_core.provider_manager.llm_response import LLMResponse
+from baml_core.stream import AsyncStream
+from baml_lib._impl.deserializer import Deserializer
+from typing import List, Optional
+
+
+import typing
+# Impl: v2
+# Client: Fallback
+# An implementation of Classify.
+
+__prompt_template = """\
+Classify:
+{conversation.thread}
+First sender: {conversation.thread[0].sender}
+Every message: {conversation.thread[*].body}
+Turns: {conversation.meta["turns"]}
+
+{
+  "tools": "Tool as string"[],
+  "sentiment": "Sentiment as string",
+  "reply": string | {
+    "from": string,
+    "body": string,
+    "sentiment": "Sentiment as string" | null
+  },
+  "confidence": float | null
+}\
+"""
+
+__input_replacers = {
+    "{conversation.meta[\"turns\"]}",
+    "{conversation.thread}",
+    "{conversation.thread[*].body}",
+    "{conversation.thread[0].sender}"
+}
+
+
+# We ignore the type here because baml does some type magic to make this work
+# for inline SpecialForms like Optional, Union, List.
+__deserializer = Deserializer[Classification](Classification)  # type: ignore
+
+# Add a deserializer that handles stream responses, which are all Partial types
+__partial_deserializer = Deserializer[PartialClassification](PartialClassification)  # type: ignore
+
+
+
+
+
+
+
+async def v2(*, conversation: Conversation, thread: Thread, hint: Optional[str] = None) -> Classification:
+    response = await Fallback.run_prompt_template(template=__prompt_template, replacers=__input_replacers, params=dict(conversation=conversation, thread=thread, hint=hint))
+    deserialized = __deserializer.from_string(response.generated)
+    return deserialized
+
+
+def v2_stream(*, conversation: Conversation, thread: Thread, hint: Optional[str] = None
+) -> AsyncStream[Classification, PartialClassification]:
+    def run_prompt() -> typing.AsyncIterator[LLMResponse]:
+        raw_stream = Fallback.run_prompt_template_stream(template=__prompt_template, replacers=__input_replacers, params=dict(conversation=conversation, thread=thread, hint=hint))
+        return raw_stream
+    stream = AsyncStream(stream_cb=run_prompt, partial_deserializer=__partial_deserializer, final_deserializer=__deserializer)
+    return stream
+
+BAMLClassify.register_impl("v2")(v2, v2_stream)
\ No newline at end of file
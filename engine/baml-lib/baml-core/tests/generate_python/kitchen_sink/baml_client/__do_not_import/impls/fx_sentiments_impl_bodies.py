@@ -0,0 +1,67 @@
+# This file is generated by the BAML compiler.
+# Do not edit this file directly.
+# Instead, edit the BAML files and recompile.
+
+# ruff: noqa: E501,F401
+# flake8: noqa: E501,F401
+# pylint: disable=unused-import,line-too-long
+# fmt: off
+
+from ..clients.client_gpt4 import GPT4
+from ..functions.fx_sentiments import BAMLSentiments
+from ..types.classes.cls_message import Message
+from ..types.enums.enm_sentiment import Sentiment
+from ..types.partial.classes.cls_message import PartialMessage
+from baml_core.provider_manager.llm_response import LLMResponse
+from baml_core.stream import AsyncStream
+from baml_lib._impl.deserializer import Deserializer
+from typing import List
+
+
+import typing
+# Impl: bodies
+# Client: GPT4
+# An implementation of Sentiments.
+
+__prompt_template = """\
+Classify the sentiment of each message:
+{arg[*]}
+
+"Sentiment as string"[]\
+"""
+
+__input_replacers = {
+    "{arg[*]}"
+}
+
+
+# We ignore the type here because baml does some type magic to make this work
+# for inline SpecialForms like Optional, Union, List.
+__deserializer = Deserializer[List[Sentiment]](List[Sentiment])  # type: ignore
+
+# Add a deserializer that handles stream responses, which are all Partial types
+__partial_deserializer = Deserializer[List[Sentiment]](List[Sentiment])  # type: ignore
+
+
+
+
+def input_adapter(arg: List[Message], /) -> List[str]:
+    return [message.body for message in arg]
+
+
+async def bodies(arg: List[Message], /) -> List[Sentiment]:
+    adapted_input = input_adapter(arg)
+    response = await GPT4.run_prompt_template(template=__prompt_template, replacers=__input_replacers, params=dict(arg=adapted_input))
+    deserialized = __deserializer.from_string(response.generated)
+    return deserialized
+
+
+def bodies_stream(arg: List[Message], /) -> AsyncStream[List[Sentiment], List[Sentiment]]:
+    def run_prompt() -> typing.AsyncIterator[LLMResponse]:
+        adapted_input = input_adapter(arg)
+        raw_stream = GPT4.run_prompt_template_stream(template=__prompt_template, replacers=__input_replacers, params=dict(arg=adapted_input))
+        return raw_stream
+    stream = AsyncStream(stream_cb=run_prompt, partial_deserializer=__partial_deserializer, final_deserializer=__deserializer)
+    return stream
+
+BAMLSentiments.register_impl("bodies")(bodies, bodies_stream)
\ No newline at end of file
@@ -0,0 +1,62 @@
+# This file is generated by the BAML compiler.
+# Do not edit this file directly.
+# Instead, edit the BAML files and recompile.
+
+# ruff: noqa: E501,F401
+# flake8: noqa: E501,F401
+# pylint: disable=unused-import,line-too-long
+# fmt: off
+
+from ..clients.client_gpt4 import GPT4
+from ..functions.fx_summarize import BAMLSummarize
+from ..types.classes.cls_message import Message
+from baml_core.provider_manager.llm_response import LLMResponse
+from baml_core.stream import AsyncStream
+from baml_lib._impl.deserializer import Deserializer
+from typing import Optional
+
+
+import typing
+# Impl: simple
+# Client: GPT4
+# An implementation of Summarize.
+
+__prompt_template = """\
+Summarize the following: {arg}
+
+{
+  "from": string,
+  "body": string,
+  "sentiment": "Sentiment as string" | null
+}\
+"""
+
+__input_replacers = {
+    "{arg}"
+}
+
+
+# We ignore the type here because baml does some type magic to make this work
+# for inline SpecialForms like Optional, Union, List.
+__deserializer = Deserializer[Message](Message)  # type: ignore
+
+# Add a deserializer that handles stream responses, which are all Partial types
+__partial_deserializer = Deserializer[Message](Message)  # type: ignore
+
+
+def output_adapter(arg: Message) -> Optional[str]:
+    return arg.body
+
+
+
+
+async def simple(arg: str, /) -> Optional[str]:
+    response = await GPT4.run_prompt_template(template=__prompt_template, replacers=__input_replacers, params=dict(arg=arg))
+    deserialized = __deserializer.from_string(response.generated)
+    return output_adapter(deserialized)
+
+
+def simple_stream(arg: str, /) -> AsyncStream[Optional[str], Optional[str]]:
+    raise NotImplementedError("Stream functions do not support output adapters")
+
+BAMLSummarize.register_impl("simple")(simple, simple_stream)
\ No newline at end of file
@@ -0,0 +1,29 @@
+# This file is generated by the BAML compiler.
+# Do not edit this file directly.
+# Instead, edit the BAML files and recompile.
+
+# ruff: noqa: E501,F401
+# flake8: noqa: E501,F401
+# pylint: disable=unused-import,line-too-long
+# fmt: off
+
+from .aliases.als_reply import Reply
+from .aliases.als_thread import Thread
+from .classes.cls_classification import Classification
+from .classes.cls_conversation import Conversation
+from .classes.cls_message import Message
+from .enums.enm_sentiment import Sentiment
+from .enums.enm_tool import Tool
+
+
+
+
+__all__ = [
+    'Reply',
+    'Thread',
+    'Classification',
+    'Conversation',
+    'Message',
+    'Sentiment',
+    'Tool'
+]
@@ -0,0 +1,24 @@
+# This file is generated by the BAML compiler.
+# Do not edit this file directly.
+# Instead, edit the BAML files and recompile.
+
+# ruff: noqa: E501,F401
+# flake8: noqa: E501,F401
+# pylint: disable=unused-import,line-too-long
+# fmt: off
+
+from ..aliases.als_reply import Reply
+from ..enums.enm_sentiment import Sentiment
+from ..enums.enm_tool import Tool
+from .cls_message import Message
+from baml_lib._impl.deserializer import register_deserializer
+from pydantic import BaseModel
+from typing import List, Optional, Union
+
+
+@register_deserializer({  })
+class Classification(BaseModel):
+    tools: List[Tool]
+    sentiment: Sentiment
+    reply: Reply
+    confidence: Optional[float] = None
@@ -0,0 +1,20 @@
+# This file is generated by the BAML compiler.
+# Do not edit this file directly.
+# Instead, edit the BAML files and recompile.
+
+# ruff: noqa: E501,F401
+# flake8: noqa: E501,F401
+# pylint: disable=unused-import,line-too-long
+# fmt: off
+
+from ..enums.enm_sentiment import Sentiment
+from baml_lib._impl.deserializer import register_deserializer
+from pydantic import BaseModel
+from typing import Optional
+
+
+@register_deserializer({ "from": "sender", })
+class Message(BaseModel):
+    sender: str
+    body: str
+    sentiment: Optional[Sentiment] = None
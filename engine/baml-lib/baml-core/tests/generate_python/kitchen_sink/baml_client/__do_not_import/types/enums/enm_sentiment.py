@@ -0,0 +1,18 @@
+# This file is generated by the BAML compiler.
+# Do not edit this file directly.
+# Instead, edit the BAML files and recompile.
+
+# ruff: noqa: E501,F401
+# flake8: noqa: E501,F401
+# pylint: disable=unused-import,line-too-long
+# fmt: off
+
+from baml_lib._impl.deserializer import register_deserializer
+from enum import Enum
+
+
+@register_deserializer({ "pos": "POSITIVE","neg": "NEGATIVE", })
+class Sentiment(str, Enum):
+    POSITIVE = "POSITIVE"
+    NEGATIVE = "NEGATIVE"
+    NEUTRAL = "NEUTRAL"
@@ -0,0 +1,21 @@
+# This file is generated by the BAML compiler.
+# Do not edit this file directly.
+# Instead, edit the BAML files and recompile.
+
+# ruff: noqa: E501,F401
+# flake8: noqa: E501,F401
+# pylint: disable=unused-import,line-too-long
+# fmt: off
+
+from .classes.cls_classification import PartialClassification
+from .classes.cls_conversation import PartialConversation
+from .classes.cls_message import PartialMessage
+
+
+
+
+__all__ = [
+    'PartialClassification',
+    'PartialConversation',
+    'PartialMessage'
+]
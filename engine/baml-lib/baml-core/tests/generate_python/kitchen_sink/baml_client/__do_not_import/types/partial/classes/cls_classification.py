@@ -0,0 +1,25 @@
+# This file is generated by the BAML compiler.
+# Do not edit this file directly.
+# Instead, edit the BAML files and recompile.
+
+# ruff: noqa: E501,F401
+# flake8: noqa: E501,F401
+# pylint: disable=unused-import,line-too-long
+# fmt: off
+
+from ...aliases.als_reply import Reply
+from ...classes.cls_message import Message
+from ...enums.enm_sentiment import Sentiment
+from ...enums.enm_tool import Tool
+from .cls_message import PartialMessage
+from baml_lib._impl.deserializer import register_deserializer
+from pydantic import BaseModel
+from typing import List, Optional, Union
+
+
+@register_deserializer({  })
+class PartialClassification(BaseModel):
+    tools: List[Tool]
+    sentiment: Optional[Sentiment] = None
+    reply: Optional[Union[str, Message]] = None
+    confidence: Optional[float] = None
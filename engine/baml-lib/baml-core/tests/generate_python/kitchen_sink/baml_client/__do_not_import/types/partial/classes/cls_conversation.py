@@ -0,0 +1,28 @@
+# This file is generated by the BAML compiler.
+# Do not edit this file directly.
+# Instead, edit the BAML files and recompile.
+
+# ruff: noqa: E501,F401
+# flake8: noqa: E501,F401
+# pylint: disable=unused-import,line-too-long
+# fmt: off
+
+from ...classes.cls_message import Message
+from ...enums.enm_sentiment import Sentiment
+from .cls_message import PartialMessage
+from baml_lib._impl.deserializer import register_deserializer
+from pydantic import BaseModel
+from typing import Dict, List, Optional, Tuple, Union
+
+
+@register_deserializer({  })
+class PartialConversation(BaseModel):
+    thread: List[PartialMessage]
+    tags: List[List[str]]
+    meta: Dict[Optional[str], Optional[int]]
+    pinned: Union[Message, str, None]
+    span: Optional[Tuple[Optional[int], Optional[int]]] = None
+    summary: Optional[str] = None
+    @property
+    def title(self) -> str:
+        return self.thread[0].body
@@ -0,0 +1,14 @@
+# This file is generated by the BAML compiler.
+# Do not edit this file directly.
+# Instead, edit the BAML files and recompile.
+
+# ruff: noqa: E501,F401
+# flake8: noqa: E501,F401
+# pylint: disable=unused-import,line-too-long
+# fmt: off
+
+
+
+from baml_lib import baml_init
+from .__do_not_import.generated_baml_client import baml
+__all__ = ['baml', 'baml_init']
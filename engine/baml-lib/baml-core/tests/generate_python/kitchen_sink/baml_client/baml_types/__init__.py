@@ -0,0 +1,38 @@
+# This file is generated by the BAML compiler.
+# Do not edit this file directly.
+# Instead, edit the BAML files and recompile.
+
+# ruff: noqa: E501,F401
+# flake8: noqa: E501,F401
+# pylint: disable=unused-import,line-too-long
+# fmt: off
+
+from ..__do_not_import.functions.fx_classify import IClassify, IClassifyOutput
+from ..__do_not_import.functions.fx_sentiments import ISentiments, ISentimentsOutput
+from ..__do_not_import.functions.fx_summarize import ISummarize, ISummarizeOutput
+from ..__do_not_import.types.aliases.als_reply import Reply
+from ..__do_not_import.types.aliases.als_thread import Thread
+from ..__do_not_import.types.classes.cls_classification import Classification
+from ..__do_not_import.types.classes.cls_conversation import Conversation
+from ..__do_not_import.types.classes.cls_message import Message
+from ..__do_not_import.types.enums.enm_sentiment import Sentiment
+from ..__do_not_import.types.enums.enm_tool import Tool
+
+
+
+
+__all__ = [
+    'IClassify',
+    'IClassifyOutput',
+    'ISentiments',
+    'ISentimentsOutput',
+    'ISummarize',
+    'ISummarizeOutput',
+    'Reply',
+    'Thread',
+    'Classification',
+    'Conversation',
+    'Message',
+    'Sentiment',
+    'Tool'
+]
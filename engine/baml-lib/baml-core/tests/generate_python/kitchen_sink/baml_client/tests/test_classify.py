@@ -0,0 +1,58 @@
+# This file is generated by the BAML compiler.
+# Do not edit this file directly.
+# Instead, edit the BAML files and recompile.
+
+# ruff: noqa: E501,F401
+# flake8: noqa: E501,F401
+# pylint: disable=unused-import,line-too-long
+# fmt: off
+
+from ..__do_not_import.generated_baml_client import baml
+from ..baml_types import Classification, Conversation, IClassify, Message, Reply, Sentiment, Thread, Tool
+from baml_lib._impl.deserializer import Deserializer
+from json import dumps
+from typing import Any, Optional
+
+
+@baml.Classify.test(exclude_impl=["v1"])
+async def test_classify_pinned(ClassifyImpl: IClassify):
+    def to_str(item: Any) -> str:
+        if isinstance(item, str):
+            return item
+        return dumps(item)
+
+    case = {"conversation": {"thread": [{"sender": "a", "body": "hi", }], "tags": [["urgent"]], "meta": {"retries": 2, }, "pinned": "keep this", "span": [0, 3], }, "hint": None, "thread": [], }
+    deserializer_conversation = Deserializer[Conversation](Conversation) # type: ignore
+    conversation = deserializer_conversation.from_string(to_str(case["conversation"]))
+    deserializer_hint = Deserializer[Optional[str]](Optional[str]) # type: ignore
+    hint = deserializer_hint.from_string(to_str(case["hint"]))
+    deserializer_thread = Deserializer[Thread](Thread) # type: ignore
+    thread = deserializer_thread.from_string(to_str(case["thread"]))
+    await ClassifyImpl(
+        conversation=conversation,
+        hint=hint,
+        thread=thread
+    )
+
+
+@baml.Classify.test
+async def test_first_case(ClassifyImpl: IClassify):
+    def to_str(item: Any) -> str:
+        if isinstance(item, str):
+            return item
+        return dumps(item)
+
+    case = {"conversation": {"thread": [{"sender": "a", "body": "hi", }], "tags": [], "meta": {}, "span": [1, 2], }, "hint": None, "thread": [], }
+    deserializer_conversation = Deserializer[Conversation](Conversation) # type: ignore
+    conversation = deserializer_conversation.from_string(to_str(case["conversation"]))
+    deserializer_hint = Deserializer[Optional[str]](Optional[str]) # type: ignore
+    hint = deserializer_hint.from_string(to_str(case["hint"]))
+    deserializer_thread = Deserializer[Thread](Thread) # type: ignore
+    thread = deserializer_thread.from_string(to_str(case["thread"]))
+    await ClassifyImpl(
+        conversation=conversation,
+        hint=hint,
+        thread=thread
+    )
+
+
@@ -0,0 +1,29 @@
+# This file is generated by the BAML compiler.
+# Do not edit this file directly.
+# Instead, edit the BAML files and recompile.
+
+# ruff: noqa: E501,F401
+# flake8: noqa: E501,F401
+# pylint: disable=unused-import,line-too-long
+# fmt: off
+
+from ..__do_not_import.generated_baml_client import baml
+from ..baml_types import ISummarize
+from baml_lib._impl.deserializer import Deserializer
+from json import dumps
+from typing import Any
+
+
+@baml.Summarize.test
+async def test_short_one(SummarizeImpl: ISummarize):
+    def to_str(item: Any) -> str:
+        if isinstance(item, str):
+            return item
+        return dumps(item)
+
+    content = to_str("hello world")
+    deserializer = Deserializer[str](str) # type: ignore
+    param = deserializer.from_string(content)
+    await SummarizeImpl(param)
+
+
@@ -0,0 +1,70 @@
+# This file is generated by the BAML compiler.
+# Do not edit this file directly.
+# Instead, edit the BAML files and recompile.
+
+# ruff: noqa: E501,F401
+# flake8: noqa: E501,F401
+# pylint: disable=unused-import,line-too-long
+# fmt: off
+
+from ..types.aliases.als_version import Version
+from ..types.classes.cls_document import Document
+from ..types.classes.cls_response import Response
+from ..types.partial.classes.cls_document import PartialDocument
+from ..types.partial.classes.cls_response import PartialResponse
+from baml_core.stream import AsyncStream
+from baml_lib._impl.functions import BaseBAMLFunction
+from typing import AsyncIterator, Callable, Protocol, runtime_checkable
+
+
+IFetchOutput = Document
+
+@runtime_checkable
+class IFetch(Protocol):
+    """
+    This is the interface for a function.
+
+    Args:
+        arg: str
+
+    Returns:
+        Document
+    """
+
+    async def __call__(self, arg: str, /) -> Document:
+        ...
+
+   
+
+@runtime_checkable
+class IFetchStream(Protocol):
+    """
+    This is the interface for a stream function.
+
+    Args:
+        arg: str
+
+    Returns:
+        AsyncStream[Document, PartialDocument]
+    """
+
+    def __call__(self, arg: str, /) -> AsyncStream[Document, PartialDocument]:
+        ...
+class IBAMLFetch(BaseBAMLFunction[Document, PartialDocument]):
+    def __init__(self) -> None:
+        super().__init__(
+            "Fetch",
+            IFetch,
+            ["v1"],
+        )
+
+    async def __call__(self, *args, **kwargs) -> Document:
+        return await self.get_impl("v1").run(*args, **kwargs)
+    
+    def stream(self, *args, **kwargs) -> AsyncStream[Document, PartialDocument]:
+        res = self.get_impl("v1").stream(*args, **kwargs)
+        return res
+
+BAMLFetch = IBAMLFetch()
+
+__all__ = [ "BAMLFetch" ]
@@ -0,0 +1,170 @@
+# This file is generated by the BAML compiler.
+# Do not edit this file directly.
+# Instead, edit the BAML files and recompile.
+
+# ruff: noqa: E501,F401
+# flake8: noqa: E501,F401
+# pylint: disable=unused-import,line-too-long
+# fmt: off
+
+from ..types.aliases.als_version import Version
+from ..types.classes.cls_document import Document
+from ..types.classes.cls_response import Response
+from ..types.partial.classes.cls_document import PartialDocument
+from ..types.partial.classes.cls_response import PartialResponse
+from baml_core.stream import AsyncStream
+from typing import Callable, Protocol, runtime_checkable
+
+
+import typing
+
+import pytest
+from contextlib import contextmanager
+from unittest import mock
+
+ImplName = typing.Literal["v1"]
+
+T = typing.TypeVar("T", bound=typing.Callable[..., typing.Any])
+CLS = typing.TypeVar("CLS", bound=type)
+
+
+IFetchOutput = Document
+
+@runtime_checkable
+class IFetch(Protocol):
+    """
+    This is the interface for a function.
+
+    Args:
+        arg: str
+
+    Returns:
+        Document
+    """
+
+    async def __call__(self, arg: str, /) -> Document:
+        ...
+
+   
+
+@runtime_checkable
+class IFetchStream(Protocol):
+    """
+    This is the interface for a stream function.
+
+    Args:
+        arg: str
+
+    Returns:
+        AsyncStream[Document, PartialDocument]
+    """
+
+    def __call__(self, arg: str, /) -> AsyncStream[Document, PartialDocument]:
+        ...
+class BAMLFetchImpl:
+    async def run(self, arg: str, /) -> Document:
+        ...
+    
+    def stream(self, arg: str, /) -> AsyncStream[Document, PartialDocument]:
+        ...
+
+class IBAMLFetch:
+    def register_impl(
+        self, name: ImplName
+    ) -> typing.Callable[[IFetch, IFetchStream], None]:
+        ...
+
+    async def __call__(self, arg: str, /) -> Document:
+        ...
+
+    def stream(self, arg: str, /) -> AsyncStream[Document, PartialDocument]:
+        ...
+
+    def get_impl(self, name: ImplName) -> BAMLFetchImpl:
+        ...
+
+    @contextmanager
+    def mock(self) -> typing.Generator[mock.AsyncMock, None, None]:
+        """
+        Utility for mocking the FetchInterface.
+
+        Usage:
+            ```python
+            # All implementations are mocked.
+
+            async def test_logic() -> None:
+                with baml.Fetch.mock() as mocked:
+                    mocked.return_value = ...
+                    result = await FetchImpl(...)
+                    assert mocked.called
+            ```
+        """
+        ...
+
+    @typing.overload
+    def test(self, test_function: T) -> T:
+        """
+        Provides a pytest.mark.parametrize decorator to facilitate testing different implementations of
+        the FetchInterface.
+
+        Args:
+            test_function : T
+                The test function to be decorated.
+
+        Usage:
+            ```python
+            # All implementations will be tested.
+
+            @baml.Fetch.test
+            async def test_logic(FetchImpl: IFetch) -> None:
+                result = await FetchImpl(...)
+            ```
+        """
+        ...
+
+    @typing.overload
+    def test(self, *, exclude_impl: typing.Iterable[ImplName]) -> pytest.MarkDecorator:
+        """
+        Provides a pytest.mark.parametrize decorator to facilitate testing different implementations of
+        the FetchInterface.
+
+        Args:
+            exclude_impl : Iterable[ImplName]
+                The names of the implementations to exclude from testing.
+
+        Usage:
+            ```python
+            # All implementations except the given impl will be tested.
+
+            @baml.Fetch.test(exclude_impl=["implname"])
+            async def test_logic(FetchImpl: IFetch) -> None:
+                result = await FetchImpl(...)
+            ```
+        """
+        ...
+
+    @typing.overload
+    def test(self, test_class: typing.Type[CLS]) -> typing.Type[CLS]:
+        """
+        Provides a pytest.mark.parametrize decorator to facilitate testing different implementations of
+        the FetchInterface.
+
+        Args:
+            test_class : Type[CLS]
+                The test class to be decorated.
+
+        Usage:
+        ```python
+        # All implementations will be tested in every test method.
+
+        @baml.Fetch.test
+        class TestClass:
+            def test_a(self, FetchImpl: IFetch) -> None:
+                ...
+            def test_b(self, FetchImpl: IFetch) -> None:
+                ...
+        ```
+        """
+        ...
+
+BAMLFetch: IBAMLFetch
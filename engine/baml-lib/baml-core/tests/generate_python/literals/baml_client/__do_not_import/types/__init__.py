@@ -0,0 +1,21 @@
+# This file is generated by the BAML compiler.
+# Do not edit this file directly.
+# Instead, edit the BAML files and recompile.
+
+# ruff: noqa: E501,F401
+# flake8: noqa: E501,F401
+# pylint: disable=unused-import,line-too-long
+# fmt: off
+
+from .aliases.als_version import Version
+from .classes.cls_document import Document
+from .classes.cls_response import Response
+
+
+
+
+__all__ = [
+    'Version',
+    'Document',
+    'Response'
+]
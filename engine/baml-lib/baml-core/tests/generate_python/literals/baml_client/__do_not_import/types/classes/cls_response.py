@@ -0,0 +1,20 @@
+# This file is generated by the BAML compiler.
+# Do not edit this file directly.
+# Instead, edit the BAML files and recompile.
+
+# ruff: noqa: E501,F401
+# flake8: noqa: E501,F401
+# pylint: disable=unused-import,line-too-long
+# fmt: off
+
+from baml_lib._impl.deserializer import register_deserializer
+from pydantic import BaseModel
+from typing import Literal, Optional
+
+
+@register_deserializer({  })
+class Response(BaseModel):
+    status: Literal["ok", "error"]
+    code: Literal[200, 404]
+    cached: Literal[True]
+    note: Optional[Literal["draft"]] = None
@@ -0,0 +1,22 @@
+# This file is generated by the BAML compiler.
+# Do not edit this file directly.
+# Instead, edit the BAML files and recompile.
+
+# ruff: noqa: E501,F401
+# flake8: noqa: E501,F401
+# pylint: disable=unused-import,line-too-long
+# fmt: off
+
+from ...aliases.als_version import Version
+from ...classes.cls_response import Response
+from .cls_response import PartialResponse
+from baml_lib._impl.deserializer import register_deserializer
+from pydantic import BaseModel
+from typing import Literal, Optional, Union
+
+
+@register_deserializer({  })
+class PartialDocument(BaseModel):
+    version: Optional[Literal[1, 2]] = None
+    response: Optional[PartialResponse] = None
+    kind: Union[Literal["report"], Response]
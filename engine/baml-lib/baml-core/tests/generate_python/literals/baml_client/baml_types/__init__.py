@@ -0,0 +1,24 @@
+# This file is generated by the BAML compiler.
+# Do not edit this file directly.
+# Instead, edit the BAML files and recompile.
+
+# ruff: noqa: E501,F401
+# flake8: noqa: E501,F401
+# pylint: disable=unused-import,line-too-long
+# fmt: off
+
+from ..__do_not_import.functions.fx_fetch import IFetch, IFetchOutput
+from ..__do_not_import.types.aliases.als_version import Version
+from ..__do_not_import.types.classes.cls_document import Document
+from ..__do_not_import.types.classes.cls_response import Response
+
+
+
+
+__all__ = [
+    'IFetch',
+    'IFetchOutput',
+    'Version',
+    'Document',
+    'Response'
+]
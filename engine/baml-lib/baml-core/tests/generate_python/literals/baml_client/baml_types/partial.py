@@ -0,0 +1,19 @@
+# This file is generated by the BAML compiler.
+# Do not edit this file directly.
+# Instead, edit the BAML files and recompile.
+
+# ruff: noqa: E501,F401
+# flake8: noqa: E501,F401
+# pylint: disable=unused-import,line-too-long
+# fmt: off
+
+from ..__do_not_import.types.partial.classes.cls_document import PartialDocument
+from ..__do_not_import.types.partial.classes.cls_response import PartialResponse
+
+
+
+
+__all__ = [
+    'PartialDocument',
+    'PartialResponse'
+]
@@ -0,0 +1,20 @@
+# This file is generated by the BAML compiler.
+# Do not edit this file directly.
+# Instead, edit the BAML files and recompile.
+
+# ruff: noqa: E501,F401
+# flake8: noqa: E501,F401
+# pylint: disable=unused-import,line-too-long
+# fmt: off
+
+from baml_core.otel import set_tags, trace
+from baml_core.services.api_types import LogSchema
+
+
+
+
+__all__ = [
+    'trace',
+    'set_tags',
+    'LogSchema'
+]
@@ -0,0 +1,71 @@
+# This file is generated by the BAML compiler.
+# Do not edit this file directly.
+# Instead, edit the BAML files and recompile.
+
+# ruff: noqa: E501,F401
+# flake8: noqa: E501,F401
+# pylint: disable=unused-import,line-too-long
+# fmt: off
+
+from ..types.classes.cls_comment import Comment
+from ..types.classes.cls_reply import Reply
+from ..types.classes.cls_tree import Tree
+from ..types.partial.classes.cls_comment import PartialComment
+from ..types.partial.classes.cls_reply import PartialReply
+from ..types.partial.classes.cls_tree import PartialTree
+from baml_core.stream import AsyncStream
+from baml_lib._impl.functions import BaseBAMLFunction
+from typing import AsyncIterator, Callable, Protocol, runtime_checkable
+
+
+IOutlineOutput = Comment
+
+@runtime_checkable
+class IOutline(Protocol):
+    """
+    This is the interface for a function.
+
+    Args:
+        arg: Tree
+
+    Returns:
+        Comment
+    """
+
+    async def __call__(self, arg: Tree, /) -> Comment:
+        ...
+
+   
+
+@runtime_checkable
+class IOutlineStream(Protocol):
+    """
+    This is the interface for a stream function.
+
+    Args:
+        arg: Tree
+
+    Returns:
+        AsyncStream[Comment, PartialComment]
+    """
+
+    def __call__(self, arg: Tree, /) -> AsyncStream[Comment, PartialComment]:
+        ...
+class IBAMLOutline(BaseBAMLFunction[Comment, PartialComment]):
+    def __init__(self) -> None:
+        super().__init__(
+            "Outline",
+            IOutline,
+            ["v1"],
+        )
+
+    async def __call__(self, *args, **kwargs) -> Comment:
+        return await self.get_impl("v1").run(*args, **kwargs)
+    
+    def stream(self, *args, **kwargs) -> AsyncStream[Comment, PartialComment]:
+        res = self.get_impl("v1").stream(*args, **kwargs)
+        return res
+
+BAMLOutline = IBAMLOutline()
+
+__all__ = [ "BAMLOutline" ]
@@ -0,0 +1,171 @@
+# This file is generated by the BAML compiler.
+# Do not edit this file directly.
+# Instead, edit the BAML files and recompile.
+
+# ruff: noqa: E501,F401
+# flake8: noqa: E501,F401
+# pylint: disable=unused-import,line-too-long
+# fmt: off
+
+from ..types.classes.cls_comment import Comment
+from ..types.classes.cls_reply import Reply
+from ..types.classes.cls_tree import Tree
+from ..types.partial.classes.cls_comment import PartialComment
+from ..types.partial.classes.cls_reply import PartialReply
+from ..types.partial.classes.cls_tree import PartialTree
+from baml_core.stream import AsyncStream
+from typing import Callable, Protocol, runtime_checkable
+
+
+import typing
+
+import pytest
+from contextlib import contextmanager
+from unittest import mock
+
+ImplName = typing.Literal["v1"]
+
+T = typing.TypeVar("T", bound=typing.Callable[..., typing.Any])
+CLS = typing.TypeVar("CLS", bound=type)
+
+
+IOutlineOutput = Comment
+
+@runtime_checkable
+class IOutline(Protocol):
+    """
+    This is the interface for a function.
+
+    Args:
+        arg: Tree
+
+    Returns:
+        Comment
+    """
+
+    async def __call__(self, arg: Tree, /) -> Comment:
+        ...
+
+   
+
+@runtime_checkable
+class IOutlineStream(Protocol):
+    """
+    This is the interface for a stream function.
+
+    Args:
+        arg: Tree
+
+    Returns:
+        AsyncStream[Comment, PartialComment]
+    """
+
+    def __call__(self, arg: Tree, /) -> AsyncStream[Comment, PartialComment]:
+        ...
+class BAMLOutlineImpl:
+    async def run(self, arg: Tree, /) -> Comment:
+        ...
+    
+    def stream(self, arg: Tree, /) -> AsyncStream[Comment, PartialComment]:
+        ...
+
+class IBAMLOutline:
+    def register_impl(
+        self, name: ImplName
+    ) -> typing.Callable[[IOutline, IOutlineStream], None]:
+        ...
+
+    async def __call__(self, arg: Tree, /) -> Comment:
+        ...
+
+    def stream(self, arg: Tree, /) -> AsyncStream[Comment, PartialComment]:
+        ...
+
+    def get_impl(self, name: ImplName) -> BAMLOutlineImpl:
+        ...
+
+    @contextmanager
+    def mock(self) -> typing.Generator[mock.AsyncMock, None, None]:
+        """
+        Utility for mocking the OutlineInterface.
+
+        Usage:
+            ```python
+            # All implementations are mocked.
+
+            async def test_logic() -> None:
+                with baml.Outline.mock() as mocked:
+                    mocked.return_value = ...
+                    result = await OutlineImpl(...)
+                    assert mocked.called
+            ```
+        """
+        ...
+
+    @typing.overload
+    def test(self, test_function: T) -> T:
+        """
+        Provides a pytest.mark.parametrize decorator to facilitate testing different implementations of
+        the OutlineInterface.
+
+        Args:
+            test_function : T
+                The test function to be decorated.
+
+        Usage:
+            ```python
+            # All implementations will be tested.
+
+            @baml.Outline.test
+            async def test_logic(OutlineImpl: IOutline) -> None:
+                result = await OutlineImpl(...)
+            ```
+        """
+        ...
+
+    @typing.overload
+    def test(self, *, exclude_impl: typing.Iterable[ImplName]) -> pytest.MarkDecorator:
+        """
+        Provides a pytest.mark.parametrize decorator to facilitate testing different implementations of
+        the OutlineInterface.
+
+        Args:
+            exclude_impl : Iterable[ImplName]
+                The names of the implementations to exclude from testing.
+
+        Usage:
+            ```python
+            # All implementations except the given impl will be tested.
+
+            @baml.Outline.test(exclude_impl=["implname"])
+            async def test_logic(OutlineImpl: IOutline) -> None:
+                result = await OutlineImpl(...)
+            ```
+        """
+        ...
+
+    @typing.overload
+    def test(self, test_class: typing.Type[CLS]) -> typing.Type[CLS]:
+        """
+        Provides a pytest.mark.parametrize decorator to facilitate testing different implementations of
+        the OutlineInterface.
+
+        Args:
+            test_class : Type[CLS]
+                The test class to be decorated.
+
+        Usage:
+        ```python
+        # All implementations will be tested in every test method.
+
+        @baml.Outline.test
+        class TestClass:
+            def test_a(self, OutlineImpl: IOutline) -> None:
+                ...
+            def test_b(self, OutlineImpl: IOutline) -> None:
+                ...
+        ```
+        """
+        ...
+
+BAMLOutline: IBAMLOutline
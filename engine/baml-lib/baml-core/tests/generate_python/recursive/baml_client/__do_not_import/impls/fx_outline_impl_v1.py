@@ -0,0 +1,71 @@
+# This file is generated by the BAML compiler.
+# Do not edit this file directly.
+# Instead, edit the BAML files and recompile.
+
+# ruff: noqa: E501,F401
+# flake8: noqa: E501,F401
+# pylint: disable=unused-import,line-too-long
+# fmt: off
+
+from ..clients.client_gpt4 import GPT4
+from ..functions.fx_outline import BAMLOutline
+from ..types.classes.cls_comment import Comment
+from ..types.classes.cls_reply import Reply
+from ..types.classes.cls_tree import Tree
+from ..types.partial.classes.cls_comment import PartialComment
+from ..types.partial.classes.cls_reply import PartialReply
+from ..types.partial.classes.cls_tree import PartialTree
+from baml_core.provider_manager.llm_response import LLMResponse
+from baml_core.stream import AsyncStream
+from baml_lib._impl.deserializer import Deserializer
+
+
+import typing
+# Impl: v1
+# Client: GPT4
+# An implementation of Outline.
+
+__prompt_template = """\
+Comment on {arg}.
+
+Comment {
+  "text": string,
+  "replies": {
+    "author": string,
+    "comment": Comment | null
+  }[]
+}\
+"""
+
+__input_replacers = {
+    "{arg}"
+}
+
+
+# We ignore the type here because baml does some type magic to make this work
+# for inline SpecialForms like Optional, Union, List.
+__deserializer = Deserializer[Comment](Comment)  # type: ignore
+
+# Add a deserializer that handles stream responses, which are all Partial types
+__partial_deserializer = Deserializer[PartialComment](PartialComment)  # type: ignore
+
+
+
+
+
+
+
+async def v1(arg: Tree, /) -> Comment:
+    response = await GPT4.run_prompt_template(template=__prompt_template, replacers=__input_replacers, params=dict(arg=arg))
+    deserialized = __deserializer.from_string(response.generated)
+    return deserialized
+
+
+def v1_stream(arg: Tree, /) -> AsyncStream[Comment, PartialComment]:
+    def run_prompt() -> typing.AsyncIterator[LLMResponse]:
+        raw_stream = GPT4.run_prompt_template_stream(template=__prompt_template, replacers=__input_replacers, params=dict(arg=arg))
+        return raw_stream
+    stream = AsyncStream(stream_cb=run_prompt, partial_deserializer=__partial_deserializer, final_deserializer=__deserializer)
+    return stream
+
+BAMLOutline.register_impl("v1")(v1, v1_stream)
\ No newline at end of file
@@ -0,0 +1,24 @@
+# This file is generated by the BAML compiler.
+# Do not edit this file directly.
+# Instead, edit the BAML files and recompile.
+
+# ruff: noqa: E501,F401
+# flake8: noqa: E501,F401
+# pylint: disable=unused-import,line-too-long
+# fmt: off
+
+from __future__ import annotations
+from ...classes.cls_comment import Comment
+from ...classes.cls_reply import Reply
+from baml_lib._impl.deserializer import register_deserializer
+from pydantic import BaseModel
+from typing import List, Optional
+
+
+@register_deserializer({  })
+class PartialComment(BaseModel):
+    text: Optional[str] = None
+    replies: List[PartialReply]
+
+from .cls_reply import PartialReply
+PartialComment.model_rebuild()
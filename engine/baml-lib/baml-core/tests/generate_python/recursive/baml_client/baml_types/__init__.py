@@ -0,0 +1,24 @@
+# This file is generated by the BAML compiler.
+# Do not edit this file directly.
+# Instead, edit the BAML files and recompile.
+
+# ruff: noqa: E501,F401
+# flake8: noqa: E501,F401
+# pylint: disable=unused-import,line-too-long
+# fmt: off
+
+from ..__do_not_import.functions.fx_outline import IOutline, IOutlineOutput
+from ..__do_not_import.types.classes.cls_comment import Comment
+from ..__do_not_import.types.classes.cls_reply import Reply
+from ..__do_not_import.types.classes.cls_tree import Tree
+
+
+
+
+__all__ = [
+    'IOutline',
+    'IOutlineOutput',
+    'Comment',
+    'Reply',
+    'Tree'
+]
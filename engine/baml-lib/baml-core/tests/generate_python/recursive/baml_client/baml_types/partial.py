@@ -0,0 +1,21 @@
+# This file is generated by the BAML compiler.
+# Do not edit this file directly.
+# Instead, edit the BAML files and recompile.
+
+# ruff: noqa: E501,F401
+# flake8: noqa: E501,F401
+# pylint: disable=unused-import,line-too-long
+# fmt: off
+
+from ..__do_not_import.types.partial.classes.cls_comment import PartialComment
+from ..__do_not_import.types.partial.classes.cls_reply import PartialReply
+from ..__do_not_import.types.partial.classes.cls_tree import PartialTree
+
+
+
+
+__all__ = [
+    'PartialComment',
+    'PartialReply',
+    'PartialTree'
+]
@@ -0,0 +1,203 @@
+// This file is auto-generated. Do not edit this file manually.
+//
+// Disable lints for this file, as it is not meant to be read.
+#![allow(
+    clippy::all,
+    dead_code,
+    non_camel_case_types,
+    non_snake_case,
+    unused_imports,
+    unused_variables
+)]
+
+/// One step of the path to a prompt variable.
+enum PromptStep {
+    /// A field of an object, by the key it serializes to, or a value of a map.
+    Key(&'static str),
+    /// An item of a list or tuple.
+    Index(usize),
+    /// Every item of a list, or every value of a map, each on its own line.
+    Each,
+}
+
+/// Formats the value at `path` inside `value` for use in a prompt.
+fn prompt_value(value: &serde_json::Value, path: &[PromptStep]) -> String {
+    let Some((step, rest)) = path.split_first() else {
+        return match value {
+            serde_json::Value::String(s) => s.clone(),
+            value => value.to_string(),
+        };
+    };
+    let next = match step {
+        PromptStep::Key(key) => value.get(*key),
+        PromptStep::Index(idx) => value.get(*idx),
+        PromptStep::Each => {
+            return prompt_items(Some(value))
+                .into_iter()
+                .map(|item| prompt_value(item, rest))
+                .collect::<Vec<_>>()
+                .join("\n");
+        }
+    };
+    next.map_or_else(String::new, |value| prompt_value(value, rest))
+}
+
+/// The value at `path` inside `value`, if there is one.
+fn prompt_lookup<'a>(
+    value: &'a serde_json::Value,
+    path: &[PromptStep],
+) -> Option<&'a serde_json::Value> {
+    path.iter().try_fold(value, |value, step| match step {
+        PromptStep::Key(key) => value.get(*key),
+        PromptStep::Index(idx) => value.get(*idx),
+        PromptStep::Each => None,
+    })
+}
+
+/// Whether the condition of an `{#if}` block is set: it's unset if it's null, false, or an empty
+/// string, list or map.
+fn prompt_is_set(value: Option<&serde_json::Value>) -> bool {
+    match value {
+        None | Some(serde_json::Value::Null) | Some(serde_json::Value::Bool(false)) => false,
+        Some(serde_json::Value::String(s)) => !s.is_empty(),
+        Some(serde_json::Value::Array(items)) => !items.is_empty(),
+        Some(serde_json::Value::Object(entries)) => !entries.is_empty(),
+        Some(_) => true,
+    }
+}
+
+/// The items of a list, or the values of a map.
+fn prompt_items(value: Option<&serde_json::Value>) -> Vec<&serde_json::Value> {
+    match value {
+        Some(serde_json::Value::Array(items)) => items.iter().collect(),
+        Some(serde_json::Value::Object(entries)) => entries.values().collect(),
+        _ => vec![],
+    }
+}
+
+/// Prompts and output parsing for `Classify`.
+pub mod classify {
+    use super::super::types::*;
+    use super::{prompt_is_set, prompt_items, prompt_lookup, prompt_value, PromptStep};
+    use serde::{Deserialize, Serialize};
+
+    /// What `Classify` returns.
+    pub type Output = ClassifyOutput;
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    #[serde(untagged)]
+    pub enum ClassifyOutput {
+        Sentiment(Sentiment),
+        Reply(Reply),
+    }
+
+    const PROMPT_V1: &str = r#"Classify this conversation: {//BAML_CLIENT_REPLACE_ME_MAGIC_input.conversation.messages//}
+The latest sender is {//BAML_CLIENT_REPLACE_ME_MAGIC_input.conversation.last_reply.sender//}.
+Hint: {//BAML_CLIENT_REPLACE_ME_MAGIC_input.hint//}
+
+"Sentiment as string" | string | {
+  "from": string,
+  "body": string,
+  "type": string | null,
+  "sentiment": "Sentiment as string" | null
+}"#;
+
+    /// Renders the prompt of the `v1` impl.
+    pub fn prompt_v1(conversation: &Conversation, hint: &Option<String>) -> String {
+        let conversation = serde_json::to_value(conversation).unwrap_or_default();
+        let hint = serde_json::to_value(hint).unwrap_or_default();
+        PROMPT_V1
+            .replace("{//BAML_CLIENT_REPLACE_ME_MAGIC_input.conversation.last_reply.sender//}", &prompt_value(&conversation, &[PromptStep::Key("last_reply"), PromptStep::Key("from")]))
+            .replace("{//BAML_CLIENT_REPLACE_ME_MAGIC_input.conversation.messages//}", &prompt_value(&conversation, &[PromptStep::Key("messages")]))
+            .replace("{//BAML_CLIENT_REPLACE_ME_MAGIC_input.hint//}", &prompt_value(&hint, &[]))
+            .to_string()
+    }
+
+    const PROMPT_V2: &str = r##"Classify "{//BAML_CLIENT_REPLACE_ME_MAGIC_input.conversation.title//}"#.
+First sender: {//BAML_CLIENT_REPLACE_ME_MAGIC_input.conversation.messages[0].sender//}
+Every sender: {//BAML_CLIENT_REPLACE_ME_MAGIC_input.conversation.messages[*].sender//}
+Done: {//BAML_CLIENT_REPLACE_ME_MAGIC_input.conversation.counts["done"]//} of {//BAML_CLIENT_REPLACE_ME_MAGIC_input.conversation.span[1]//}
+
+"Sentiment as string" | string | {
+  "from": string,
+  "body": string,
+  "type": string | null,
+  "sentiment": "Sentiment as string" | null
+}"##;
+
+    /// Renders the prompt of the `v2` impl.
+    pub fn prompt_v2(conversation: &Conversation, hint: &Option<String>) -> String {
+        let conversation = serde_json::to_value(conversation).unwrap_or_default();
+        let hint = serde_json::to_value(hint).unwrap_or_default();
+        PROMPT_V2
+            .replace("{//BAML_CLIENT_REPLACE_ME_MAGIC_input.conversation.counts[\"done\"]//}", &prompt_value(&conversation, &[PromptStep::Key("counts"), PromptStep::Key("done")]))
+            .replace("{//BAML_CLIENT_REPLACE_ME_MAGIC_input.conversation.messages[*].sender//}", &prompt_value(&conversation, &[PromptStep::Key("messages"), PromptStep::Each, PromptStep::Key("from")]))
+            .replace("{//BAML_CLIENT_REPLACE_ME_MAGIC_input.conversation.messages[0].sender//}", &prompt_value(&conversation, &[PromptStep::Key("messages"), PromptStep::Index(0), PromptStep::Key("from")]))
+            .replace("{//BAML_CLIENT_REPLACE_ME_MAGIC_input.conversation.span[1]//}", &prompt_value(&conversation, &[PromptStep::Key("span"), PromptStep::Index(1)]))
+            .replace("{//BAML_CLIENT_REPLACE_ME_MAGIC_input.conversation.title//}", &prompt_value(&conversation, &[PromptStep::Key("title")]))
+            .to_string()
+    }
+
+    /// Renders the prompt of the default impl.
+    pub fn prompt(conversation: &Conversation, hint: &Option<String>) -> String {
+        prompt_v1(conversation, hint)
+    }
+
+    /// Parses what the LLM returned for any impl of `Classify`.
+    pub fn parse(raw: &str) -> serde_json::Result<Output> {
+        serde_json::from_str(raw)
+    }
+}
+
+/// Prompts and output parsing for `Summarize`.
+pub mod summarize {
+    use super::super::types::*;
+    use super::{prompt_is_set, prompt_items, prompt_lookup, prompt_value, PromptStep};
+    use serde::{Deserialize, Serialize};
+
+    /// What `Summarize` returns.
+    pub type Output = String;
+
+    const PROMPT_SIMPLE: &str = r#"Summarize: {//BAML_CLIENT_REPLACE_ME_MAGIC_input//}
+Bodies: {//BAML_CLIENT_REPLACE_ME_MAGIC_input[*].body//}"#;
+
+    /// Renders the prompt of the `simple` impl.
+    pub fn prompt_simple(arg: &Thread) -> String {
+        let arg = serde_json::to_value(arg).unwrap_or_default();
+        PROMPT_SIMPLE
+            .replace("{//BAML_CLIENT_REPLACE_ME_MAGIC_input//}", &prompt_value(&arg, &[]))
+            .replace("{//BAML_CLIENT_REPLACE_ME_MAGIC_input[*].body//}", &prompt_value(&arg, &[PromptStep::Each, PromptStep::Key("body")]))
+            .to_string()
+    }
+
+    /// Renders the prompt of the `by_sentiment` impl.
+    pub fn prompt_by_sentiment(arg: &Thread) -> String {
+        let arg = serde_json::to_value(arg).unwrap_or_default();
+        let mut __prompt = String::new();
+        __prompt += r#"Summarize:
+"#;
+        for message in prompt_items(prompt_lookup(&arg, &[])) {
+            if prompt_is_set(prompt_lookup(message, &[PromptStep::Key("sentiment")])) {
+                __prompt += &r#"[{//BAML_CLIENT_REPLACE_ME_MAGIC_message.sentiment//}] {//BAML_CLIENT_REPLACE_ME_MAGIC_message.body//}
+"#
+                    .replace("{//BAML_CLIENT_REPLACE_ME_MAGIC_message.body//}", &prompt_value(message, &[PromptStep::Key("body")]))
+                    .replace("{//BAML_CLIENT_REPLACE_ME_MAGIC_message.sentiment//}", &prompt_value(message, &[PromptStep::Key("sentiment")]));
+            } else {
+                __prompt += &r#"{//BAML_CLIENT_REPLACE_ME_MAGIC_message.body//}
+"#
+                    .replace("{//BAML_CLIENT_REPLACE_ME_MAGIC_message.body//}", &prompt_value(message, &[PromptStep::Key("body")]));
+            }
+        }
+        __prompt
+    }
+
+    /// Renders the prompt of the default impl.
+    pub fn prompt(arg: &Thread) -> String {
+        prompt_simple(arg)
+    }
+
+    /// Parses what the LLM returned for any impl of `Summarize`.
+    pub fn parse(raw: &str) -> serde_json::Result<Output> {
+        serde_json::from_str(raw)
+    }
+}
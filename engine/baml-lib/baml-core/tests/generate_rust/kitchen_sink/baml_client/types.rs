@@ -0,0 +1,57 @@
+// This file is auto-generated. Do not edit this file manually.
+//
+// Disable lints for this file, as it is not meant to be read.
+#![allow(
+    clippy::all,
+    dead_code,
+    non_camel_case_types,
+    non_snake_case,
+    unused_imports,
+    unused_variables
+)]
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Sentiment {
+    #[serde(rename = "pos")]
+    Positive,
+    #[serde(rename = "neg")]
+    Negative,
+    Neutral,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Conversation {
+    pub messages: Vec<Message>,
+    pub scores: Vec<Vec<f64>>,
+    pub counts: std::collections::HashMap<String, i64>,
+    pub last_reply: Option<ConversationLastReply>,
+    pub span: (i64, i64),
+    pub title: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ConversationLastReply {
+    Message(Message),
+    String(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Message {
+    #[serde(rename = "from")]
+    pub sender: String,
+    pub body: String,
+    pub r#type: Option<String>,
+    pub sentiment: Option<Sentiment>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Reply {
+    String(String),
+    Message(Message),
+}
+
+pub type Thread = Vec<Message>;
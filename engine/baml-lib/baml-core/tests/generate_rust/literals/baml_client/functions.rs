@@ -0,0 +1,122 @@
+// This file is auto-generated. Do not edit this file manually.
+//
+// Disable lints for this file, as it is not meant to be read.
+#![allow(
+    clippy::all,
+    dead_code,
+    non_camel_case_types,
+    non_snake_case,
+    unused_imports,
+    unused_variables
+)]
+
+/// One step of the path to a prompt variable.
+enum PromptStep {
+    /// A field of an object, by the key it serializes to, or a value of a map.
+    Key(&'static str),
+    /// An item of a list or tuple.
+    Index(usize),
+    /// Every item of a list, or every value of a map, each on its own line.
+    Each,
+}
+
+/// Formats the value at `path` inside `value` for use in a prompt.
+fn prompt_value(value: &serde_json::Value, path: &[PromptStep]) -> String {
+    let Some((step, rest)) = path.split_first() else {
+        return match value {
+            serde_json::Value::String(s) => s.clone(),
+            value => value.to_string(),
+        };
+    };
+    let next = match step {
+        PromptStep::Key(key) => value.get(*key),
+        PromptStep::Index(idx) => value.get(*idx),
+        PromptStep::Each => {
+            return prompt_items(Some(value))
+                .into_iter()
+                .map(|item| prompt_value(item, rest))
+                .collect::<Vec<_>>()
+                .join("\n");
+        }
+    };
+    next.map_or_else(String::new, |value| prompt_value(value, rest))
+}
+
+/// The value at `path` inside `value`, if there is one.
+fn prompt_lookup<'a>(
+    value: &'a serde_json::Value,
+    path: &[PromptStep],
+) -> Option<&'a serde_json::Value> {
+    path.iter().try_fold(value, |value, step| match step {
+        PromptStep::Key(key) => value.get(*key),
+        PromptStep::Index(idx) => value.get(*idx),
+        PromptStep::Each => None,
+    })
+}
+
+/// Whether the condition of an `{#if}` block is set: it's unset if it's null, false, or an empty
+/// string, list or map.
+fn prompt_is_set(value: Option<&serde_json::Value>) -> bool {
+    match value {
+        None | Some(serde_json::Value::Null) | Some(serde_json::Value::Bool(false)) => false,
+        Some(serde_json::Value::String(s)) => !s.is_empty(),
+        Some(serde_json::Value::Array(items)) => !items.is_empty(),
+        Some(serde_json::Value::Object(entries)) => !entries.is_empty(),
+        Some(_) => true,
+    }
+}
+
+/// The items of a list, or the values of a map.
+fn prompt_items(value: Option<&serde_json::Value>) -> Vec<&serde_json::Value> {
+    match value {
+        Some(serde_json::Value::Array(items)) => items.iter().collect(),
+        Some(serde_json::Value::Object(entries)) => entries.values().collect(),
+        _ => vec![],
+    }
+}
+
+/// Prompts and output parsing for `Fetch`.
+pub mod fetch {
+    use super::super::types::*;
+    use super::{prompt_is_set, prompt_items, prompt_lookup, prompt_value, PromptStep};
+    use serde::{Deserialize, Serialize};
+
+    /// What `Fetch` returns.
+    pub type Output = Document;
+
+    const PROMPT_V1: &str = r#"Fetch {//BAML_CLIENT_REPLACE_ME_MAGIC_input//}.
+
+{
+  "version": 1 | 2,
+  "response": {
+    "status": "ok" | "error",
+    "code": 200 | 404,
+    "cached": true,
+    "note": "draft" | null
+  },
+  "kind": "report" | {
+    "status": "ok" | "error",
+    "code": 200 | 404,
+    "cached": true,
+    "note": "draft" | null
+  }
+}"#;
+
+    /// Renders the prompt of the `v1` impl.
+    pub fn prompt_v1(arg: &String) -> String {
+        let arg = serde_json::to_value(arg).unwrap_or_default();
+        PROMPT_V1
+            .replace("{//BAML_CLIENT_REPLACE_ME_MAGIC_input//}", &prompt_value(&arg, &[]))
+            .to_string()
+    }
+
+    /// Renders the prompt of the default impl.
+    pub fn prompt(arg: &String) -> String {
+        prompt_v1(arg)
+    }
+
+    /// Parses what the LLM returned for any impl of `Fetch`.
+    pub fn parse(raw: &str) -> serde_json::Result<Output> {
+        serde_json::from_str(raw)
+    }
+}
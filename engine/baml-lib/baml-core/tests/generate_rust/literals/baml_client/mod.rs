@@ -0,0 +1,20 @@
+// This file is auto-generated. Do not edit this file manually.
+//
+// Disable lints for this file, as it is not meant to be read.
+#![allow(
+    clippy::all,
+    dead_code,
+    non_camel_case_types,
+    non_snake_case,
+    unused_imports,
+    unused_variables
+)]
+
+//! Types and prompts generated from BAML.
+//!
+//! The generated code depends on `serde` (with the `derive` feature) and `serde_json`.
+
+pub mod functions;
+pub mod types;
+
+pub use types::*;
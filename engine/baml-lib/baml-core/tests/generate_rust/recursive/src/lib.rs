@@ -0,0 +1,2 @@
+#[path = "../baml_client/mod.rs"]
+pub mod baml_client;
@@ -0,0 +1,138 @@
+// This file is auto-generated. Do not edit this file manually.
+//
+// Disable formatting for this file to avoid linting errors.
+// tslint:disable
+// @ts-nocheck
+
+import { Message, Sentiment } from './types';
+import { BamlStream } from '@boundaryml/baml_client/baml_lib/stream/baml_stream';
+
+
+type IClassify = (arg: Message) => Promise<Sentiment>
+type IClassifyStream = (arg: Message) => BamlStream<Sentiment, Sentiment>
+
+type ClassifyImpls = 'v1';
+
+interface ClassifyImpl {
+    run: IClassify;
+    stream: IClassifyStream;
+    name: ClassifyImpls;
+}
+
+interface ClassifyFunction {
+  registerImpl: (name: ClassifyImpls, impl: ClassifyImpl) => void;
+  getImpl: (name: ClassifyImpls) => ClassifyImpl;
+  stream: IClassifyStream;
+}
+
+function createClassifyInstance(): IClassify & ClassifyFunction {
+
+  const registry: Record<ClassifyImpls, ClassifyImpl> = {}
+
+  const wrapper: ClassifyFunction = {
+    getImpl: (name: ClassifyImpls) => {
+      const impl = registry[name];
+      if (!impl) {
+        throw new Error(`No implementation for Classify with name ${name}`);
+      }
+      return impl;
+    },
+    registerImpl: (name: ClassifyImpls, impl: ClassifyImpl) => {
+      if (registry[name]) {
+        throw new Error(`Implementation for Classify with name ${name} already exists`);
+      }
+      registry[name] = impl;
+    },
+    validate: () => {
+      const targets = ['v1'];
+      const impls = Object.keys(registry);
+      const missing = targets.filter(t => !impls.includes(t));
+      if (missing.length > 0) {
+        throw new Error(`Missing implementations for Classify: ${missing.join(', ')}`);
+      }
+    },
+    stream: (params: Message) => {
+      return wrapper.getImpl('v1').stream(params);
+    }
+  };
+
+  const impl = async (params: Message) => {
+    return wrapper.getImpl('v1').run(params);
+  };
+
+  Object.assign(impl, wrapper);
+
+  return impl as  IClassify & ClassifyFunction;
+}
+
+const Classify = createClassifyInstance();
+
+type IRateMessages = (args: {
+  messages: Message[], topic: string
+}) => Promise<Sentiment>
+type IRateMessagesStream = (args: {
+  messages: Message[], topic: string
+}) => BamlStream<Sentiment, Sentiment>
+
+type RateMessagesImpls = 'v1';
+
+interface RateMessagesImpl {
+    run: IRateMessages;
+    stream: IRateMessagesStream;
+    name: RateMessagesImpls;
+}
+
+interface RateMessagesFunction {
+  registerImpl: (name: RateMessagesImpls, impl: RateMessagesImpl) => void;
+  getImpl: (name: RateMessagesImpls) => RateMessagesImpl;
+  stream: IRateMessagesStream;
+}
+
+function createRateMessagesInstance(): IRateMessages & RateMessagesFunction {
+
+  const registry: Record<RateMessagesImpls, RateMessagesImpl> = {}
+
+  const wrapper: RateMessagesFunction = {
+    getImpl: (name: RateMessagesImpls) => {
+      const impl = registry[name];
+      if (!impl) {
+        throw new Error(`No implementation for RateMessages with name ${name}`);
+      }
+      return impl;
+    },
+    registerImpl: (name: RateMessagesImpls, impl: RateMessagesImpl) => {
+      if (registry[name]) {
+        throw new Error(`Implementation for RateMessages with name ${name} already exists`);
+      }
+      registry[name] = impl;
+    },
+    validate: () => {
+      const targets = ['v1'];
+      const impls = Object.keys(registry);
+      const missing = targets.filter(t => !impls.includes(t));
+      if (missing.length > 0) {
+        throw new Error(`Missing implementations for RateMessages: ${missing.join(', ')}`);
+      }
+    },
+    stream: (params : {
+      messages: Message[], topic: string
+    }) => {
+      return wrapper.getImpl('v1').stream(params);
+    }
+  };
+
+  const impl = async (params : {
+    messages: Message[], topic: string
+  }) => {
+    return wrapper.getImpl('v1').run(params);
+  };
+
+  Object.assign(impl, wrapper);
+
+  return impl as  IRateMessages & RateMessagesFunction;
+}
+
+const RateMessages = createRateMessagesInstance();
+
+
+export { Classify, IClassify, IClassifyStream, ClassifyFunction, RateMessages, IRateMessages, IRateMessagesStream, RateMessagesFunction }
\ No newline at end of file
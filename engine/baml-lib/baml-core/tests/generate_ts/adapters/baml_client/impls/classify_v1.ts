@@ -0,0 +1,69 @@
+// This file is auto-generated. Do not edit this file manually.
+//
+// Disable formatting for this file to avoid linting errors.
+// tslint:disable
+// @ts-nocheck
+
+import { GPT4 } from '../client';
+import { Classify } from '../function';
+import { schema } from '../json_schema';
+import { Message, Sentiment } from '../types';
+import { InternalMessage } from '../types_internal';
+import { Deserializer } from '@boundaryml/baml_client/baml_lib/deserializer/deserializer';
+import { BamlStream } from '@boundaryml/baml_client/baml_lib/stream/baml_stream';
+
+
+const prompt_template = `\
+Classify: {//BAML_CLIENT_REPLACE_ME_MAGIC_input//}
+
+Sentiment
+---
+POSITIVE
+NEGATIVE\
+`;
+
+const deserializer = new Deserializer<Sentiment>(schema, {
+  $ref: '#/definitions/Classify_output'
+});
+const partialDeserializer = new Deserializer<Sentiment>(schema, {
+  $ref: '#/definitions/Classify_output'
+}, true);
+
+const inputAdapter = (arg: Message): string => {
+  return arg.body;
+};
+
+// The prompt template, and the value of each of its variables.
+const templateArgs = (
+input: Message
+): [string, { [key: string]: string }] => {
+  const arg = inputAdapter(input);
+  return [
+    prompt_template,
+    {
+      "{//BAML_CLIENT_REPLACE_ME_MAGIC_input//}": arg,
+    }
+  ];
+};
+
+Classify.registerImpl('v1', {
+  name: 'v1',
+  run: async (
+input: Message
+  ): Promise<Sentiment> => {
+    const [prompt, replacers] = templateArgs(input);
+    const result = await GPT4.run_prompt_template(prompt, replacers);
+
+    return deserializer.coerce(result);
+  },
+  stream: (
+input: Message
+  ): BamlStream<Sentiment, Sentiment> => {
+    const [prompt, replacers] = templateArgs(input);
+    return new BamlStream(
+      () => GPT4.run_prompt_template_stream(prompt, replacers),
+      partialDeserializer,
+      deserializer
+    );
+  }
+});
@@ -0,0 +1,9 @@
+// This file is auto-generated. Do not edit this file manually.
+//
+// Disable formatting for this file to avoid linting errors.
+// tslint:disable
+// @ts-nocheck
+
+import './classify_v1';
+
+import './ratemessages_v1';
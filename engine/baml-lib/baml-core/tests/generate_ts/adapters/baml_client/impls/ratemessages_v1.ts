@@ -0,0 +1,72 @@
+// This file is auto-generated. Do not edit this file manually.
+//
+// Disable formatting for this file to avoid linting errors.
+// tslint:disable
+// @ts-nocheck
+
+import { GPT4 } from '../client';
+import { RateMessages } from '../function';
+import { schema } from '../json_schema';
+import { Message, Rating, Sentiment } from '../types';
+import { InternalMessage } from '../types_internal';
+import { Deserializer } from '@boundaryml/baml_client/baml_lib/deserializer/deserializer';
+import { BamlStream } from '@boundaryml/baml_client/baml_lib/stream/baml_stream';
+
+
+const prompt_template = `\
+Rate the mood of these messages:
+{//BAML_CLIENT_REPLACE_ME_MAGIC_input[*]//}
+
+{
+  "sentiment": "Sentiment as string",
+  "reasoning": string
+}\
+`;
+
+const deserializer = new Deserializer<Rating>(schema, {"$ref":"#/definitions/Rating"});
+
+const inputAdapter = ({ messages, topic }: { messages: Message[], topic: string }): string[] => {
+  return messages.map((m) => `${m.sender} on ${topic}: ${m.body}`);
+};
+
+const outputAdapter = (arg: Rating): Sentiment => {
+  return arg.sentiment;
+};
+
+// The prompt template, and the value of each of its variables.
+const templateArgs = (
+args: {
+  messages: Message[], topic: string
+}
+): [string, { [key: string]: string }] => {
+  const messages = args.messages.map(x => InternalMessage.from(x));
+  const topic = args.topic;
+  const arg = inputAdapter({ messages, topic });
+  return [
+    prompt_template,
+    {
+      "{//BAML_CLIENT_REPLACE_ME_MAGIC_input[*]//}": Object.values(arg).map((x0) => x0).join("\n"),
+    }
+  ];
+};
+
+RateMessages.registerImpl('v1', {
+  name: 'v1',
+  run: async (
+args: {
+  messages: Message[], topic: string
+}
+  ): Promise<Sentiment> => {
+    const [prompt, replacers] = templateArgs(args);
+    const result = await GPT4.run_prompt_template(prompt, replacers);
+
+    return outputAdapter(deserializer.coerce(result));
+  },
+  stream: (
+args: {
+  messages: Message[], topic: string
+}
+  ): BamlStream<Sentiment, Sentiment> => {
+    throw new Error('Stream functions do not support output adapters');
+  }
+});
@@ -0,0 +1,20 @@
+// This file is auto-generated. Do not edit this file manually.
+//
+// Disable formatting for this file to avoid linting errors.
+// tslint:disable
+// @ts-nocheck
+
+import { registerEnumDeserializer, registerObjectDeserializer } from '@boundaryml/baml_client/baml_lib/deserializer/deserializer';
+import { JSONSchema7 } from 'json-schema';
+
+
+const schema: JSONSchema7 = {"definitions":{"Sentiment":{"title":"Sentiment","enum":[{"const":"POSITIVE"},{"const":"NEGATIVE"}]},"Message":{"title":"Message","type":"object","properties":{"sender":{"type":"string"},"body":{"type":"string"}},"required":["sender","body"]},"Rating":{"title":"Rating","type":"object","properties":{"sentiment":{"$ref":"#/definitions/Sentiment"},"reasoning":{"type":"string"}},"required":["sentiment","reasoning"]},"Classify_input":{"$ref":"#/definitions/Message","title":"Classify input"},"RateMessages_input":{"type":"object","properties":{"messages":{"type":"array","items":{"$ref":"#/definitions/Message"}},"topic":{"type":"string"}},"required":[],"title":"RateMessages input"},"Classify_output":{"$ref":"#/definitions/Sentiment","title":"Classify output"},"RateMessages_output":{"$ref":"#/definitions/Sentiment","title":"RateMessages output"}}};
+
+registerEnumDeserializer(schema.definitions.Sentiment, { });
+
+registerObjectDeserializer(schema.definitions.Message, { });
+
+registerObjectDeserializer(schema.definitions.Rating, { });
+
+
+export { schema }
\ No newline at end of file
@@ -0,0 +1,23 @@
+// This file is auto-generated. Do not edit this file manually.
+//
+// Disable formatting for this file to avoid linting errors.
+// tslint:disable
+// @ts-nocheck
+
+const enum Sentiment {
+    POSITIVE = "POSITIVE",
+    NEGATIVE = "NEGATIVE",
+}
+
+interface Message {
+  sender: string;
+  body: string;
+}
+
+interface Rating {
+  sentiment: Sentiment;
+  reasoning: string;
+}
+
+
+export { Sentiment, Message, Rating }
\ No newline at end of file
@@ -0,0 +1,90 @@
+// This file is auto-generated. Do not edit this file manually.
+//
+// Disable formatting for this file to avoid linting errors.
+// tslint:disable
+// @ts-nocheck
+
+import { Message, Rating, Sentiment } from './types';
+
+
+// Function to check if a value is a member of the Sentiment enum
+function isSentiment(value: any): value is Sentiment {
+  return Object.values(Sentiment).includes(value);
+}
+
+// Function to validate if an object is a Message object
+function isMessage(obj: any): obj is Message {
+  return (
+    obj &&
+    typeof obj === "object"
+    && ("sender" in obj && (typeof obj.sender === 'string'))
+    && ("body" in obj && (typeof obj.body === 'string'))
+  );
+}
+
+
+class InternalMessage implements Message {
+  private constructor(private data: {
+    sender: string,
+    body: string,
+  }, private raw: Message) {}
+
+  static from(data: Message): InternalMessage {
+    return new InternalMessage({
+      sender: data.sender,
+      body: data.body,
+    }, data);
+  }
+
+  get sender(): string {
+    return this.data.sender;
+  }
+  get body(): string {
+    return this.data.body;
+  }
+
+
+  toJSON(): string {
+    return JSON.stringify(this.raw, null, 2);
+  }
+}
+
+// Function to validate if an object is a Rating object
+function isRating(obj: any): obj is Rating {
+  return (
+    obj &&
+    typeof obj === "object"
+    && ("sentiment" in obj && (isSentiment(obj.sentiment)))
+    && ("reasoning" in obj && (typeof obj.reasoning === 'string'))
+  );
+}
+
+
+class InternalRating implements Rating {
+  private constructor(private data: {
+    sentiment: Sentiment,
+    reasoning: string,
+  }, private raw: Rating) {}
+
+  static from(data: Rating): InternalRating {
+    return new InternalRating({
+      sentiment: data.sentiment,
+      reasoning: data.reasoning,
+    }, data);
+  }
+
+  get sentiment(): Sentiment {
+    return this.data.sentiment;
+  }
+  get reasoning(): string {
+    return this.data.reasoning;
+  }
+
+
+  toJSON(): string {
+    return JSON.stringify(this.raw, null, 2);
+  }
+}
+
+
+export { InternalMessage, InternalRating }
\ No newline at end of file
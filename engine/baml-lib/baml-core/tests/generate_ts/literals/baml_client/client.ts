@@ -0,0 +1,15 @@
+// This file is auto-generated. Do not edit this file manually.
+//
+// Disable formatting for this file to avoid linting errors.
+// tslint:disable
+// @ts-nocheck
+
+import { IClient, clientManager } from '@boundaryml/baml_client/baml_lib/client_manager';
+
+
+const GPT4: IClient = clientManager.createClient('GPT4', 'baml-openai-chat', {
+    model: "gpt-4"
+});
+
+
+export { GPT4 }
\ No newline at end of file
@@ -0,0 +1,72 @@
+// This file is auto-generated. Do not edit this file manually.
+//
+// Disable formatting for this file to avoid linting errors.
+// tslint:disable
+// @ts-nocheck
+
+import { Document } from './types';
+import { PartialDocument } from './types_partial';
+import { BamlStream } from '@boundaryml/baml_client/baml_lib/stream/baml_stream';
+
+
+type IFetch = (arg: string) => Promise<Document>
+type IFetchStream = (arg: string) => BamlStream<Document, PartialDocument>
+
+type FetchImpls = 'v1';
+
+interface FetchImpl {
+    run: IFetch;
+    stream: IFetchStream;
+    name: FetchImpls;
+}
+
+interface FetchFunction {
+  registerImpl: (name: FetchImpls, impl: FetchImpl) => void;
+  getImpl: (name: FetchImpls) => FetchImpl;
+  stream: IFetchStream;
+}
+
+function createFetchInstance(): IFetch & FetchFunction {
+
+  const registry: Record<FetchImpls, FetchImpl> = {}
+
+  const wrapper: FetchFunction = {
+    getImpl: (name: FetchImpls) => {
+      const impl = registry[name];
+      if (!impl) {
+        throw new Error(`No implementation for Fetch with name ${name}`);
+      }
+      return impl;
+    },
+    registerImpl: (name: FetchImpls, impl: FetchImpl) => {
+      if (registry[name]) {
+        throw new Error(`Implementation for Fetch with name ${name} already exists`);
+      }
+      registry[name] = impl;
+    },
+    validate: () => {
+      const targets = ['v1'];
+      const impls = Object.keys(registry);
+      const missing = targets.filter(t => !impls.includes(t));
+      if (missing.length > 0) {
+        throw new Error(`Missing implementations for Fetch: ${missing.join(', ')}`);
+      }
+    },
+    stream: (params: string) => {
+      return wrapper.getImpl('v1').stream(params);
+    }
+  };
+
+  const impl = async (params: string) => {
+    return wrapper.getImpl('v1').run(params);
+  };
+
+  Object.assign(impl, wrapper);
+
+  return impl as  IFetch & FetchFunction;
+}
+
+const Fetch = createFetchInstance();
+
+
+export { Fetch, IFetch, IFetchStream, FetchFunction }
\ No newline at end of file
@@ -0,0 +1,75 @@
+// This file is auto-generated. Do not edit this file manually.
+//
+// Disable formatting for this file to avoid linting errors.
+// tslint:disable
+// @ts-nocheck
+
+import { GPT4 } from '../client';
+import { Fetch } from '../function';
+import { schema } from '../json_schema';
+import { Document } from '../types';
+import { PartialDocument } from '../types_partial';
+import { Deserializer } from '@boundaryml/baml_client/baml_lib/deserializer/deserializer';
+import { BamlStream } from '@boundaryml/baml_client/baml_lib/stream/baml_stream';
+
+
+const prompt_template = `\
+Fetch {//BAML_CLIENT_REPLACE_ME_MAGIC_input//}.
+
+{
+  "version": 1 | 2,
+  "response": {
+    "status": "ok" | "error",
+    "code": 200 | 404,
+    "cached": true,
+    "note": "draft" | null
+  },
+  "kind": "report" | {
+    "status": "ok" | "error",
+    "code": 200 | 404,
+    "cached": true,
+    "note": "draft" | null
+  }
+}\
+`;
+
+const deserializer = new Deserializer<Document>(schema, {
+  $ref: '#/definitions/Fetch_output'
+});
+const partialDeserializer = new Deserializer<PartialDocument>(schema, {
+  $ref: '#/definitions/Fetch_output'
+}, true);
+
+// The prompt template, and the value of each of its variables.
+const templateArgs = (
+arg: string
+): [string, { [key: string]: string }] => {
+  return [
+    prompt_template,
+    {
+      "{//BAML_CLIENT_REPLACE_ME_MAGIC_input//}": arg,
+    }
+  ];
+};
+
+Fetch.registerImpl('v1', {
+  name: 'v1',
+  run: async (
+arg: string
+  ): Promise<Document> => {
+    const [prompt, replacers] = templateArgs(arg);
+    const result = await GPT4.run_prompt_template(prompt, replacers);
+
+    return deserializer.coerce(result);
+  },
+  stream: (
+arg: string
+  ): BamlStream<Document, PartialDocument> => {
+    const [prompt, replacers] = templateArgs(arg);
+    return new BamlStream(
+      () => GPT4.run_prompt_template_stream(prompt, replacers),
+      partialDeserializer,
+      deserializer
+    );
+  }
+});
@@ -0,0 +1,14 @@
+// This file is auto-generated. Do not edit this file manually.
+//
+// Disable formatting for this file to avoid linting errors.
+// tslint:disable
+// @ts-nocheck
+
+import * as f from "./function"
+import "./impls"
+
+const BamlClient = {
+  Fetch: f.Fetch,
+}
+
+export default BamlClient
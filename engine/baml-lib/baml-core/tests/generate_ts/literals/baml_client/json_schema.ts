@@ -0,0 +1,18 @@
+// This file is auto-generated. Do not edit this file manually.
+//
+// Disable formatting for this file to avoid linting errors.
+// tslint:disable
+// @ts-nocheck
+
+import { registerEnumDeserializer, registerObjectDeserializer } from '@boundaryml/baml_client/baml_lib/deserializer/deserializer';
+import { JSONSchema7 } from 'json-schema';
+
+
+const schema: JSONSchema7 = {"definitions":{"Document":{"title":"Document","type":"object","properties":{"version":{"$ref":"#/definitions/Version"},"response":{"$ref":"#/definitions/Response"},"kind":{"anyOf":[{"type":"string","const":"report","title":"\"report\""},{"$ref":"#/definitions/Response","title":"Response"}]}},"required":["version","response","kind"]},"Response":{"title":"Response","type":"object","properties":{"status":{"type":"string","enum":["ok","error"]},"code":{"type":"integer","enum":[200,404]},"cached":{"type":"boolean","const":true},"note":{"anyOf":[{"type":"string","const":"draft","title":"\"draft\""},{"type":"null","title":"null"}],"default":null}},"required":["status","code","cached"]},"Version":{"type":"integer","enum":[1,2],"title":"Version"},"Fetch_input":{"type":"string","title":"Fetch input"},"Fetch_output":{"$ref":"#/definitions/Document","title":"Fetch output"}}};
+
+registerObjectDeserializer(schema.definitions.Document, { });
+
+registerObjectDeserializer(schema.definitions.Response, { });
+
+
+export { schema }
\ No newline at end of file
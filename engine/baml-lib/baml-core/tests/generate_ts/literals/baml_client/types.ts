@@ -0,0 +1,23 @@
+// This file is auto-generated. Do not edit this file manually.
+//
+// Disable formatting for this file to avoid linting errors.
+// tslint:disable
+// @ts-nocheck
+
+interface Document {
+  version: Version;
+  response: Response;
+  kind: "report" | Response;
+}
+
+interface Response {
+  status: "ok" | "error";
+  code: 200 | 404;
+  cached: true;
+  note: "draft" | null;
+}
+
+type Version = 1 | 2;
+
+
+export { Document, Response, Version }
\ No newline at end of file
@@ -0,0 +1,131 @@
+// This file is auto-generated. Do not edit this file manually.
+//
+// Disable formatting for this file to avoid linting errors.
+// tslint:disable
+// @ts-nocheck
+
+import { Document, Response } from './types';
+
+
+// Function to validate if an object is a Document object
+function isDocument(obj: any): obj is Document {
+  return (
+    obj &&
+    typeof obj === "object"
+    && ("version" in obj && ((obj.version === 1) || (obj.version === 2)))
+    && ("response" in obj && (isResponse(obj.response)))
+    && ("kind" in obj && ((obj.kind === "report") || (isResponse(obj.kind))))
+  );
+}
+
+
+class InternalDocument implements Document {
+  private constructor(private data: {
+    version: 1 | 2,
+    response: InternalResponse,
+    kind: "report" | Response,
+  }, private raw: Document) {}
+
+  static from(data: Document): InternalDocument {
+    return new InternalDocument({
+      version: ((x) => {
+if (x === 1) {
+  return x;
+}
+if (x === 2) {
+  return x;
+}
+})(data.version),
+      response: InternalResponse.from(data.response),
+      kind: ((x) => {
+if (x === "report") {
+  return x;
+}
+if (isResponse(x)) {
+  return InternalResponse.from(x);
+}
+})(data.kind),
+    }, data);
+  }
+
+  get version(): 1 | 2 {
+    return this.data.version;
+  }
+  get response(): InternalResponse {
+    return this.data.response;
+  }
+  get kind(): "report" | Response {
+    return this.data.kind;
+  }
+
+
+  toJSON(): string {
+    return JSON.stringify(this.raw, null, 2);
+  }
+}
+
+// Function to validate if an object is a Response object
+function isResponse(obj: any): obj is Response {
+  return (
+    obj &&
+    typeof obj === "object"
+    && ("status" in obj && ((obj.status === "ok") || (obj.status === "error")))
+    && ("code" in obj && ((obj.code === 200) || (obj.code === 404)))
+    && ("cached" in obj && (obj.cached === true))
+    && ("note" in obj && ((obj.note === null || obj.note === undefined) || obj.note === "draft"))
+  );
+}
+
+
+class InternalResponse implements Response {
+  private constructor(private data: {
+    status: "ok" | "error",
+    code: 200 | 404,
+    cached: true,
+    note: "draft" | null,
+  }, private raw: Response) {}
+
+  static from(data: Response): InternalResponse {
+    return new InternalResponse({
+      status: ((x) => {
+if (x === "ok") {
+  return x;
+}
+if (x === "error") {
+  return x;
+}
+})(data.status),
+      code: ((x) => {
+if (x === 200) {
+  return x;
+}
+if (x === 404) {
+  return x;
+}
+})(data.code),
+      cached: data.cached,
+      note: (data.note === null || data.note === undefined) ? null : data.note,
+    }, data);
+  }
+
+  get status(): "ok" | "error" {
+    return this.data.status;
+  }
+  get code(): 200 | 404 {
+    return this.data.code;
+  }
+  get cached(): true {
+    return this.data.cached;
+  }
+  get note(): "draft" | null {
+    return this.data.note;
+  }
+
+
+  toJSON(): string {
+    return JSON.stringify(this.raw, null, 2);
+  }
+}
+
+
+export { InternalDocument, InternalResponse }
\ No newline at end of file
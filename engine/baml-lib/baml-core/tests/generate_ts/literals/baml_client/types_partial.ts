@@ -0,0 +1,24 @@
+// This file is auto-generated. Do not edit this file manually.
+//
+// Disable formatting for this file to avoid linting errors.
+// tslint:disable
+// @ts-nocheck
+
+import { Response } from './types';
+
+
+interface PartialDocument {
+  version: 1 | 2 | null;
+  response: PartialResponse | null;
+  kind: "report" | Response | null;
+}
+
+interface PartialResponse {
+  status: "ok" | "error" | null;
+  code: 200 | 404 | null;
+  cached: true | null;
+  note: "draft" | null;
+}
+
+
+export { PartialDocument, PartialResponse }
\ No newline at end of file
@@ -0,0 +1,72 @@
+// This file is auto-generated. Do not edit this file manually.
+//
+// Disable formatting for this file to avoid linting errors.
+// tslint:disable
+// @ts-nocheck
+
+import { Comment, Tree } from './types';
+import { PartialComment } from './types_partial';
+import { BamlStream } from '@boundaryml/baml_client/baml_lib/stream/baml_stream';
+
+
+type IOutline = (arg: Tree) => Promise<Comment>
+type IOutlineStream = (arg: Tree) => BamlStream<Comment, PartialComment>
+
+type OutlineImpls = 'v1';
+
+interface OutlineImpl {
+    run: IOutline;
+    stream: IOutlineStream;
+    name: OutlineImpls;
+}
+
+interface OutlineFunction {
+  registerImpl: (name: OutlineImpls, impl: OutlineImpl) => void;
+  getImpl: (name: OutlineImpls) => OutlineImpl;
+  stream: IOutlineStream;
+}
+
+function createOutlineInstance(): IOutline & OutlineFunction {
+
+  const registry: Record<OutlineImpls, OutlineImpl> = {}
+
+  const wrapper: OutlineFunction = {
+    getImpl: (name: OutlineImpls) => {
+      const impl = registry[name];
+      if (!impl) {
+        throw new Error(`No implementation for Outline with name ${name}`);
+      }
+      return impl;
+    },
+    registerImpl: (name: OutlineImpls, impl: OutlineImpl) => {
+      if (registry[name]) {
+        throw new Error(`Implementation for Outline with name ${name} already exists`);
+      }
+      registry[name] = impl;
+    },
+    validate: () => {
+      const targets = ['v1'];
+      const impls = Object.keys(registry);
+      const missing = targets.filter(t => !impls.includes(t));
+      if (missing.length > 0) {
+        throw new Error(`Missing implementations for Outline: ${missing.join(', ')}`);
+      }
+    },
+    stream: (params: Tree) => {
+      return wrapper.getImpl('v1').stream(params);
+    }
+  };
+
+  const impl = async (params: Tree) => {
+    return wrapper.getImpl('v1').run(params);
+  };
+
+  Object.assign(impl, wrapper);
+
+  return impl as  IOutline & OutlineFunction;
+}
+
+const Outline = createOutlineInstance();
+
+
+export { Outline, IOutline, IOutlineStream, OutlineFunction }
\ No newline at end of file
@@ -0,0 +1,68 @@
+// This file is auto-generated. Do not edit this file manually.
+//
+// Disable formatting for this file to avoid linting errors.
+// tslint:disable
+// @ts-nocheck
+
+import { GPT4 } from '../client';
+import { Outline } from '../function';
+import { schema } from '../json_schema';
+import { Comment, Tree } from '../types';
+import { InternalTree } from '../types_internal';
+import { PartialComment } from '../types_partial';
+import { Deserializer } from '@boundaryml/baml_client/baml_lib/deserializer/deserializer';
+import { BamlStream } from '@boundaryml/baml_client/baml_lib/stream/baml_stream';
+
+
+const prompt_template = `\
+Comment on {//BAML_CLIENT_REPLACE_ME_MAGIC_input//}.
+
+Comment {
+  "text": string,
+  "replies": {
+    "author": string,
+    "comment": Comment | null
+  }[]
+}\
+`;
+
+const deserializer = new Deserializer<Comment>(schema, {
+  $ref: '#/definitions/Outline_output'
+});
+const partialDeserializer = new Deserializer<PartialComment>(schema, {
+  $ref: '#/definitions/Outline_output'
+}, true);
+
+// The prompt template, and the value of each of its variables.
+const templateArgs = (
+arg: Tree
+): [string, { [key: string]: string }] => {
+  return [
+    prompt_template,
+    {
+      "{//BAML_CLIENT_REPLACE_ME_MAGIC_input//}": arg,
+    }
+  ];
+};
+
+Outline.registerImpl('v1', {
+  name: 'v1',
+  run: async (
+arg: Tree
+  ): Promise<Comment> => {
+    const [prompt, replacers] = templateArgs(arg);
+    const result = await GPT4.run_prompt_template(prompt, replacers);
+
+    return deserializer.coerce(result);
+  },
+  stream: (
+arg: Tree
+  ): BamlStream<Comment, PartialComment> => {
+    const [prompt, replacers] = templateArgs(arg);
+    return new BamlStream(
+      () => GPT4.run_prompt_template_stream(prompt, replacers),
+      partialDeserializer,
+      deserializer
+    );
+  }
+});
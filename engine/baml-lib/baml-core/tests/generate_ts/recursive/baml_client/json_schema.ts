@@ -0,0 +1,20 @@
+// This file is auto-generated. Do not edit this file manually.
+//
+// Disable formatting for this file to avoid linting errors.
+// tslint:disable
+// @ts-nocheck
+
+import { registerEnumDeserializer, registerObjectDeserializer } from '@boundaryml/baml_client/baml_lib/deserializer/deserializer';
+import { JSONSchema7 } from 'json-schema';
+
+
+const schema: JSONSchema7 = {"definitions":{"Comment":{"title":"Comment","type":"object","properties":{"text":{"type":"string"},"replies":{"type":"array","items":{"$ref":"#/definitions/Reply"}}},"required":["text","replies"]},"Reply":{"title":"Reply","type":"object","properties":{"author":{"type":"string"},"comment":{"anyOf":[{"$ref":"#/definitions/Comment","title":"Comment"},{"type":"null","title":"null"}],"default":null}},"required":["author"]},"Tree":{"title":"Tree","type":"object","properties":{"value":{"type":"integer"},"children":{"type":"array","items":{"$ref":"#/definitions/Tree"}}},"required":["value","children"]},"Outline_input":{"$ref":"#/definitions/Tree","title":"Outline input"},"Outline_output":{"$ref":"#/definitions/Comment","title":"Outline output"}}};
+
+registerObjectDeserializer(schema.definitions.Comment, { });
+
+registerObjectDeserializer(schema.definitions.Reply, { });
+
+registerObjectDeserializer(schema.definitions.Tree, { });
+
+
+export { schema }
\ No newline at end of file
@@ -0,0 +1,23 @@
+// This file is auto-generated. Do not edit this file manually.
+//
+// Disable formatting for this file to avoid linting errors.
+// tslint:disable
+// @ts-nocheck
+
+interface Comment {
+  text: string;
+  replies: Reply[];
+}
+
+interface Reply {
+  author: string;
+  comment: Comment | null;
+}
+
+interface Tree {
+  value: number;
+  children: Tree[];
+}
+
+
+export { Comment, Reply, Tree }
\ No newline at end of file
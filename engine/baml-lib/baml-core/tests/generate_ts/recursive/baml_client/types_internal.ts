@@ -0,0 +1,122 @@
+// This file is auto-generated. Do not edit this file manually.
+//
+// Disable formatting for this file to avoid linting errors.
+// tslint:disable
+// @ts-nocheck
+
+import { Comment, Reply, Tree } from './types';
+
+
+// Function to validate if an object is a Comment object
+function isComment(obj: any): obj is Comment {
+  return (
+    obj &&
+    typeof obj === "object"
+    && ("text" in obj && (typeof obj.text === 'string'))
+    && ("replies" in obj && (Array.isArray(obj.replies) && obj.replies.every((x: any) => isReply(x))))
+  );
+}
+
+
+class InternalComment implements Comment {
+  private constructor(private data: {
+    text: string,
+    replies: InternalReply[],
+  }, private raw: Comment) {}
+
+  static from(data: Comment): InternalComment {
+    return new InternalComment({
+      text: data.text,
+      replies: data.replies.map(x => InternalReply.from(x)),
+    }, data);
+  }
+
+  get text(): string {
+    return this.data.text;
+  }
+  get replies(): InternalReply[] {
+    return this.data.replies;
+  }
+
+
+  toJSON(): string {
+    return JSON.stringify(this.raw, null, 2);
+  }
+}
+
+// Function to validate if an object is a Reply object
+function isReply(obj: any): obj is Reply {
+  return (
+    obj &&
+    typeof obj === "object"
+    && ("author" in obj && (typeof obj.author === 'string'))
+    && ("comment" in obj && ((obj.comment === null || obj.comment === undefined) || isComment(obj.comment)))
+  );
+}
+
+
+class InternalReply implements Reply {
+  private constructor(private data: {
+    author: string,
+    comment: Comment | null,
+  }, private raw: Reply) {}
+
+  static from(data: Reply): InternalReply {
+    return new InternalReply({
+      author: data.author,
+      comment: (data.comment === null || data.comment === undefined) ? null : InternalComment.from(data.comment),
+    }, data);
+  }
+
+  get author(): string {
+    return this.data.author;
+  }
+  get comment(): Comment | null {
+    return this.data.comment;
+  }
+
+
+  toJSON(): string {
+    return JSON.stringify(this.raw, null, 2);
+  }
+}
+
+// Function to validate if an object is a Tree object
+function isTree(obj: any): obj is Tree {
+  return (
+    obj &&
+    typeof obj === "object"
+    && ("value" in obj && (typeof obj.value === 'number'))
+    && ("children" in obj && (Array.isArray(obj.children) && obj.children.every((x: any) => isTree(x))))
+  );
+}
+
+
+class InternalTree implements Tree {
+  private constructor(private data: {
+    value: number,
+    children: InternalTree[],
+  }, private raw: Tree) {}
+
+  static from(data: Tree): InternalTree {
+    return new InternalTree({
+      value: data.value,
+      children: data.children.map(x => InternalTree.from(x)),
+    }, data);
+  }
+
+  get value(): number {
+    return this.data.value;
+  }
+  get children(): InternalTree[] {
+    return this.data.children;
+  }
+
+
+  toJSON(): string {
+    return JSON.stringify(this.raw, null, 2);
+  }
+}
+
+
+export { InternalComment, InternalReply, InternalTree }
\ No newline at end of file
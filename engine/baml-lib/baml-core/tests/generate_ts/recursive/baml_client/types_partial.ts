@@ -0,0 +1,18 @@
+// This file is auto-generated. Do not edit this file manually.
+//
+// Disable formatting for this file to avoid linting errors.
+// tslint:disable
+// @ts-nocheck
+
+interface PartialComment {
+  text: string | null;
+  replies: PartialReply[] | null;
+}
+
+interface PartialReply {
+  author: string | null;
+  comment: PartialComment | null;
+}
+
+
+export { PartialComment, PartialReply }
\ No newline at end of file
@@ -0,0 +1,198 @@
+// This file is auto-generated. Do not edit this file manually.
+//
+// Disable formatting for this file to avoid linting errors.
+// tslint:disable
+// @ts-nocheck
+
+import { Person, Plan, Step, Task } from './types';
+import { PartialPlan, PartialStep } from './types_partial';
+import { BamlStream } from '@boundaryml/baml_client/baml_lib/stream/baml_stream';
+
+
+type IListSteps = (arg: string) => Promise<Step[]>
+type IListStepsStream = (arg: string) => BamlStream<Step[], PartialStep[]>
+
+type ListStepsImpls = 'v1';
+
+interface ListStepsImpl {
+    run: IListSteps;
+    stream: IListStepsStream;
+    name: ListStepsImpls;
+}
+
+interface ListStepsFunction {
+  registerImpl: (name: ListStepsImpls, impl: ListStepsImpl) => void;
+  getImpl: (name: ListStepsImpls) => ListStepsImpl;
+  stream: IListStepsStream;
+}
+
+function createListStepsInstance(): IListSteps & ListStepsFunction {
+
+  const registry: Record<ListStepsImpls, ListStepsImpl> = {}
+
+  const wrapper: ListStepsFunction = {
+    getImpl: (name: ListStepsImpls) => {
+      const impl = registry[name];
+      if (!impl) {
+        throw new Error(`No implementation for ListSteps with name ${name}`);
+      }
+      return impl;
+    },
+    registerImpl: (name: ListStepsImpls, impl: ListStepsImpl) => {
+      if (registry[name]) {
+        throw new Error(`Implementation for ListSteps with name ${name} already exists`);
+      }
+      registry[name] = impl;
+    },
+    validate: () => {
+      const targets = ['v1'];
+      const impls = Object.keys(registry);
+      const missing = targets.filter(t => !impls.includes(t));
+      if (missing.length > 0) {
+        throw new Error(`Missing implementations for ListSteps: ${missing.join(', ')}`);
+      }
+    },
+    stream: (params: string) => {
+      return wrapper.getImpl('v1').stream(params);
+    }
+  };
+
+  const impl = async (params: string) => {
+    return wrapper.getImpl('v1').run(params);
+  };
+
+  Object.assign(impl, wrapper);
+
+  return impl as  IListSteps & ListStepsFunction;
+}
+
+const ListSteps = createListStepsInstance();
+
+type IMakePlan = (args: {
+  goal: string, people: Person[]
+}) => Promise<Plan>
+type IMakePlanStream = (args: {
+  goal: string, people: Person[]
+}) => BamlStream<Plan, PartialPlan>
+
+type MakePlanImpls = 'v1';
+
+interface MakePlanImpl {
+    run: IMakePlan;
+    stream: IMakePlanStream;
+    name: MakePlanImpls;
+}
+
+interface MakePlanFunction {
+  registerImpl: (name: MakePlanImpls, impl: MakePlanImpl) => void;
+  getImpl: (name: MakePlanImpls) => MakePlanImpl;
+  stream: IMakePlanStream;
+}
+
+function createMakePlanInstance(): IMakePlan & MakePlanFunction {
+
+  const registry: Record<MakePlanImpls, MakePlanImpl> = {}
+
+  const wrapper: MakePlanFunction = {
+    getImpl: (name: MakePlanImpls) => {
+      const impl = registry[name];
+      if (!impl) {
+        throw new Error(`No implementation for MakePlan with name ${name}`);
+      }
+      return impl;
+    },
+    registerImpl: (name: MakePlanImpls, impl: MakePlanImpl) => {
+      if (registry[name]) {
+        throw new Error(`Implementation for MakePlan with name ${name} already exists`);
+      }
+      registry[name] = impl;
+    },
+    validate: () => {
+      const targets = ['v1'];
+      const impls = Object.keys(registry);
+      const missing = targets.filter(t => !impls.includes(t));
+      if (missing.length > 0) {
+        throw new Error(`Missing implementations for MakePlan: ${missing.join(', ')}`);
+      }
+    },
+    stream: (params : {
+      goal: string, people: Person[]
+    }) => {
+      return wrapper.getImpl('v1').stream(params);
+    }
+  };
+
+  const impl = async (params : {
+    goal: string, people: Person[]
+  }) => {
+    return wrapper.getImpl('v1').run(params);
+  };
+
+  Object.assign(impl, wrapper);
+
+  return impl as  IMakePlan & MakePlanFunction;
+}
+
+const MakePlan = createMakePlanInstance();
+
+type ITitle = (arg: Task) => Promise<string | null>
+type ITitleStream = (arg: Task) => BamlStream<string | null, string | null>
+
+type TitleImpls = 'v1';
+
+interface TitleImpl {
+    run: ITitle;
+    stream: ITitleStream;
+    name: TitleImpls;
+}
+
+interface TitleFunction {
+  registerImpl: (name: TitleImpls, impl: TitleImpl) => void;
+  getImpl: (name: TitleImpls) => TitleImpl;
+  stream: ITitleStream;
+}
+
+function createTitleInstance(): ITitle & TitleFunction {
+
+  const registry: Record<TitleImpls, TitleImpl> = {}
+
+  const wrapper: TitleFunction = {
+    getImpl: (name: TitleImpls) => {
+      const impl = registry[name];
+      if (!impl) {
+        throw new Error(`No implementation for Title with name ${name}`);
+      }
+      return impl;
+    },
+    registerImpl: (name: TitleImpls, impl: TitleImpl) => {
+      if (registry[name]) {
+        throw new Error(`Implementation for Title with name ${name} already exists`);
+      }
+      registry[name] = impl;
+    },
+    validate: () => {
+      const targets = ['v1'];
+      const impls = Object.keys(registry);
+      const missing = targets.filter(t => !impls.includes(t));
+      if (missing.length > 0) {
+        throw new Error(`Missing implementations for Title: ${missing.join(', ')}`);
+      }
+    },
+    stream: (params: Task) => {
+      return wrapper.getImpl('v1').stream(params);
+    }
+  };
+
+  const impl = async (params: Task) => {
+    return wrapper.getImpl('v1').run(params);
+  };
+
+  Object.assign(impl, wrapper);
+
+  return impl as  ITitle & TitleFunction;
+}
+
+const Title = createTitleInstance();
+
+
+export { ListSteps, IListSteps, IListStepsStream, ListStepsFunction, MakePlan, IMakePlan, IMakePlanStream, MakePlanFunction, Title, ITitle, ITitleStream, TitleFunction }
\ No newline at end of file
@@ -0,0 +1,11 @@
+// This file is auto-generated. Do not edit this file manually.
+//
+// Disable formatting for this file to avoid linting errors.
+// tslint:disable
+// @ts-nocheck
+
+import './liststeps_v1';
+
+import './makeplan_v1';
+
+import './title_v1';
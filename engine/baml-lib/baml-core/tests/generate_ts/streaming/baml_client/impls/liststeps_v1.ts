@@ -0,0 +1,64 @@
+// This file is auto-generated. Do not edit this file manually.
+//
+// Disable formatting for this file to avoid linting errors.
+// tslint:disable
+// @ts-nocheck
+
+import { GPT4 } from '../client';
+import { ListSteps } from '../function';
+import { schema } from '../json_schema';
+import { Step } from '../types';
+import { PartialStep } from '../types_partial';
+import { Deserializer } from '@boundaryml/baml_client/baml_lib/deserializer/deserializer';
+import { BamlStream } from '@boundaryml/baml_client/baml_lib/stream/baml_stream';
+
+
+const prompt_template = `\
+List the steps to {//BAML_CLIENT_REPLACE_ME_MAGIC_input//}.
+
+{
+  "title": string,
+  "done": bool
+}[]\
+`;
+
+const deserializer = new Deserializer<Step[]>(schema, {
+  $ref: '#/definitions/ListSteps_output'
+});
+const partialDeserializer = new Deserializer<PartialStep[]>(schema, {
+  $ref: '#/definitions/ListSteps_output'
+}, true);
+
+// The prompt template, and the value of each of its variables.
+const templateArgs = (
+arg: string
+): [string, { [key: string]: string }] => {
+  return [
+    prompt_template,
+    {
+      "{//BAML_CLIENT_REPLACE_ME_MAGIC_input//}": arg,
+    }
+  ];
+};
+
+ListSteps.registerImpl('v1', {
+  name: 'v1',
+  run: async (
+arg: string
+  ): Promise<Step[]> => {
+    const [prompt, replacers] = templateArgs(arg);
+    const result = await GPT4.run_prompt_template(prompt, replacers);
+
+    return deserializer.coerce(result);
+  },
+  stream: (
+arg: string
+  ): BamlStream<Step[], PartialStep[]> => {
+    const [prompt, replacers] = templateArgs(arg);
+    return new BamlStream(
+      () => GPT4.run_prompt_template_stream(prompt, replacers),
+      partialDeserializer,
+      deserializer
+    );
+  }
+});
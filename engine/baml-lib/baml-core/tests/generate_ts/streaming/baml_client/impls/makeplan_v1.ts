@@ -0,0 +1,89 @@
+// This file is auto-generated. Do not edit this file manually.
+//
+// Disable formatting for this file to avoid linting errors.
+// tslint:disable
+// @ts-nocheck
+
+import { GPT4 } from '../client';
+import { MakePlan } from '../function';
+import { schema } from '../json_schema';
+import { Person, Plan } from '../types';
+import { InternalPerson } from '../types_internal';
+import { PartialPlan } from '../types_partial';
+import { Deserializer } from '@boundaryml/baml_client/baml_lib/deserializer/deserializer';
+import { promptIsSet, promptItems } from '@boundaryml/baml_client/baml_lib/prompt_blocks';
+import { BamlStream } from '@boundaryml/baml_client/baml_lib/stream/baml_stream';
+
+
+const deserializer = new Deserializer<Plan>(schema, {
+  $ref: '#/definitions/MakePlan_output'
+});
+const partialDeserializer = new Deserializer<PartialPlan>(schema, {
+  $ref: '#/definitions/MakePlan_output'
+}, true);
+
+// The prompt template, and the value of each of its variables.
+const templateArgs = (
+args: {
+  goal: string, people: Person[]
+}
+): [string, { [key: string]: string }] => {
+  const goal = args.goal;
+  const people = args.people.map(x => InternalPerson.from(x));
+  const prompt_template = `\
+Make a plan to {//BAML_CLIENT_REPLACE_ME_MAGIC_input.goal//} with these people:
+${promptItems(people).map((person) => `- ${person.name}
+`).join("")}
+{
+  "tasks": {
+    "name": string,
+    "priority": "Priority as string" | null,
+    "steps": {
+      "title": string,
+      "done": bool
+    }[],
+    "owner": {
+      "name": string,
+      "tags": string[]
+    } | null,
+    "notes": string | {
+      "name": string,
+      "tags": string[]
+    }
+  }[],
+  "summary": string
+}\
+`;
+  return [
+    prompt_template,
+    {
+      "{//BAML_CLIENT_REPLACE_ME_MAGIC_input.goal//}": goal,
+    }
+  ];
+};
+
+MakePlan.registerImpl('v1', {
+  name: 'v1',
+  run: async (
+args: {
+  goal: string, people: Person[]
+}
+  ): Promise<Plan> => {
+    const [prompt, replacers] = templateArgs(args);
+    const result = await GPT4.run_prompt_template(prompt, replacers);
+
+    return deserializer.coerce(result);
+  },
+  stream: (
+args: {
+  goal: string, people: Person[]
+}
+  ): BamlStream<Plan, PartialPlan> => {
+    const [prompt, replacers] = templateArgs(args);
+    return new BamlStream(
+      () => GPT4.run_prompt_template_stream(prompt, replacers),
+      partialDeserializer,
+      deserializer
+    );
+  }
+});
@@ -0,0 +1,59 @@
+// This file is auto-generated. Do not edit this file manually.
+//
+// Disable formatting for this file to avoid linting errors.
+// tslint:disable
+// @ts-nocheck
+
+import { GPT4 } from '../client';
+import { Title } from '../function';
+import { schema } from '../json_schema';
+import { Task } from '../types';
+import { InternalTask } from '../types_internal';
+import { Deserializer } from '@boundaryml/baml_client/baml_lib/deserializer/deserializer';
+import { BamlStream } from '@boundaryml/baml_client/baml_lib/stream/baml_stream';
+
+
+const prompt_template = `\
+Give {//BAML_CLIENT_REPLACE_ME_MAGIC_input.name//} a title.\
+`;
+
+const deserializer = new Deserializer<string | null>(schema, {
+  $ref: '#/definitions/Title_output'
+});
+const partialDeserializer = new Deserializer<string | null>(schema, {
+  $ref: '#/definitions/Title_output'
+}, true);
+
+// The prompt template, and the value of each of its variables.
+const templateArgs = (
+arg: Task
+): [string, { [key: string]: string }] => {
+  return [
+    prompt_template,
+    {
+      "{//BAML_CLIENT_REPLACE_ME_MAGIC_input.name//}": arg.name,
+    }
+  ];
+};
+
+Title.registerImpl('v1', {
+  name: 'v1',
+  run: async (
+arg: Task
+  ): Promise<string | null> => {
+    const [prompt, replacers] = templateArgs(arg);
+    const result = await GPT4.run_prompt_template(prompt, replacers);
+
+    return deserializer.coerce(result);
+  },
+  stream: (
+arg: Task
+  ): BamlStream<string | null, string | null> => {
+    const [prompt, replacers] = templateArgs(arg);
+    return new BamlStream(
+      () => GPT4.run_prompt_template_stream(prompt, replacers),
+      partialDeserializer,
+      deserializer
+    );
+  }
+});
@@ -0,0 +1,24 @@
+// This file is auto-generated. Do not edit this file manually.
+//
+// Disable formatting for this file to avoid linting errors.
+// tslint:disable
+// @ts-nocheck
+
+import { registerEnumDeserializer, registerObjectDeserializer } from '@boundaryml/baml_client/baml_lib/deserializer/deserializer';
+import { JSONSchema7 } from 'json-schema';
+
+
+const schema: JSONSchema7 = {"definitions":{"Priority":{"title":"Priority","enum":[{"const":"LOW"},{"const":"HIGH"}]},"Person":{"title":"Person","type":"object","properties":{"name":{"type":"string"},"tags":{"type":"array","items":{"type":"string"}}},"required":["name","tags"]},"Plan":{"title":"Plan","type":"object","properties":{"tasks":{"$ref":"#/definitions/Tasks"},"summary":{"type":"string"}},"required":["tasks","summary"]},"Step":{"title":"Step","type":"object","properties":{"title":{"type":"string"},"done":{"type":"boolean"}},"required":["title","done"]},"Task":{"title":"Task","type":"object","properties":{"name":{"type":"string"},"priority":{"anyOf":[{"$ref":"#/definitions/Priority","title":"Priority"},{"type":"null","title":"null"}],"default":null},"steps":{"type":"array","items":{"$ref":"#/definitions/Step"}},"owner":{"anyOf":[{"$ref":"#/definitions/Person","title":"Person"},{"type":"null","title":"null"}],"default":null},"notes":{"anyOf":[{"type":"string","title":"string"},{"$ref":"#/definitions/Person","title":"Person"}]}},"required":["name","steps","notes"]},"Tasks":{"type":"array","items":{"$ref":"#/definitions/Task"},"title":"Tasks"},"ListSteps_input":{"type":"string","title":"ListSteps input"},"MakePlan_input":{"type":"object","properties":{"goal":{"type":"string"},"people":{"type":"array","items":{"$ref":"#/definitions/Person"}}},"required":[],"title":"MakePlan input"},"Title_input":{"$ref":"#/definitions/Task","title":"Title input"},"ListSteps_output":{"type":"array","items":{"$ref":"#/definitions/Step"},"title":"ListSteps output"},"MakePlan_output":{"$ref":"#/definitions/Plan","title":"MakePlan output"},"Title_output":{"type":["string","null"],"default":null,"title":"Title output"}}};
+
+registerEnumDeserializer(schema.definitions.Priority, { });
+
+registerObjectDeserializer(schema.definitions.Person, { });
+
+registerObjectDeserializer(schema.definitions.Plan, { });
+
+registerObjectDeserializer(schema.definitions.Step, { });
+
+registerObjectDeserializer(schema.definitions.Task, { });
+
+
+export { schema }
\ No newline at end of file
@@ -0,0 +1,38 @@
+// This file is auto-generated. Do not edit this file manually.
+//
+// Disable formatting for this file to avoid linting errors.
+// tslint:disable
+// @ts-nocheck
+
+const enum Priority {
+    LOW = "LOW",
+    HIGH = "HIGH",
+}
+
+interface Person {
+  name: string;
+  tags: string[];
+}
+
+interface Plan {
+  tasks: Tasks;
+  summary: string;
+}
+
+interface Step {
+  title: string;
+  done: boolean;
+}
+
+interface Task {
+  name: string;
+  priority: Priority | null;
+  steps: Step[];
+  owner: Person | null;
+  notes: string | Person;
+}
+
+type Tasks = Task[];
+
+
+export { Priority, Person, Plan, Step, Task, Tasks }
\ No newline at end of file
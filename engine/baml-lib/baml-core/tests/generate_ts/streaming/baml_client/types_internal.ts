@@ -0,0 +1,189 @@
+// This file is auto-generated. Do not edit this file manually.
+//
+// Disable formatting for this file to avoid linting errors.
+// tslint:disable
+// @ts-nocheck
+
+import { Person, Plan, Priority, Step, Task } from './types';
+
+
+// Function to check if a value is a member of the Priority enum
+function isPriority(value: any): value is Priority {
+  return Object.values(Priority).includes(value);
+}
+
+// Function to validate if an object is a Person object
+function isPerson(obj: any): obj is Person {
+  return (
+    obj &&
+    typeof obj === "object"
+    && ("name" in obj && (typeof obj.name === 'string'))
+    && ("tags" in obj && (Array.isArray(obj.tags) && obj.tags.every((x: any) => typeof x === 'string')))
+  );
+}
+
+
+class InternalPerson implements Person {
+  private constructor(private data: {
+    name: string,
+    tags: string[],
+  }, private raw: Person) {}
+
+  static from(data: Person): InternalPerson {
+    return new InternalPerson({
+      name: data.name,
+      tags: data.tags.map(x => x),
+    }, data);
+  }
+
+  get name(): string {
+    return this.data.name;
+  }
+  get tags(): string[] {
+    return this.data.tags;
+  }
+
+
+  toJSON(): string {
+    return JSON.stringify(this.raw, null, 2);
+  }
+}
+
+// Function to validate if an object is a Plan object
+function isPlan(obj: any): obj is Plan {
+  return (
+    obj &&
+    typeof obj === "object"
+    && ("tasks" in obj && (Array.isArray(obj.tasks) && obj.tasks.every((x: any) => isTask(x))))
+    && ("summary" in obj && (typeof obj.summary === 'string'))
+  );
+}
+
+
+class InternalPlan implements Plan {
+  private constructor(private data: {
+    tasks: InternalTask[],
+    summary: string,
+  }, private raw: Plan) {}
+
+  static from(data: Plan): InternalPlan {
+    return new InternalPlan({
+      tasks: data.tasks.map(x => InternalTask.from(x)),
+      summary: data.summary,
+    }, data);
+  }
+
+  get tasks(): InternalTask[] {
+    return this.data.tasks;
+  }
+  get summary(): string {
+    return this.data.summary;
+  }
+
+
+  toJSON(): string {
+    return JSON.stringify(this.raw, null, 2);
+  }
+}
+
+// Function to validate if an object is a Step object
+function isStep(obj: any): obj is Step {
+  return (
+    obj &&
+    typeof obj === "object"
+    && ("title" in obj && (typeof obj.title === 'string'))
+    && ("done" in obj && (typeof obj.done === 'boolean'))
+  );
+}
+
+
+class InternalStep implements Step {
+  private constructor(private data: {
+    title: string,
+    done: boolean,
+  }, private raw: Step) {}
+
+  static from(data: Step): InternalStep {
+    return new InternalStep({
+      title: data.title,
+      done: data.done,
+    }, data);
+  }
+
+  get title(): string {
+    return this.data.title;
+  }
+  get done(): boolean {
+    return this.data.done;
+  }
+
+
+  toJSON(): string {
+    return JSON.stringify(this.raw, null, 2);
+  }
+}
+
+// Function to validate if an object is a Task object
+function isTask(obj: any): obj is Task {
+  return (
+    obj &&
+    typeof obj === "object"
+    && ("name" in obj && (typeof obj.name === 'string'))
+    && ("priority" in obj && ((obj.priority === null || obj.priority === undefined) || isPriority(obj.priority)))
+    && ("steps" in obj && (Array.isArray(obj.steps) && obj.steps.every((x: any) => isStep(x))))
+    && ("owner" in obj && ((obj.owner === null || obj.owner === undefined) || isPerson(obj.owner)))
+    && ("notes" in obj && ((typeof obj.notes === 'string') || (isPerson(obj.notes))))
+  );
+}
+
+
+class InternalTask implements Task {
+  private constructor(private data: {
+    name: string,
+    priority: Priority | null,
+    steps: InternalStep[],
+    owner: Person | null,
+    notes: string | Person,
+  }, private raw: Task) {}
+
+  static from(data: Task): InternalTask {
+    return new InternalTask({
+      name: data.name,
+      priority: (data.priority === null || data.priority === undefined) ? null : data.priority,
+      steps: data.steps.map(x => InternalStep.from(x)),
+      owner: (data.owner === null || data.owner === undefined) ? null : InternalPerson.from(data.owner),
+      notes: ((x) => {
+if (typeof x === 'string') {
+  return x;
+}
+if (isPerson(x)) {
+  return InternalPerson.from(x);
+}
+})(data.notes),
+    }, data);
+  }
+
+  get name(): string {
+    return this.data.name;
+  }
+  get priority(): Priority | null {
+    return this.data.priority;
+  }
+  get steps(): InternalStep[] {
+    return this.data.steps;
+  }
+  get owner(): Person | null {
+    return this.data.owner;
+  }
+  get notes(): string | Person {
+    return this.data.notes;
+  }
+
+
+  toJSON(): string {
+    return JSON.stringify(this.raw, null, 2);
+  }
+}
+
+
+export { InternalPerson, InternalPlan, InternalStep, InternalTask }
\ No newline at end of file
@@ -0,0 +1,34 @@
+// This file is auto-generated. Do not edit this file manually.
+//
+// Disable formatting for this file to avoid linting errors.
+// tslint:disable
+// @ts-nocheck
+
+import { Person, Priority } from './types';
+
+
+interface PartialPerson {
+  name: string | null;
+  tags: string[] | null;
+}
+
+interface PartialPlan {
+  tasks: PartialTask[] | null;
+  summary: string | null;
+}
+
+interface PartialStep {
+  title: string | null;
+  done: boolean | null;
+}
+
+interface PartialTask {
+  name: string | null;
+  priority: Priority | null;
+  steps: PartialStep[] | null;
+  owner: PartialPerson | null;
+  notes: string | Person | null;
+}
+
+
+export { PartialPerson, PartialPlan, PartialStep, PartialTask }
\ No newline at end of file
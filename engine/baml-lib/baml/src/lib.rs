@@ -7,8 +7,9 @@ pub use internal_baml_core::{
     self,
     internal_baml_diagnostics::{self, Diagnostics, SourceFile},
     internal_baml_parser_database::{self},
-    internal_baml_schema_ast, Configuration, ValidatedSchema,
+    internal_baml_schema_ast, Configuration, GeneratorLanguage, ValidatedSchema,
 };
+pub use internal_baml_jsonish;
 
 /// Parses and validate a schema, but skip analyzing everything except datasource and generator
 /// blocks.
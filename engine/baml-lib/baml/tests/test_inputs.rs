@@ -0,0 +1,171 @@
+use std::path::PathBuf;
+
+use baml_lib::SourceFile;
+
+const SCHEMA: &str = r#"
+enum Mood {
+  Happy
+  Sad
+}
+
+class Person {
+  name string
+  age int?
+  moods Mood[]
+}
+
+function Greet {
+  input Person
+  output string
+}
+
+function Compare {
+  input (first: Person, scores: {string: float}, pair: (int, string))
+  output bool
+}
+"#;
+
+/// Validates the schema along with test files, and returns every error along with the text it
+/// points at.
+fn errors(tests: &[(&str, &str)]) -> Vec<(String, String)> {
+    let root = PathBuf::from("/project");
+    let mut files = vec![SourceFile::from((root.join("main.baml"), SCHEMA))];
+    files.extend(
+        tests
+            .iter()
+            .map(|(path, content)| SourceFile::from((root.join(path), *content))),
+    );
+
+    let schema = baml_lib::validate(&root, files);
+    schema
+        .diagnostics
+        .errors()
+        .iter()
+        .map(|e| {
+            let span = e.span();
+            (
+                e.message().to_string(),
+                span.file.as_str()[span.start..span.end].to_string(),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn valid_inputs() {
+    let errors = errors(&[
+        (
+            "__tests__/Greet/bob.json",
+            r#"{"input": {"name": "Bob", "moods": ["Happy"]}}"#,
+        ),
+        (
+            "__tests__/Compare/group/scores.json",
+            r#"{"input": {
+                "first": {"name": "Ann", "age": 3, "moods": []},
+                "scores": {"a": 1, "b": 2.5},
+                "pair": [1, "one"]
+            }}"#,
+        ),
+    ]);
+    assert_eq!(errors, vec![]);
+}
+
+#[test]
+fn invalid_inputs_point_at_the_value() {
+    let errors = errors(&[(
+        "__tests__/Greet/bob.json",
+        r#"{
+  "input": {
+    "name": 42,
+    "age": 1.5,
+    "moods": ["Happy", "Angry"],
+    "nickname": "B"
+  }
+}"#,
+    )]);
+    assert_eq!(
+        errors,
+        vec![
+            (
+                "Error validating: Expected `string` at `input.name`, got a number.".into(),
+                "42".into()
+            ),
+            (
+                "Error validating: Expected `int` at `input.age`, got a number.".into(),
+                "1.5".into()
+            ),
+            (
+                "Error validating: `Angry` is not a value of enum `Mood` at `input.moods[1]`. Expected one of: `Happy`, `Sad`.".into(),
+                "\"Angry\"".into()
+            ),
+            (
+                "Error validating: Unknown field `nickname` of class `Person` at `input`.".into(),
+                "\"nickname\"".into()
+            ),
+        ]
+    );
+}
+
+#[test]
+fn invalid_named_args() {
+    let errors = errors(&[(
+        "__tests__/Compare/scores.json",
+        r#"{"input": {"first": {"moods": []}, "scores": {"a": "high"}, "pair": [1]}}"#,
+    )]);
+    assert_eq!(
+        errors,
+        vec![
+            (
+                "Error validating: Missing `name` of class `Person` at `input.first`.".into(),
+                "{\"moods\": []}".into()
+            ),
+            (
+                "Error validating: Expected `float` at `input.scores.a`, got a string.".into(),
+                "\"high\"".into()
+            ),
+            (
+                "Error validating: Expected `(int, string)` with 2 items at `input.pair`, got a list."
+                    .into(),
+                "[1]".into()
+            ),
+        ]
+    );
+}
+
+#[test]
+fn unknown_function_directory() {
+    let errors = errors(&[("__tests__/Gret/bob.json", r#"{"input": {}}"#)]);
+    assert_eq!(errors.len(), 1, "{:?}", errors);
+    assert_eq!(
+        errors[0].0,
+        "`__tests__/Gret` does not match any function. Did you mean one of these: `Greet`, `Compare`?"
+    );
+}
+
+#[test]
+fn assertions_in_test_files() {
+    let errors = errors(&[(
+        "__tests__/Greet/bob.json",
+        r#"{
+  "input": {"name": "Bob", "moods": []},
+  "assert": [
+    {"matches": "^Hi"},
+    {"path": "$.name", "not_null": true},
+    {"equals": 42}
+  ]
+}"#,
+    )]);
+    assert_eq!(
+        errors,
+        vec![
+            (
+                "Error validating: Invalid path `$.name`: `string` has no field `name`.".into(),
+                "\"$.name\"".into()
+            ),
+            (
+                "Error validating: Expected `string` at `$`, got a number.".into(),
+                "42".into()
+            ),
+        ]
+    );
+}
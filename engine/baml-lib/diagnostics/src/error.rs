@@ -481,6 +481,53 @@ impl DatamodelError {
         Self::new(msg, span)
     }
 
+    pub fn new_test_function_not_found_error(
+        function_name: &str,
+        names: Vec<String>,
+        span: Span,
+    ) -> DatamodelError {
+        let close_names = sort_by_match(function_name, &names, Some(10));
+
+        let prefix = format!("`__tests__/{}` does not match any function.", function_name);
+        let msg = if close_names.is_empty() {
+            prefix
+        } else if close_names.len() == 1 {
+            format!("{} Did you mean `{}`?", prefix, close_names[0])
+        } else {
+            let suggestions = close_names.join("`, `");
+            format!("{} Did you mean one of these: `{}`?", prefix, suggestions)
+        };
+
+        Self::new(msg, span)
+    }
+
+    pub fn new_client_option_not_known_error(
+        option_name: &str,
+        provider: &str,
+        names: Vec<String>,
+        span: Span,
+    ) -> DatamodelError {
+        // Only suggest an option that looks like a typo of this one.
+        let close_names = sort_by_match(option_name, &names, Some(1))
+            .into_iter()
+            .filter(|name| {
+                strsim::osa_distance(&name.to_lowercase(), &option_name.to_lowercase())
+                    <= (option_name.len() / 3).max(1)
+            })
+            .collect::<Vec<_>>();
+
+        let prefix = format!(
+            "Unknown option `{}` for a `{}` client.",
+            option_name, provider
+        );
+        let msg = match close_names.first() {
+            Some(close_name) => format!("{} Did you mean `{}`?", prefix, close_name),
+            None => prefix,
+        };
+
+        Self::new(msg, span)
+    }
+
     pub fn new_attribute_not_known_error(attribute_name: &str, span: Span) -> DatamodelError {
         Self::new(format!("Attribute not known: \"@{attribute_name}\"."), span)
     }
@@ -119,6 +119,42 @@ impl DatamodelWarning {
         Self::new(message.to_string(), span)
     }
 
+    pub fn prompt_near_context_limit(
+        prompt_tokens: usize,
+        output_schema_tokens: usize,
+        model: &str,
+        context_size: usize,
+        max_tokens: Option<usize>,
+        span: Span,
+    ) -> DatamodelWarning {
+        let window = format!(
+            "`{}` has a context window of {} tokens",
+            model, context_size
+        );
+        let window = match max_tokens {
+            Some(max_tokens) => format!(
+                "{} and `max_tokens` reserves {} of them for the response",
+                window, max_tokens
+            ),
+            None => window,
+        };
+        let fit = match context_size
+            .checked_sub(max_tokens.unwrap_or_default())
+            .and_then(|available| available.checked_sub(prompt_tokens))
+        {
+            Some(left) => format!("so only {} are left for the inputs", left),
+            None => "so the prompt doesn't fit".to_string(),
+        };
+
+        Self::new(
+            format!(
+                "This prompt is about {} tokens before its inputs are added ({} of them for the output schema). {}, {}.",
+                prompt_tokens, output_schema_tokens, window, fit
+            ),
+            span,
+        )
+    }
+
     /// The user-facing warning message.
     pub fn message(&self) -> &str {
         &self.message
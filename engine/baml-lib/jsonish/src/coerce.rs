@@ -0,0 +1,719 @@
+use std::cmp::Reverse;
+
+use internal_baml_core::ir::{
+    FieldType, IntermediateRepr, LiteralValue, NodeAttributes, TypeValue,
+};
+use serde_json::{json, Map, Value};
+
+use crate::{parse, Deserializer, Error, Fix, FixKind};
+
+/// Coerces parsed output to a type, keeping track of the fixes it applies.
+pub(crate) struct Coercer<'ir> {
+    deserializer: Deserializer<'ir>,
+    /// The path to the value being coerced, e.g. `["steps", "[1]", "title"]`.
+    path: Vec<String>,
+    fixes: Vec<Fix>,
+    matches: Matches,
+}
+
+/// How much of the output matched the fields of classes, to tell union members apart.
+#[derive(Clone, Copy, Default)]
+struct Matches {
+    /// Fields whose keys were in the output.
+    fields: usize,
+    /// Of those, the fields whose keys were their alias.
+    aliases: usize,
+}
+
+/// A member of a union the output could be coerced to.
+struct Candidate<'t> {
+    member: &'t FieldType,
+    /// Whether the output named the member in its discriminator.
+    named: bool,
+    value: Value,
+    fixes: Vec<Fix>,
+    matches: Matches,
+}
+
+impl<'ir> Coercer<'ir> {
+    pub(crate) fn new(deserializer: Deserializer<'ir>) -> Self {
+        Self {
+            deserializer,
+            path: vec![],
+            fixes: vec![],
+            matches: Matches::default(),
+        }
+    }
+
+    pub(crate) fn into_fixes(self) -> Vec<Fix> {
+        self.fixes
+    }
+
+    /// Coerces the text a model returned. Unless `target` is a string, the JSON in the text is
+    /// tried first, then the text itself.
+    pub(crate) fn coerce_raw(&mut self, raw: &str, target: &FieldType) -> Result<Value, Error> {
+        let text = Value::String(raw.trim().to_string());
+        if is_string(self.deserializer.ir, target) {
+            return self.coerce(&text, target);
+        }
+        let parsed = parse::parse(raw).map_err(|err| self.error(err.to_string()))?;
+        let Some((parsed, fixes)) = parsed.filter(|(value, _)| !value.is_string()) else {
+            return self.coerce(&text, target);
+        };
+        let coerced = self.attempt(|c| {
+            for kind in fixes {
+                c.fix(kind);
+            }
+            c.coerce(&parsed, target)
+        });
+        match coerced {
+            Ok((value, fixes)) => {
+                self.fixes.extend(fixes);
+                Ok(value)
+            }
+            Err(err) => self.coerce(&text, target).map_err(|_| err),
+        }
+    }
+
+    pub(crate) fn coerce(&mut self, value: &Value, target: &FieldType) -> Result<Value, Error> {
+        match target {
+            FieldType::TypeAlias(name) => {
+                self.coerce(value, self.deserializer.ir.alias_target(name))
+            }
+            FieldType::Optional(inner) => self.coerce_optional(value, inner),
+            FieldType::Primitive(t) => self.coerce_primitive(value, *t),
+            FieldType::Literal(literal) => self.coerce_literal(value, literal),
+            FieldType::Enum(name) => self.coerce_enum(value, name),
+            FieldType::Class(name) => self.coerce_class(value, name),
+            FieldType::List(item) => self.coerce_list(value, item),
+            FieldType::Map(key, item) => self.coerce_map(value, key, item),
+            FieldType::Tuple(items) => self.coerce_tuple(value, items),
+            FieldType::Union(members) => self.coerce_union(value, members),
+        }
+    }
+
+    fn path(&self) -> String {
+        let mut path = String::new();
+        for segment in &self.path {
+            if !path.is_empty() && !segment.starts_with('[') {
+                path.push('.');
+            }
+            path.push_str(segment);
+        }
+        path
+    }
+
+    fn fix(&mut self, kind: FixKind) {
+        self.fixes.push(Fix {
+            path: self.path(),
+            kind,
+        });
+    }
+
+    fn error(&self, message: String) -> Error {
+        Error {
+            path: self.path(),
+            message,
+        }
+    }
+
+    fn expected(&self, expected: &str, value: &Value) -> Error {
+        self.error(format!("Expected {}, got {}", expected, type_name(value)))
+    }
+
+    /// Runs `f` on the value at `segment` of the current one.
+    fn at<T>(&mut self, segment: String, f: impl FnOnce(&mut Self) -> T) -> T {
+        self.path.push(segment);
+        let result = f(self);
+        self.path.pop();
+        result
+    }
+
+    /// Runs `f`, returning the fixes it applied instead of keeping them. Nothing is kept if `f`
+    /// fails.
+    fn attempt(
+        &mut self,
+        f: impl FnOnce(&mut Self) -> Result<Value, Error>,
+    ) -> Result<(Value, Vec<Fix>), Error> {
+        let start = self.fixes.len();
+        let matches = self.matches;
+        let result = f(self);
+        let fixes = self.fixes.split_off(start);
+        if result.is_err() {
+            self.matches = matches;
+        }
+        result.map(|value| (value, fixes))
+    }
+
+    /// The JSON value a string holds, for targets a string cannot be.
+    fn parse_string(&mut self, s: &str) -> Option<Value> {
+        let (value, fixes) = parse::parse(s)
+            .ok()
+            .flatten()
+            .filter(|(value, _)| !value.is_string())?;
+        for kind in fixes {
+            self.fix(kind);
+        }
+        Some(value)
+    }
+
+    /// The alias a field or enum value goes by in the prompt, if it has one.
+    fn alias<'a>(&self, attributes: &'a NodeAttributes) -> Option<&'a str> {
+        self.deserializer
+            .r#impl
+            .and_then(|(function, r#impl)| attributes.get_override(function, r#impl, "alias"))
+            .or_else(|| attributes.get("alias"))
+            .and_then(|alias| alias.as_string_value())
+    }
+
+    fn coerce_optional(&mut self, value: &Value, inner: &FieldType) -> Result<Value, Error> {
+        match value {
+            Value::Null => return Ok(Value::Null),
+            Value::String(s) if !is_string(self.deserializer.ir, inner) && is_null_text(s) => {
+                self.fix(FixKind::Converted {
+                    from: "string",
+                    to: "null",
+                });
+                return Ok(Value::Null);
+            }
+            _ => {}
+        }
+        match self.attempt(|c| c.coerce(value, inner)) {
+            Ok((value, fixes)) => {
+                self.fixes.extend(fixes);
+                Ok(value)
+            }
+            Err(err) => {
+                self.fix(FixKind::DefaultedToNull {
+                    error: err.to_string(),
+                });
+                Ok(Value::Null)
+            }
+        }
+    }
+
+    fn coerce_primitive(&mut self, value: &Value, t: TypeValue) -> Result<Value, Error> {
+        match (t, value) {
+            (TypeValue::String | TypeValue::Char, Value::String(_)) => Ok(value.clone()),
+            (TypeValue::String | TypeValue::Char, Value::Null) => {
+                Err(self.expected(primitive_name(t), value))
+            }
+            (TypeValue::String | TypeValue::Char, _) => {
+                self.fix(FixKind::Converted {
+                    from: type_name(value),
+                    to: "string",
+                });
+                Ok(Value::String(value.to_string()))
+            }
+            (TypeValue::Int, Value::Number(n)) => match n.as_i64() {
+                Some(_) => Ok(value.clone()),
+                None => {
+                    self.fix(FixKind::Converted {
+                        from: "float",
+                        to: "int",
+                    });
+                    let n = n.as_f64().unwrap_or_default().round() as i64;
+                    Ok(Value::Number(n.into()))
+                }
+            },
+            (TypeValue::Float, Value::Number(_))
+            | (TypeValue::Bool, Value::Bool(_))
+            | (TypeValue::Null, Value::Null) => Ok(value.clone()),
+            (TypeValue::Int | TypeValue::Float | TypeValue::Bool, Value::Array(items))
+                if items.len() == 1 =>
+            {
+                self.fix(FixKind::UnwrappedList);
+                self.coerce_primitive(&items[0], t)
+            }
+            (_, Value::String(s)) => self.coerce_scalar_string(s, t),
+            (_, _) => Err(self.expected(primitive_name(t), value)),
+        }
+    }
+
+    /// Coerces a string to a number, bool or null.
+    fn coerce_scalar_string(&mut self, s: &str, t: TypeValue) -> Result<Value, Error> {
+        let text = unquote(s.trim());
+        let converted = match t {
+            TypeValue::Int | TypeValue::Float => parse::parse_number(text),
+            TypeValue::Bool if text.eq_ignore_ascii_case("true") => Some(Value::Bool(true)),
+            TypeValue::Bool if text.eq_ignore_ascii_case("false") => Some(Value::Bool(false)),
+            TypeValue::Null if text.is_empty() || is_null_text(text) => Some(Value::Null),
+            _ => None,
+        };
+        if let Some(converted) = converted {
+            self.fix(FixKind::Converted {
+                from: "string",
+                to: match t {
+                    TypeValue::Int => "int",
+                    TypeValue::Float => "float",
+                    TypeValue::Bool => "bool",
+                    _ => "null",
+                },
+            });
+            return self.coerce_primitive(&converted, t);
+        }
+        match self.parse_string(s) {
+            Some(value) => self.coerce_primitive(&value, t),
+            None => Err(self.error(format!("Expected {}, got `{}`", primitive_name(t), text))),
+        }
+    }
+
+    /// Coerces to the type of the literal, then checks that the value is the literal. Strings may
+    /// differ in case.
+    fn coerce_literal(&mut self, value: &Value, literal: &LiteralValue) -> Result<Value, Error> {
+        let expected = json!(literal);
+        let coerced = self.coerce_primitive(value, literal.type_value())?;
+        if coerced == expected {
+            return Ok(coerced);
+        }
+        match (&coerced, literal) {
+            (Value::String(found), LiteralValue::String(s))
+                if unquote(found.trim()).eq_ignore_ascii_case(s) =>
+            {
+                self.fix(FixKind::MatchedLiteral {
+                    found: found.clone(),
+                    value: s.clone(),
+                });
+                Ok(expected)
+            }
+            _ => Err(self.error(format!("Expected {}, got {}", expected, coerced))),
+        }
+    }
+
+    fn coerce_enum(&mut self, value: &Value, name: &str) -> Result<Value, Error> {
+        let Some(r#enum) = self.deserializer.ir.find_enum(name) else {
+            return Err(self.error(format!("Unknown enum `{}`", name)));
+        };
+        let text = match value {
+            Value::String(s) => unquote(s.trim()).to_string(),
+            Value::Number(_) | Value::Bool(_) => value.to_string(),
+            Value::Array(items) if items.len() == 1 => {
+                self.fix(FixKind::UnwrappedList);
+                return self.coerce_enum(&items[0], name);
+            }
+            _ => return Err(self.expected(&format!("a value of `{}`", name), value)),
+        };
+
+        // Each value, along with the text the prompt shows for it.
+        let values = r#enum
+            .elem()
+            .values
+            .iter()
+            .map(|v| {
+                (
+                    v.elem.0.as_str(),
+                    self.alias(&v.attributes).unwrap_or(&v.elem.0),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        if let Some((value, _)) = values.iter().find(|(_, key)| *key == text) {
+            return Ok(Value::String(value.to_string()));
+        }
+        let matched = match values.iter().find(|(value, key)| {
+            key.eq_ignore_ascii_case(&text) || value.eq_ignore_ascii_case(&text)
+        }) {
+            Some((value, _)) => Some(*value),
+            // The value may be part of a longer answer, e.g. `Happy, because ...`.
+            None => {
+                let mut mentioned = values
+                    .iter()
+                    .filter(|(value, key)| contains_word(&text, key) || contains_word(&text, value))
+                    .map(|(value, _)| *value);
+                match (mentioned.next(), mentioned.next()) {
+                    (Some(value), None) => Some(value),
+                    _ => None,
+                }
+            }
+        };
+        match matched {
+            Some(value) => {
+                self.fix(FixKind::MatchedEnumValue {
+                    found: text,
+                    value: value.to_string(),
+                });
+                Ok(Value::String(value.to_string()))
+            }
+            None => Err(self.error(format!(
+                "`{}` is not a value of `{}`. Expected one of: {}",
+                text,
+                name,
+                values
+                    .iter()
+                    .map(|(_, key)| *key)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ))),
+        }
+    }
+
+    fn coerce_class(&mut self, value: &Value, name: &str) -> Result<Value, Error> {
+        let Some(class) = self.deserializer.ir.find_class(name) else {
+            return Err(self.error(format!("Unknown class `{}`", name)));
+        };
+        let entries = match value {
+            Value::Object(entries) => entries,
+            Value::Array(items) if items.len() == 1 => {
+                self.fix(FixKind::UnwrappedList);
+                return self.coerce_class(&items[0], name);
+            }
+            Value::String(s) => match self.parse_string(s) {
+                Some(value) => return self.coerce_class(&value, name),
+                None => return Err(self.expected(&format!("an object of `{}`", name), value)),
+            },
+            _ => return Err(self.expected(&format!("an object of `{}`", name), value)),
+        };
+
+        let entries = entries.iter().collect::<Vec<_>>();
+        let mut used = vec![false; entries.len()];
+        let mut result = Map::new();
+        let mut missing = vec![];
+        for field in &class.elem().static_fields {
+            let field_name = &field.elem.name;
+            let field_type = &field.elem.r#type.elem;
+            let key = self.alias(&field.attributes).unwrap_or(field_name);
+
+            let found = entries.iter().position(|(k, _)| *k == key).or_else(|| {
+                entries.iter().enumerate().position(|(idx, (k, _))| {
+                    !used[idx]
+                        && (k.eq_ignore_ascii_case(key) || k.eq_ignore_ascii_case(field_name))
+                })
+            });
+            match found {
+                Some(idx) => {
+                    used[idx] = true;
+                    let (k, v) = entries[idx];
+                    self.matches.fields += 1;
+                    if k == key && key != field_name {
+                        self.matches.aliases += 1;
+                    }
+                    if k != key {
+                        self.fix(FixKind::RenamedField {
+                            key: k.clone(),
+                            field: field_name.clone(),
+                        });
+                    }
+                    let v = self.at(field_name.clone(), |c| c.coerce(v, field_type))?;
+                    result.insert(field_name.clone(), v);
+                }
+                None if accepts_null(self.deserializer.ir, field_type) => {
+                    self.fix(FixKind::MissingOptionalField {
+                        field: field_name.clone(),
+                    });
+                    result.insert(field_name.clone(), Value::Null);
+                }
+                None => missing.push(field_name.as_str()),
+            }
+        }
+
+        if !missing.is_empty() {
+            return Err(self.error(format!(
+                "Missing required fields of `{}`: {}",
+                name,
+                missing.join(", ")
+            )));
+        }
+        for ((k, _), used) in entries.iter().zip(used) {
+            if !used {
+                self.fix(FixKind::UnknownField { key: k.to_string() });
+            }
+        }
+        Ok(Value::Object(result))
+    }
+
+    fn coerce_list(&mut self, value: &Value, item: &FieldType) -> Result<Value, Error> {
+        let items = match value {
+            Value::Array(items) => items,
+            Value::Null => return Err(self.expected("a list", value)),
+            Value::String(s) => {
+                if let Some(value) = self.parse_string(s) {
+                    return self.coerce_list(&value, item);
+                }
+                return self.coerce_single_item_list(value, item);
+            }
+            _ => return self.coerce_single_item_list(value, item),
+        };
+
+        let mut result = vec![];
+        for (idx, v) in items.iter().enumerate() {
+            let coerced = self.at(format!("[{}]", idx), |c| c.attempt(|c| c.coerce(v, item)));
+            match coerced {
+                Ok((v, fixes)) => {
+                    self.fixes.extend(fixes);
+                    result.push(v);
+                }
+                Err(err) => self.fix(FixKind::DroppedListItem {
+                    error: err.to_string(),
+                }),
+            }
+        }
+        Ok(Value::Array(result))
+    }
+
+    fn coerce_single_item_list(&mut self, value: &Value, item: &FieldType) -> Result<Value, Error> {
+        let (value, fixes) = self.attempt(|c| c.coerce(value, item))?;
+        self.fix(FixKind::WrappedInList);
+        self.fixes.extend(fixes);
+        Ok(Value::Array(vec![value]))
+    }
+
+    fn coerce_map(
+        &mut self,
+        value: &Value,
+        key: &FieldType,
+        item: &FieldType,
+    ) -> Result<Value, Error> {
+        let entries = match value {
+            Value::Object(entries) => entries,
+            Value::String(s) => match self.parse_string(s) {
+                Some(value) => return self.coerce_map(&value, key, item),
+                None => return Err(self.expected("a map", value)),
+            },
+            _ => return Err(self.expected("a map", value)),
+        };
+
+        let mut result = Map::new();
+        for (k, v) in entries {
+            let (k, v) = self.at(format!("[{:?}]", k), |c| {
+                let k = match c.coerce(&Value::String(k.clone()), key)? {
+                    Value::String(k) => k,
+                    k => k.to_string(),
+                };
+                Ok((k, c.coerce(v, item)?))
+            })?;
+            result.insert(k, v);
+        }
+        Ok(Value::Object(result))
+    }
+
+    fn coerce_tuple(&mut self, value: &Value, items: &[FieldType]) -> Result<Value, Error> {
+        let expected = format!("a list of {} items", items.len());
+        let values = match value {
+            Value::Array(values) if values.len() == items.len() => values,
+            Value::String(s) => match self.parse_string(s) {
+                Some(value) => return self.coerce_tuple(&value, items),
+                None => return Err(self.expected(&expected, value)),
+            },
+            Value::Array(values) => {
+                return Err(self.error(format!(
+                    "Expected {}, got {} items",
+                    expected,
+                    values.len()
+                )))
+            }
+            _ => return Err(self.expected(&expected, value)),
+        };
+
+        values
+            .iter()
+            .zip(items)
+            .enumerate()
+            .map(|(idx, (v, t))| self.at(format!("[{}]", idx), |c| c.coerce(v, t)))
+            .collect::<Result<Vec<_>, _>>()
+            .map(Value::Array)
+    }
+
+    /// Coerces to the member the output fits best. If the output names members in their
+    /// discriminators, only those are considered. Otherwise members are ranked by the fields the
+    /// output has, then by the fixes they take, then by the fields it gives by alias. Members that
+    /// rank the same but differ make the output ambiguous.
+    fn coerce_union(&mut self, value: &Value, members: &[FieldType]) -> Result<Value, Error> {
+        let base = self.matches;
+        let mut candidates = vec![];
+        let mut errors = vec![];
+        for member in members {
+            let named = match self.discriminate(value, member) {
+                Ok(named) => named,
+                Err(message) => {
+                    errors.push(message);
+                    continue;
+                }
+            };
+            self.matches = Matches::default();
+            match self.attempt(|c| c.coerce(value, member)) {
+                Ok((value, fixes)) => candidates.push(Candidate {
+                    member,
+                    named,
+                    value,
+                    fixes,
+                    matches: self.matches,
+                }),
+                Err(err) => errors.push(err.message),
+            }
+        }
+        self.matches = base;
+
+        if candidates.iter().any(|c| c.named) {
+            candidates.retain(|c| c.named);
+        }
+        let score =
+            |c: &Candidate<'_>| (c.matches.fields, Reverse(c.fixes.len()), c.matches.aliases);
+        let Some(best) = candidates.iter().map(score).max() else {
+            return Err(self.error(format!(
+                "Matched no member of the union: {}",
+                errors.join("; ")
+            )));
+        };
+        let mut tied = candidates.into_iter().filter(|c| score(c) == best);
+        let Some(winner) = tied.next() else {
+            unreachable!("the best score belongs to a candidate");
+        };
+        let ir = self.deserializer.ir;
+        let rivals = tied
+            .filter(|c| {
+                c.value != winner.value || class_name(ir, c.member) != class_name(ir, winner.member)
+            })
+            .collect::<Vec<_>>();
+        if !rivals.is_empty() {
+            return Err(self.error(format!(
+                "The output matches several members of the union equally well: {}",
+                std::iter::once(&winner)
+                    .chain(&rivals)
+                    .map(|c| format!("`{}`", c.member))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )));
+        }
+
+        self.matches.fields += winner.matches.fields;
+        self.matches.aliases += winner.matches.aliases;
+        self.fixes.extend(winner.fixes);
+        Ok(winner.value)
+    }
+
+    /// Whether the output names `member`, a class with a discriminator, in that field. Errs if
+    /// the output names something else there.
+    fn discriminate(&self, value: &Value, member: &FieldType) -> Result<bool, String> {
+        let Some(name) = class_name(self.deserializer.ir, member) else {
+            return Ok(false);
+        };
+        let Some(class) = self.deserializer.ir.find_class(name) else {
+            return Ok(false);
+        };
+        let Some(field) = class
+            .item
+            .attributes
+            .get("discriminator")
+            .and_then(|field| field.as_string_value())
+            .and_then(|field| {
+                class
+                    .elem()
+                    .static_fields
+                    .iter()
+                    .find(|f| f.elem.name == field)
+            })
+        else {
+            return Ok(false);
+        };
+        let key = self.alias(&field.attributes).unwrap_or(&field.elem.name);
+
+        let parsed;
+        let entries = match value {
+            Value::Object(entries) => entries,
+            Value::String(s) => match parse::parse(s) {
+                Ok(Some((Value::Object(entries), _))) => {
+                    parsed = entries;
+                    &parsed
+                }
+                _ => return Ok(false),
+            },
+            _ => return Ok(false),
+        };
+        let Some(tag) = entries
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(key) || k.eq_ignore_ascii_case(&field.elem.name))
+            .and_then(|(_, v)| v.as_str())
+        else {
+            return Ok(false);
+        };
+
+        let tag = unquote(tag.trim());
+        let class_key = self.alias(&class.item.attributes).unwrap_or(name);
+        match tag.eq_ignore_ascii_case(name) || tag.eq_ignore_ascii_case(class_key) {
+            true => Ok(true),
+            false => Err(format!(
+                "The discriminator `{}` is `{}`, not `{}`",
+                key, tag, class_key
+            )),
+        }
+    }
+}
+
+/// The class a type stands for, if it is one.
+fn class_name<'a>(ir: &'a IntermediateRepr, t: &'a FieldType) -> Option<&'a str> {
+    match t {
+        FieldType::Class(name) => Some(name),
+        FieldType::TypeAlias(name) => class_name(ir, ir.alias_target(name)),
+        _ => None,
+    }
+}
+
+/// The BAML type of a JSON value.
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "bool",
+        Value::Number(n) if n.is_f64() => "float",
+        Value::Number(_) => "int",
+        Value::String(_) => "string",
+        Value::Array(_) => "list",
+        Value::Object(_) => "object",
+    }
+}
+
+fn primitive_name(t: TypeValue) -> &'static str {
+    match t {
+        TypeValue::String => "a string",
+        TypeValue::Char => "a char",
+        TypeValue::Int => "an int",
+        TypeValue::Float => "a float",
+        TypeValue::Bool => "a bool",
+        TypeValue::Null => "null",
+    }
+}
+
+/// `text` without the quotes around it, if it has any.
+fn unquote(text: &str) -> &str {
+    ['"', '\'', '`']
+        .iter()
+        .find_map(|q| text.strip_prefix(*q).and_then(|t| t.strip_suffix(*q)))
+        .unwrap_or(text)
+}
+
+fn is_null_text(text: &str) -> bool {
+    let text = unquote(text.trim());
+    text.eq_ignore_ascii_case("null") || text.eq_ignore_ascii_case("none")
+}
+
+fn is_string(ir: &IntermediateRepr, t: &FieldType) -> bool {
+    match t {
+        FieldType::Primitive(TypeValue::String | TypeValue::Char)
+        | FieldType::Literal(LiteralValue::String(_)) => true,
+        FieldType::TypeAlias(name) => is_string(ir, ir.alias_target(name)),
+        FieldType::Optional(inner) => is_string(ir, inner),
+        _ => false,
+    }
+}
+
+/// Whether a missing value of `t` can be null.
+fn accepts_null(ir: &IntermediateRepr, t: &FieldType) -> bool {
+    match t {
+        FieldType::Optional(_) | FieldType::Primitive(TypeValue::Null) => true,
+        FieldType::TypeAlias(name) => accepts_null(ir, ir.alias_target(name)),
+        FieldType::Union(members) => members.iter().any(|m| accepts_null(ir, m)),
+        _ => false,
+    }
+}
+
+/// Whether `word` appears in `text` on its own, ignoring case.
+fn contains_word(text: &str, word: &str) -> bool {
+    if word.is_empty() {
+        return false;
+    }
+    let (text, word) = (text.to_lowercase(), word.to_lowercase());
+    let is_word_char = |c: Option<char>| c.is_some_and(|c| c.is_alphanumeric() || c == '_');
+    text.match_indices(&word).any(|(idx, _)| {
+        !is_word_char(text[..idx].chars().next_back())
+            && !is_word_char(text[idx + word.len()..].chars().next())
+    })
+}
@@ -0,0 +1,121 @@
+//! Parses what a model returns into the type a function expects.
+//!
+//! Models rarely return clean JSON: it comes wrapped in markdown fences or prose, with single
+//! quotes or trailing commas, with enum values in the wrong case, or without the fields it had
+//! nothing to say about. The deserializer tolerates all of that, coerces the output to an IR
+//! [`FieldType`], and reports every fix it had to apply, so that every client parses the same
+//! output the same way.
+
+#![deny(rust_2018_idioms, unsafe_code)]
+
+mod coerce;
+mod parse;
+#[cfg(test)]
+mod tests;
+
+use internal_baml_core::ir::{FieldType, IntermediateRepr};
+use serde_json::Value;
+
+/// A model's output, coerced to the expected type.
+#[derive(Debug, serde::Serialize)]
+pub struct Parsed {
+    pub value: Value,
+    /// The fixes it took to get `value` from the output, in the order they were applied.
+    pub fixes: Vec<Fix>,
+}
+
+/// A fix applied to part of the output.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct Fix {
+    /// Where in the value the fix was applied, e.g. `steps[1].title`. Empty for the whole value.
+    pub path: String,
+    #[serde(flatten)]
+    pub kind: FixKind,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum FixKind {
+    /// The JSON was inside a markdown code block.
+    StrippedMarkdown,
+    /// The JSON was surrounded by other text.
+    ExtractedJson,
+    /// The JSON was invalid, e.g. it used single quotes, unquoted keys or trailing commas, or was
+    /// cut off.
+    RepairedJson,
+    /// A value of one JSON type was converted to another, e.g. `"42"` to `42`.
+    Converted {
+        from: &'static str,
+        to: &'static str,
+    },
+    /// A list with a single item stood for the item.
+    UnwrappedList,
+    /// A single item stood for a list of it.
+    WrappedInList,
+    /// An enum value was written in the wrong case, or in the middle of other text.
+    MatchedEnumValue { found: String, value: String },
+    /// A string literal was written in the wrong case or in quotes.
+    MatchedLiteral { found: String, value: String },
+    /// A key stood for the field, in the wrong case or by its name instead of its alias.
+    RenamedField { key: String, field: String },
+    /// A key matched no field of the class and was left out.
+    UnknownField { key: String },
+    /// An optional field was missing and set to null.
+    MissingOptionalField { field: String },
+    /// An item of a list could not be parsed and was left out.
+    DroppedListItem { error: String },
+    /// An optional value could not be parsed and was set to null.
+    DefaultedToNull { error: String },
+}
+
+/// Why the output could not be parsed.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct Error {
+    /// Where in the value the error occurred, e.g. `steps[1].title`. Empty for the whole value.
+    pub path: String,
+    pub message: String,
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.path.is_empty() {
+            true => f.write_str(&self.message),
+            false => write!(f, "{}: {}", self.path, self.message),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Parses model output against the types of an IR.
+#[derive(Clone, Copy)]
+pub struct Deserializer<'ir> {
+    ir: &'ir IntermediateRepr,
+    /// The function and impl whose aliases override those of the types.
+    r#impl: Option<(&'ir str, &'ir str)>,
+}
+
+impl<'ir> Deserializer<'ir> {
+    pub fn new(ir: &'ir IntermediateRepr) -> Self {
+        Self { ir, r#impl: None }
+    }
+
+    /// Uses the aliases the impl `r#impl` of `function` gives fields and enum values in its
+    /// `override` blocks, as its prompt does.
+    pub fn for_impl(self, function: &'ir str, r#impl: &'ir str) -> Self {
+        Self {
+            r#impl: Some((function, r#impl)),
+            ..self
+        }
+    }
+
+    /// Parses `raw`, the text a model returned, into a value of `target`.
+    pub fn from_str(&self, raw: &str, target: &FieldType) -> Result<Parsed, Error> {
+        let mut coercer = coerce::Coercer::new(*self);
+        let value = coercer.coerce_raw(raw, target)?;
+        Ok(Parsed {
+            value,
+            fixes: coercer.into_fixes(),
+        })
+    }
+}
@@ -0,0 +1,311 @@
+use serde_json::{Map, Number, Value};
+
+use crate::FixKind;
+
+/// How deep objects and lists may nest, the same limit as `serde_json`'s, so that repairing
+/// JSON can't overflow the stack.
+pub(crate) const MAX_DEPTH: usize = 128;
+
+/// The JSON in the text nests objects and lists deeper than [`MAX_DEPTH`].
+#[derive(Debug)]
+pub(crate) struct TooDeep;
+
+impl std::fmt::Display for TooDeep {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "The JSON nests objects and lists deeper than {} levels.",
+            MAX_DEPTH
+        )
+    }
+}
+
+/// The JSON value in `text`, along with the fixes it took to get it, or `None` if `text` has
+/// none. JSON that is cut off is closed where it ends.
+pub(crate) fn parse(text: &str) -> Result<Option<(Value, Vec<FixKind>)>, TooDeep> {
+    let text = text.trim();
+    if let Ok(value) = serde_json::from_str(text) {
+        return Ok(Some((value, vec![])));
+    }
+
+    if let Some(block) = markdown_block(text) {
+        if let Some((value, mut fixes)) = parse(block)? {
+            fixes.insert(0, FixKind::StrippedMarkdown);
+            return Ok(Some((value, fixes)));
+        }
+    }
+
+    let Some((start, end)) = json_span(text) else {
+        return Ok(None);
+    };
+    let mut fixes = vec![];
+    if start > 0 || end < text.len() {
+        fixes.push(FixKind::ExtractedJson);
+    }
+    let json = &text[start..end];
+    match serde_json::from_str(json) {
+        Ok(value) => Ok(Some((value, fixes))),
+        Err(_) => {
+            let Some(value) = Repairer::new(json).value()? else {
+                return Ok(None);
+            };
+            fixes.push(FixKind::RepairedJson);
+            Ok(Some((value, fixes)))
+        }
+    }
+}
+
+/// The contents of the first markdown code block of `text`, without its language tag. A block
+/// that is never closed runs to the end of `text`.
+fn markdown_block(text: &str) -> Option<&str> {
+    let start = text.find("```")? + 3;
+    let start = start + text[start..].find('\n')? + 1;
+    let end = text[start..]
+        .find("```")
+        .map_or(text.len(), |end| start + end);
+    Some(&text[start..end])
+}
+
+/// The byte range of the first object or list of `text`. One that is never closed runs to the
+/// end of `text`.
+fn json_span(text: &str) -> Option<(usize, usize)> {
+    let start = text.find(['{', '['])?;
+    let mut open = vec![];
+    let mut quote = None;
+    let mut escaped = false;
+    for (idx, c) in text[start..].char_indices() {
+        match (quote, c) {
+            (Some(_), _) if escaped => escaped = false,
+            (Some(_), '\\') => escaped = true,
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '{' | '[') => open.push(c),
+            (None, '}' | ']') => {
+                open.pop();
+                if open.is_empty() {
+                    return Some((start, start + idx + 1));
+                }
+            }
+            (None, _) => {}
+        }
+    }
+    Some((start, text.len()))
+}
+
+/// Parses JSON the way a model tends to get it wrong: with single quotes, unquoted keys and
+/// values, trailing commas, comments, Python literals, or cut off.
+struct Repairer {
+    chars: Vec<char>,
+    pos: usize,
+    /// How many objects and lists are open.
+    depth: usize,
+}
+
+impl Repairer {
+    fn new(text: &str) -> Self {
+        Self {
+            chars: text.chars().collect(),
+            pos: 0,
+            depth: 0,
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn starts_with(&self, s: &str) -> bool {
+        s.chars()
+            .enumerate()
+            .all(|(idx, c)| self.chars.get(self.pos + idx) == Some(&c))
+    }
+
+    /// Skips whitespace and comments.
+    fn skip_whitespace(&mut self) {
+        loop {
+            if self.starts_with("//") {
+                while !matches!(self.peek(), None | Some('\n')) {
+                    self.pos += 1;
+                }
+            } else if self.starts_with("/*") {
+                self.pos += 2;
+                while self.peek().is_some() && !self.starts_with("*/") {
+                    self.pos += 1;
+                }
+                self.pos = (self.pos + 2).min(self.chars.len());
+            } else if self.peek().is_some_and(char::is_whitespace) {
+                self.pos += 1;
+            } else {
+                return;
+            }
+        }
+    }
+
+    /// Skips whitespace, comments and commas.
+    fn skip_separators(&mut self) {
+        self.skip_whitespace();
+        while self.peek() == Some(',') {
+            self.pos += 1;
+            self.skip_whitespace();
+        }
+    }
+
+    fn value(&mut self) -> Result<Option<Value>, TooDeep> {
+        self.skip_whitespace();
+        let Some(c) = self.peek() else {
+            return Ok(None);
+        };
+        match c {
+            '{' | '[' => {
+                if self.depth == MAX_DEPTH {
+                    return Err(TooDeep);
+                }
+                self.depth += 1;
+                let value = match c {
+                    '{' => self.object(),
+                    _ => self.array(),
+                };
+                self.depth -= 1;
+                value.map(Some)
+            }
+            '"' | '\'' => Ok(Some(Value::String(self.string()))),
+            _ => Ok(self.bare_value()),
+        }
+    }
+
+    fn object(&mut self) -> Result<Value, TooDeep> {
+        self.pos += 1;
+        let mut entries = Map::new();
+        loop {
+            self.skip_separators();
+            let key = match self.peek() {
+                None => break,
+                Some('}') => {
+                    self.pos += 1;
+                    break;
+                }
+                Some('"' | '\'') => self.string(),
+                Some(_) => self.bare(&[':', ',', '}', '\n']),
+            };
+            self.skip_whitespace();
+            if self.peek() != Some(':') {
+                // A key without a value, e.g. because the output was cut off after it.
+                if key.is_empty() {
+                    self.pos += 1;
+                }
+                continue;
+            }
+            self.pos += 1;
+            self.skip_whitespace();
+            if matches!(self.peek(), None | Some(',' | '}')) {
+                continue;
+            }
+            match self.value()? {
+                Some(value) => {
+                    entries.insert(key, value);
+                }
+                None => self.pos += 1,
+            }
+        }
+        Ok(Value::Object(entries))
+    }
+
+    fn array(&mut self) -> Result<Value, TooDeep> {
+        self.pos += 1;
+        let mut items = vec![];
+        loop {
+            self.skip_separators();
+            match self.peek() {
+                None => break,
+                Some(']') => {
+                    self.pos += 1;
+                    break;
+                }
+                Some(_) => match self.value()? {
+                    Some(value) => items.push(value),
+                    None => self.pos += 1,
+                },
+            }
+        }
+        Ok(Value::Array(items))
+    }
+
+    /// A string in single or double quotes. One that is never closed runs to the end.
+    fn string(&mut self) -> String {
+        let quote = self.chars[self.pos];
+        self.pos += 1;
+        let mut s = String::new();
+        while let Some(c) = self.peek() {
+            self.pos += 1;
+            match c {
+                c if c == quote => break,
+                '\\' => {
+                    let Some(escaped) = self.peek() else {
+                        break;
+                    };
+                    self.pos += 1;
+                    match escaped {
+                        'n' => s.push('\n'),
+                        't' => s.push('\t'),
+                        'r' => s.push('\r'),
+                        'b' => s.push('\u{8}'),
+                        'f' => s.push('\u{c}'),
+                        'u' => {
+                            let hex = self.chars[self.pos..].iter().take(4).collect::<String>();
+                            match u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32) {
+                                Some(c) if hex.len() == 4 => {
+                                    s.push(c);
+                                    self.pos += 4;
+                                }
+                                _ => s.push('u'),
+                            }
+                        }
+                        c => s.push(c),
+                    }
+                }
+                c => s.push(c),
+            }
+        }
+        s
+    }
+
+    /// Text up to any of `ends`, or a closing bracket, without surrounding whitespace.
+    fn bare(&mut self, ends: &[char]) -> String {
+        let start = self.pos;
+        while let Some(c) = self.peek() {
+            if ends.contains(&c) || c == ']' || c == '}' {
+                break;
+            }
+            self.pos += 1;
+        }
+        self.chars[start..self.pos]
+            .iter()
+            .collect::<String>()
+            .trim()
+            .to_string()
+    }
+
+    /// An unquoted value: a literal, a number, or otherwise a string.
+    fn bare_value(&mut self) -> Option<Value> {
+        let text = self.bare(&[',', '\n']);
+        if text.is_empty() {
+            return None;
+        }
+        Some(match text.as_str() {
+            "true" | "True" => Value::Bool(true),
+            "false" | "False" => Value::Bool(false),
+            "null" | "None" => Value::Null,
+            _ => parse_number(&text).unwrap_or(Value::String(text)),
+        })
+    }
+}
+
+/// `text` as a number, if it is one.
+pub(crate) fn parse_number(text: &str) -> Option<Value> {
+    if let Ok(n) = text.parse::<i64>() {
+        return Some(Value::Number(n.into()));
+    }
+    let n = text.parse::<f64>().ok()?;
+    Number::from_f64(n).map(Value::Number)
+}
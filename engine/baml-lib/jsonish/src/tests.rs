@@ -0,0 +1,527 @@
+use std::path::PathBuf;
+
+use indoc::indoc;
+use internal_baml_core::{
+    internal_baml_diagnostics::SourceFile,
+    ir::{self, FieldType, IntermediateRepr, LiteralValue, TypeValue},
+};
+use serde_json::json;
+
+use crate::{Deserializer, Fix, FixKind};
+
+const SCHEMA: &str = r##"
+enum Mood {
+  Happy
+  Sad @alias("down")
+}
+
+class Step {
+  title string
+  done bool
+  minutes int?
+}
+
+class Plan {
+  goal string @alias("objective")
+  mood Mood
+  steps Step[]
+}
+
+class Answer {
+  text string
+  confidence float?
+}
+
+class Note {
+  text string
+}
+
+class Comment {
+  text string
+}
+
+class Memo {
+  body string @alias("text")
+}
+
+class Refusal {
+  reason string
+}
+
+class Success {
+  status string
+  detail string
+  @@discriminator("status")
+}
+
+class Failure {
+  status string
+  detail string
+  @@discriminator("status")
+}
+
+function MakePlan {
+  input string
+  output Plan
+}
+
+client<llm> GPT4 {
+  provider baml-openai-chat
+  options {
+    model gpt-4
+  }
+}
+
+impl<llm, MakePlan> v1 {
+  client GPT4
+  override Mood {
+    Happy @alias("up")
+  }
+  prompt #"
+    Plan {#input}.
+  "#
+}
+"##;
+
+fn load_ir() -> IntermediateRepr {
+    let root_path = PathBuf::from("/project/baml_src");
+    let file = SourceFile::from((root_path.join("main.baml"), SCHEMA.to_string()));
+    let schema = internal_baml_core::validate(&root_path, vec![file]);
+    assert!(
+        !schema.diagnostics.has_errors(),
+        "{}",
+        schema.diagnostics.to_pretty_string()
+    );
+    ir::to_ir(&schema.db).unwrap()
+}
+
+fn class(name: &str) -> FieldType {
+    FieldType::Class(name.to_string())
+}
+
+fn fix(path: &str, kind: FixKind) -> Fix {
+    Fix {
+        path: path.to_string(),
+        kind,
+    }
+}
+
+#[test]
+fn parses_clean_json() {
+    let ir = load_ir();
+    let parsed = Deserializer::new(&ir)
+        .from_str(
+            r#"{"objective": "Cook", "mood": "Happy", "steps": [{"title": "Shop", "done": true, "minutes": 30}]}"#,
+            &class("Plan"),
+        )
+        .unwrap();
+    assert_eq!(
+        parsed.value,
+        json!({
+            "goal": "Cook",
+            "mood": "Happy",
+            "steps": [{ "title": "Shop", "done": true, "minutes": 30 }],
+        })
+    );
+    assert_eq!(parsed.fixes, vec![]);
+}
+
+#[test]
+fn finds_json_in_markdown_and_prose() {
+    let ir = load_ir();
+    let raw = indoc! {r#"
+        Here is the step:
+        ```json
+        {"title": "Shop", "done": false, "minutes": null}
+        ```
+        Let me know if you need anything else!
+    "#};
+    let parsed = Deserializer::new(&ir)
+        .from_str(raw, &class("Step"))
+        .unwrap();
+    assert_eq!(
+        parsed.value,
+        json!({ "title": "Shop", "done": false, "minutes": null })
+    );
+    assert_eq!(parsed.fixes, vec![fix("", FixKind::StrippedMarkdown)]);
+
+    let raw = r#"Sure! {"title": "Shop", "done": false} Hope that helps."#;
+    let parsed = Deserializer::new(&ir)
+        .from_str(raw, &class("Step"))
+        .unwrap();
+    assert_eq!(
+        parsed.fixes,
+        vec![
+            fix("", FixKind::ExtractedJson),
+            fix(
+                "",
+                FixKind::MissingOptionalField {
+                    field: "minutes".into()
+                }
+            ),
+        ]
+    );
+}
+
+#[test]
+fn repairs_invalid_json() {
+    let ir = load_ir();
+    let raw = indoc! {r#"
+        {
+          objective: 'Cook dinner',
+          // The mood of the plan
+          'mood': sad,
+          steps: [
+            {title: "It's \"done\"", done: True, minutes: '5'},
+          ],
+        }
+    "#};
+    let parsed = Deserializer::new(&ir)
+        .from_str(raw, &class("Plan"))
+        .unwrap();
+    assert_eq!(
+        parsed.value,
+        json!({
+            "goal": "Cook dinner",
+            "mood": "Sad",
+            "steps": [{ "title": "It's \"done\"", "done": true, "minutes": 5 }],
+        })
+    );
+    assert_eq!(
+        parsed.fixes,
+        vec![
+            fix("", FixKind::RepairedJson),
+            fix(
+                "mood",
+                FixKind::MatchedEnumValue {
+                    found: "sad".into(),
+                    value: "Sad".into()
+                }
+            ),
+            fix(
+                "steps[0].minutes",
+                FixKind::Converted {
+                    from: "string",
+                    to: "int"
+                }
+            ),
+        ]
+    );
+}
+
+#[test]
+fn closes_cut_off_json() {
+    let ir = load_ir();
+    let raw = r#"{"objective": "Cook", "mood": "Happy", "steps": [{"title": "Shop", "done": true}, {"title": "Co"#;
+    let parsed = Deserializer::new(&ir)
+        .from_str(raw, &class("Plan"))
+        .unwrap();
+    assert_eq!(
+        parsed.value["steps"],
+        json!([{ "title": "Shop", "done": true, "minutes": null }])
+    );
+    assert_eq!(
+        parsed.fixes.last(),
+        Some(&fix(
+            "steps",
+            FixKind::DroppedListItem {
+                error: "steps[1]: Missing required fields of `Step`: done".into()
+            }
+        ))
+    );
+}
+
+#[test]
+fn rejects_deeply_nested_json() {
+    let ir = load_ir();
+    let ints = FieldType::List(Box::new(FieldType::Primitive(TypeValue::Int)));
+    let deserializer = Deserializer::new(&ir);
+
+    let err = deserializer
+        .from_str(&"[".repeat(200_000), &ints)
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "The JSON nests objects and lists deeper than 128 levels."
+    );
+
+    let nested = format!("{}1{}", "[".repeat(100), "]".repeat(99));
+    assert!(deserializer.from_str(&nested, &ints).is_ok());
+}
+
+#[test]
+fn matches_enum_values() {
+    let ir = load_ir();
+    let mood = FieldType::Enum("Mood".into());
+    let deserializer = Deserializer::new(&ir);
+
+    assert_eq!(
+        deserializer.from_str("Happy", &mood).unwrap().value,
+        "Happy"
+    );
+    assert_eq!(
+        deserializer.from_str("\"down\"", &mood).unwrap().value,
+        "Sad"
+    );
+    let parsed = deserializer
+        .from_str("The mood is sad, since it rained.", &mood)
+        .unwrap();
+    assert_eq!(parsed.value, "Sad");
+    assert_eq!(
+        parsed.fixes,
+        vec![fix(
+            "",
+            FixKind::MatchedEnumValue {
+                found: "The mood is sad, since it rained.".into(),
+                value: "Sad".into()
+            }
+        )]
+    );
+
+    let err = deserializer.from_str("Happy or sad", &mood).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "`Happy or sad` is not a value of `Mood`. Expected one of: Happy, down"
+    );
+}
+
+#[test]
+fn uses_aliases_of_the_impl() {
+    let ir = load_ir();
+    let mood = FieldType::Enum("Mood".into());
+    let parsed = Deserializer::new(&ir)
+        .for_impl("MakePlan", "v1")
+        .from_str("up", &mood)
+        .unwrap();
+    assert_eq!(parsed.value, "Happy");
+    assert_eq!(parsed.fixes, vec![]);
+
+    let parsed = Deserializer::new(&ir).from_str("up", &mood);
+    assert!(parsed.is_err());
+}
+
+#[test]
+fn reports_fields() {
+    let ir = load_ir();
+    let raw = r#"{"Goal": "Cook", "mood": "Happy", "steps": [], "notes": "none"}"#;
+    let parsed = Deserializer::new(&ir)
+        .from_str(raw, &class("Plan"))
+        .unwrap();
+    assert_eq!(
+        parsed.value,
+        json!({ "goal": "Cook", "mood": "Happy", "steps": [] })
+    );
+    assert_eq!(
+        parsed.fixes,
+        vec![
+            fix(
+                "",
+                FixKind::RenamedField {
+                    key: "Goal".into(),
+                    field: "goal".into()
+                }
+            ),
+            fix(
+                "",
+                FixKind::UnknownField {
+                    key: "notes".into()
+                }
+            ),
+        ]
+    );
+
+    let err = Deserializer::new(&ir)
+        .from_str(r#"{"objective": "Cook"}"#, &class("Plan"))
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Missing required fields of `Plan`: mood, steps"
+    );
+}
+
+#[test]
+fn coerces_primitives() {
+    let ir = load_ir();
+    let deserializer = Deserializer::new(&ir);
+    let int = FieldType::Primitive(TypeValue::Int);
+    let string = FieldType::Primitive(TypeValue::String);
+    let ints = FieldType::List(Box::new(int.clone()));
+
+    assert_eq!(deserializer.from_str(" 42\n", &int).unwrap().value, 42);
+    assert_eq!(deserializer.from_str("[4.6]", &int).unwrap().value, 5);
+    assert_eq!(
+        deserializer
+            .from_str("  {\"a\": 1}\n", &string)
+            .unwrap()
+            .value,
+        "{\"a\": 1}"
+    );
+    assert_eq!(deserializer.from_str("7", &ints).unwrap().value, json!([7]));
+
+    let parsed = deserializer.from_str("[1, \"two\", 3]", &ints).unwrap();
+    assert_eq!(parsed.value, json!([1, 3]));
+    assert_eq!(
+        parsed.fixes,
+        vec![fix(
+            "",
+            FixKind::DroppedListItem {
+                error: "[1]: Expected an int, got `two`".into()
+            }
+        )]
+    );
+
+    let err = deserializer.from_str("seven", &int).unwrap_err();
+    assert_eq!(err.to_string(), "Expected an int, got `seven`");
+}
+
+#[test]
+fn prefers_union_members_without_fixes() {
+    let ir = load_ir();
+    let target = FieldType::Union(vec![
+        FieldType::Primitive(TypeValue::Int),
+        FieldType::Primitive(TypeValue::Bool),
+        FieldType::Primitive(TypeValue::Null),
+    ]);
+    let deserializer = Deserializer::new(&ir);
+
+    assert_eq!(deserializer.from_str("true", &target).unwrap().value, true);
+    assert_eq!(
+        deserializer.from_str("null", &target).unwrap().value,
+        json!(null)
+    );
+    let err = deserializer.from_str("maybe", &target).unwrap_err();
+    assert_eq!(
+        err.message,
+        "Matched no member of the union: Expected an int, got `maybe`; Expected a bool, got `maybe`; Expected null, got `maybe`"
+    );
+}
+
+#[test]
+fn matches_literals() {
+    let ir = load_ir();
+    let deserializer = Deserializer::new(&ir);
+    let literal = |value: LiteralValue| FieldType::Literal(value);
+    let status = FieldType::Union(vec![
+        literal(LiteralValue::String("ok".into())),
+        literal(LiteralValue::String("error".into())),
+    ]);
+
+    assert_eq!(deserializer.from_str("ok", &status).unwrap().value, "ok");
+    let parsed = deserializer.from_str("\"Error\"", &status).unwrap();
+    assert_eq!(parsed.value, "error");
+    assert_eq!(
+        parsed.fixes,
+        vec![fix(
+            "",
+            FixKind::MatchedLiteral {
+                found: "\"Error\"".into(),
+                value: "error".into()
+            }
+        )]
+    );
+    let err = deserializer.from_str("pending", &status).unwrap_err();
+    assert_eq!(
+        err.message,
+        "Matched no member of the union: Expected \"ok\", got \"pending\"; Expected \"error\", got \"pending\""
+    );
+
+    let code = literal(LiteralValue::Int(404));
+    assert_eq!(deserializer.from_str("\"404\"", &code).unwrap().value, 404);
+    let err = deserializer.from_str("200", &code).unwrap_err();
+    assert_eq!(err.to_string(), "Expected 404, got 200");
+    let cached = literal(LiteralValue::Bool(true));
+    assert_eq!(deserializer.from_str("True", &cached).unwrap().value, true);
+}
+
+fn union(names: &[&str]) -> FieldType {
+    FieldType::Union(names.iter().map(|name| class(name)).collect())
+}
+
+#[test]
+fn scores_union_members() {
+    let ir = load_ir();
+    let deserializer = Deserializer::new(&ir);
+    let answer_or_refusal = union(&["Answer", "Refusal"]);
+    let answer_or_note = union(&["Answer", "Note"]);
+
+    assert_eq!(
+        deserializer
+            .from_str(r#"{"reason": "Can't say"}"#, &answer_or_refusal)
+            .unwrap()
+            .value,
+        json!({ "reason": "Can't say" })
+    );
+    // More fields present beats fewer fixes.
+    let parsed = deserializer
+        .from_str(r#"{"text": "Yes", "Confidence": "0.9"}"#, &answer_or_note)
+        .unwrap();
+    assert_eq!(parsed.value, json!({ "text": "Yes", "confidence": 0.9 }));
+    assert_eq!(parsed.fixes.len(), 2);
+    // With the same fields present, the member that takes fewer fixes wins.
+    let parsed = deserializer
+        .from_str(r#"{"text": "Yes"}"#, &answer_or_note)
+        .unwrap();
+    assert_eq!(parsed.value, json!({ "text": "Yes" }));
+    assert_eq!(parsed.fixes, vec![]);
+    // Then the member whose aliases the keys use.
+    assert_eq!(
+        deserializer
+            .from_str(r#"{"text": "Yes"}"#, &union(&["Answer", "Memo"]))
+            .unwrap()
+            .value,
+        json!({ "body": "Yes" })
+    );
+}
+
+#[test]
+fn reports_ambiguous_unions() {
+    let ir = load_ir();
+    let deserializer = Deserializer::new(&ir);
+
+    // The same class twice is not ambiguous.
+    let parsed = deserializer
+        .from_str(r#"{"text": "Yes"}"#, &union(&["Note", "Answer", "Note"]))
+        .unwrap();
+    assert_eq!(parsed.value, json!({ "text": "Yes" }));
+
+    let err = deserializer
+        .from_str(r#"{"text": "Yes"}"#, &union(&["Note", "Answer", "Comment"]))
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "The output matches several members of the union equally well: `Note`, `Comment`"
+    );
+}
+
+#[test]
+fn uses_discriminators() {
+    let ir = load_ir();
+    let target = union(&["Success", "Failure", "Refusal"]);
+    let deserializer = Deserializer::new(&ir);
+
+    let parsed = deserializer
+        .from_str(r#"{"Status": "failure", "detail": "Timed out"}"#, &target)
+        .unwrap();
+    assert_eq!(
+        parsed.value,
+        json!({ "status": "failure", "detail": "Timed out" })
+    );
+    assert_eq!(
+        parsed.fixes,
+        vec![fix(
+            "",
+            FixKind::RenamedField {
+                key: "Status".into(),
+                field: "status".into()
+            }
+        )]
+    );
+
+    let err = deserializer
+        .from_str(r#"{"status": "pending"}"#, &target)
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Matched no member of the union: The discriminator `status` is `pending`, not `Success`; The discriminator `status` is `pending`, not `Failure`; Missing required fields of `Refusal`: reason"
+    );
+}
@@ -5,6 +5,7 @@ use internal_baml_schema_ast::ast::{
 
 mod alias;
 mod description;
+mod discriminator;
 mod get;
 mod meta;
 mod to_string_attribute;
@@ -64,6 +65,11 @@ fn resolve_class_attributes<'db>(class_id: ClassId, ast_class: &'db Class, ctx:
     // Now validate the class attributes.
     ctx.visit_attributes(class_id.into());
     class_attributes.serilizer = to_string_attribute::visit(ctx, true);
+    // @@discriminator
+    if ctx.visit_optional_single_attr("discriminator") {
+        discriminator::visit_discriminator_attribute(&mut class_attributes, ast_class, ctx);
+        ctx.validate_visited_arguments();
+    }
     ctx.validate_visited_attributes();
 
     ctx.types
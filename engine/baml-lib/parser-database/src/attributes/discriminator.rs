@@ -0,0 +1,27 @@
+use internal_baml_schema_ast::ast::{Class, WithName};
+
+use crate::{coerce, context::Context, types::ClassAttributes};
+
+pub(super) fn visit_discriminator_attribute(
+    attributes: &mut ClassAttributes,
+    ast_class: &Class,
+    ctx: &mut Context<'_>,
+) {
+    match ctx
+        .visit_default_arg_with_idx("discriminator")
+        .map(|(_, value)| coerce::string(value, ctx.diagnostics))
+    {
+        Ok(Some(field)) => {
+            if ast_class.iter_fields().any(|(_, f)| f.name() == field) {
+                attributes.discriminator = Some(ctx.interner.intern(field));
+            } else {
+                ctx.push_attribute_validation_error(
+                    &format!("`{}` is not a field of `{}`.", field, ast_class.name()),
+                    true,
+                );
+            }
+        }
+        Err(err) => ctx.push_error(err), // not flattened for error handing legacy reasons
+        Ok(None) => (),
+    };
+}
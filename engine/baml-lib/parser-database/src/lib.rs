@@ -34,6 +34,8 @@ mod context;
 mod interner;
 mod names;
 mod printer;
+mod rename;
+mod render_prompt;
 mod types;
 
 use std::collections::{HashMap, HashSet};
@@ -41,16 +43,20 @@ use std::collections::{HashMap, HashSet};
 pub use coerce_expression::{coerce, coerce_array, coerce_opt};
 use either::Either;
 pub use internal_baml_schema_ast::ast;
-use internal_baml_schema_ast::ast::{SchemaAst, WithIdentifier, WithName, WithSpan};
+use internal_baml_schema_ast::ast::{SchemaAst, WithName, WithSpan};
 pub use printer::WithStaticRenames;
+pub use render_prompt::{RenderedMessage, RenderedPrompt};
+pub use rename::{RenameTarget, TextEdit};
 pub use types::{
-    ContantDelayStrategy, DynamicStringAttributes, ExponentialBackoffStrategy, PrinterType,
-    PromptRepr, PromptVariable, RetryPolicy, RetryPolicyStrategy, StaticStringAttributes,
-    StaticType, ToStringAttributes,
+    split_prompt_blocks, AssertionCheck, ClientStrategy, ClientStrategyKind, ContantDelayStrategy,
+    DynamicStringAttributes, ExponentialBackoffStrategy, OutputPath, PathSegment, PrinterType,
+    PromptPart, PromptRepr, PromptVariable, RetryPolicy, RetryPolicyStrategy,
+    StaticStringAttributes, StaticType, TestAssertion, ToStringAttributes,
 };
 
 use self::{context::Context, interner::StringId, types::Types};
 use internal_baml_diagnostics::{DatamodelError, DatamodelWarning, Diagnostics};
+use internal_baml_prompt_parser::ast::ForBlock;
 use names::Names;
 pub use printer::WithSerialize;
 
@@ -127,7 +133,7 @@ impl ParserDatabase {
     /// Updates the prompt
     pub fn finalize(&mut self, diag: &mut Diagnostics) {
         self.link_functions(diag);
-        self.finalize_dependencies(diag);
+        self.finalize_dependencies();
         self.finalize_prompt_validation(diag);
     }
 
@@ -150,89 +156,33 @@ impl ParserDatabase {
         })
     }
 
-    fn finalize_dependencies(&mut self, diag: &mut Diagnostics) {
-        let mut deps = self
+    fn finalize_dependencies(&mut self) {
+        // Classes may refer to each other, so follow the dependencies of each one until nothing
+        // new turns up. Aliases were already expanded when resolving types.
+        let deps = self
             .types
             .class_dependencies
-            .iter()
-            .map(|f| {
-                (
-                    *f.0,
-                    f.1.iter()
-                        .fold((0, 0, 0), |prev, i| match self.find_type_by_str(i) {
-                            Some(Either::Left(_)) => (prev.0 + 1, prev.1 + 1, prev.2),
-                            Some(Either::Right(_)) => (prev.0 + 1, prev.1, prev.2 + 1),
-                            _ => prev,
-                        }),
-                )
+            .keys()
+            .map(|&id| {
+                let mut deps = HashSet::new();
+                let mut pending = self.types.class_dependencies[&id]
+                    .iter()
+                    .collect::<Vec<_>>();
+                while let Some(dep) = pending.pop() {
+                    if !deps.insert(dep.clone()) {
+                        continue;
+                    }
+                    if let Some(Either::Left(walker)) = self.find_type_by_str(dep) {
+                        pending.extend(self.types.class_dependencies[&walker.id].iter());
+                    }
+                }
+                // A recursive class does not depend on itself.
+                deps.remove(self.ast[id].name());
+                (id, deps)
             })
             .collect::<Vec<_>>();
-
-        // Can only process deps which have 0 class dependencies.
-        let mut max_loops = 100;
-        while !deps.is_empty() && max_loops > 0 {
-            max_loops -= 1;
-            // Remove all the ones which have 0 class dependencies.
-            let removed = deps
-                .iter()
-                .filter(|(_, v)| v.1 == 0)
-                .map(|(k, _)| k.clone())
-                .collect::<Vec<_>>();
-            deps.retain(|(_, v)| v.1 > 0);
-            for cls in removed {
-                let child_deps = self
-                    .types
-                    .class_dependencies
-                    .get(&cls)
-                    // These must exist by definition so safe to unwrap.
-                    .unwrap()
-                    .iter()
-                    .filter_map(|f| match self.find_type_by_str(f) {
-                        Some(Either::Left(walker)) => Some(
-                            walker
-                                .dependencies()
-                                .iter()
-                                .map(|f| f.clone())
-                                .collect::<Vec<_>>(),
-                        ),
-                        Some(Either::Right(walker)) => Some(vec![walker.name().to_string()]),
-                        _ => panic!("Unknown class `{}`", f),
-                    })
-                    .flatten()
-                    .collect::<HashSet<_>>();
-                let name = self.ast[cls].name();
-                deps.iter_mut()
-                    .filter(|(k, _)| self.types.class_dependencies[k].contains(name))
-                    .for_each(|(_, v)| {
-                        v.1 -= 1;
-                    });
-
-                // Get the dependencies of all my dependencies.
-                self.types
-                    .class_dependencies
-                    .get_mut(&cls)
-                    .unwrap()
-                    .extend(child_deps);
-            }
-        }
-
-        if max_loops == 0 && !deps.is_empty() {
-            let circular_deps = deps
-                .iter()
-                .map(|(k, _)| self.ast[*k].name())
-                .collect::<Vec<_>>()
-                .join(" -> ");
-
-            deps.iter().for_each(|(k, _)| {
-                diag.push_error(DatamodelError::new_validation_error(
-                    &format!(
-                        "Circular dependency detected for class `{}`.\n{}",
-                        self.ast[*k].name(),
-                        circular_deps
-                    ),
-                    self.ast[*k].identifier().span().clone(),
-                ));
-            });
+        for (id, deps) in deps {
+            self.types.class_dependencies.insert(id, deps);
         }
 
         // Additionally ensure the same thing for functions, but since we've already handled classes,
@@ -250,6 +200,7 @@ impl ParserDatabase {
                             Some(walker.dependencies().iter().map(|f| f.clone()))
                         }
                         Some(Either::Right(_)) => None,
+                        None if self.find_type_alias_by_str(f).is_some() => None,
                         _ => panic!("Unknown class `{}`", f),
                     })
                     .flatten()
@@ -262,6 +213,7 @@ impl ParserDatabase {
                             Some(walker.dependencies().iter().map(|f| f.clone()))
                         }
                         Some(Either::Right(_)) => None,
+                        None if self.find_type_alias_by_str(f).is_some() => None,
                         _ => panic!("Unknown class `{}`", f),
                     })
                     .flatten()
@@ -288,11 +240,29 @@ impl ParserDatabase {
             if let Some(fn_walker) = variant.walk_function() {
                 // Now lets validate the prompt is what we expect.
                 let prompt_variables = &variant.properties().prompt_replacements;
+                // The item of each open `{#for}` block, or `None` for `{#if}` blocks.
+                let mut open_blocks: Vec<Option<&str>> = vec![];
 
                 let num_errors = prompt_variables.iter().fold(0, |count, f| match f {
-                    PromptVariable::Input(variable) => {
+                    PromptVariable::Input(variable)
+                    | PromptVariable::If(variable)
+                    | PromptVariable::For(ForBlock {
+                        collection: variable,
+                        ..
+                    }) => {
+                        let loop_items = open_blocks.iter().flatten().copied().collect::<Vec<_>>();
+                        match f {
+                            PromptVariable::If(_) => open_blocks.push(None),
+                            PromptVariable::For(blk) => open_blocks.push(Some(&blk.item.0)),
+                            _ => {}
+                        }
                         // Ensure the prompt has an input path that works.
-                        match types::post_prompt::process_input(self, fn_walker, variable) {
+                        match types::post_prompt::process_input(
+                            fn_walker,
+                            variable,
+                            &loop_items,
+                            variant.properties().input_adapter.is_some(),
+                        ) {
                             Ok(replacer) => {
                                 input_replacers.insert(variable.to_owned(), replacer);
                                 count
@@ -303,6 +273,11 @@ impl ParserDatabase {
                             }
                         }
                     }
+                    PromptVariable::Else(_) => count,
+                    PromptVariable::End(_) => {
+                        open_blocks.pop();
+                        count
+                    }
                     PromptVariable::Enum(blk) => {
                         // Ensure the prompt has an enum path that works.
                         match types::post_prompt::process_print_enum(
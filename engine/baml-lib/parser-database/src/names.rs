@@ -94,6 +94,10 @@ pub(super) fn resolve_names(ctx: &mut Context<'_>) {
 
                 &mut names.tops
             }
+            (_, ast::Top::TypeAlias(alias)) => {
+                validate_type_alias_name(alias, ctx.diagnostics);
+                &mut names.tops
+            }
             (_, ast::Top::Function(_)) => {
                 unreachable!("Encountered impossible function declaration during parsing")
             }
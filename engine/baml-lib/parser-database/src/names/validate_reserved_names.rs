@@ -58,6 +58,10 @@ pub(crate) fn validate_class_fiel_name(
     );
 }
 
+pub(crate) fn validate_type_alias_name(ast_alias: &ast::TypeAlias, diagnostics: &mut Diagnostics) {
+    validate_name("type alias", ast_alias.identifier(), diagnostics, true);
+}
+
 pub(crate) fn validate_function_name(ast_func: &ast::Function, diagnostics: &mut Diagnostics) {
     validate_name("function", ast_func.identifier(), diagnostics, true);
 }
@@ -2,24 +2,13 @@ use std::collections::HashMap;
 
 use internal_baml_diagnostics::DatamodelError;
 use internal_baml_prompt_parser::ast::PrinterBlock;
-use internal_baml_schema_ast::ast::WithName;
+use internal_baml_schema_ast::ast::{self, WithName};
 
 mod print_enum_default;
 mod print_type_default;
+mod template_printer;
 
-#[cfg(feature = "use-pyo3")]
-use pyo3::Python;
-#[cfg(feature = "use-pyo3")]
-mod enum_printer;
-#[cfg(feature = "use-pyo3")]
-mod printer;
-#[cfg(feature = "use-pyo3")]
-mod type_printer;
-
-#[cfg(feature = "use-pyo3")]
-use enum_printer::setup_printer as setup_enum_printer;
-#[cfg(feature = "use-pyo3")]
-use type_printer::setup_printer as setup_type_printer;
+pub(crate) use template_printer::validate_template;
 
 use crate::{
     interner::StringId,
@@ -28,6 +17,10 @@ use crate::{
     ParserDatabase,
 };
 
+/// The classes being serialized, outermost first, and whether each was referred to again further
+/// in.
+pub(crate) type Expanding = Vec<(ast::ClassId, bool)>;
+
 /// Trait
 pub trait WithSerializeableContent {
     /// Trait to render an object.
@@ -129,39 +122,16 @@ pub trait WithSerialize: WithSerializeableContent {
     ) -> Result<String, DatamodelError>;
 }
 
-#[cfg(feature = "use-pyo3")]
-pub fn serialize_with_printer(
-    is_enum: bool,
-    printer: Option<String>,
-    json: serde_json::Value,
-) -> Result<String, String> {
-    pyo3::prepare_freethreaded_python();
-
-    Python::with_gil(|py| {
-        let printer = if is_enum {
-            setup_enum_printer(py, printer.as_deref())
-                .map_err(|e| format!("Failed to create enum printer: {}", e))?
-        } else {
-            setup_type_printer(py, printer.as_deref())
-                .map_err(|e| format!("Failed to create type printer: {}", e))?
-        };
-
-        printer.print(py, json)
-    })
-}
-
-#[cfg(not(feature = "use-pyo3"))]
+/// Renders `json` with the given printer template, or with the built-in
+/// printer when there is none.
 pub fn serialize_with_printer(
     is_enum: bool,
     template: Option<String>,
     json: serde_json::Value,
 ) -> Result<String, String> {
-    if template.is_some() {
-        return Err("printer keyword is not yet supported".to_string());
-    }
-    if is_enum {
-        Ok(print_enum_default::print_enum(json))
-    } else {
-        Ok(print_type_default::print_entry(json))
+    match template {
+        Some(template) => template_printer::render(is_enum, &template, &json),
+        None if is_enum => print_enum_default::print_enum(json),
+        None => print_type_default::print_entry(json),
     }
 }
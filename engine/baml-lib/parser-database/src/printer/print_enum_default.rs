@@ -22,7 +22,7 @@ struct EnumType {
 
 // Utility functions similar to the Python version
 
-fn as_comment(text: &str) -> String {
+pub(super) fn as_comment(text: &str) -> String {
     text.lines()
         .map(|line| format!("/// {}", line.trim()))
         .collect::<Vec<String>>()
@@ -38,8 +38,13 @@ fn print_enum_value(value: &EnumValue) -> String {
     }
 }
 
-pub(crate) fn print_enum(val: serde_json::Value) -> String {
-    let enm: EnumType = serde_json::from_value(val).unwrap();
+pub(super) fn print_enum_value_json(val: serde_json::Value) -> Result<String, String> {
+    let value: EnumValue = serde_json::from_value(val).map_err(|e| e.to_string())?;
+    Ok(print_enum_value(&value))
+}
+
+pub(crate) fn print_enum(val: serde_json::Value) -> Result<String, String> {
+    let enm: EnumType = serde_json::from_value(val).map_err(|e| e.to_string())?;
     let mut block = vec![];
 
     if let Some(description) = &enm.meta.description {
@@ -53,5 +58,5 @@ pub(crate) fn print_enum(val: serde_json::Value) -> String {
         block.push(print_enum_value(value));
     }
 
-    block.join("\n")
+    Ok(block.join("\n"))
 }
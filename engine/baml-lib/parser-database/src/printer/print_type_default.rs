@@ -1,91 +1,14 @@
-use serde::{de::Error, Deserialize, Serialize};
 use serde_json::Value;
 
-// Define Rust structs to represent the TypedDicts
-#[derive(Serialize, Deserialize, Debug)]
-#[serde(rename_all = "camelCase")]
-struct Meta {
-    description: Option<String>,
-}
-
-#[derive(Serialize, Deserialize, Debug)]
-#[serde(rename_all = "camelCase")]
-struct PrimitiveType {
-    // In Rust, Literal types are not supported, so we'll use String
-    optional: bool,
-    value: String,
-}
-
-#[derive(Serialize, Debug)]
-#[serde(rename_all = "camelCase")]
-struct EnumType {
-    name: String,
-    optional: bool,
-}
-
-#[derive(Serialize, Debug)]
-#[serde(rename_all = "camelCase")]
-struct FieldType {
-    name: String,
-    meta: Option<Meta>,
-
-    type_meta: Box<DataType>,
-}
-
-#[derive(Serialize, Debug)]
-#[serde(rename_all = "camelCase")]
-struct ClassType {
-    fields: Vec<FieldType>,
-    optional: Option<bool>,
-}
-
-#[derive(Serialize, Debug)]
-#[serde(rename_all = "camelCase")]
-struct ListType {
-    dims: i32,
-    inner: Box<DataType>,
-}
-
-#[derive(Serialize, Debug)]
-#[serde(rename_all = "camelCase")]
-struct InlineType {
-    type_meta: Box<DataType>,
-}
-
-#[derive(Serialize, Debug)]
-#[serde(rename_all = "camelCase")]
-struct OutputType {
-    value: Box<DataType>,
-}
-
-#[derive(Serialize, Debug)]
-#[serde(rename_all = "camelCase")]
-struct UnionType {
-    members: Vec<DataType>,
-}
-
-// Use an enum to represent the union of types
-#[derive(Serialize, Debug)]
-#[serde(rename_all = "camelCase", untagged)]
-enum DataType {
-    Primitive(PrimitiveType),
-    Class(ClassType),
-    List(ListType),
-    Inline(InlineType),
-    Union(UnionType),
-    Output(OutputType),
-    Enum(EnumType),
-}
-
 // Utility functions similar to the Python version
-fn as_comment(text: &str) -> String {
+pub(super) fn as_comment(text: &str) -> String {
     text.lines()
         .map(|line| format!("// {}", line.trim()))
         .collect::<Vec<String>>()
         .join("\n")
 }
 
-fn as_indented_string(content: &str, level: usize) -> String {
+pub(super) fn as_indented_string(content: &str, level: usize) -> String {
     let indentation = "  ".repeat(level);
     content
         .lines()
@@ -94,7 +17,7 @@ fn as_indented_string(content: &str, level: usize) -> String {
         .join("\n")
 }
 
-fn print_optional(value: &str, is_optional: bool) -> String {
+pub(super) fn print_optional(value: &str, is_optional: bool) -> String {
     if is_optional {
         format!("{} | null", value)
     } else {
@@ -102,143 +25,96 @@ fn print_optional(value: &str, is_optional: bool) -> String {
     }
 }
 
-// The print functions will need to be adapted to Rust's ownership and borrowing rules
-
-// Main function to print data type - this would need to be adapted to return a Rust String type
-fn print_type(item: &DataType) -> String {
-    match item {
-        DataType::Primitive(p) => print_primitive(p),
-        DataType::Class(c) => print_class(c),
-        DataType::List(l) => print_list(l),
-        DataType::Inline(i) => print_type(&*i.type_meta),
-        DataType::Union(u) => print_union(u),
-        DataType::Output(o) => print_type(&*o.value),
-        DataType::Enum(e) => print_enum(e),
-    }
-}
-
-fn print_primitive(item: &PrimitiveType) -> String {
-    print_optional(&item.value, item.optional)
-}
-
-fn print_class(item: &ClassType) -> String {
-    let fields: Vec<String> = item
-        .fields
-        .iter()
-        .map(|field| {
-            let description = field
-                .meta
-                .as_ref()
-                .and_then(|m| m.description.clone())
-                .unwrap_or_default();
-            let comment = if !description.is_empty() {
-                as_comment(&description) + "\n"
-            } else {
-                "".to_string()
-            };
-            let field_value = print_type(&field.type_meta);
-            format!("{}\"{}\": {}", comment, field.name, field_value)
-        })
-        .collect();
-
-    let class_content = as_indented_string(&fields.join(",\n"), 1);
-    let optional = item.optional.unwrap_or(false);
-    print_optional(&format!("{{\n{}\n}}", class_content), optional)
-}
-
-fn print_list(item: &ListType) -> String {
-    let inner_type = print_type(&*item.inner);
-    let dims_str = (0..item.dims).map(|_| "[]").collect::<String>();
-    format!("{}{}", inner_type, dims_str)
-}
-
-fn print_enum(item: &EnumType) -> String {
-    print_optional(&format!("\"{} as string\"", item.name), item.optional)
-}
-
-fn print_union(item: &UnionType) -> String {
-    let member_types: Vec<String> = item
-        .members
-        .iter()
-        .map(|member| print_type(member))
-        .collect();
-    member_types.join(" | ")
-}
-
-fn parse_field_type(json_input: &Value) -> Result<FieldType, serde_json::Error> {
-    let name = json_input["name"].as_str().unwrap().to_string();
-    let meta: Option<Meta> = serde_json::from_value(json_input["meta"].clone())?;
-    let type_meta = parse_data_type(&json_input["type_meta"])?;
-    Ok(FieldType {
-        name,
-        meta,
-        type_meta: Box::new(type_meta),
-    })
-}
-
-fn parse_data_type(json_input: &Value) -> Result<DataType, serde_json::Error> {
-    let json_input = json_input.as_object().unwrap();
-
-    match json_input["rtype"].as_str() {
-        Some("primitive") => Ok(DataType::Primitive(PrimitiveType {
-            optional: json_input["optional"].as_bool().unwrap_or(false),
-            value: json_input["value"].as_str().unwrap().to_string(),
-        })),
+fn str_field<'a>(item: &'a Value, key: &str) -> Result<&'a str, String> {
+    item[key]
+        .as_str()
+        .ok_or_else(|| format!("Expected '{}' to be a string: {}", key, item))
+}
+
+fn array_field<'a>(item: &'a Value, key: &str) -> Result<&'a Vec<Value>, String> {
+    item[key]
+        .as_array()
+        .ok_or_else(|| format!("Expected '{}' to be an array", key))
+}
+
+/// Prints the outermost layer of `item` the default way, handing every nested
+/// type to `print_nested`.
+///
+/// This is what lets custom printers override how one kind of type is printed
+/// while falling back to the defaults for the rest.
+pub(super) fn print_type_with(
+    item: &Value,
+    print_nested: &dyn Fn(&Value) -> Result<String, String>,
+) -> Result<String, String> {
+    match item["rtype"].as_str() {
+        Some("primitive") => Ok(print_optional(
+            str_field(item, "value")?,
+            item["optional"].as_bool().unwrap_or(false),
+        )),
+        Some("literal") => Ok(print_optional(
+            &item["value"].to_string(),
+            item["optional"].as_bool().unwrap_or(false),
+        )),
         Some("class") => {
-            let fields: Vec<FieldType> = json_input["fields"]
-                .as_array()
-                .ok_or_else(|| Error::custom("Expected 'fields' to be an array"))?
+            let fields = array_field(item, "fields")?
                 .iter()
-                .map(parse_field_type)
-                .collect::<Result<Vec<_>, _>>()?;
-            Ok(DataType::Class(ClassType {
-                fields,
-                optional: json_input["optional"].as_bool(),
-            }))
-        }
-        Some("enum") => {
-            let name = json_input["name"].as_str().unwrap().to_string();
-            let optional = json_input["optional"].as_bool().unwrap_or(false);
-            Ok(DataType::Enum(EnumType { name, optional }))
+                .map(|field| {
+                    let comment = match field["meta"]["description"].as_str() {
+                        Some(description) if !description.is_empty() => {
+                            as_comment(description) + "\n"
+                        }
+                        _ => "".to_string(),
+                    };
+                    let field_value = print_nested(&field["type_meta"])?;
+                    Ok(format!(
+                        "{}\"{}\": {}",
+                        comment,
+                        str_field(field, "name")?,
+                        field_value
+                    ))
+                })
+                .collect::<Result<Vec<_>, String>>()?;
+
+            let class_content = as_indented_string(&fields.join(",\n"), 1);
+            // A class that contains itself is named, so that it can be referred to within.
+            let name = match item["recursive"].as_bool().unwrap_or(false) {
+                true => format!("{} ", str_field(item, "name")?),
+                false => "".to_string(),
+            };
+            Ok(print_optional(
+                &format!("{}{{\n{}\n}}", name, class_content),
+                item["optional"].as_bool().unwrap_or(false),
+            ))
         }
+        Some("class_ref") => Ok(print_optional(
+            str_field(item, "name")?,
+            item["optional"].as_bool().unwrap_or(false),
+        )),
+        Some("enum") => Ok(print_optional(
+            &format!("\"{} as string\"", str_field(item, "name")?),
+            item["optional"].as_bool().unwrap_or(false),
+        )),
         Some("list") => {
-            let inner = parse_data_type(&json_input["inner"])?;
-            Ok(DataType::List(ListType {
-                dims: json_input["dims"].as_i64().unwrap() as i32,
-                inner: Box::new(inner),
-            }))
+            let inner_type = print_nested(&item["inner"])?;
+            let dims = item["dims"]
+                .as_u64()
+                .ok_or_else(|| "Expected 'dims' to be a number".to_string())?;
+            Ok(format!("{}{}", inner_type, "[]".repeat(dims as usize)))
         }
-        Some("inline") => {
-            let inner = parse_data_type(&json_input["value"])?;
-            Ok(DataType::Inline(InlineType {
-                type_meta: Box::new(inner),
-            }))
-        }
-        Some("output") => {
-            let inner = parse_data_type(&json_input["value"])?;
-            Ok(DataType::Output(OutputType {
-                value: Box::new(inner),
-            }))
-        }
-        Some("union") => {
-            let members: Vec<DataType> = json_input["options"]
-                .as_array()
-                .ok_or_else(|| Error::custom("Expected 'options' to be an array"))?
-                .iter()
-                .map(parse_data_type)
-                .collect::<Result<Vec<_>, _>>()?;
-            Ok(DataType::Union(UnionType { members }))
-        }
-        other => Err(Error::custom(format!(
-            "unknown type {:?} {:?}",
-            other, json_input
-        ))),
+        Some("inline") | Some("output") => print_nested(&item["value"]),
+        Some("union") => Ok(array_field(item, "options")?
+            .iter()
+            .map(print_nested)
+            .collect::<Result<Vec<_>, _>>()?
+            .join(" | ")),
+        other => Err(format!("unknown type {:?} {}", other, item)),
     }
 }
 
-pub(crate) fn print_entry(json_input: serde_json::Value) -> String {
-    // Print the type of the input
-    let parsed: DataType = parse_data_type(&json_input).expect("Invalid JSON input");
+fn print_type(item: &Value) -> Result<String, String> {
+    print_type_with(item, &print_type)
+}
 
-    print_type(&parsed)
+pub(crate) fn print_entry(json_input: serde_json::Value) -> Result<String, String> {
+    print_type(&json_input)
 }
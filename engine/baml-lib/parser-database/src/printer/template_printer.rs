@@ -0,0 +1,325 @@
+//! Renders `printer` blocks, whose `template` is a handlebars template.
+//!
+//! A `printer<type>` template is rendered once for every node of the type
+//! being printed. The node is the template's context and looks like one of:
+//!
+//! ```ignore
+//! { "rtype": "primitive", "value": "string" | "int" | ..., "optional": bool }
+//! { "rtype": "enum", "name": string, "optional": bool }
+//! { "rtype": "class", "name": string, "optional": bool, "meta": { .. },
+//!   "fields": [{ "name": string, "meta": { .. }, "type_meta": <node> }] }
+//! { "rtype": "list", "dims": int, "inner": <node> }
+//! { "rtype": "union", "optional": bool, "options": [<node>] }
+//! { "rtype": "inline", "value": <node> }   // an argument of the function
+//! { "rtype": "output", "value": <node> }   // `output` itself
+//! ```
+//!
+//! `meta` holds the `@description` and any `@meta(..)` entries. Names already
+//! have any `@alias` applied.
+//!
+//! The following helpers are available:
+//!
+//! - `{{print_type node}}` renders `node` with this same template.
+//! - `{{print_default node}}` renders `node` the way the built-in printer
+//!   would, still using this template for anything nested in it.
+//! - `{{print_optional text optional}}` appends ` | null` when `optional`.
+//! - `{{as_comment text}}` prefixes every line of `text` with `// `.
+//! - `{{indent text level=1}}` indents every line of `text` by two spaces per level.
+//!
+//! A `printer<enum>` template is rendered once per enum, with the context
+//! `{ "name": string, "meta": { .. }, "values": [{ "name": string, "meta": { .. } }] }`,
+//! and gets `print_default`, `print_enum_value` and `as_comment` (which uses `/// `).
+
+use handlebars::{
+    handlebars_helper, Context, Handlebars, Helper, HelperResult, Output, RenderContext,
+    RenderError, TemplateError,
+};
+use serde_json::Value;
+
+use super::{print_enum_default, print_type_default};
+
+const TEMPLATE_NAME: &str = "printer";
+
+handlebars_helper!(type_comment: |text: str| print_type_default::as_comment(text));
+handlebars_helper!(enum_comment: |text: str| print_enum_default::as_comment(text));
+handlebars_helper!(indent: |text: str, {level: u64 = 1}| {
+    print_type_default::as_indented_string(text, level as usize)
+});
+handlebars_helper!(print_optional: |text: str, optional: bool| {
+    print_type_default::print_optional(text, optional)
+});
+
+fn param<'a>(h: &'a Helper<'_, '_>) -> Result<&'a Value, RenderError> {
+    h.param(0)
+        .map(|p| p.value())
+        .ok_or_else(|| RenderError::new(format!("`{}` expects one argument", h.name())))
+}
+
+fn print_type(
+    h: &Helper<'_, '_>,
+    r: &Handlebars<'_>,
+    _: &Context,
+    _: &mut RenderContext<'_, '_>,
+    out: &mut dyn Output,
+) -> HelperResult {
+    out.write(&r.render(TEMPLATE_NAME, param(h)?)?)?;
+    Ok(())
+}
+
+fn print_type_default(
+    h: &Helper<'_, '_>,
+    r: &Handlebars<'_>,
+    _: &Context,
+    _: &mut RenderContext<'_, '_>,
+    out: &mut dyn Output,
+) -> HelperResult {
+    let printed = print_type_default::print_type_with(param(h)?, &|nested| {
+        r.render(TEMPLATE_NAME, nested).map_err(|e| e.to_string())
+    })
+    .map_err(RenderError::new)?;
+    out.write(&printed)?;
+    Ok(())
+}
+
+fn print_enum_default(
+    h: &Helper<'_, '_>,
+    _: &Handlebars<'_>,
+    _: &Context,
+    _: &mut RenderContext<'_, '_>,
+    out: &mut dyn Output,
+) -> HelperResult {
+    let printed = print_enum_default::print_enum(param(h)?.clone()).map_err(RenderError::new)?;
+    out.write(&printed)?;
+    Ok(())
+}
+
+fn print_enum_value(
+    h: &Helper<'_, '_>,
+    _: &Handlebars<'_>,
+    _: &Context,
+    _: &mut RenderContext<'_, '_>,
+    out: &mut dyn Output,
+) -> HelperResult {
+    let printed =
+        print_enum_default::print_enum_value_json(param(h)?.clone()).map_err(RenderError::new)?;
+    out.write(&printed)?;
+    Ok(())
+}
+
+fn describe_template_error(e: &TemplateError) -> String {
+    match (e.line_no, e.column_no) {
+        (Some(line), Some(col)) => format!("line {}, column {}: {}", line, col, e.reason()),
+        _ => e.reason().to_string(),
+    }
+}
+
+fn setup_printer(is_enum: bool, template: &str) -> Result<Handlebars<'static>, String> {
+    let mut reg = Handlebars::new();
+    reg.register_escape_fn(handlebars::no_escape);
+
+    if is_enum {
+        reg.register_helper("print_default", Box::new(print_enum_default));
+        reg.register_helper("print_enum_value", Box::new(print_enum_value));
+        reg.register_helper("as_comment", Box::new(enum_comment));
+    } else {
+        reg.register_helper("print_type", Box::new(print_type));
+        reg.register_helper("print_default", Box::new(print_type_default));
+        reg.register_helper("print_optional", Box::new(print_optional));
+        reg.register_helper("as_comment", Box::new(type_comment));
+        reg.register_helper("indent", Box::new(indent));
+    }
+
+    reg.register_template_string(TEMPLATE_NAME, template)
+        .map_err(|e| describe_template_error(&e))?;
+    Ok(reg)
+}
+
+/// Checks that `template` is a well-formed printer template.
+pub(crate) fn validate_template(template: &str) -> Result<(), String> {
+    setup_printer(false, template).map(|_| ())
+}
+
+/// Renders `json` with the printer `template`.
+pub(crate) fn render(is_enum: bool, template: &str, json: &Value) -> Result<String, String> {
+    setup_printer(is_enum, template)?
+        .render(TEMPLATE_NAME, json)
+        .map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn person() -> Value {
+        json!({
+            "rtype": "output",
+            "value": {
+                "rtype": "inline",
+                "value": {
+                    "rtype": "class",
+                    "name": "Person",
+                    "optional": false,
+                    "meta": {},
+                    "fields": [
+                        {
+                            "name": "name",
+                            "meta": { "description": "Full name\n  of the person" },
+                            "type_meta": { "rtype": "primitive", "value": "string", "optional": false },
+                        },
+                        {
+                            "name": "tags",
+                            "meta": {},
+                            "type_meta": {
+                                "rtype": "list",
+                                "dims": 2,
+                                "inner": { "rtype": "enum", "name": "Tag", "optional": true },
+                            },
+                        },
+                        {
+                            "name": "pet",
+                            "meta": {},
+                            "type_meta": {
+                                "rtype": "union",
+                                "optional": false,
+                                "options": [
+                                    { "rtype": "primitive", "value": "int", "optional": false },
+                                    {
+                                        "rtype": "class",
+                                        "name": "Pet",
+                                        "optional": true,
+                                        "meta": {},
+                                        "fields": [{
+                                            "name": "kind",
+                                            "meta": {},
+                                            "type_meta": { "rtype": "primitive", "value": "string", "optional": true },
+                                        }],
+                                    },
+                                ],
+                            },
+                        },
+                    ],
+                },
+            },
+        })
+    }
+
+    fn color() -> Value {
+        json!({
+            "name": "Color",
+            "meta": { "description": "A color" },
+            "values": [
+                { "name": "RED", "meta": { "description": "warm" } },
+                { "name": "BLUE", "meta": {} },
+            ],
+        })
+    }
+
+    #[test]
+    fn print_default_matches_builtin_type_printer() {
+        let expected = print_type_default::print_entry(person()).unwrap();
+        assert_eq!(
+            render(false, "{{print_default this}}", &person()).unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn helpers_reproduce_builtin_type_printer() {
+        let template = r#"{{~#if (eq rtype "primitive")~}}
+{{print_optional value optional}}
+{{~else~}}
+{{print_default this}}
+{{~/if~}}"#;
+        assert_eq!(
+            render(false, template, &person()).unwrap(),
+            print_type_default::print_entry(person()).unwrap()
+        );
+    }
+
+    #[test]
+    fn overrides_apply_to_nested_types() {
+        let template = r#"{{#if (eq rtype "enum")}}{{name}}{{else}}{{print_default this}}{{/if}}"#;
+        assert_eq!(
+            render(false, template, &person()).unwrap(),
+            "{\n  // Full name\n  // of the person\n  \"name\": string,\n  \"tags\": Tag[][],\n  \"pet\": int | {\n    \"kind\": string | null\n  } | null\n}"
+        );
+    }
+
+    #[test]
+    fn recursive_classes_are_named() {
+        let tree = json!({
+            "rtype": "class",
+            "name": "Tree",
+            "optional": false,
+            "recursive": true,
+            "meta": {},
+            "fields": [
+                {
+                    "name": "value",
+                    "meta": {},
+                    "type_meta": { "rtype": "primitive", "value": "int", "optional": false },
+                },
+                {
+                    "name": "children",
+                    "meta": {},
+                    "type_meta": {
+                        "rtype": "list",
+                        "dims": 1,
+                        "inner": { "rtype": "class_ref", "name": "Tree", "optional": false },
+                    },
+                },
+                {
+                    "name": "parent",
+                    "meta": {},
+                    "type_meta": { "rtype": "class_ref", "name": "Tree", "optional": true },
+                },
+            ],
+        });
+        assert_eq!(
+            render(false, "{{print_default this}}", &tree).unwrap(),
+            "Tree {\n  \"value\": int,\n  \"children\": Tree[],\n  \"parent\": Tree | null\n}"
+        );
+    }
+
+    #[test]
+    fn literals_are_printed_as_values() {
+        let reply = json!({
+            "rtype": "union",
+            "optional": false,
+            "options": [
+                { "rtype": "literal", "value": "ok", "optional": false },
+                { "rtype": "literal", "value": 404, "optional": false },
+                { "rtype": "literal", "value": true, "optional": true },
+            ],
+        });
+        assert_eq!(
+            render(false, "{{print_default this}}", &reply).unwrap(),
+            "\"ok\" | 404 | true | null"
+        );
+    }
+
+    #[test]
+    fn print_default_matches_builtin_enum_printer() {
+        assert_eq!(
+            render(true, "{{print_default this}}", &color()).unwrap(),
+            print_enum_default::print_enum(color()).unwrap()
+        );
+        assert_eq!(
+            render(
+                true,
+                "{{as_comment meta.description}}\n{{name}}\n---{{#each values}}\n{{print_enum_value this}}{{/each}}",
+                &color()
+            )
+            .unwrap(),
+            "/// A color\nColor\n---\nRED: warm\nBLUE"
+        );
+    }
+
+    #[test]
+    fn invalid_templates_are_rejected() {
+        assert!(validate_template("{{#if rtype}}unterminated").is_err());
+        assert!(render(false, "{{print_type}}", &person()).is_err());
+    }
+}
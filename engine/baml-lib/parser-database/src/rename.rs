@@ -0,0 +1,681 @@
+use std::path::Path;
+
+use either::Either;
+use internal_baml_diagnostics::{DatamodelError, Span};
+use internal_baml_prompt_parser::ast::{Variable, VariableSegment};
+use internal_baml_schema_ast::ast::{
+    self, FieldType, Identifier, TopId, WithIdentifier, WithName, WithSpan,
+};
+use internal_baml_schema_ast::json::{JsonKind, JsonValue};
+
+use crate::{
+    types::{StaticType, TestCase},
+    AssertionCheck, ParserDatabase, PathSegment, PromptVariable,
+};
+
+/// Aliases may refer to other aliases; this bounds how deep prompt variables are resolved.
+const MAX_ALIAS_DEPTH: usize = 16;
+
+/// A named item of the schema that can be renamed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RenameTarget {
+    /// A class.
+    Class(ast::ClassId),
+    /// An enum.
+    Enum(ast::EnumId),
+    /// A value of an enum.
+    EnumValue(ast::EnumId, ast::EnumValueId),
+    /// A field of a class.
+    Field(ast::ClassId, ast::FieldId),
+    /// A type alias.
+    TypeAlias(ast::TypeAliasId),
+    /// A function.
+    Function(ast::FunctionId),
+    /// An impl of a function.
+    Variant(ast::VariantConfigId),
+    /// A client.
+    Client(ast::ClientId),
+}
+
+/// Replaces the text at `span` with `new_text`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextEdit {
+    /// The text to replace.
+    pub span: Span,
+    /// The replacement.
+    pub new_text: String,
+}
+
+impl ParserDatabase {
+    /// The item declared or referenced at the byte `offset` of `file`, if it can be renamed.
+    pub fn rename_target_at(&self, file: &Path, offset: usize) -> Option<RenameTarget> {
+        self.occurrences()
+            .into_iter()
+            .find(|(span, _)| span.file.path_buf() == file && span.contains(offset))
+            .map(|(_, target)| target)
+    }
+
+    /// The edits that rename `target` to `new_name`: its declaration, every type reference,
+    /// `override` block and impl that names it, every prompt variable that goes through it, and
+    /// the test inputs and expected outputs that name it.
+    ///
+    /// Returns an error if `new_name` is not a valid name for the item or is already taken.
+    pub fn rename(
+        &self,
+        target: RenameTarget,
+        new_name: &str,
+    ) -> Result<Vec<TextEdit>, DatamodelError> {
+        self.validate_new_name(target, new_name)?;
+
+        let mut edits = self
+            .occurrences()
+            .into_iter()
+            .filter(|(_, t)| *t == target)
+            .map(|(span, _)| TextEdit {
+                span,
+                new_text: new_name.to_string(),
+            })
+            .collect::<Vec<_>>();
+        edits.sort_by(|a, b| {
+            (a.span.file.path_buf(), a.span.start).cmp(&(b.span.file.path_buf(), b.span.start))
+        });
+        edits.dedup();
+
+        Ok(edits)
+    }
+
+    fn declaration(&self, target: RenameTarget) -> (&'static str, &Identifier) {
+        match target {
+            RenameTarget::Class(id) => ("class", self.ast[id].identifier()),
+            RenameTarget::Enum(id) => ("enum", self.ast[id].identifier()),
+            RenameTarget::EnumValue(id, value) => ("enum value", self.ast[id][value].identifier()),
+            RenameTarget::Field(id, field) => ("class field", self.ast[id][field].identifier()),
+            RenameTarget::TypeAlias(id) => ("type alias", self.ast[id].identifier()),
+            RenameTarget::Function(id) => ("function", self.ast[id].identifier()),
+            RenameTarget::Variant(id) => ("impl", self.ast[id].identifier()),
+            RenameTarget::Client(id) => ("client", self.ast[id].identifier()),
+        }
+    }
+
+    fn validate_new_name(
+        &self,
+        target: RenameTarget,
+        new_name: &str,
+    ) -> Result<(), DatamodelError> {
+        let (kind, declaration) = self.declaration(target);
+        let error = |message: &str| {
+            Err(DatamodelError::new_name_error(
+                kind,
+                message,
+                declaration.span().clone(),
+            ))
+        };
+
+        let mut chars = new_name.chars();
+        if !chars
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+            || !chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        {
+            return error(
+                "Only letters, numbers, and _ are allowed, and it must not start with a number.",
+            );
+        }
+        let require_upper_case =
+            !matches!(target, RenameTarget::Field(..) | RenameTarget::Variant(..));
+        if require_upper_case && !new_name.starts_with(|c: char| c.is_ascii_uppercase()) {
+            return error("Must start with an uppercase letter.");
+        }
+        match Identifier::from((new_name, declaration.span().clone())) {
+            Identifier::Primitive(..) => {
+                return error(&format!("{} is a primitive type.", new_name))
+            }
+            Identifier::Local(..) if StaticType::try_from_str(new_name).is_none() => {}
+            _ => return error("This name is reserved."),
+        }
+
+        let taken = match target {
+            RenameTarget::EnumValue(id, _) => self.ast[id]
+                .iter_values()
+                .any(|(_, value)| value.name() == new_name),
+            RenameTarget::Field(id, _) => self.ast[id]
+                .iter_fields()
+                .any(|(_, field)| field.name() == new_name),
+            // Impls only need a unique name among the impls of their function.
+            RenameTarget::Variant(id) => self.walk_variants().any(|variant| {
+                variant.id != id
+                    && variant.function_identifier().name() == self.ast[id].function_name().name()
+                    && variant.ast_variant().name() == new_name
+            }),
+            _ => self.ast.iter_tops().any(|(top_id, top)| {
+                !matches!(
+                    top_id,
+                    TopId::Generator(_) | TopId::Variant(_) | TopId::Config((_, "test_case"))
+                ) && top.name() == new_name
+            }),
+        };
+        if taken && new_name != declaration.name() {
+            return error(&format!("`{}` is already taken.", new_name));
+        }
+
+        Ok(())
+    }
+
+    /// Every declaration and reference of a renameable item, with the span of its name.
+    fn occurrences(&self) -> Vec<(Span, RenameTarget)> {
+        let mut found = vec![];
+
+        for (top_id, top) in self.ast.iter_tops() {
+            match (top_id, top) {
+                (TopId::Class(id), ast::Top::Class(class)) => {
+                    found.push((class.identifier().span().clone(), RenameTarget::Class(id)));
+                    for (field_id, field) in class.iter_fields() {
+                        found.push((
+                            field.identifier().span().clone(),
+                            RenameTarget::Field(id, field_id),
+                        ));
+                        self.type_references(&field.field_type, &mut found);
+                    }
+                }
+                (TopId::Enum(id), ast::Top::Enum(r#enum)) => {
+                    found.push((r#enum.identifier().span().clone(), RenameTarget::Enum(id)));
+                    for (value_id, value) in r#enum.iter_values() {
+                        found.push((
+                            value.identifier().span().clone(),
+                            RenameTarget::EnumValue(id, value_id),
+                        ));
+                    }
+                }
+                (TopId::TypeAlias(id), ast::Top::TypeAlias(alias)) => {
+                    found.push((
+                        alias.identifier().span().clone(),
+                        RenameTarget::TypeAlias(id),
+                    ));
+                    self.type_references(alias.value(), &mut found);
+                }
+                (TopId::Function(id), ast::Top::Function(function)) => {
+                    found.push((
+                        function.identifier().span().clone(),
+                        RenameTarget::Function(id),
+                    ));
+                    for idn in function
+                        .input()
+                        .flat_idns()
+                        .into_iter()
+                        .chain(function.output().flat_idns())
+                    {
+                        self.type_reference(idn, &mut found);
+                    }
+                    for (_, field) in function.iter_fields() {
+                        if field.name() != "default_impl" {
+                            continue;
+                        }
+                        let default_impl = field.value.as_ref().and_then(|v| v.as_string_value());
+                        if let Some((name, span)) = default_impl {
+                            let variant = self.walk_variants().find(|variant| {
+                                variant.function_identifier().name() == function.name()
+                                    && variant.ast_variant().name() == name
+                            });
+                            if let Some(variant) = variant {
+                                found.push((unquoted(span), RenameTarget::Variant(variant.id)));
+                            }
+                        }
+                    }
+                }
+                (TopId::Variant(id), ast::Top::Variant(variant)) => {
+                    self.variant_occurrences(id, variant, &mut found)
+                }
+                (TopId::Client(id), ast::Top::Client(client)) => {
+                    found.push((client.identifier().span().clone(), RenameTarget::Client(id)));
+                    let strategy = self
+                        .types
+                        .client_properties
+                        .get(&id)
+                        .and_then(|properties| properties.strategy.as_ref());
+                    for (name, span) in strategy.iter().flat_map(|s| &s.clients) {
+                        if let Some(client) = self.find_client(name) {
+                            found.push((unquoted(span), RenameTarget::Client(client.id)));
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        for test_case in self.types.test_cases.values() {
+            // `__tests__` files name their function with their directory, which can't be edited.
+            let (name, span) = &test_case.function;
+            if span.file.path().ends_with(".json") {
+                continue;
+            }
+            let Some(function) = self.find_function_by_name(name) else {
+                continue;
+            };
+            found.push((unquoted(span), RenameTarget::Function(function.id)));
+            for (name, span) in &test_case.impls {
+                if let Some(variant) = function.walk_variants().find(|v| v.name() == name) {
+                    found.push((unquoted(span), RenameTarget::Variant(variant.id)));
+                }
+            }
+        }
+
+        for test_case in self.types.test_cases.values() {
+            let Some(function) = self.find_function_by_name(&test_case.function.0) else {
+                continue;
+            };
+            self.test_case_references(function.ast_function(), test_case, &mut found);
+        }
+
+        found
+    }
+
+    fn variant_occurrences(
+        &self,
+        id: ast::VariantConfigId,
+        variant: &ast::Variant,
+        found: &mut Vec<(Span, RenameTarget)>,
+    ) {
+        found.push((
+            variant.identifier().span().clone(),
+            RenameTarget::Variant(id),
+        ));
+        let function = self.find_function(variant.function_name());
+        if let Some(function) = function {
+            found.push((
+                variant.function_name().span().clone(),
+                RenameTarget::Function(function.id),
+            ));
+        }
+
+        for (_, field) in variant.iter_fields() {
+            let client = match (field.name(), &field.value) {
+                ("client", Some(value)) => value.as_string_value(),
+                _ => None,
+            };
+            if let Some((name, span)) = client {
+                if let Some(client) = self.find_client(name) {
+                    found.push((unquoted(span), RenameTarget::Client(client.id)));
+                }
+            }
+        }
+
+        for (_, serializer) in variant.iter_serializers() {
+            let target = self.type_reference(serializer.identifier(), found);
+            for (_, field) in serializer.iter_fields() {
+                let member = match target {
+                    Some(RenameTarget::Class(class_id)) => self.ast[class_id]
+                        .iter_fields()
+                        .find(|(_, f)| f.name() == field.name())
+                        .map(|(field_id, _)| RenameTarget::Field(class_id, field_id)),
+                    Some(RenameTarget::Enum(enum_id)) => self.ast[enum_id]
+                        .iter_values()
+                        .find(|(_, v)| v.name() == field.name())
+                        .map(|(value_id, _)| RenameTarget::EnumValue(enum_id, value_id)),
+                    _ => None,
+                };
+                if let Some(member) = member {
+                    found.push((field.identifier().span().clone(), member));
+                }
+            }
+        }
+
+        for adapter in &variant.adapters {
+            self.type_references(&adapter.from, found);
+            self.type_references(&adapter.to, found);
+        }
+
+        let Some(properties) = self.types.variant_properties.get(&id) else {
+            return;
+        };
+        // The item name and types of each open `{#for}` block, or `None` for `{#if}` blocks.
+        let mut open_blocks: Vec<Option<(&str, Vec<FieldType>)>> = vec![];
+        for prompt_variable in &properties.prompt_replacements {
+            match prompt_variable {
+                PromptVariable::Input(variable) | PromptVariable::If(variable) => {
+                    if let Some(function) = function {
+                        self.input_references(
+                            function.ast_function(),
+                            &open_blocks,
+                            variable,
+                            found,
+                        );
+                    }
+                    if let PromptVariable::If(_) = prompt_variable {
+                        open_blocks.push(None);
+                    }
+                }
+                PromptVariable::For(block) => {
+                    let collections = match function {
+                        Some(function) => self.input_references(
+                            function.ast_function(),
+                            &open_blocks,
+                            &block.collection,
+                            found,
+                        ),
+                        None => vec![],
+                    };
+                    let items = collections
+                        .iter()
+                        .flat_map(|t| self.items(t, &VariableSegment::Each, 0))
+                        .collect();
+                    open_blocks.push(Some((&block.item.0, items)));
+                }
+                PromptVariable::End(_) => {
+                    open_blocks.pop();
+                }
+                PromptVariable::Enum(block) | PromptVariable::Type(block) => {
+                    let target = self.find_type_by_str(&block.target.text).map(|t| match t {
+                        Either::Left(class) => RenameTarget::Class(class.id),
+                        Either::Right(r#enum) => RenameTarget::Enum(r#enum.id),
+                    });
+                    if let Some(target) = target {
+                        found.push((block.target.span.clone(), target));
+                    }
+                }
+                PromptVariable::Chat(_) | PromptVariable::Else(_) => {}
+            }
+        }
+    }
+
+    fn type_references(&self, field_type: &FieldType, found: &mut Vec<(Span, RenameTarget)>) {
+        for idn in field_type.flat_idns() {
+            self.type_reference(idn, found);
+        }
+    }
+
+    /// Records `idn` if it names a class, enum or type alias, and returns what it names.
+    fn type_reference(
+        &self,
+        idn: &Identifier,
+        found: &mut Vec<(Span, RenameTarget)>,
+    ) -> Option<RenameTarget> {
+        if !matches!(idn, Identifier::Local(..)) {
+            return None;
+        }
+        let target = match self.find_type(idn) {
+            Some(Either::Left(class)) => RenameTarget::Class(class.id),
+            Some(Either::Right(r#enum)) => RenameTarget::Enum(r#enum.id),
+            None => RenameTarget::TypeAlias(self.find_type_alias(idn)?.id),
+        };
+        found.push((idn.span().clone(), target));
+        Some(target)
+    }
+
+    /// Records the class fields that the segments of `{#input.a[0].b}` go through, and returns the
+    /// types the variable may have. Variables can also start with the item of a `{#for}` block.
+    fn input_references(
+        &self,
+        function: &ast::Function,
+        open_blocks: &[Option<(&str, Vec<FieldType>)>],
+        variable: &Variable,
+        found: &mut Vec<(Span, RenameTarget)>,
+    ) -> Vec<FieldType> {
+        let segments = variable.segment_spans();
+        let root = variable.path[0].as_field();
+        let loop_item = open_blocks
+            .iter()
+            .rev()
+            .flatten()
+            .find(|(item, _)| Some(*item) == root);
+        let (mut current, first) = match (loop_item, function.input()) {
+            (Some((_, items)), _) => (items.clone(), 1),
+            (None, ast::FunctionArgs::Unnamed(arg)) => (vec![arg.field_type.clone()], 1),
+            (None, ast::FunctionArgs::Named(args)) => {
+                let arg = variable
+                    .path
+                    .get(1)
+                    .and_then(VariableSegment::as_field)
+                    .and_then(|name| {
+                        args.iter_args()
+                            .find(|(_, (arg_name, _))| arg_name.name() == name)
+                    });
+                match arg {
+                    Some((_, (_, arg))) => (vec![arg.field_type.clone()], 2),
+                    None => return vec![],
+                }
+            }
+        };
+
+        for (segment, span) in segments.iter().enumerate().skip(first) {
+            let VariableSegment::Field(name) = &variable.path[segment] else {
+                current = current
+                    .iter()
+                    .flat_map(|field_type| self.items(field_type, &variable.path[segment], 0))
+                    .collect();
+                continue;
+            };
+            let fields = current
+                .iter()
+                .flat_map(|field_type| self.fields_named(field_type, name, 0))
+                .collect::<Vec<_>>();
+            if fields.is_empty() {
+                return vec![];
+            }
+
+            current = vec![];
+            for (class_id, field_id) in fields {
+                found.push((span.clone(), RenameTarget::Field(class_id, field_id)));
+                current.push(self.ast[class_id][field_id].field_type.clone());
+            }
+        }
+        current
+    }
+
+    /// Records the class fields and enum values named by the input of a test, in `test` blocks
+    /// and `__tests__` files alike, and by the values its assertions expect.
+    fn test_case_references(
+        &self,
+        function: &ast::Function,
+        test_case: &TestCase,
+        found: &mut Vec<(Span, RenameTarget)>,
+    ) {
+        match function.input() {
+            ast::FunctionArgs::Unnamed(arg) => {
+                self.value_references(&arg.field_type, &test_case.input, found)
+            }
+            ast::FunctionArgs::Named(args) => {
+                let JsonKind::Object(entries) = &test_case.input.kind else {
+                    return;
+                };
+                for (name, _, value) in entries {
+                    let arg = args
+                        .iter_args()
+                        .find(|(_, (arg_name, _))| arg_name.name() == name);
+                    if let Some((_, (_, arg))) = arg {
+                        self.value_references(&arg.field_type, value, found);
+                    }
+                }
+            }
+        }
+
+        let ast::FunctionArgs::Unnamed(output) = function.output() else {
+            return;
+        };
+        for assertion in &test_case.assertions {
+            let mut current = vec![output.field_type.clone()];
+            for segment in &assertion.path.segments {
+                current = match segment {
+                    PathSegment::Field(name) => current
+                        .iter()
+                        .flat_map(|t| self.fields_named(t, name, 0))
+                        .map(|(class_id, field_id)| self.ast[class_id][field_id].field_type.clone())
+                        .collect(),
+                    PathSegment::Index(idx) => current
+                        .iter()
+                        .flat_map(|t| self.items(t, &VariableSegment::Index(*idx), 0))
+                        .collect(),
+                };
+            }
+            let expected = match &assertion.check {
+                AssertionCheck::Equals(expected) => std::slice::from_ref(expected),
+                AssertionCheck::OneOf(options) => options.as_slice(),
+                AssertionCheck::Matches(..) | AssertionCheck::NotNull => &[],
+            };
+            for field_type in &current {
+                for value in expected {
+                    self.value_references(field_type, value, found);
+                }
+            }
+        }
+    }
+
+    /// Records the keys of `value` that name a class field and the strings that name an enum
+    /// value, given that `value` is of `field_type`.
+    fn value_references(
+        &self,
+        field_type: &FieldType,
+        value: &JsonValue,
+        found: &mut Vec<(Span, RenameTarget)>,
+    ) {
+        match &value.kind {
+            JsonKind::Object(entries) => {
+                for (key, key_span, entry) in entries {
+                    for (class_id, field_id) in self.fields_named(field_type, key, 0) {
+                        if let Some(span) = name_span(key_span, key) {
+                            found.push((span, RenameTarget::Field(class_id, field_id)));
+                        }
+                        let field_type = &self.ast[class_id][field_id].field_type;
+                        self.value_references(field_type, entry, found);
+                    }
+                    for value_type in self.items(field_type, &VariableSegment::Key(key.clone()), 0)
+                    {
+                        self.value_references(&value_type, entry, found);
+                    }
+                }
+            }
+            JsonKind::Array(items) => {
+                for (idx, item) in items.iter().enumerate() {
+                    for item_type in self.items(field_type, &VariableSegment::Index(idx), 0) {
+                        self.value_references(&item_type, item, found);
+                    }
+                }
+            }
+            JsonKind::String(s) => {
+                for (enum_id, value_id) in self.enum_values_named(field_type, s, 0) {
+                    if let Some(span) = name_span(&value.span, s) {
+                        found.push((span, RenameTarget::EnumValue(enum_id, value_id)));
+                    }
+                }
+            }
+            JsonKind::Null | JsonKind::Bool(_) | JsonKind::Number(_) => {}
+        }
+    }
+
+    /// The types `segment` (`[0]`, `["key"]` or `[*]`) may select from a value of `field_type`.
+    fn items(
+        &self,
+        field_type: &FieldType,
+        segment: &VariableSegment,
+        depth: usize,
+    ) -> Vec<FieldType> {
+        if depth > MAX_ALIAS_DEPTH {
+            return vec![];
+        }
+        match (field_type, segment) {
+            (FieldType::Union(_, types, _), _) => types
+                .iter()
+                .flat_map(|t| self.items(t, segment, depth + 1))
+                .collect(),
+            (FieldType::Identifier(_, idn), _) => match self.find_type_alias(idn) {
+                Some(alias) => self.items(alias.target(), segment, depth + 1),
+                None => vec![],
+            },
+            (
+                FieldType::List(item, dims, span),
+                VariableSegment::Index(_) | VariableSegment::Each,
+            ) => match dims {
+                1 => vec![item.as_ref().clone()],
+                _ => vec![FieldType::List(item.clone(), dims - 1, span.clone())],
+            },
+            (FieldType::Tuple(_, items, _), VariableSegment::Index(idx)) => {
+                items.get(*idx).cloned().into_iter().collect()
+            }
+            (FieldType::Dictionary(kv, _), VariableSegment::Key(_) | VariableSegment::Each) => {
+                vec![kv.1.clone()]
+            }
+            _ => vec![],
+        }
+    }
+
+    /// The fields called `name` of the classes a value of `field_type` may be.
+    fn fields_named(
+        &self,
+        field_type: &FieldType,
+        name: &str,
+        depth: usize,
+    ) -> Vec<(ast::ClassId, ast::FieldId)> {
+        if depth > MAX_ALIAS_DEPTH {
+            return vec![];
+        }
+        match field_type {
+            FieldType::Union(_, types, _) => types
+                .iter()
+                .flat_map(|t| self.fields_named(t, name, depth + 1))
+                .collect(),
+            FieldType::Identifier(_, idn) => match self.find_type(idn) {
+                Some(Either::Left(class)) => class
+                    .ast_class()
+                    .iter_fields()
+                    .filter(|(_, field)| field.name() == name)
+                    .map(|(field_id, _)| (class.id, field_id))
+                    .collect(),
+                Some(Either::Right(_)) => vec![],
+                None => match self.find_type_alias(idn) {
+                    Some(alias) => self.fields_named(alias.target(), name, depth + 1),
+                    None => vec![],
+                },
+            },
+            // Fields of the items of lists, maps and tuples need an index first, e.g. `[0]`.
+            _ => vec![],
+        }
+    }
+
+    /// The values called `name` of the enums a value of `field_type` may be.
+    fn enum_values_named(
+        &self,
+        field_type: &FieldType,
+        name: &str,
+        depth: usize,
+    ) -> Vec<(ast::EnumId, ast::EnumValueId)> {
+        if depth > MAX_ALIAS_DEPTH {
+            return vec![];
+        }
+        match field_type {
+            FieldType::Union(_, types, _) => types
+                .iter()
+                .flat_map(|t| self.enum_values_named(t, name, depth + 1))
+                .collect(),
+            FieldType::Identifier(_, idn) => match self.find_type(idn) {
+                Some(Either::Right(r#enum)) => r#enum
+                    .ast_enum()
+                    .iter_values()
+                    .filter(|(_, value)| value.name() == name)
+                    .map(|(value_id, _)| (r#enum.id, value_id))
+                    .collect(),
+                Some(Either::Left(_)) => vec![],
+                None => match self.find_type_alias(idn) {
+                    Some(alias) => self.enum_values_named(alias.target(), name, depth + 1),
+                    None => vec![],
+                },
+            },
+            _ => vec![],
+        }
+    }
+}
+
+/// The span of `name` written at `span`, without its quotes. `None` if it is written with
+/// escapes, which an edit would have to keep.
+fn name_span(span: &Span, name: &str) -> Option<Span> {
+    let span = unquoted(span);
+    (&span.file.as_str()[span.start..span.end] == name).then_some(span)
+}
+
+/// String values keep their quotes in their span; the edit only replaces what is inside them.
+fn unquoted(span: &Span) -> Span {
+    let text = &span.file.as_str()[span.start..span.end];
+    if text.len() >= 2 && text.starts_with('"') && text.ends_with('"') {
+        Span::new(span.file.clone(), span.start + 1, span.end - 1)
+    } else {
+        span.clone()
+    }
+}
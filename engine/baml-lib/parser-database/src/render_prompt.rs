@@ -0,0 +1,265 @@
+use internal_baml_prompt_parser::ast::VariableSegment;
+use internal_baml_schema_ast::ast;
+use internal_baml_schema_ast::ast::WithName;
+use serde_json::Value;
+
+use crate::{split_prompt_blocks, walkers::VariantWalker, ParserDatabase, PromptPart, PromptRepr};
+
+/// A message of a rendered chat prompt.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct RenderedMessage {
+    /// The role of the `{#chat(..)}` block, or `system` for the text before the first one.
+    pub role: String,
+    /// The text of the message.
+    pub content: String,
+}
+
+/// The prompt of an impl, with its variables filled in. See [`VariantWalker::render_prompt`] for
+/// how it may differ from what the generated clients send.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+#[serde(untagged)]
+pub enum RenderedPrompt {
+    /// A prompt without `{#chat(..)}` blocks.
+    Completion(String),
+    /// A prompt split into messages by its `{#chat(..)}` blocks.
+    Chat(Vec<RenderedMessage>),
+}
+
+impl ParserDatabase {
+    /// The impl named `r#impl` of the function named `function`, or why there is none.
+    pub fn find_variant_by_name(
+        &self,
+        function: &str,
+        r#impl: &str,
+    ) -> Result<VariantWalker<'_>, String> {
+        let function = self.find_function_by_name(function).ok_or_else(|| {
+            format!(
+                "Unknown function `{}`. Could be one of: {}",
+                function,
+                self.valid_function_names().join(", ")
+            )
+        })?;
+        function
+            .walk_variants()
+            .find(|variant| variant.name() == r#impl)
+            .ok_or_else(|| {
+                format!(
+                    "Function `{}` has no impl `{}`. Could be one of: {}",
+                    function.name(),
+                    r#impl,
+                    names(function.walk_variants().map(|v| v.name().to_string()))
+                )
+            })
+    }
+
+    /// The input of the test named `test` of the function named `function`, or why there is none.
+    pub fn find_test_input(&self, function: &str, test: &str) -> Result<Value, String> {
+        let Some(function) = self.find_function_by_name(function) else {
+            return Err(format!("Unknown function `{}`.", function));
+        };
+        function
+            .walk_tests()
+            .find(|t| t.name() == test)
+            .map(|t| t.test_case().input.to_json())
+            .ok_or_else(|| {
+                format!(
+                    "Function `{}` has no test `{}`. Could be one of: {}",
+                    function.name(),
+                    test,
+                    names(function.walk_tests().map(|t| t.name().to_string()))
+                )
+            })
+    }
+}
+
+fn names(names: impl Iterator<Item = String>) -> String {
+    let names = names.collect::<Vec<_>>();
+    match names.is_empty() {
+        true => "(none)".to_string(),
+        false => names.join(", "),
+    }
+}
+
+impl VariantWalker<'_> {
+    /// Renders the prompt of the impl for `input`, which is the input of the function the way a
+    /// test writes it: the value of the argument, or an object with a value for each named
+    /// argument.
+    ///
+    /// With an input adapter, `input` is what the adapter returns instead, since only the
+    /// generated clients can run it.
+    ///
+    /// Strings render as they are, other values (including null) as JSON, and missing values as
+    /// nothing. The generated clients render strings the same way, but other values the way their
+    /// language turns them into strings, e.g. `None` and `['a', 'b']` in Python or `null` and
+    /// `a,b` in TypeScript, so the preview only matches them for strings. The output of
+    /// `{#print_type(..)}` and `{#print_enum(..)}` is already part of the prompt.
+    pub fn render_prompt(self, input: &Value) -> RenderedPrompt {
+        let params = match self.walk_function().map(|f| f.ast_function().input()) {
+            Some(ast::FunctionArgs::Named(_)) if self.properties().input_adapter.is_none() => {
+                input.clone()
+            }
+            _ => serde_json::json!({ "arg": input }),
+        };
+        let renderer = Renderer {
+            params: &params,
+            paths: self.properties().replacers.0.values().collect(),
+        };
+
+        match self.to_prompt() {
+            PromptRepr::String(prompt, _) => RenderedPrompt::Completion(renderer.render(&prompt)),
+            PromptRepr::Chat(parts, _) => RenderedPrompt::Chat(
+                parts
+                    .iter()
+                    .map(|(chat, content)| RenderedMessage {
+                        role: chat
+                            .map_or("system", |chat| chat.role.0.as_str())
+                            .to_string(),
+                        content: renderer.render(content),
+                    })
+                    .collect(),
+            ),
+        }
+    }
+}
+
+struct Renderer<'a> {
+    params: &'a Value,
+    /// The path of every variable of the prompt, e.g. `arg.items[0]`.
+    paths: Vec<&'a String>,
+}
+
+impl<'a> Renderer<'a> {
+    fn render(&self, prompt: &str) -> String {
+        let mut rendered = String::new();
+        self.render_parts(&split_prompt_blocks(prompt), &mut vec![], &mut rendered);
+        rendered
+    }
+
+    /// `scope` holds the item of each enclosing `{#for}` block.
+    fn render_parts<'p>(
+        &self,
+        parts: &[PromptPart<'p>],
+        scope: &mut Vec<(&'p str, &'a Value)>,
+        rendered: &mut String,
+    ) {
+        for part in parts {
+            match part {
+                PromptPart::Text(text) => self.render_text(text, scope, rendered),
+                PromptPart::If {
+                    condition,
+                    then,
+                    otherwise,
+                } => {
+                    let parts = match is_set(self.lookup(condition, scope)) {
+                        true => then,
+                        false => otherwise,
+                    };
+                    self.render_parts(parts, scope, rendered);
+                }
+                PromptPart::For {
+                    item,
+                    collection,
+                    body,
+                } => {
+                    for value in items(self.lookup(collection, scope)) {
+                        scope.push((*item, value));
+                        self.render_parts(body, scope, rendered);
+                        scope.pop();
+                    }
+                }
+            }
+        }
+    }
+
+    /// Replaces every `{path}` of `text` with the value it leads to.
+    fn render_text(&self, text: &str, scope: &[(&str, &'a Value)], rendered: &mut String) {
+        let mut rest = text;
+        while let Some(idx) = rest.find('{') {
+            rendered.push_str(&rest[..idx]);
+            rest = &rest[idx..];
+            let path = self.paths.iter().find(|path| {
+                rest[1..].starts_with(path.as_str()) && rest[1 + path.len()..].starts_with('}')
+            });
+            match path {
+                Some(path) => {
+                    if let Some(segments) = VariableSegment::parse_path(path) {
+                        let root = segments[0].as_field().and_then(|r| self.root(r, scope));
+                        if let Some(value) = root {
+                            rendered.push_str(&format_value(value, &segments[1..]));
+                        }
+                    }
+                    rest = &rest[path.len() + 2..];
+                }
+                None => {
+                    rendered.push('{');
+                    rest = &rest[1..];
+                }
+            }
+        }
+        rendered.push_str(rest);
+    }
+
+    /// The item of the innermost `{#for}` block named `name`, or the argument named `name`.
+    fn root(&self, name: &str, scope: &[(&str, &'a Value)]) -> Option<&'a Value> {
+        match scope.iter().rev().find(|(item, _)| *item == name) {
+            Some((_, value)) => Some(value),
+            None => self.params.get(name),
+        }
+    }
+
+    /// The value at `path`, if there is one. Conditions and collections never use `[*]`.
+    fn lookup(&self, path: &str, scope: &[(&str, &'a Value)]) -> Option<&'a Value> {
+        let segments = VariableSegment::parse_path(path)?;
+        let root = self.root(segments.first()?.as_field()?, scope)?;
+        segments[1..]
+            .iter()
+            .try_fold(root, |value, segment| match segment {
+                VariableSegment::Field(key) | VariableSegment::Key(key) => value.get(key),
+                VariableSegment::Index(idx) => value.get(idx),
+                VariableSegment::Each => None,
+            })
+    }
+}
+
+/// Formats the value at `path` inside `value`. `[*]` puts every item on its own line.
+fn format_value(value: &Value, path: &[VariableSegment]) -> String {
+    let Some((segment, rest)) = path.split_first() else {
+        return match value {
+            Value::String(s) => s.clone(),
+            value => value.to_string(),
+        };
+    };
+    let next = match segment {
+        VariableSegment::Field(key) | VariableSegment::Key(key) => value.get(key),
+        VariableSegment::Index(idx) => value.get(idx),
+        VariableSegment::Each => {
+            return items(Some(value))
+                .into_iter()
+                .map(|item| format_value(item, rest))
+                .collect::<Vec<_>>()
+                .join("\n");
+        }
+    };
+    next.map_or_else(String::new, |value| format_value(value, rest))
+}
+
+/// Whether the condition of an `{#if}` block is set: it's unset if it's null, false, or an empty
+/// string, list or map.
+fn is_set(value: Option<&Value>) -> bool {
+    match value {
+        None | Some(Value::Null) | Some(Value::Bool(false)) => false,
+        Some(Value::String(s)) => !s.is_empty(),
+        Some(Value::Array(items)) => !items.is_empty(),
+        Some(Value::Object(entries)) => !entries.is_empty(),
+        Some(_) => true,
+    }
+}
+
+/// The items of a list, or the values of a map.
+fn items(value: Option<&Value>) -> Vec<&Value> {
+    match value {
+        Some(Value::Array(items)) => items.iter().collect(),
+        Some(Value::Object(entries)) => entries.values().collect(),
+        _ => vec![],
+    }
+}
@@ -5,22 +5,27 @@ use crate::coerce;
 use crate::{context::Context, DatamodelError};
 
 use internal_baml_diagnostics::{DatamodelWarning, Span};
-use internal_baml_prompt_parser::ast::{ChatBlock, PrinterBlock, Variable};
+use internal_baml_prompt_parser::ast::{ChatBlock, ForBlock, PrinterBlock, Variable};
 use internal_baml_schema_ast::ast::{
     self, AdapterId, ClassId, ClientId, ConfigurationId, EnumId, EnumValueId, Expression, FieldId,
-    FieldType, FunctionId, RawString, SerializerFieldId, VariantConfigId, VariantSerializerId,
-    WithIdentifier, WithName, WithSpan,
+    FieldType, FunctionId, RawString, SerializerFieldId, TypeAliasId, VariantConfigId,
+    VariantSerializerId, WithIdentifier, WithName, WithSpan,
 };
+use internal_baml_schema_ast::json::JsonValue;
 
 mod configurations;
 pub(crate) mod post_prompt;
 mod prompt;
+mod prompt_blocks;
+mod test_assertions;
 mod to_string_attributes;
 mod types;
 
 use log::info;
 use prompt::validate_prompt;
 
+pub use prompt_blocks::{split_prompt_blocks, PromptPart};
+pub use test_assertions::{AssertionCheck, OutputPath, PathSegment, TestAssertion};
 pub use to_string_attributes::{
     DynamicStringAttributes, StaticStringAttributes, ToStringAttributes,
 };
@@ -33,6 +38,10 @@ pub(super) fn resolve_types(ctx: &mut Context<'_>) {
             (_, ast::Top::Enum(enm)) => visit_enum(enm, ctx),
             (ast::TopId::Class(idx), ast::Top::Class(model)) => visit_class(idx, model, ctx),
             (_, ast::Top::Class(_)) => unreachable!("Class misconfigured"),
+            (ast::TopId::TypeAlias(idx), ast::Top::TypeAlias(alias)) => {
+                visit_type_alias(idx, alias, ctx)
+            }
+            (_, ast::Top::TypeAlias(_)) => unreachable!("Type alias misconfigured"),
             (ast::TopId::Function(idx), ast::Top::Function(function)) => {
                 visit_function(idx, function, ctx)
             }
@@ -52,6 +61,8 @@ pub(super) fn resolve_types(ctx: &mut Context<'_>) {
             (_, ast::Top::Config(_)) => unreachable!("Config misconfigured"),
         }
     }
+
+    expand_type_alias_dependencies(ctx);
 }
 
 #[derive(Debug, Clone)]
@@ -65,6 +76,14 @@ pub enum PromptVariable {
     Type(PrinterBlock),
     /// Chat
     Chat(ChatBlock),
+    /// `{#if ..}`, with its condition.
+    If(Variable),
+    /// `{#else}`
+    Else(Span),
+    /// `{#for .. in ..}`
+    For(ForBlock),
+    /// `{#end}`
+    End(Span),
 }
 
 impl Hash for PromptVariable {
@@ -86,6 +105,23 @@ impl Hash for PromptVariable {
                 "type".hash(state);
                 blk.hash(state);
             }
+            PromptVariable::If(var) => {
+                "if".hash(state);
+                var.text.hash(state);
+            }
+            PromptVariable::Else(span) => {
+                "else".hash(state);
+                span.start.hash(state);
+            }
+            PromptVariable::For(blk) => {
+                "for".hash(state);
+                blk.item.0.hash(state);
+                blk.collection.text.hash(state);
+            }
+            PromptVariable::End(span) => {
+                "end".hash(state);
+                span.start.hash(state);
+            }
         }
     }
 }
@@ -98,6 +134,12 @@ impl<'a> PromptVariable {
             PromptVariable::Enum(blk) => blk.key(),
             PromptVariable::Type(blk) => blk.key(),
             PromptVariable::Chat(blk) => blk.key(),
+            PromptVariable::If(var) => format!("{{#if {}}}", var.key()),
+            PromptVariable::Else(_) => "{#else}".to_string(),
+            PromptVariable::For(blk) => {
+                format!("{{#for {} in {}}}", blk.item.0, blk.collection.key())
+            }
+            PromptVariable::End(_) => "{#end}".to_string(),
         }
     }
 }
@@ -119,9 +161,23 @@ pub struct VariantProperties {
         HashMap<PrinterBlock, String>,
         Vec<ChatBlock>,
     ),
+    /// Converts the function's input into what the prompt uses, e.g. `adapter<input, Doc>`.
+    pub input_adapter: Option<(AdapterId, Vec<RawString>)>,
+    /// Converts what the LLM returns into the function's output, e.g. `adapter<Doc, output>`.
     pub output_adapter: Option<(AdapterId, Vec<RawString>)>,
 }
 
+fn adapter_for_language<'a>(
+    adapter: Option<&'a (AdapterId, Vec<RawString>)>,
+    language: &str,
+) -> Option<&'a str> {
+    adapter.and_then(|f| {
+        f.1.iter()
+            .find(|r| r.language.as_ref().map(|(l, _)| l.as_str()) == Some(language))
+            .map(|r| r.value())
+    })
+}
+
 /// The representation of a prompt.
 pub enum PromptRepr<'a> {
     /// For single string prompts
@@ -134,12 +190,12 @@ pub enum PromptRepr<'a> {
 }
 
 impl VariantProperties {
+    pub fn input_adapter_for_language(&self, language: &str) -> Option<&str> {
+        adapter_for_language(self.input_adapter.as_ref(), language)
+    }
+
     pub fn output_adapter_for_language(&self, language: &str) -> Option<&str> {
-        self.output_adapter.as_ref().and_then(|f| {
-            f.1.iter()
-                .find(|r| r.language.as_ref().map(|(l, _)| l.as_str()) == Some(language))
-                .map(|r| r.value())
-        })
+        adapter_for_language(self.output_adapter.as_ref(), language)
     }
 
     pub fn to_prompt<'a>(&'a self) -> PromptRepr<'a> {
@@ -150,8 +206,12 @@ impl VariantProperties {
         let prompt = input
             .iter()
             .fold(self.prompt.value.clone(), |prompt, (k, val)| {
-                // Only add the input if it's used in the prompt
+                // Blocks keep their markers, with the path in place of the key.
                 let key = k.key();
+                let prompt = prompt
+                    .replace(&format!("{{#if {}}}", key), &format!("{{#if {}}}", val))
+                    .replace(&format!(" in {}}}", key), &format!(" in {}}}", val));
+                // Only add the input if it's used in the prompt
                 if prompt.contains(&key) {
                     used_inputs.push(val.clone());
                     prompt.replace(&key, &format!("{{{}}}", val))
@@ -225,13 +285,53 @@ pub struct ClientProperties {
     pub provider: (String, Span),
     pub retry_policy: Option<(String, Span)>,
     pub options: Vec<(String, Expression)>,
+    /// Set for composite clients, which delegate each request to other clients.
+    pub strategy: Option<ClientStrategy>,
+    /// Options the provider does not declare, which are passed through without validation.
+    pub unchecked_options: Vec<(String, Span)>,
+}
+
+/// How a composite client picks the client that handles a request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+pub enum ClientStrategyKind {
+    /// `baml-fallback`: try each client in order until one succeeds.
+    Fallback,
+    /// `baml-round-robin`: rotate through the clients, one request each.
+    RoundRobin,
+}
+
+impl ClientStrategyKind {
+    /// The strategy of a composite client's provider, if the provider is a composite one.
+    pub fn from_provider(provider: &str) -> Option<Self> {
+        match provider {
+            "baml-fallback" => Some(ClientStrategyKind::Fallback),
+            "baml-round-robin" => Some(ClientStrategyKind::RoundRobin),
+            _ => None,
+        }
+    }
+}
+
+/// The clients a composite client delegates to.
+#[derive(Debug, Clone)]
+pub struct ClientStrategy {
+    /// How the client is picked.
+    pub kind: ClientStrategyKind,
+    /// The names of the clients, in order.
+    pub clients: Vec<(String, Span)>,
+    /// The retry policies named by the entries of the strategy.
+    pub retry_policies: Vec<(String, Span)>,
 }
 
 #[derive(Debug, Clone)]
 pub struct TestCase {
     pub function: (String, Span),
-    pub content: RawString,
+    /// The input, from a `__tests__` JSON file or the `input` of a `test` block.
+    pub input: JsonValue,
     pub group: Option<(String, Span)>,
+    /// The impls to run the test against. Empty means every impl of the function.
+    pub impls: Vec<(String, Span)>,
+    /// Expectations on the parsed output of the function.
+    pub assertions: Vec<TestAssertion>,
 }
 
 #[derive(Debug, Clone)]
@@ -307,6 +407,7 @@ pub(super) struct Types {
     pub(super) enum_attributes: HashMap<ast::EnumId, EnumAttributes>,
     pub(super) class_attributes: HashMap<ast::ClassId, ClassAttributes>,
     pub(super) class_dependencies: HashMap<ast::ClassId, HashSet<String>>,
+    pub(super) type_alias_dependencies: HashMap<ast::TypeAliasId, HashSet<String>>,
     pub(super) function: HashMap<ast::FunctionId, FunctionType>,
     pub(super) variant_attributes: HashMap<ast::VariantConfigId, VariantAttributes>,
     pub(super) variant_properties: HashMap<ast::VariantConfigId, VariantProperties>,
@@ -384,6 +485,74 @@ fn visit_class<'db>(class_id: ast::ClassId, class: &'db ast::Class, ctx: &mut Co
     ctx.types.class_dependencies.insert(class_id, used_types);
 }
 
+fn visit_type_alias<'db>(idx: TypeAliasId, alias: &'db ast::TypeAlias, ctx: &mut Context<'db>) {
+    let used_types = alias
+        .value()
+        .flat_idns()
+        .into_iter()
+        .filter(|id| id.is_valid_type() && !matches!(id, ast::Identifier::Primitive(..)))
+        .map(|f| f.name().to_string())
+        .collect::<HashSet<_>>();
+    ctx.types.type_alias_dependencies.insert(idx, used_types);
+}
+
+/// Class and function dependencies only name the aliases they use directly. Add whatever those
+/// aliases stand for, so dependency tracking sees through them.
+fn expand_type_alias_dependencies(ctx: &mut Context<'_>) {
+    let classes = ctx
+        .types
+        .class_dependencies
+        .iter()
+        .map(|(id, deps)| (*id, with_type_alias_targets(ctx, deps)))
+        .collect::<Vec<_>>();
+    let functions = ctx
+        .types
+        .function
+        .iter()
+        .map(|(id, func)| {
+            let (input, output) = &func.dependencies;
+            (
+                *id,
+                (
+                    with_type_alias_targets(ctx, input),
+                    with_type_alias_targets(ctx, output),
+                ),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    for (id, deps) in classes {
+        ctx.types.class_dependencies.insert(id, deps);
+    }
+    for (id, deps) in functions {
+        ctx.types.function.get_mut(&id).unwrap().dependencies = deps;
+    }
+}
+
+fn with_type_alias_targets(ctx: &Context<'_>, deps: &HashSet<String>) -> HashSet<String> {
+    let mut result = deps.clone();
+    let mut pending = deps.iter().cloned().collect::<Vec<_>>();
+
+    // Aliases may refer to each other (even cyclically), so only expand each name once.
+    while let Some(name) = pending.pop() {
+        let alias_id = ctx
+            .interner
+            .lookup(&name)
+            .and_then(|name_id| ctx.names.tops.get(&name_id))
+            .and_then(|top_id| top_id.as_type_alias_id());
+
+        if let Some(alias_id) = alias_id {
+            for dep in &ctx.types.type_alias_dependencies[&alias_id] {
+                if result.insert(dep.clone()) {
+                    pending.push(dep.clone());
+                }
+            }
+        }
+    }
+
+    result
+}
+
 fn visit_function<'db>(idx: FunctionId, function: &'db ast::Function, ctx: &mut Context<'db>) {
     let input_deps = function
         .input()
@@ -443,6 +612,7 @@ fn visit_client<'db>(idx: ClientId, client: &'db ast::Client, ctx: &mut Context<
     let mut provider = None;
     let mut retry_policy = None;
     let mut options: Vec<(String, Expression)> = Vec::new();
+    let mut unchecked_options = Vec::new();
     client
         .iter_fields()
         .for_each(|(_idx, field)| match field.name() {
@@ -494,6 +664,19 @@ fn visit_client<'db>(idx: ClientId, client: &'db ast::Client, ctx: &mut Context<
                     _ => {}
                 };
             }
+            "unchecked_options" => match field.value.as_ref().and_then(|v| v.as_array()) {
+                Some((keys, _)) => keys.iter().for_each(|key| match key.as_string_value() {
+                    Some((key, span)) => unchecked_options.push((key.to_string(), span.clone())),
+                    None => ctx.push_error(DatamodelError::new_validation_error(
+                        "Expected the name of an option.",
+                        key.span().clone(),
+                    )),
+                }),
+                None => ctx.push_error(DatamodelError::new_validation_error(
+                    "Expected a list of option names, e.g. `unchecked_options [logprobs]`",
+                    field.span().clone(),
+                )),
+            },
             config => ctx.push_error(DatamodelError::new_validation_error(
                 &format!("Unknown field `{}` in client", config),
                 field.span().clone(),
@@ -518,12 +701,16 @@ fn visit_client<'db>(idx: ClientId, client: &'db ast::Client, ctx: &mut Context<
                 options,
             ) {
                 (Some(provider), options) => {
+                    let strategy = ClientStrategyKind::from_provider(provider.0)
+                        .map(|kind| visit_client_strategy(provider, kind, &options, ctx));
                     ctx.types.client_properties.insert(
                         idx,
                         ClientProperties {
                             provider: (provider.0.to_string(), provider.1.clone()),
                             retry_policy,
                             options,
+                            strategy,
+                            unchecked_options,
                         },
                     );
                 }
@@ -539,6 +726,155 @@ fn visit_client<'db>(idx: ClientId, client: &'db ast::Client, ctx: &mut Context<
     }
 }
 
+/// Returns the clients and retry policies listed in the `strategy` option of a composite client.
+/// The option is left in place for the generated code. Whether the clients and retry policies
+/// exist is validated once all of them are known.
+fn visit_client_strategy(
+    (provider, provider_span): (&str, &Span),
+    kind: ClientStrategyKind,
+    options: &[(String, Expression)],
+    ctx: &mut Context<'_>,
+) -> ClientStrategy {
+    let mut strategy = ClientStrategy {
+        kind,
+        clients: vec![],
+        retry_policies: vec![],
+    };
+
+    for (key, value) in options.iter().filter(|(key, _)| key != "strategy") {
+        ctx.push_error(DatamodelError::new_validation_error(
+            &format!(
+                "Unknown option `{}` for a `{}` client. Only `strategy` is supported.",
+                key, provider
+            ),
+            value.span().clone(),
+        ));
+    }
+
+    let value = options
+        .iter()
+        .find(|(key, _)| key == "strategy")
+        .map(|(_, value)| value);
+    let items = match value {
+        Some(ast::Expression::Array(items, _)) => items,
+        Some(other) => {
+            ctx.push_error(DatamodelError::new_validation_error(
+                "Expected a list of clients, e.g. `strategy [ClientA, ClientB]`",
+                other.span().clone(),
+            ));
+            return strategy;
+        }
+        None => {
+            ctx.push_error(DatamodelError::new_validation_error(
+                &format!(
+                    "Missing `strategy` option in `{}` client. e.g. `strategy [ClientA, ClientB]`",
+                    provider
+                ),
+                provider_span.clone(),
+            ));
+            return strategy;
+        }
+    };
+
+    if items.is_empty() {
+        ctx.push_error(DatamodelError::new_validation_error(
+            "The strategy must list at least one client.",
+            value.unwrap().span().clone(),
+        ));
+    }
+
+    for item in items {
+        visit_client_strategy_item(item, &mut strategy, ctx);
+    }
+    strategy
+}
+
+/// A strategy item is the name of a client. Fallback clients also take a map, e.g.
+/// `{ client ClientA, on_status_code [429], retry_policy Quick }`.
+fn visit_client_strategy_item(
+    item: &Expression,
+    strategy: &mut ClientStrategy,
+    ctx: &mut Context<'_>,
+) {
+    if let Some((name, span)) = item.as_string_value() {
+        strategy.clients.push((name.to_string(), span.clone()));
+        return;
+    }
+
+    let entries = match (strategy.kind, item.as_map()) {
+        (ClientStrategyKind::Fallback, Some((entries, _))) => entries,
+        _ => {
+            ctx.push_error(DatamodelError::new_validation_error(
+                "Expected the name of a client.",
+                item.span().clone(),
+            ));
+            return;
+        }
+    };
+
+    let mut client = None;
+    for (key, value) in entries {
+        match key.as_string_value() {
+            Some(("client", _)) => match value.as_string_value() {
+                Some((name, span)) => client = Some((name.to_string(), span.clone())),
+                None => ctx.push_error(DatamodelError::new_validation_error(
+                    "Expected the name of a client.",
+                    value.span().clone(),
+                )),
+            },
+            Some(("on_status_code", _)) => visit_status_codes(value, ctx),
+            Some(("retry_policy", _)) => match value.as_string_value() {
+                Some((name, span)) => strategy
+                    .retry_policies
+                    .push((name.to_string(), span.clone())),
+                None => ctx.push_error(DatamodelError::new_validation_error(
+                    "Expected the name of a retry_policy.",
+                    value.span().clone(),
+                )),
+            },
+            _ => ctx.push_error(DatamodelError::new_property_not_known_error(
+                &key.to_string(),
+                key.span().clone(),
+                ["client", "on_status_code", "retry_policy"],
+            )),
+        }
+    }
+
+    match client {
+        Some(client) => strategy.clients.push(client),
+        None => ctx.push_error(DatamodelError::new_validation_error(
+            "Missing `client` in strategy entry. e.g. `{ client ClientA }`",
+            item.span().clone(),
+        )),
+    }
+}
+
+/// `on_status_code` lists the HTTP status codes on which to fall back, e.g. `[429, 503]`.
+fn visit_status_codes(value: &Expression, ctx: &mut Context<'_>) {
+    let Some((codes, _)) = value.as_array() else {
+        ctx.push_error(DatamodelError::new_value_parser_error(
+            "a list of status codes, e.g. `[429, 503]`",
+            &value.to_string(),
+            value.span().clone(),
+        ));
+        return;
+    };
+
+    for code in codes {
+        let is_status_code = code
+            .as_numeric_value()
+            .and_then(|(num, _)| num.parse::<i64>().ok())
+            .is_some_and(|num| (100..=599).contains(&num));
+        if !is_status_code {
+            ctx.push_error(DatamodelError::new_value_parser_error(
+                "an HTTP status code from 100 to 599",
+                &code.to_string(),
+                code.span().clone(),
+            ));
+        }
+    }
+}
+
 fn visit_variant<'db>(idx: VariantConfigId, variant: &'db ast::Variant, ctx: &mut Context<'db>) {
     if !variant.is_llm() {
         ctx.push_error(DatamodelError::new_validation_error(
@@ -631,7 +967,7 @@ fn visit_variant<'db>(idx: VariantConfigId, variant: &'db ast::Variant, ctx: &mu
     };
 
     // Ensure that the adapters are valid.
-    let (_input_adapter, output_adapter) =
+    let (input_adapter, output_adapter) =
         variant
             .iter_adapters()
             .fold((None, None), |prev, (idx, adapter)| {
@@ -682,18 +1018,15 @@ fn visit_variant<'db>(idx: VariantConfigId, variant: &'db ast::Variant, ctx: &mu
                             Some(
                                 arr.iter()
                                     .filter_map(|item| coerce::raw_string(item, ctx.diagnostics))
+                                    .cloned()
                                     .collect::<Vec<_>>(),
                             )
                         } else {
                             coerce::raw_string(&adapter.converter, ctx.diagnostics)
-                                .map(|raw| vec![raw])
+                                .map(|raw| vec![raw.clone()])
                         };
 
                         if let Some(impls) = impls {
-                            ctx.push_warning(DatamodelWarning::new(
-                                "The `input` adapter is note yet supported.".into(),
-                                adapter.span().clone(),
-                            ));
                             return (Some((idx, impls)), prev.1);
                         }
                     }
@@ -749,6 +1082,7 @@ fn visit_variant<'db>(idx: VariantConfigId, variant: &'db ast::Variant, ctx: &mu
                     },
                     prompt_replacements: replacers,
                     replacers: Default::default(),
+                    input_adapter,
                     output_adapter,
                 },
             );
@@ -1,14 +1,23 @@
 use internal_baml_diagnostics::{DatamodelError, Span};
-use internal_baml_schema_ast::ast::{
-    ConfigurationId, PrinterConfig, RetryPolicyConfig, WithIdentifier, WithName, WithSpan,
+use internal_baml_schema_ast::{
+    ast::{
+        ConfigurationId, Expression, PrinterConfig, RetryPolicyConfig, WithIdentifier, WithName,
+        WithSpan,
+    },
+    json::{self, JsonValue},
 };
 use regex::Regex;
 
-use crate::{coerce, coerce_expression::coerce_map, context::Context};
+use crate::{
+    coerce,
+    coerce_expression::{coerce_array, coerce_map},
+    context::Context,
+    printer::validate_template,
+};
 
 use super::{
-    ContantDelayStrategy, ExponentialBackoffStrategy, Printer, PrinterType, RetryPolicy,
-    RetryPolicyStrategy,
+    test_assertions::parse_assertions, ContantDelayStrategy, ExponentialBackoffStrategy, Printer,
+    PrinterType, RetryPolicy, RetryPolicyStrategy,
 };
 
 fn dedent(s: &str) -> String {
@@ -62,6 +71,15 @@ pub(crate) fn visit_printer<'db>(
             )),
         });
 
+    if let Some((template, span)) = &template {
+        if let Err(e) = validate_template(template) {
+            ctx.push_error(DatamodelError::new_validation_error(
+                &format!("Invalid printer template: {}", e),
+                span.clone(),
+            ));
+        }
+    }
+
     match (
         template,
         coerce::string_with_span(&config.printer_type, ctx.diagnostics),
@@ -266,8 +284,10 @@ pub(crate) fn visit_test_case<'db>(
     ctx: &mut Context<'db>,
 ) {
     let mut function_name = None;
-    let mut test_case = None;
+    let mut input = None;
     let mut group = None;
+    let mut impls = vec![];
+    let mut assertions = vec![];
 
     config
         .iter_fields()
@@ -276,7 +296,7 @@ pub(crate) fn visit_test_case<'db>(
                 ctx.push_error(DatamodelError::new_config_property_missing_value_error(
                     name,
                     config.name(),
-                    "printer",
+                    "test",
                     f.identifier().span().clone(),
                 ))
             }
@@ -284,39 +304,70 @@ pub(crate) fn visit_test_case<'db>(
                 Some((t, span)) => function_name = Some((t.to_string(), span.clone())),
                 None => {}
             },
-            ("input", Some(val)) => match coerce::raw_string(&val, ctx.diagnostics) {
-                Some(raw_string) => test_case = Some(raw_string),
-                None => {}
+            ("input", Some(val)) => match test_value(val) {
+                Ok(value) => input = Some(value),
+                Err(err) => ctx.push_error(err),
+            },
+            ("assert", Some(val)) => match test_value(val) {
+                Ok(value) => {
+                    let mut errors = vec![];
+                    assertions = parse_assertions(&value, &mut errors);
+                    errors.into_iter().for_each(|e| ctx.push_error(e));
+                }
+                Err(err) => ctx.push_error(err),
             },
             ("group", Some(val)) => match coerce::string_with_span(&val, ctx.diagnostics) {
                 Some((t, span)) => group = Some((t.to_string(), span.clone())),
                 None => {}
             },
+            ("impls", Some(val)) => {
+                if let Some(names) = coerce_array(
+                    val,
+                    &|val, diagnostics| {
+                        coerce::string_with_span(val, diagnostics)
+                            .map(|(name, span)| (name.to_string(), span.clone()))
+                    },
+                    ctx.diagnostics,
+                ) {
+                    impls = names;
+                }
+            }
             (name, Some(_)) => ctx.push_error(DatamodelError::new_property_not_known_error(
                 name,
                 f.identifier().span().clone(),
-                ["function", "input", "group"].to_vec(),
+                ["function", "input", "group", "impls", "assert"].to_vec(),
             )),
         });
 
-    match (function_name, test_case) {
+    match (function_name, input) {
         (None, _) => ctx.push_error(DatamodelError::new_validation_error(
-            "Missing `function_name` property",
+            "Missing `function` property",
             config.identifier().span().clone(),
         )),
         (Some(function_name), None) => ctx.push_error(DatamodelError::new_validation_error(
-            "Missing `test_case` property",
+            "Missing `input` property",
             function_name.1.clone(),
         )),
-        (Some(function), Some(test_case)) => {
+        (Some(function), Some(input)) => {
             ctx.types.test_cases.insert(
                 idx,
                 super::TestCase {
                     function,
-                    content: test_case.clone(),
+                    input,
                     group,
+                    impls,
+                    assertions,
                 },
             );
         }
     }
 }
+
+/// The value of a test's `input` or `assert`. A raw string holds JSON, as do `__tests__` files.
+/// Anything else is a BAML value, e.g. `input { name Bob }`.
+fn test_value(val: &Expression) -> Result<JsonValue, DatamodelError> {
+    match val.as_raw_string_value() {
+        Some(raw_string) => json::parse_span(raw_string.span()),
+        None => JsonValue::from_expression(val),
+    }
+}
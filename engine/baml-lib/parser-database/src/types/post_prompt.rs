@@ -1,6 +1,6 @@
 use either::Either;
 use internal_baml_diagnostics::{DatamodelError, DatamodelWarning, Diagnostics};
-use internal_baml_prompt_parser::ast::{PrinterBlock, Variable};
+use internal_baml_prompt_parser::ast::{PrinterBlock, Variable, VariableSegment};
 use internal_baml_schema_ast::ast::{self, WithName};
 
 use crate::{
@@ -8,41 +8,48 @@ use crate::{
     ParserDatabase, WithSerialize,
 };
 
+/// Checks a `{#input...}` variable refers to an argument of the function, and returns the path to
+/// render, e.g. `arg.items[0]` or `my_arg.name`. The rest of the path is type-checked once the
+/// IR is built.
+///
+/// Variables that start with the item of an enclosing `{#for}` block are rendered as written.
+/// With an input adapter, `input` is whatever the adapter returns, so it's rendered as `arg`.
 pub(crate) fn process_input(
-    db: &ParserDatabase,
     walker: FunctionWalker<'_>,
     variable: &Variable,
+    loop_items: &[&str],
+    has_input_adapter: bool,
 ) -> Result<String, DatamodelError> {
-    if variable.path[0] != "input" {
+    if let Some(root) = variable.path[0].as_field() {
+        if loop_items.contains(&root) {
+            return Ok(VariableSegment::format_path(&variable.path));
+        }
+    }
+    if variable.path[0].as_field() != Some("input") {
         return Err(DatamodelError::new_validation_error(
             "Must start with `input`",
             variable.span.clone(),
         ));
     }
 
-    match walker.ast_function().input() {
-        ast::FunctionArgs::Unnamed(arg) => {
-            validate_variable_path(db, variable, 1, &arg.field_type)?;
+    match (has_input_adapter, walker.ast_function().input()) {
+        (true, _) | (false, ast::FunctionArgs::Unnamed(_)) => {
             let mut new_path = variable.path.clone();
-            new_path[0] = "arg".to_string();
-            return Ok(new_path.join("."));
+            new_path[0] = VariableSegment::Field("arg".to_string());
+            Ok(VariableSegment::format_path(&new_path))
         }
-        ast::FunctionArgs::Named(args) => {
-            if variable.path.len() < 2 {
+        (false, ast::FunctionArgs::Named(args)) => {
+            let Some(path_name) = variable.path.get(1).and_then(VariableSegment::as_field) else {
                 return Err(DatamodelError::new_validation_error(
                     "Named arguments must have at least one argument (input.my_var_name)",
                     variable.span.clone(),
                 ));
-            }
-            let path_name = &variable.path[1];
+            };
             match args
                 .iter_args()
                 .find(|(_, (name, _))| name.name() == path_name)
             {
-                Some((_, (_, arg))) => {
-                    validate_variable_path(db, variable, 2, &arg.field_type)?;
-                    return Ok(variable.path[1..].join("."));
-                }
+                Some(_) => Ok(VariableSegment::format_path(&variable.path[1..])),
                 None => Err(DatamodelError::new_validation_error(
                     &format!(
                         "Unknown arg `{}`. Could be one of: {}",
@@ -167,105 +174,3 @@ pub(crate) fn process_print_type(
         )),
     }
 }
-
-fn validate_variable_path(
-    db: &ParserDatabase,
-    variable: &Variable,
-    next_index: usize,
-    current: &ast::FieldType,
-) -> Result<(), DatamodelError> {
-    if next_index >= variable.path.len() {
-        // Consider throwing a warning if current is not primitive.
-        return Ok(());
-    }
-
-    let next_path_name = variable.path[next_index].clone();
-    match current {
-        ast::FieldType::Union(_, ft, _) => match ft
-            .into_iter()
-            .any(|ft| validate_variable_path(db, variable, next_index, ft).is_ok())
-        {
-            true => Ok(()),
-            false => Err(DatamodelError::new_validation_error(
-                &format!("Unknown field `{}` in Union", next_path_name),
-                variable.span.clone(),
-            )),
-        },
-        ast::FieldType::Dictionary(_, _) => Err(DatamodelError::new_validation_error(
-            "Dictionary types are not supported",
-            variable.span.clone(),
-        )),
-        ast::FieldType::Tuple(_, _, _) => Err(DatamodelError::new_validation_error(
-            "Tuple types are not supported",
-            variable.span.clone(),
-        )),
-        ast::FieldType::List(_, _, _) => Err(DatamodelError::new_validation_error(
-            "List types are not yet indexable in the prompt",
-            variable.span.clone(),
-        )),
-        ast::FieldType::Identifier(_, idn) => match db.find_type(&idn) {
-            Some(Either::Left(cls)) => {
-                match cls
-                    .static_fields()
-                    .find(|f| f.name() == next_path_name.as_str())
-                {
-                    Some(field) => {
-                        let t = field.r#type();
-                        validate_variable_path(db, variable, next_index + 1, t)
-                    }
-                    None => match cls
-                        .dynamic_fields()
-                        .find(|f| f.name() == next_path_name.as_str())
-                    {
-                        Some(_) => {
-                            // Throw an error if the next path is not the last path.
-                            if next_index + 1 < variable.path.len() {
-                                Err(DatamodelError::new_validation_error(
-                                    "get properties must be the last path in the prompt",
-                                    variable.span.clone(),
-                                ))
-                            } else {
-                                Ok(())
-                            }
-                        }
-                        None => Err(DatamodelError::new_validation_error(
-                            &format!(
-                                "Unknown field `{}` in class `{}`",
-                                next_path_name,
-                                idn.name()
-                            ),
-                            variable.span.clone(),
-                        )),
-                    },
-                }
-            }
-            Some(Either::Right(_)) => Err(DatamodelError::new_validation_error(
-                "Enum values are not indexable in the prompt",
-                variable.span.clone(),
-            )),
-            None => match idn {
-                ast::Identifier::Primitive(_p, _) => Err(DatamodelError::new_validation_error(
-                    &format!(
-                        "{0} has no field {1}. {0} is of type: {2}",
-                        variable.path[..next_index].join("."),
-                        next_path_name,
-                        idn.name()
-                    ),
-                    variable.span.clone(),
-                )),
-                ast::Identifier::Ref(_, _) => Err(DatamodelError::new_validation_error(
-                    "Namespace imports (using '.') are not yet supported.",
-                    variable.span.clone(),
-                )),
-                ast::Identifier::ENV(_, _) => Err(DatamodelError::new_validation_error(
-                    "Environment variables are not indexable in the prompt",
-                    variable.span.clone(),
-                )),
-                _ => Err(DatamodelError::new_validation_error(
-                    &format!("Unknown type `{}`.", idn.name()),
-                    variable.span.clone(),
-                )),
-            },
-        },
-    }
-}
@@ -1,10 +1,10 @@
-use internal_baml_diagnostics::DatamodelError;
+use internal_baml_diagnostics::{DatamodelError, Span};
 use internal_baml_prompt_parser::ast::{CodeBlock, PromptAst, Top, Variable};
 use internal_baml_schema_ast::ast::RawString;
 
 use crate::context::Context;
 
-use super::PromptVariable;
+use super::{prompt_blocks::next_marker, PromptVariable};
 
 /// Function returns the raw_string without any comments.
 pub(super) fn validate_prompt(
@@ -80,6 +80,7 @@ fn process_prompt_ast(ctx: &mut Context<'_>, ast: PromptAst) -> (String, Vec<Pro
     let mut full_prompt_text = String::new();
 
     let mut replacers = Vec::default();
+    let mut open_blocks: Vec<OpenBlock> = vec![];
 
     for (top_id, top) in ast.iter_tops() {
         match (top_id, top) {
@@ -125,7 +126,7 @@ fn process_prompt_ast(ctx: &mut Context<'_>, ast: PromptAst) -> (String, Vec<Pro
                 }
 
                 let raw_string = code_block.as_str();
-                let replacement = process_code_block(ctx, code_block.to_owned());
+                let replacement = process_code_block(ctx, code_block.to_owned(), &mut open_blocks);
                 match replacement {
                     Some(replacement) => {
                         full_prompt_text.push_str(&format!("{}", replacement.key()));
@@ -137,16 +138,136 @@ fn process_prompt_ast(ctx: &mut Context<'_>, ast: PromptAst) -> (String, Vec<Pro
         }
     }
 
-    (full_prompt_text, replacers)
+    for block in open_blocks {
+        ctx.push_error(DatamodelError::new_validation_error(
+            &format!(
+                "This `{{#{}}}` block is never closed. Add `{{#end}}` after it.",
+                if block.item.is_some() { "for" } else { "if" }
+            ),
+            block.span,
+        ));
+    }
+
+    (trim_block_lines(&full_prompt_text), replacers)
 }
 
-fn process_code_block(ctx: &mut Context<'_>, code_block: CodeBlock) -> Option<PromptVariable> {
+/// An `{#if}` or `{#for}` block whose `{#end}` hasn't been reached yet.
+struct OpenBlock {
+    span: Span,
+    /// The name of the item of a `{#for}` block.
+    item: Option<String>,
+    has_else: bool,
+}
+
+/// Drops the indentation and newline of lines that only hold block markers, so blocks don't leave
+/// blank lines behind.
+fn trim_block_lines(prompt: &str) -> String {
+    prompt
+        .split_inclusive('\n')
+        .map(|line| {
+            let content = line.trim();
+            let mut rest = content;
+            while let Some((0, end, _)) = next_marker(rest) {
+                rest = rest[end..].trim_start();
+            }
+            match rest.is_empty() && !content.is_empty() {
+                true => content,
+                false => line,
+            }
+        })
+        .collect()
+}
+
+fn process_code_block(
+    ctx: &mut Context<'_>,
+    code_block: CodeBlock,
+    open_blocks: &mut Vec<OpenBlock>,
+) -> Option<PromptVariable> {
+    let loop_items = open_blocks
+        .iter()
+        .filter_map(|block| block.item.as_deref())
+        .collect::<Vec<_>>();
+
     match code_block {
-        CodeBlock::Chat(c) => Some(PromptVariable::Chat(c)),
-        CodeBlock::Variable(var) => match process_input(ctx, &var) {
+        CodeBlock::Chat(c) => match open_blocks.last() {
+            Some(block) => {
+                ctx.push_error(DatamodelError::new_validation_error(
+                    &format!(
+                        "`{{#chat(..)}}` can't be used inside {} block.",
+                        if block.item.is_some() {
+                            "a `{#for}`"
+                        } else {
+                            "an `{#if}`"
+                        }
+                    ),
+                    c.role.1.clone(),
+                ));
+                None
+            }
+            None => Some(PromptVariable::Chat(c)),
+        },
+        CodeBlock::Variable(var) => match process_input(ctx, &var, &loop_items) {
             true => Some(PromptVariable::Input(var)),
             false => None,
         },
+        CodeBlock::If(var) => {
+            let valid = process_input(ctx, &var, &loop_items);
+            open_blocks.push(OpenBlock {
+                span: var.span.clone(),
+                item: None,
+                has_else: false,
+            });
+            valid.then_some(PromptVariable::If(var))
+        }
+        CodeBlock::For(blk) => {
+            let (item, item_span) = &blk.item;
+            let mut valid = process_input(ctx, &blk.collection, &loop_items);
+            if item == "input" || loop_items.contains(&item.as_str()) {
+                ctx.push_error(DatamodelError::new_validation_error(
+                    &format!(
+                        "`{}` is already used by {}. Pick another name for the items.",
+                        item,
+                        if item == "input" {
+                            "the inputs of the function"
+                        } else {
+                            "an enclosing `{#for}` block"
+                        }
+                    ),
+                    item_span.clone(),
+                ));
+                valid = false;
+            }
+            open_blocks.push(OpenBlock {
+                span: blk.span.clone(),
+                item: Some(item.clone()),
+                has_else: false,
+            });
+            valid.then_some(PromptVariable::For(blk))
+        }
+        CodeBlock::Else(span) => {
+            let error = match open_blocks.last_mut() {
+                Some(block) if block.item.is_none() && !block.has_else => {
+                    block.has_else = true;
+                    return Some(PromptVariable::Else(span));
+                }
+                Some(block) if block.item.is_none() => {
+                    "An `{#if}` block can only have one `{#else}`."
+                }
+                _ => "`{#else}` can only be used inside an `{#if}` block.",
+            };
+            ctx.push_error(DatamodelError::new_validation_error(error, span));
+            None
+        }
+        CodeBlock::End(span) => match open_blocks.pop() {
+            Some(_) => Some(PromptVariable::End(span)),
+            None => {
+                ctx.push_error(DatamodelError::new_validation_error(
+                    "There is no `{#if}` or `{#for}` block for this `{#end}` to close.",
+                    span,
+                ));
+                None
+            }
+        },
         CodeBlock::PrintEnum(blk) => match process_print_enum(ctx, &blk.target) {
             true => Some(PromptVariable::Enum(blk)),
             false => None,
@@ -174,14 +295,22 @@ fn process_print_type(_ctx: &mut Context<'_>, _variable: &Variable) -> bool {
     return true;
 }
 
-fn process_input(ctx: &mut Context<'_>, variable: &Variable) -> bool {
-    if variable.path[0] != "input" {
-        ctx.push_error(DatamodelError::new_validation_error(
-            "Must start with `input`",
-            variable.span.clone(),
-        ));
-        false
-    } else {
-        true
+fn process_input(ctx: &mut Context<'_>, variable: &Variable, loop_items: &[&str]) -> bool {
+    match variable.path[0].as_field() {
+        Some(root) if root == "input" || loop_items.contains(&root) => true,
+        _ => {
+            let message = match loop_items {
+                [] => "Must start with `input`".to_string(),
+                _ => format!(
+                    "Must start with `input` or one of: {}",
+                    loop_items.join(", ")
+                ),
+            };
+            ctx.push_error(DatamodelError::new_validation_error(
+                &message,
+                variable.span.clone(),
+            ));
+            false
+        }
     }
 }
@@ -0,0 +1,152 @@
+/// A piece of a prompt, with its `{#if}` and `{#for}` blocks nested.
+///
+/// The prompt of a variant keeps its blocks as markers, e.g. `{#if <key>}`, `{#else}`,
+/// `{#for doc in <key>}` and `{#end}`, where `<key>` is the input replacer of the condition or the
+/// collection. Once rendered, the key is replaced by its path, e.g. `{#if arg.context}`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PromptPart<'a> {
+    /// Text, which may still contain replacers.
+    Text(&'a str),
+    /// `{#if condition} then {#else} otherwise {#end}`
+    If {
+        /// The key or path of the condition.
+        condition: &'a str,
+        /// Used if the condition is set.
+        then: Vec<PromptPart<'a>>,
+        /// Used otherwise. Empty without an `{#else}`.
+        otherwise: Vec<PromptPart<'a>>,
+    },
+    /// `{#for item in collection} body {#end}`
+    For {
+        /// The name each item goes by in the body.
+        item: &'a str,
+        /// The key or path of the collection.
+        collection: &'a str,
+        /// Repeated for every item.
+        body: Vec<PromptPart<'a>>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Marker<'a> {
+    If(&'a str),
+    Else,
+    For(&'a str, &'a str),
+    End,
+}
+
+/// Finds the next block marker in `text`, along with where it starts and ends.
+pub(crate) fn next_marker(text: &str) -> Option<(usize, usize, Marker<'_>)> {
+    let mut from = 0;
+    while let Some(idx) = text[from..].find("{#") {
+        let start = from + idx;
+        let rest = &text[start + 2..];
+        let marker = if rest.starts_with("else}") {
+            Some((5, Marker::Else))
+        } else if rest.starts_with("end}") {
+            Some((4, Marker::End))
+        } else if let Some(arg) = rest.strip_prefix("if ") {
+            marker_arg(arg).map(|(len, condition)| (3 + len, Marker::If(condition)))
+        } else if let Some(for_block) = rest.strip_prefix("for ") {
+            for_block.split_once(" in ").and_then(|(item, arg)| {
+                marker_arg(arg).map(|(len, collection)| {
+                    (4 + item.len() + 4 + len, Marker::For(item, collection))
+                })
+            })
+        } else {
+            None
+        };
+        match marker {
+            Some((len, marker)) => return Some((start, start + 2 + len, marker)),
+            None => from = start + 2,
+        }
+    }
+    None
+}
+
+/// The argument of a marker, and the length of the rest of the marker including its closing `}`.
+fn marker_arg(text: &str) -> Option<(usize, &str)> {
+    let end = if text.starts_with("{//") {
+        text.find("//}")? + 3
+    } else {
+        text.find('}')?
+    };
+    match text[end..].starts_with('}') {
+        true => Some((end + 1, &text[..end])),
+        false => None,
+    }
+}
+
+/// A block whose `{#end}` hasn't been reached yet.
+struct OpenBlock<'a> {
+    marker: Marker<'a>,
+    /// The parts before the `{#else}`, once it's reached.
+    then: Option<Vec<PromptPart<'a>>>,
+    parts: Vec<PromptPart<'a>>,
+}
+
+/// Splits a prompt into its text and blocks.
+pub fn split_prompt_blocks(prompt: &str) -> Vec<PromptPart<'_>> {
+    let mut parts = vec![];
+    let mut open: Vec<OpenBlock<'_>> = vec![];
+    let mut rest = prompt;
+
+    loop {
+        let next = next_marker(rest);
+        let text = &rest[..next.map_or(rest.len(), |(start, ..)| start)];
+        let current = open.last_mut().map_or(&mut parts, |block| &mut block.parts);
+        if !text.is_empty() {
+            current.push(PromptPart::Text(text));
+        }
+        let Some((_, end, marker)) = next else {
+            break;
+        };
+        rest = &rest[end..];
+
+        match marker {
+            Marker::If(_) | Marker::For(..) => open.push(OpenBlock {
+                marker,
+                then: None,
+                parts: vec![],
+            }),
+            Marker::Else => {
+                if let Some(block) = open.last_mut() {
+                    if matches!(block.marker, Marker::If(_)) && block.then.is_none() {
+                        block.then = Some(std::mem::take(&mut block.parts));
+                    }
+                }
+            }
+            Marker::End => {
+                if let Some(block) = open.pop() {
+                    close_block(block, open.last_mut().map_or(&mut parts, |b| &mut b.parts));
+                }
+            }
+        }
+    }
+    // Validation reports unclosed blocks, so this is only for robustness.
+    while let Some(block) = open.pop() {
+        close_block(block, open.last_mut().map_or(&mut parts, |b| &mut b.parts));
+    }
+    parts
+}
+
+fn close_block<'a>(block: OpenBlock<'a>, parent: &mut Vec<PromptPart<'a>>) {
+    parent.push(match (block.marker, block.then) {
+        (Marker::If(condition), Some(then)) => PromptPart::If {
+            condition,
+            then,
+            otherwise: block.parts,
+        },
+        (Marker::If(condition), None) => PromptPart::If {
+            condition,
+            then: block.parts,
+            otherwise: vec![],
+        },
+        (Marker::For(item, collection), _) => PromptPart::For {
+            item,
+            collection,
+            body: block.parts,
+        },
+        (Marker::Else | Marker::End, _) => return,
+    });
+}
@@ -0,0 +1,367 @@
+use std::fmt;
+
+use internal_baml_diagnostics::{DatamodelError, Span};
+use internal_baml_schema_ast::json::{JsonKind, JsonValue};
+use regex::Regex;
+use serde_json::Value;
+
+const ASSERTION_PROPERTIES: [&str; 5] = ["path", "equals", "one_of", "matches", "not_null"];
+
+/// An expectation on the parsed output of a test, e.g.
+/// `{ path "$.sentiment", one_of [Happy, Sad] }`.
+#[derive(Debug, Clone)]
+pub struct TestAssertion {
+    /// Where in the output to look. Defaults to the whole output.
+    pub path: OutputPath,
+    /// What the value at `path` must be.
+    pub check: AssertionCheck,
+    /// The whole assertion.
+    pub span: Span,
+}
+
+/// A check on a single value of the output.
+#[derive(Debug, Clone)]
+pub enum AssertionCheck {
+    /// The value must be equal to this one.
+    Equals(JsonValue),
+    /// The value must be equal to one of these, e.g. a subset of an enum's values.
+    OneOf(Vec<JsonValue>),
+    /// The value must be a string matching this pattern.
+    Matches(Regex, Span),
+    /// The value must be present and not null.
+    NotNull,
+}
+
+/// A JSON path into the output, e.g. `$.items[0].name`. The leading `$` is optional.
+#[derive(Debug, Clone)]
+pub struct OutputPath {
+    /// The steps from the root of the output. Empty for the whole output.
+    pub segments: Vec<PathSegment>,
+    /// Where the path is written.
+    pub span: Span,
+}
+
+/// One step of an [`OutputPath`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PathSegment {
+    /// A field of an object, e.g. `.name` or `["first name"]`.
+    Field(String),
+    /// An item of a list, e.g. `[0]`.
+    Index(usize),
+}
+
+impl OutputPath {
+    fn parse(path: &str, span: Span) -> Result<Self, DatamodelError> {
+        let invalid = |reason: &str| {
+            DatamodelError::new_validation_error(
+                &format!("Invalid path `{}`: {}.", path, reason),
+                span.clone(),
+            )
+        };
+
+        let mut rest = path.trim();
+        rest = rest.strip_prefix('$').unwrap_or(rest);
+        let mut segments = vec![];
+        let mut first = true;
+        while !rest.is_empty() {
+            if let Some(index) = rest.strip_prefix('[') {
+                let (index, after) = index
+                    .split_once(']')
+                    .ok_or_else(|| invalid("missing `]`"))?;
+                let segment = match index.strip_prefix('"').and_then(|i| i.strip_suffix('"')) {
+                    Some(key) => PathSegment::Field(key.to_string()),
+                    None => PathSegment::Index(
+                        index
+                            .parse()
+                            .map_err(|_| invalid("expected an index or a quoted key in `[]`"))?,
+                    ),
+                };
+                segments.push(segment);
+                rest = after;
+            } else {
+                // Only the first field may leave out the `.`, e.g. `name` for `$.name`.
+                let field = match rest.strip_prefix('.') {
+                    Some(field) => field,
+                    None if first && path.trim() == rest => rest,
+                    None => return Err(invalid("expected `.` or `[`")),
+                };
+                let end = field.find(['.', '[']).unwrap_or(field.len());
+                if end == 0 {
+                    return Err(invalid("expected a field name"));
+                }
+                segments.push(PathSegment::Field(field[..end].to_string()));
+                rest = &field[end..];
+            }
+            first = false;
+        }
+
+        Ok(OutputPath { segments, span })
+    }
+
+    /// The value at this path, if there is one.
+    pub fn resolve<'a>(&self, value: &'a Value) -> Option<&'a Value> {
+        self.segments
+            .iter()
+            .try_fold(value, |value, segment| match segment {
+                PathSegment::Field(field) => value.get(field),
+                PathSegment::Index(index) => value.get(index),
+            })
+    }
+}
+
+impl fmt::Display for OutputPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "$")?;
+        for segment in &self.segments {
+            match segment {
+                PathSegment::Field(field) => write!(f, ".{}", field)?,
+                PathSegment::Index(index) => write!(f, "[{}]", index)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl TestAssertion {
+    /// Checks the parsed output of a test. Missing values count as null.
+    pub fn evaluate(&self, output: &Value) -> Result<(), String> {
+        let actual = self.path.resolve(output).unwrap_or(&Value::Null);
+        let passed = match &self.check {
+            AssertionCheck::Equals(expected) => json_eq(actual, &expected.to_json()),
+            AssertionCheck::OneOf(options) => options
+                .iter()
+                .any(|option| json_eq(actual, &option.to_json())),
+            AssertionCheck::Matches(pattern, _) => {
+                actual.as_str().is_some_and(|s| pattern.is_match(s))
+            }
+            AssertionCheck::NotNull => !actual.is_null(),
+        };
+        if passed {
+            return Ok(());
+        }
+
+        let expectation = match &self.check {
+            AssertionCheck::Equals(expected) => format!("to equal {}", expected.to_json()),
+            AssertionCheck::OneOf(options) => format!(
+                "to be one of {}",
+                options
+                    .iter()
+                    .map(|option| option.to_json().to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            AssertionCheck::Matches(pattern, _) => format!("to match /{}/", pattern),
+            AssertionCheck::NotNull => "to not be null".to_string(),
+        };
+        Err(format!(
+            "Expected `{}` {}, got {}.",
+            self.path, expectation, actual
+        ))
+    }
+}
+
+/// Like `==`, but `1` and `1.0` are equal, and so are missing and null fields.
+fn json_eq(a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        (Value::Number(a), Value::Number(b)) => a.as_f64() == b.as_f64(),
+        (Value::Array(a), Value::Array(b)) => {
+            a.len() == b.len() && a.iter().zip(b).all(|(a, b)| json_eq(a, b))
+        }
+        (Value::Object(a), Value::Object(b)) => a.keys().chain(b.keys()).all(|key| {
+            json_eq(
+                a.get(key).unwrap_or(&Value::Null),
+                b.get(key).unwrap_or(&Value::Null),
+            )
+        }),
+        _ => a == b,
+    }
+}
+
+/// Parses the `assert` property of a test: a list of assertions, each with an optional `path` and
+/// exactly one check.
+pub(crate) fn parse_assertions(
+    value: &JsonValue,
+    errors: &mut Vec<DatamodelError>,
+) -> Vec<TestAssertion> {
+    let JsonKind::Array(items) = &value.kind else {
+        errors.push(DatamodelError::new_validation_error(
+            &format!("`assert` must be a list, got {}.", value.description()),
+            value.span.clone(),
+        ));
+        return vec![];
+    };
+
+    items
+        .iter()
+        .filter_map(|item| match parse_assertion(item) {
+            Ok(assertion) => Some(assertion),
+            Err(err) => {
+                errors.push(err);
+                None
+            }
+        })
+        .collect()
+}
+
+fn parse_assertion(item: &JsonValue) -> Result<TestAssertion, DatamodelError> {
+    let JsonKind::Object(entries) = &item.kind else {
+        return Err(DatamodelError::new_validation_error(
+            &format!(
+                "An assertion must be an object, got {}.",
+                item.description()
+            ),
+            item.span.clone(),
+        ));
+    };
+
+    let mut path = None;
+    let mut checks = vec![];
+    for (key, key_span, value) in entries {
+        match key.as_str() {
+            "path" => match &value.kind {
+                JsonKind::String(p) => path = Some(OutputPath::parse(p, value.span.clone())?),
+                _ => {
+                    return Err(DatamodelError::new_validation_error(
+                        &format!("`path` must be a string, got {}.", value.description()),
+                        value.span.clone(),
+                    ))
+                }
+            },
+            "equals" => checks.push(AssertionCheck::Equals(value.clone())),
+            "one_of" => match &value.kind {
+                JsonKind::Array(options) => checks.push(AssertionCheck::OneOf(options.clone())),
+                _ => {
+                    return Err(DatamodelError::new_validation_error(
+                        &format!("`one_of` must be a list, got {}.", value.description()),
+                        value.span.clone(),
+                    ))
+                }
+            },
+            "matches" => match &value.kind {
+                JsonKind::String(pattern) => match Regex::new(pattern) {
+                    Ok(regex) => checks.push(AssertionCheck::Matches(regex, value.span.clone())),
+                    Err(err) => {
+                        return Err(DatamodelError::new_validation_error(
+                            &format!("Invalid regex: {}", err),
+                            value.span.clone(),
+                        ))
+                    }
+                },
+                _ => {
+                    return Err(DatamodelError::new_validation_error(
+                        &format!("`matches` must be a string, got {}.", value.description()),
+                        value.span.clone(),
+                    ))
+                }
+            },
+            "not_null" => match &value.kind {
+                JsonKind::Bool(true) => checks.push(AssertionCheck::NotNull),
+                _ => {
+                    return Err(DatamodelError::new_validation_error(
+                        "`not_null` must be `true`.",
+                        value.span.clone(),
+                    ))
+                }
+            },
+            _ => {
+                return Err(DatamodelError::new_property_not_known_error(
+                    key,
+                    key_span.clone(),
+                    ASSERTION_PROPERTIES.to_vec(),
+                ))
+            }
+        }
+    }
+
+    if checks.len() != 1 {
+        return Err(DatamodelError::new_validation_error(
+            "An assertion needs exactly one of `equals`, `one_of`, `matches` or `not_null`.",
+            item.span.clone(),
+        ));
+    }
+    Ok(TestAssertion {
+        path: match path {
+            Some(path) => path,
+            None => OutputPath {
+                segments: vec![],
+                span: item.span.clone(),
+            },
+        },
+        check: checks.remove(0),
+        span: item.span.clone(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use internal_baml_diagnostics::SourceFile;
+    use serde_json::json;
+
+    use super::*;
+
+    fn span() -> Span {
+        Span::empty(SourceFile::from(("test.baml".into(), "")))
+    }
+
+    fn path(path: &str) -> Result<Vec<PathSegment>, String> {
+        OutputPath::parse(path, span())
+            .map(|p| p.segments)
+            .map_err(|e| e.message().to_string())
+    }
+
+    #[test]
+    fn parses_paths() {
+        use PathSegment::*;
+        assert_eq!(path("$"), Ok(vec![]));
+        assert_eq!(path(""), Ok(vec![]));
+        assert_eq!(
+            path("$.items[0].name"),
+            Ok(vec![Field("items".into()), Index(0), Field("name".into())])
+        );
+        assert_eq!(
+            path("items[\"a b\"]"),
+            Ok(vec![Field("items".into()), Field("a b".into())])
+        );
+        assert!(path("$.items[x]").is_err());
+        assert!(path("$items").is_err());
+        assert!(path("$.items.").is_err());
+    }
+
+    #[test]
+    fn evaluates_checks() {
+        let output = json!({ "mood": "Happy", "score": 1, "summary": "The end", "note": null });
+        let assertion = |path: &str, check| TestAssertion {
+            path: OutputPath::parse(path, span()).unwrap(),
+            check,
+            span: span(),
+        };
+        let value = |kind| JsonValue { kind, span: span() };
+
+        assert!(assertion(
+            "score",
+            AssertionCheck::Equals(value(JsonKind::Number("1.0".into())))
+        )
+        .evaluate(&output)
+        .is_ok());
+        assert!(assertion(
+            "$.mood",
+            AssertionCheck::OneOf(vec![value(JsonKind::String("Happy".into()))])
+        )
+        .evaluate(&output)
+        .is_ok());
+        assert!(assertion(
+            "$.summary",
+            AssertionCheck::Matches(Regex::new("^The").unwrap(), span())
+        )
+        .evaluate(&output)
+        .is_ok());
+        assert_eq!(
+            assertion("$.note", AssertionCheck::NotNull).evaluate(&output),
+            Err("Expected `$.note` to not be null, got null.".into())
+        );
+        assert_eq!(
+            assertion("$.missing", AssertionCheck::NotNull).evaluate(&output),
+            Err("Expected `$.missing` to not be null, got null.".into())
+        );
+    }
+}
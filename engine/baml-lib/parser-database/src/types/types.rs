@@ -2,6 +2,8 @@ use std::collections::HashMap;
 
 use internal_baml_schema_ast::ast::{EnumValueId, FieldId, SerializerFieldId, VariantSerializerId};
 
+use crate::interner::StringId;
+
 use super::to_string_attributes::ToStringAttributes;
 
 #[derive(Debug, Default)]
@@ -16,6 +18,9 @@ pub struct ClassAttributes {
     pub field_serilizers: HashMap<FieldId, ToStringAttributes>,
 
     pub serilizer: Option<ToStringAttributes>,
+
+    /// The field named by `@@discriminator`, which tells the class apart in a union.
+    pub discriminator: Option<StringId>,
 }
 
 #[derive(Debug, Default)]
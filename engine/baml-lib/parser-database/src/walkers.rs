@@ -12,6 +12,7 @@ mod configuration;
 mod r#enum;
 mod field;
 mod function;
+mod type_alias;
 mod variants;
 
 pub use client::*;
@@ -22,6 +23,7 @@ pub use function::*;
 use internal_baml_schema_ast::ast::{Identifier, TopId, WithName};
 pub use r#class::*;
 pub use r#enum::*;
+pub use type_alias::*;
 pub use variants::*;
 
 /// A generic walker. Only walkers intantiated with a concrete ID type (`I`) are useful.
@@ -87,6 +89,21 @@ impl<'db> crate::ParserDatabase {
         }
     }
 
+    /// Find a type alias by name.
+    pub fn find_type_alias_by_str(&'db self, name: &str) -> Option<TypeAliasWalker<'db>> {
+        self.find_top_by_str(name)
+            .and_then(|top_id| top_id.as_type_alias_id())
+            .map(|alias_id| self.walk(alias_id))
+    }
+
+    /// Find a type alias by name.
+    pub fn find_type_alias(&'db self, idn: &Identifier) -> Option<TypeAliasWalker<'db>> {
+        match idn {
+            Identifier::Local(local, _) => self.find_type_alias_by_str(local),
+            _ => None,
+        }
+    }
+
     /// Find a model by name.
     pub fn find_class(&'db self, idn: &Identifier) -> Option<ClassWalker<'db>> {
         self.find_type(idn).and_then(|either| match either {
@@ -141,6 +158,7 @@ impl<'db> crate::ParserDatabase {
     pub fn valid_type_names(&'db self) -> Vec<String> {
         let mut names: Vec<String> = self.walk_classes().map(|c| c.name().to_string()).collect();
         names.extend(self.walk_enums().map(|e| e.name().to_string()));
+        names.extend(self.walk_type_aliases().map(|a| a.name().to_string()));
         // Add primitive types
         names.extend(
             vec!["string", "int", "float", "bool"]
@@ -196,6 +214,17 @@ impl<'db> crate::ParserDatabase {
             })
     }
 
+    /// Walk all type aliases in the schema.
+    pub fn walk_type_aliases(&self) -> impl Iterator<Item = TypeAliasWalker<'_>> {
+        self.ast()
+            .iter_tops()
+            .filter_map(|(top_id, _)| top_id.as_type_alias_id())
+            .map(move |top_id| Walker {
+                db: self,
+                id: top_id,
+            })
+    }
+
     /// Walk all classes in the schema.
     pub fn walk_functions(&self) -> impl Iterator<Item = FunctionWalker<'_>> {
         self.ast()
@@ -8,12 +8,12 @@ use serde_json::json;
 
 use crate::{
     ast::{self, WithName, WithSpan},
-    printer::{serialize_with_printer, WithSerializeableContent, WithStaticRenames},
+    printer::{serialize_with_printer, Expanding, WithSerializeableContent, WithStaticRenames},
     types::ToStringAttributes,
     WithSerialize,
 };
 
-use super::{field::FieldWalker, EnumWalker, VariantWalker};
+use super::{field::FieldWalker, EnumWalker, TypeAliasWalker, VariantWalker};
 
 /// A `class` declaration in the Prisma schema.
 pub type ClassWalker<'db> = super::Walker<'db, ast::ClassId>;
@@ -75,6 +75,13 @@ impl<'db> ClassWalker<'db> {
             })
     }
 
+    /// Find all type aliases used by this class and any of its fields.
+    pub fn required_type_aliases(self) -> impl Iterator<Item = TypeAliasWalker<'db>> {
+        self.db.types.class_dependencies[&self.class_id()]
+            .iter()
+            .filter_map(|f| self.db.find_type_alias_by_str(f))
+    }
+
     /// Find all classes used by this class and any of its fields.
     pub fn required_classes(self) -> impl Iterator<Item = ClassWalker<'db>> {
         self.db.types.class_dependencies[&self.class_id()]
@@ -85,6 +92,18 @@ impl<'db> ClassWalker<'db> {
                 None => None,
             })
     }
+
+    /// The field named by `@@discriminator`, whose value tells the class apart from the other
+    /// members of a union.
+    pub fn discriminator(self) -> Option<FieldWalker<'db>> {
+        let name = self
+            .db
+            .types
+            .class_attributes
+            .get(&self.id)?
+            .discriminator?;
+        self.static_fields().find(|f| f.name() == &self.db[name])
+    }
 }
 
 impl<'db> WithIdentifier for ClassWalker<'db> {
@@ -101,13 +120,41 @@ impl<'db> WithSpan for ClassWalker<'db> {
 
 impl<'db> WithSerializeableContent for ClassWalker<'db> {
     fn serialize_data(&self, variant: &VariantWalker<'_>) -> serde_json::Value {
+        self.serialize_expanding(variant, &mut vec![])
+    }
+}
+
+impl<'db> ClassWalker<'db> {
+    /// Serializes the class, unless it is in `expanding`: a class that contains itself is only
+    /// expanded once, and is referred to by name within itself.
+    pub(crate) fn serialize_expanding(
+        &self,
+        variant: &VariantWalker<'_>,
+        expanding: &mut Expanding,
+    ) -> serde_json::Value {
+        if let Some((_, referred)) = expanding.iter_mut().find(|(id, _)| *id == self.id) {
+            *referred = true;
+            return json!({
+                "rtype": "class_ref",
+                "optional": false,
+                "name": self.alias(variant),
+            });
+        }
+
+        expanding.push((self.id, false));
+        let fields = self
+            .static_fields()
+            .map(|f| f.serialize_expanding(variant, expanding))
+            .collect::<Vec<_>>();
+        let recursive = expanding.pop().is_some_and(|(_, referred)| referred);
         json!({
             "rtype": "class",
             "optional": false,
             "name": self.alias(variant),
             "meta": self.meta(variant),
-            "fields": self.static_fields().map(|f| f.serialize_data(variant)).collect::<Vec<_>>(),
+            "fields": fields,
             // Dynamic fields are not serialized.
+            "recursive": recursive,
         })
     }
 }
@@ -1,7 +1,7 @@
 use std::ops::Deref;
 
 use crate::{
-    printer::{WithSerializeableContent, WithStaticRenames},
+    printer::{Expanding, WithSerializeableContent, WithStaticRenames},
     types::{DynamicStringAttributes, StaticStringAttributes, ToStringAttributes},
     ParserDatabase,
 };
@@ -77,62 +77,107 @@ impl<'db> WithName for FieldWalker<'db> {
 
 impl<'db> WithSerializeableContent for (&ParserDatabase, &FieldType) {
     fn serialize_data(&self, variant: &VariantWalker<'_>) -> serde_json::Value {
-        match self.1 {
-            FieldType::Tuple(..) | FieldType::Dictionary(..) => json!({
-                "rtype": "unsupported",
-                "optional": false,
-            }),
-            FieldType::Union(airty, fts, _) => json!({
-                "rtype": "union",
-                "optional": airty.is_optional(),
-                "options": fts.iter().map(|ft| (self.0, ft).serialize_data(variant)).collect::<Vec<_>>(),
-            }),
-            FieldType::List(ft, dims, _) => json!({
-                "rtype": "list",
-                "dims": dims,
-                "inner": (self.0, ft.deref()).serialize_data(variant),
-            }),
-            FieldType::Identifier(arity, Identifier::Primitive(name, ..)) => {
-                json!({
-                    "rtype": "primitive",
-                    "optional": arity.is_optional(),
-                    "value": match name {
-                        ast::TypeValue::Bool => "bool",
-                        ast::TypeValue::Int => "int",
-                        ast::TypeValue::Float => "float",
-                        ast::TypeValue::Char => "char",
-                        ast::TypeValue::String => "string",
-                        ast::TypeValue::Null => "null",
+        serialize_type(self.0, self.1, variant, &mut vec![])
+    }
+}
+
+/// Serializes `field_type`, with the classes in `expanding` referred to by name.
+pub(crate) fn serialize_type(
+    db: &ParserDatabase,
+    field_type: &FieldType,
+    variant: &VariantWalker<'_>,
+    expanding: &mut Expanding,
+) -> serde_json::Value {
+    match field_type {
+        FieldType::Tuple(..) | FieldType::Dictionary(..) => json!({
+            "rtype": "unsupported",
+            "optional": false,
+        }),
+        FieldType::Union(airty, fts, _) => json!({
+            "rtype": "union",
+            "optional": airty.is_optional(),
+            "options": fts.iter().map(|ft| serialize_type(db, ft, variant, expanding)).collect::<Vec<_>>(),
+        }),
+        FieldType::List(ft, dims, _) => json!({
+            "rtype": "list",
+            "dims": dims,
+            "inner": serialize_type(db, ft.deref(), variant, expanding),
+        }),
+        FieldType::Identifier(arity, Identifier::Primitive(name, ..)) => {
+            json!({
+                "rtype": "primitive",
+                "optional": arity.is_optional(),
+                "value": match name {
+                    ast::TypeValue::Bool => "bool",
+                    ast::TypeValue::Int => "int",
+                    ast::TypeValue::Float => "float",
+                    ast::TypeValue::Char => "char",
+                    ast::TypeValue::String => "string",
+                    ast::TypeValue::Null => "null",
+                }
+            })
+        }
+        FieldType::Literal(arity, value, _) => json!({
+            "rtype": "literal",
+            "optional": arity.is_optional(),
+            "value": value,
+        }),
+        FieldType::Identifier(arity, Identifier::Local(name, ..)) => {
+            match db.find_type_by_str(name) {
+                Some(either::Either::Left(cls)) => {
+                    let mut data = cls.serialize_expanding(variant, expanding);
+                    if arity.is_optional() {
+                        if let Some(data) = data.as_object_mut() {
+                            data.insert("optional".into(), true.into());
+                        }
                     }
-                })
-            }
-            FieldType::Identifier(arity, Identifier::Local(name, ..)) => {
-                match self.0.find_type_by_str(name) {
-                    Some(either::Either::Left(cls)) => cls.serialize_data(variant),
-                    Some(either::Either::Right(enm)) => {
-                        json!({
-                            "rtype": "enum",
-                            "optional": arity.is_optional(),
-                            "name": enm.alias(variant),
-                        })
+                    data
+                }
+                Some(either::Either::Right(enm)) => {
+                    json!({
+                        "rtype": "enum",
+                        "optional": arity.is_optional(),
+                        "name": enm.alias(variant),
+                    })
+                }
+                None => match db.find_type_alias_by_str(name) {
+                    Some(alias) => {
+                        let mut data = serialize_type(db, alias.target(), variant, expanding);
+                        if arity.is_optional() {
+                            if let Some(data) = data.as_object_mut() {
+                                data.insert("optional".into(), true.into());
+                            }
+                        }
+                        data
                     }
                     None => json!({
                         "rtype": "unsupported",
                         "optional": false,
                     }),
-                }
+                },
             }
-            FieldType::Identifier(..) => serde_json::Value::Null,
         }
+        FieldType::Identifier(..) => serde_json::Value::Null,
     }
 }
 
 impl<'db> WithSerializeableContent for FieldWalker<'db> {
     fn serialize_data(&self, variant: &VariantWalker<'_>) -> serde_json::Value {
+        self.serialize_expanding(variant, &mut vec![])
+    }
+}
+
+impl<'db> FieldWalker<'db> {
+    /// Serializes the field, with the classes in `expanding` referred to by name.
+    pub(crate) fn serialize_expanding(
+        &self,
+        variant: &VariantWalker<'_>,
+        expanding: &mut Expanding,
+    ) -> serde_json::Value {
         json!({
             "name": self.alias(variant),
             "meta": self.meta(variant),
-            "type_meta": (self.db, self.r#type()).serialize_data(variant),
+            "type_meta": serialize_type(self.db, self.r#type(), variant, expanding),
         })
     }
 }
@@ -11,7 +11,9 @@ use crate::{
     WithSerialize,
 };
 
-use super::{ClassWalker, ConfigurationWalker, EnumWalker, VariantWalker, Walker};
+use super::{
+    ClassWalker, ConfigurationWalker, EnumWalker, TypeAliasWalker, VariantWalker, Walker,
+};
 
 use std::iter::ExactSizeIterator;
 
@@ -211,6 +213,14 @@ impl<'db> ArgWalker<'db> {
                 None => None,
             })
     }
+
+    /// The type aliases this argument uses.
+    pub fn required_type_aliases(self) -> impl Iterator<Item = TypeAliasWalker<'db>> {
+        let (input, output) = &self.db.types.function[&self.function_id()].dependencies;
+        if self.id.1 { input } else { output }
+            .iter()
+            .filter_map(|f| self.db.find_type_alias_by_str(f))
+    }
 }
 
 impl<'db> WithSerializeableContent for ArgWalker<'db> {
@@ -0,0 +1,78 @@
+use std::collections::HashSet;
+
+use either::Either;
+use internal_baml_schema_ast::ast::{WithDocumentation, WithIdentifier, WithSpan};
+
+use crate::ast;
+
+use super::{ClassWalker, EnumWalker};
+
+/// A `type` alias declaration in the schema.
+pub type TypeAliasWalker<'db> = super::Walker<'db, ast::TypeAliasId>;
+
+impl<'db> TypeAliasWalker<'db> {
+    /// The ID of the type alias in the db
+    pub fn type_alias_id(self) -> ast::TypeAliasId {
+        self.id
+    }
+
+    /// The AST node.
+    pub fn ast_type_alias(self) -> &'db ast::TypeAlias {
+        &self.db.ast[self.id]
+    }
+
+    /// The type the alias stands for.
+    pub fn target(self) -> &'db ast::FieldType {
+        self.ast_type_alias().value()
+    }
+
+    /// The names of the classes, enums and aliases the target refers to directly.
+    pub fn dependencies(self) -> &'db HashSet<String> {
+        &self.db.types.type_alias_dependencies[&self.id]
+    }
+
+    /// Find all enums used by the target of this alias.
+    pub fn required_enums(self) -> impl Iterator<Item = EnumWalker<'db>> {
+        self.dependencies()
+            .iter()
+            .filter_map(|f| match self.db.find_type_by_str(f) {
+                Some(Either::Right(walker)) => Some(walker),
+                _ => None,
+            })
+    }
+
+    /// Find all classes used by the target of this alias.
+    pub fn required_classes(self) -> impl Iterator<Item = ClassWalker<'db>> {
+        self.dependencies()
+            .iter()
+            .filter_map(|f| match self.db.find_type_by_str(f) {
+                Some(Either::Left(walker)) => Some(walker),
+                _ => None,
+            })
+    }
+
+    /// Find all other aliases used by the target of this alias.
+    pub fn required_type_aliases(self) -> impl Iterator<Item = TypeAliasWalker<'db>> {
+        self.dependencies()
+            .iter()
+            .filter_map(|f| self.db.find_type_alias_by_str(f))
+    }
+}
+
+impl<'db> WithIdentifier for TypeAliasWalker<'db> {
+    fn identifier(&self) -> &ast::Identifier {
+        self.ast_type_alias().identifier()
+    }
+}
+
+impl<'db> WithSpan for TypeAliasWalker<'db> {
+    fn span(&self) -> &internal_baml_diagnostics::Span {
+        self.ast_type_alias().span()
+    }
+}
+
+impl<'db> WithDocumentation for TypeAliasWalker<'db> {
+    fn documentation(&self) -> Option<&str> {
+        self.ast_type_alias().documentation()
+    }
+}
@@ -5,7 +5,7 @@ use crate::{
     types::{PromptRepr, ToStringAttributes, VariantProperties},
 };
 
-use super::{ClassWalker, ClientWalker, EnumWalker, FunctionWalker, Walker};
+use super::{ClassWalker, ClientWalker, EnumWalker, FunctionWalker, TypeAliasWalker, Walker};
 
 /// A `function` declaration in the Prisma schema.
 pub type VariantWalker<'db> = Walker<'db, ast::VariantConfigId>;
@@ -114,6 +114,29 @@ impl<'db> VariantWalker<'db> {
             .into_iter()
     }
 
+    /// The type aliases the output of this impl uses.
+    pub fn output_required_type_aliases(self) -> impl Iterator<Item = TypeAliasWalker<'db>> {
+        if let Some((idx, _)) = self.properties().output_adapter {
+            let adapter = &self.ast_variant()[idx];
+
+            return adapter
+                .from
+                .flat_idns()
+                .iter()
+                .filter_map(|f| self.db.find_type_alias(f))
+                .collect::<Vec<_>>()
+                .into_iter();
+        }
+
+        self.walk_function()
+            .unwrap()
+            .walk_output_args()
+            .map(|f| f.required_type_aliases())
+            .flatten()
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
     /// The name of the function.
     pub fn output_required_classes(self) -> impl Iterator<Item = ClassWalker<'db>> {
         if let Some((idx, _)) = self.properties().output_adapter {
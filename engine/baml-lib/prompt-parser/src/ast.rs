@@ -6,12 +6,12 @@ mod traits;
 mod variable;
 mod white_space_text;
 
-pub use code_block::{ChatBlock, CodeBlock, CodeBlockId, PrinterBlock};
+pub use code_block::{ChatBlock, CodeBlock, CodeBlockId, ForBlock, PrinterBlock};
 pub use comment_block::{CommentBlock, CommentBlockId};
 pub use internal_baml_diagnostics::Span;
 pub use prompt_text::{PromptText, PromptTextId};
 pub use top::Top;
-pub use variable::{Variable, VariableId};
+pub use variable::{Variable, VariableId, VariableSegment};
 pub use white_space_text::{WhiteSpace, WhiteSpaceId};
 
 pub use traits::{WithDocumentation, WithName, WithSpan};
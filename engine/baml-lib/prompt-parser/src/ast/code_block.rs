@@ -22,6 +22,15 @@ pub enum CodeBlock {
     PrintType(PrinterBlock),
     Variable(Variable),
     Chat(ChatBlock),
+    /// `{#if input.x}`: the text up to the matching `{#else}` or `{#end}` is only used if
+    /// `input.x` is set.
+    If(Variable),
+    /// `{#else}`
+    Else(Span),
+    /// `{#for item in input.list}`
+    For(ForBlock),
+    /// `{#end}`: closes the innermost `{#if}` or `{#for}`.
+    End(Span),
 }
 
 impl CodeBlock {
@@ -31,6 +40,10 @@ impl CodeBlock {
             CodeBlock::PrintType(printer_block) => printer_block.target.text.as_str(),
             CodeBlock::Variable(variable) => variable.text.as_str(),
             CodeBlock::Chat(chat_block) => chat_block.role.0.as_str(),
+            CodeBlock::If(condition) => condition.text.as_str(),
+            CodeBlock::Else(_) => "else",
+            CodeBlock::For(for_block) => for_block.collection.text.as_str(),
+            CodeBlock::End(_) => "end",
         }
     }
 }
@@ -83,6 +96,22 @@ impl WithSpan for ChatBlock {
     }
 }
 
+/// `{#for item in collection}`: repeats the text up to the matching `{#end}` for every item of
+/// `collection`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ForBlock {
+    /// The name each item goes by inside the loop.
+    pub item: (String, Span),
+    pub collection: Variable,
+    pub span: Span,
+}
+
+impl WithSpan for ForBlock {
+    fn span(&self) -> &Span {
+        &self.span
+    }
+}
+
 impl WithSpan for CodeBlock {
     fn span(&self) -> &Span {
         match self {
@@ -90,6 +119,9 @@ impl WithSpan for CodeBlock {
             CodeBlock::PrintEnum(v) => v.span(),
             CodeBlock::PrintType(v) => v.span(),
             CodeBlock::Chat(v) => v.span(),
+            CodeBlock::If(v) => v.span(),
+            CodeBlock::Else(span) | CodeBlock::End(span) => span,
+            CodeBlock::For(v) => v.span(),
         }
     }
 }
@@ -19,6 +19,10 @@ impl Top {
             Top::CodeBlock(CodeBlock::PrintEnum(..)) => "print_enum",
             Top::CodeBlock(CodeBlock::PrintType(..)) => "print_type",
             Top::CodeBlock(CodeBlock::Chat(..)) => "chat",
+            Top::CodeBlock(CodeBlock::If(..)) => "if",
+            Top::CodeBlock(CodeBlock::Else(..)) => "else",
+            Top::CodeBlock(CodeBlock::For(..)) => "for",
+            Top::CodeBlock(CodeBlock::End(..)) => "end",
             Top::WhiteSpace(..) => "white_space",
         }
     }
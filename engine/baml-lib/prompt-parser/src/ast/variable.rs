@@ -14,18 +14,111 @@ impl VariableId {
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Variable {
-    /// Entire unparsed text of the variable.  (input.something.bar)
+    /// Entire unparsed text of the variable.  (input.something[0].bar)
     pub text: String,
-    /// [input, something, bar]
-    pub path: Vec<String>,
+    /// [input, something, [0], bar]
+    pub path: Vec<VariableSegment>,
     pub span: Span,
 }
 
+/// One step of a variable's path.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum VariableSegment {
+    /// `name`, or `.name` after the first segment.
+    Field(String),
+    /// `[0]`: an item of a list.
+    Index(usize),
+    /// `["key"]`: a value of a map.
+    Key(String),
+    /// `[*]`: every item of a list, or every value of a map.
+    Each,
+}
+
+impl VariableSegment {
+    /// The name of a `.name` segment.
+    pub fn as_field(&self) -> Option<&str> {
+        match self {
+            VariableSegment::Field(name) => Some(name),
+            _ => None,
+        }
+    }
+
+    /// Writes a path the way it is written in a prompt, e.g. `arg.items[0]["key"]`.
+    pub fn format_path(path: &[VariableSegment]) -> String {
+        let mut text = String::new();
+        for segment in path {
+            match segment {
+                VariableSegment::Field(name) if text.is_empty() => text += name,
+                VariableSegment::Field(name) => text += &format!(".{}", name),
+                VariableSegment::Index(idx) => text += &format!("[{}]", idx),
+                VariableSegment::Key(key) => text += &format!("[\"{}\"]", key),
+                VariableSegment::Each => text += "[*]",
+            }
+        }
+        text
+    }
+
+    /// Reads back a path written by [`VariableSegment::format_path`].
+    pub fn parse_path(text: &str) -> Option<Vec<VariableSegment>> {
+        let mut path = vec![];
+        let mut rest = text;
+        while !rest.is_empty() {
+            if let Some(index) = rest.strip_prefix('[') {
+                let (index, after) = index.split_once(']')?;
+                path.push(match index {
+                    "*" => VariableSegment::Each,
+                    _ => match index.strip_prefix('"').and_then(|i| i.strip_suffix('"')) {
+                        Some(key) => VariableSegment::Key(key.to_string()),
+                        None => VariableSegment::Index(index.parse().ok()?),
+                    },
+                });
+                rest = after;
+            } else {
+                let field = match rest.strip_prefix('.') {
+                    Some(field) => field,
+                    None if path.is_empty() => rest,
+                    None => return None,
+                };
+                let end = field.find(['.', '[']).unwrap_or(field.len());
+                if end == 0 {
+                    return None;
+                }
+                path.push(VariableSegment::Field(field[..end].to_string()));
+                rest = &field[end..];
+            }
+        }
+        Some(path)
+    }
+}
+
 impl Variable {
     /// Unique Key
     pub fn key(&self) -> String {
         format!("{{//BAML_CLIENT_REPLACE_ME_MAGIC_{}//}}", self.text)
     }
+
+    /// The span of each segment of the path.
+    pub fn segment_spans(&self) -> Vec<Span> {
+        let text = &self.span.file.as_str()[self.span.start..self.span.end];
+        let mut cursor = 0;
+        let mut spans = vec![];
+        for segment in &self.path {
+            let written = match segment {
+                VariableSegment::Field(name) => name.clone(),
+                other => VariableSegment::format_path(std::slice::from_ref(other)),
+            };
+            let Some(start) = text[cursor..].find(&written).map(|i| i + cursor) else {
+                break;
+            };
+            cursor = start + written.len();
+            spans.push(Span::new(
+                self.span.file.clone(),
+                self.span.start + start,
+                self.span.start + cursor,
+            ));
+        }
+        spans
+    }
 }
 
 impl Hash for Variable {
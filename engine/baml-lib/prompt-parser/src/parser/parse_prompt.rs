@@ -69,7 +69,7 @@ pub fn parse_prompt(
                                 ),
                                 Rule::dangling_code_block => {
                                     diagnostics.push_error(DatamodelError::new_parser_error(
-                                        "{#input..} or {#print_enum(..)} or {#print_type(..)} or {// some comment //}".to_string(),
+                                        "{#input..} or {#print_enum(..)} or {#print_type(..)} or {#if ..} or {#for .. in ..} or {// some comment //}".to_string(),
                                         raw_string.to_raw_span(inner.as_span()),
                                     ));
                                 }
@@ -131,6 +131,15 @@ fn handle_code_block(
                 raw_string,
                 num_chat_blocks,
             ),
+            Rule::if_block | Rule::for_block => {
+                handle_control_block(current, top_level_definitions, diagnostics, raw_string)
+            }
+            Rule::else_block => top_level_definitions.push(Top::CodeBlock(CodeBlock::Else(
+                raw_string.to_raw_span(current.as_span()),
+            ))),
+            Rule::end_block => top_level_definitions.push(Top::CodeBlock(CodeBlock::End(
+                raw_string.to_raw_span(current.as_span()),
+            ))),
             Rule::WHITESPACE => {}
             _ => unreachable_rule!(current, Rule::code_block),
         }
@@ -143,6 +152,53 @@ fn handle_variable(
     diagnostics: &mut Diagnostics,
     raw_string: &RawString,
 ) {
+    if let Some(variable) = parse_variable(current, diagnostics, raw_string) {
+        top_level_definitions.push(Top::CodeBlock(CodeBlock::Variable(variable)));
+    }
+}
+
+fn handle_control_block(
+    current: pest::iterators::Pair<'_, Rule>,
+    top_level_definitions: &mut Vec<Top>,
+    diagnostics: &mut Diagnostics,
+    raw_string: &RawString,
+) {
+    let span = raw_string.to_raw_span(current.as_span());
+    let is_if = current.as_rule() == Rule::if_block;
+
+    let mut item = None;
+    let mut collection = None;
+    for inner in current.into_inner() {
+        match inner.as_rule() {
+            Rule::identifier => {
+                item = Some((
+                    inner.as_str().to_string(),
+                    raw_string.to_raw_span(inner.as_span()),
+                ))
+            }
+            Rule::variable => collection = parse_variable(inner, diagnostics, raw_string),
+            Rule::WHITESPACE => {}
+            _ => unreachable_rule!(inner, Rule::for_block),
+        }
+    }
+
+    let block = match (is_if, item, collection) {
+        (true, _, Some(condition)) => CodeBlock::If(condition),
+        (false, Some(item), Some(collection)) => CodeBlock::For(ForBlock {
+            item,
+            collection,
+            span,
+        }),
+        _ => return,
+    };
+    top_level_definitions.push(Top::CodeBlock(block));
+}
+
+fn parse_variable(
+    current: pest::iterators::Pair<'_, Rule>,
+    diagnostics: &mut Diagnostics,
+    raw_string: &RawString,
+) -> Option<Variable> {
     assert_correct_parser!(current, Rule::variable);
 
     let span = raw_string.to_raw_span(current.as_span());
@@ -150,10 +206,12 @@ fn handle_variable(
 
     let type_path = current
         .into_inner()
-        .filter_map(|inner| {
-            if let Rule::identifier = inner.as_rule() {
-                Some(inner.as_str().trim_end().to_string())
-            } else {
+        .filter_map(|inner| match inner.as_rule() {
+            Rule::identifier => Some(VariableSegment::Field(
+                inner.as_str().trim_end().to_string(),
+            )),
+            Rule::index => handle_index(inner, diagnostics, raw_string),
+            _ => {
                 diagnostics.push_error(DatamodelError::new_parser_error(
                     format!("Unexpected rule: {:?}", inner.as_rule()),
                     raw_string.to_raw_span(inner.as_span()),
@@ -168,14 +226,42 @@ fn handle_variable(
             "Missing type name".to_string(),
             span.clone(),
         ));
-        return;
+        return None;
     }
 
-    top_level_definitions.push(Top::CodeBlock(CodeBlock::Variable(Variable {
+    Some(Variable {
         path: type_path,
         text: raw_text,
         span,
-    })));
+    })
+}
+
+fn handle_index(
+    current: pest::iterators::Pair<'_, Rule>,
+    diagnostics: &mut Diagnostics,
+    raw_string: &RawString,
+) -> Option<VariableSegment> {
+    assert_correct_parser!(current, Rule::index);
+
+    let inner = current.into_inner().next()?;
+    match inner.as_rule() {
+        Rule::index_number => match inner.as_str().parse() {
+            Ok(idx) => Some(VariableSegment::Index(idx)),
+            Err(_) => {
+                diagnostics.push_error(DatamodelError::new_parser_error(
+                    format!("Index `{}` is too large", inner.as_str()),
+                    raw_string.to_raw_span(inner.as_span()),
+                ));
+                None
+            }
+        },
+        Rule::index_key => {
+            let key = inner.as_str();
+            Some(VariableSegment::Key(key[1..key.len() - 1].to_string()))
+        }
+        Rule::index_each => Some(VariableSegment::Each),
+        _ => unreachable_rule!(inner, Rule::index),
+    }
 }
 
 fn handle_print_block(
@@ -252,7 +338,7 @@ fn handle_print_block(
         (Some(true), Some((argument, arg_span))) => Some(CodeBlock::PrintEnum(PrinterBlock {
             printer,
             target: Variable {
-                path: vec![argument.clone()],
+                path: vec![VariableSegment::Field(argument.clone())],
                 text: argument.clone(),
                 span: arg_span.clone(),
             },
@@ -260,7 +346,7 @@ fn handle_print_block(
         (Some(false), Some((argument, arg_span))) => Some(CodeBlock::PrintType(PrinterBlock {
             printer,
             target: Variable {
-                path: vec![argument.clone()],
+                path: vec![VariableSegment::Field(argument.clone())],
                 text: argument.clone(),
                 span: arg_span.clone(),
             },
@@ -311,8 +397,8 @@ fn parse_arg_list(
         if current.as_rule() == Rule::WHITESPACE {
             continue;
         }
-        assert_correct_parser!(current, Rule::variable);
-        // For every variable in the arg list
+        assert_correct_parser!(current, Rule::type_name);
+        // For every type in the arg list
         current
             .into_inner()
             .for_each(|inner| match inner.as_rule() {
@@ -323,7 +409,7 @@ fn parse_arg_list(
                     ));
                 }
                 Rule::WHITESPACE => {}
-                _ => unreachable_rule!(inner, Rule::variable),
+                _ => unreachable_rule!(inner, Rule::type_name),
             });
     }
 
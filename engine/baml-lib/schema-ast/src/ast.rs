@@ -20,9 +20,11 @@ mod retry_policy_config;
 mod serializer;
 mod top;
 mod traits;
+mod type_alias;
 mod variant;
 
 pub(crate) use self::comment::Comment;
+pub(crate) use self::expression::dedent;
 
 pub use adapter::Adapter;
 pub use argument::{ArguementId, Argument, ArgumentsList};
@@ -31,7 +33,7 @@ pub use client::Client;
 pub use config::ConfigBlockProperty;
 pub use configurations::Configuration;
 pub use expression::{Expression, RawString};
-pub use field::{Field, FieldArity, FieldType};
+pub use field::{Field, FieldArity, FieldType, LiteralValue};
 pub use find_at_position::*;
 pub use function::{FuncArguementId, Function, FunctionArg, FunctionArgs, NamedFunctionArgList};
 pub use generator_config::GeneratorConfig;
@@ -46,6 +48,7 @@ pub use retry_policy_config::RetryPolicyConfig;
 pub use serializer::{Serializer, SerializerField, SerializerFieldId};
 pub use top::Top;
 pub use traits::{WithAttributes, WithDocumentation, WithIdentifier, WithName, WithSpan};
+pub use type_alias::TypeAlias;
 pub use variant::{
     AdapterId, FieldId as VariantFieldId, SerializerId as VariantSerializerId, Variant,
 };
@@ -107,6 +110,17 @@ impl std::ops::Index<ClassId> for SchemaAst {
     }
 }
 
+/// An opaque identifier for a type alias in a schema AST.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TypeAliasId(u32);
+impl std::ops::Index<TypeAliasId> for SchemaAst {
+    type Output = TypeAlias;
+
+    fn index(&self, index: TypeAliasId) -> &Self::Output {
+        self.tops[index.0 as usize].as_type_alias().unwrap()
+    }
+}
+
 /// An opaque identifier for a model in a schema AST. Use the
 /// `schema[model_id]` syntax to resolve the id to an `ast::Model`.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -177,6 +191,9 @@ pub enum TopId {
     // A class declaration
     Class(ClassId),
 
+    // A type alias declaration
+    TypeAlias(TypeAliasId),
+
     // A function declaration
     Function(FunctionId),
 
@@ -217,6 +234,14 @@ impl TopId {
         }
     }
 
+    /// Try to interpret the top as a type alias.
+    pub fn as_type_alias_id(self) -> Option<TypeAliasId> {
+        match self {
+            TopId::TypeAlias(id) => Some(id),
+            _ => None,
+        }
+    }
+
     /// Try to interpret the top as a function.
     pub fn as_function_id(self) -> Option<FunctionId> {
         match self {
@@ -261,6 +286,7 @@ impl std::ops::Index<TopId> for SchemaAst {
         let idx = match index {
             TopId::Enum(EnumId(idx)) => idx,
             TopId::Class(ClassId(idx)) => idx,
+            TopId::TypeAlias(TypeAliasId(idx)) => idx,
             TopId::Function(FunctionId(idx)) => idx,
             TopId::Client(ClientId(idx)) => idx,
             TopId::Generator(GeneratorConfigId(idx)) => idx,
@@ -276,6 +302,7 @@ fn top_idx_to_top_id(top_idx: usize, top: &Top) -> TopId {
     match top {
         Top::Enum(_) => TopId::Enum(EnumId(top_idx as u32)),
         Top::Class(_) => TopId::Class(ClassId(top_idx as u32)),
+        Top::TypeAlias(_) => TopId::TypeAlias(TypeAliasId(top_idx as u32)),
         Top::Function(_) => TopId::Function(FunctionId(top_idx as u32)),
         Top::Client(_) => TopId::Client(ClientId(top_idx as u32)),
         Top::Generator(_) => TopId::Generator(GeneratorConfigId(top_idx as u32)),
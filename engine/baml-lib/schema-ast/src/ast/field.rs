@@ -108,9 +108,40 @@ impl FieldArity {
     }
 }
 
+/// A value that stands for a type of its own, e.g. the `"ok"` in `status "ok" | "error"`.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+#[serde(untagged)]
+pub enum LiteralValue {
+    String(String),
+    Int(i64),
+    Bool(bool),
+}
+
+impl LiteralValue {
+    /// The primitive type the value belongs to.
+    pub fn type_value(&self) -> TypeValue {
+        match self {
+            LiteralValue::String(_) => TypeValue::String,
+            LiteralValue::Int(_) => TypeValue::Int,
+            LiteralValue::Bool(_) => TypeValue::Bool,
+        }
+    }
+}
+
+impl std::fmt::Display for LiteralValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LiteralValue::String(s) => write!(f, "{:?}", s),
+            LiteralValue::Int(i) => write!(f, "{}", i),
+            LiteralValue::Bool(b) => write!(f, "{}", b),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum FieldType {
     Identifier(FieldArity, Identifier),
+    Literal(FieldArity, LiteralValue, Span),
     // The second field is the number of dims for the list
     List(Box<FieldType>, u32, Span),
     Tuple(FieldArity, Vec<FieldType>, Span),
@@ -123,6 +154,7 @@ impl FieldType {
     pub fn span(&self) -> &Span {
         match self {
             FieldType::Identifier(.., idn) => idn.span(),
+            FieldType::Literal(.., span) => span,
             FieldType::Union(.., span) => span,
             FieldType::Tuple(.., span) => span,
             FieldType::Dictionary(.., span) => span,
@@ -141,6 +173,11 @@ impl FieldType {
             FieldType::Identifier(_arity, idn) => {
                 Ok(FieldType::Identifier(FieldArity::Optional, idn.to_owned()))
             }
+            FieldType::Literal(_arity, value, span) => Ok(FieldType::Literal(
+                FieldArity::Optional,
+                value.to_owned(),
+                span.to_owned(),
+            )),
             FieldType::Union(arity, items, span) => {
                 let mut items = items.clone();
 
@@ -175,6 +212,7 @@ impl FieldType {
                         _ => false,
                     }
             }
+            FieldType::Literal(arity, ..) => arity.is_optional(),
             FieldType::Union(arity, f, ..) => {
                 arity.is_optional() || f.iter().any(|t| t.is_nullable())
             }
@@ -192,6 +230,7 @@ impl FieldType {
                 Identifier::Primitive(TypeValue::Null, _) => true,
                 _ => true,
             },
+            FieldType::Literal(..) => true,
             // There's a bug with unions where we cant parse optionals in unions right now
             FieldType::Union(arity, f, ..) => false,
             FieldType::Tuple(arity, ..) => true,
@@ -205,6 +244,7 @@ impl FieldType {
     pub fn flat_idns(&self) -> Vec<&Identifier> {
         match self {
             FieldType::Identifier(.., idn) => vec![idn],
+            FieldType::Literal(..) => vec![],
             FieldType::Union(_, f, ..) => f.iter().flat_map(|t| t.flat_idns()).collect(),
             FieldType::Tuple(_, f, ..) => f.iter().flat_map(|t| t.flat_idns()).collect(),
             FieldType::Dictionary(kv, _) => {
@@ -229,6 +269,9 @@ impl std::fmt::Display for FieldType {
                     if arity.is_optional() { "?" } else { "" }
                 )
             }
+            FieldType::Literal(arity, value, _) => {
+                write!(f, "{}{}", value, if arity.is_optional() { "?" } else { "" })
+            }
             FieldType::Union(arity, ft, _) => {
                 let mut ft = ft.iter().map(|t| t.to_string()).collect::<Vec<_>>();
                 ft.sort();
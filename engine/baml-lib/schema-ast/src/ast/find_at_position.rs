@@ -8,6 +8,25 @@ impl ast::SchemaAst {
                 ast::TopId::Enum(enum_id) => {
                     SchemaPosition::Enum(enum_id, EnumPosition::new(&self[enum_id], position))
                 }
+                ast::TopId::Class(class_id) => {
+                    SchemaPosition::Class(class_id, ClassPosition::new(&self[class_id], position))
+                }
+                ast::TopId::TypeAlias(alias_id) => SchemaPosition::TypeAlias(
+                    alias_id,
+                    TypeAliasPosition::new(&self[alias_id], position),
+                ),
+                ast::TopId::Function(function_id) => SchemaPosition::Function(
+                    function_id,
+                    FunctionPosition::new(&self[function_id], position),
+                ),
+                ast::TopId::Variant(variant_id) => SchemaPosition::Variant(
+                    variant_id,
+                    VariantPosition::new(&self[variant_id], position),
+                ),
+                ast::TopId::Client(client_id) => SchemaPosition::Client(
+                    client_id,
+                    ClientPosition::new(&self[client_id], position),
+                ),
                 // Falling back to TopLevel as "not implemented"
                 _ => SchemaPosition::TopLevel,
             })
@@ -44,6 +63,16 @@ pub enum SchemaPosition<'ast> {
     TopLevel,
     /// In an enum
     Enum(ast::EnumId, EnumPosition<'ast>),
+    /// In a class
+    Class(ast::ClassId, ClassPosition<'ast>),
+    /// In a type alias
+    TypeAlias(ast::TypeAliasId, TypeAliasPosition<'ast>),
+    /// In a function
+    Function(ast::FunctionId, FunctionPosition<'ast>),
+    /// In an impl
+    Variant(ast::VariantConfigId, VariantPosition<'ast>),
+    /// In a client
+    Client(ast::ClientId, ClientPosition),
 }
 
 /// A cursor position in a context.
@@ -51,6 +80,8 @@ pub enum SchemaPosition<'ast> {
 pub enum EnumPosition<'ast> {
     /// In the enum, but not somewhere more specific.
     Enum,
+    /// On the name of the enum.
+    Name,
     // /// In an attribute (attr name, attr index, position).
     // EnumAttribute(&'ast str, usize, AttributePosition<'ast>),
     /// In a value.
@@ -59,6 +90,10 @@ pub enum EnumPosition<'ast> {
 
 impl<'ast> EnumPosition<'ast> {
     fn new(r#enum: &'ast ast::Enum, position: usize) -> Self {
+        if r#enum.identifier().span().contains(position) {
+            return EnumPosition::Name;
+        }
+
         for (enum_value_id, value) in r#enum.iter_values() {
             if value.span().contains(position) {
                 return EnumPosition::Value(enum_value_id, EnumValuePosition::new(value, position));
@@ -122,3 +157,247 @@ impl<'ast> EnumValuePosition<'ast> {
         EnumValuePosition::Value
     }
 }
+
+/// The identifier of a type reference under the cursor, if any.
+fn find_type_at_position(field_type: &ast::FieldType, position: usize) -> Option<&ast::Identifier> {
+    field_type
+        .flat_idns()
+        .into_iter()
+        .find(|idn| idn.span().contains(position))
+}
+
+/// A cursor position in a class.
+#[derive(Debug)]
+pub enum ClassPosition<'ast> {
+    /// In the class, but not somewhere more specific.
+    Class,
+    /// On the name of the class.
+    Name,
+    /// In a field.
+    Field(ast::FieldId, FieldPosition<'ast>),
+}
+
+impl<'ast> ClassPosition<'ast> {
+    fn new(class: &'ast ast::Class, position: usize) -> Self {
+        if class.identifier().span().contains(position) {
+            return ClassPosition::Name;
+        }
+
+        for (field_id, field) in class.iter_fields() {
+            if field.span().contains(position) {
+                return ClassPosition::Field(field_id, FieldPosition::new(field, position));
+            }
+        }
+
+        ClassPosition::Class
+    }
+}
+
+/// A cursor position in a class field.
+#[derive(Debug)]
+pub enum FieldPosition<'ast> {
+    /// In the field, but not somewhere more specific.
+    Field,
+    /// On the name of the field.
+    Name,
+    /// On a type referenced by the field.
+    Type(&'ast ast::Identifier),
+}
+
+impl<'ast> FieldPosition<'ast> {
+    fn new(field: &'ast ast::Field, position: usize) -> Self {
+        if field.identifier().span().contains(position) {
+            return FieldPosition::Name;
+        }
+
+        match find_type_at_position(&field.field_type, position) {
+            Some(idn) => FieldPosition::Type(idn),
+            None => FieldPosition::Field,
+        }
+    }
+}
+
+/// A cursor position in a type alias.
+#[derive(Debug)]
+pub enum TypeAliasPosition<'ast> {
+    /// In the alias, but not somewhere more specific.
+    TypeAlias,
+    /// On the name of the alias.
+    Name,
+    /// On a type referenced by the alias.
+    Type(&'ast ast::Identifier),
+}
+
+impl<'ast> TypeAliasPosition<'ast> {
+    fn new(alias: &'ast ast::TypeAlias, position: usize) -> Self {
+        if alias.identifier().span().contains(position) {
+            return TypeAliasPosition::Name;
+        }
+
+        match find_type_at_position(alias.value(), position) {
+            Some(idn) => TypeAliasPosition::Type(idn),
+            None => TypeAliasPosition::TypeAlias,
+        }
+    }
+}
+
+/// A cursor position in a function.
+#[derive(Debug)]
+pub enum FunctionPosition<'ast> {
+    /// In the function, but not somewhere more specific.
+    Function,
+    /// On the name of the function.
+    Name,
+    /// On the name of a named input argument.
+    Argument(&'ast ast::Identifier),
+    /// On a type referenced by the input or output.
+    Type(&'ast ast::Identifier),
+}
+
+impl<'ast> FunctionPosition<'ast> {
+    fn new(function: &'ast ast::Function, position: usize) -> Self {
+        if function.identifier().span().contains(position) {
+            return FunctionPosition::Name;
+        }
+
+        for args in [function.input(), function.output()] {
+            match args {
+                ast::FunctionArgs::Unnamed(arg) => {
+                    if let Some(idn) = find_type_at_position(&arg.field_type, position) {
+                        return FunctionPosition::Type(idn);
+                    }
+                }
+                ast::FunctionArgs::Named(list) => {
+                    for (name, arg) in list.args.iter() {
+                        if name.span().contains(position) {
+                            return FunctionPosition::Argument(name);
+                        }
+                        if let Some(idn) = find_type_at_position(&arg.field_type, position) {
+                            return FunctionPosition::Type(idn);
+                        }
+                    }
+                }
+            }
+        }
+
+        FunctionPosition::Function
+    }
+}
+
+/// A cursor position in an impl.
+#[derive(Debug)]
+pub enum VariantPosition<'ast> {
+    /// In the impl, but not somewhere more specific.
+    Variant,
+    /// On the name of the impl.
+    Name,
+    /// On the name of the function the impl implements.
+    FunctionName(&'ast ast::Identifier),
+    /// On the value of the `client` field (name, span).
+    Client(&'ast str, &'ast ast::Span),
+    /// On a `{#...}` variable inside the prompt.
+    PromptVariable(PromptVariablePosition<'ast>),
+}
+
+impl<'ast> VariantPosition<'ast> {
+    fn new(variant: &'ast ast::Variant, position: usize) -> Self {
+        if variant.identifier().span().contains(position) {
+            return VariantPosition::Name;
+        }
+
+        if variant.function_name().span().contains(position) {
+            return VariantPosition::FunctionName(variant.function_name());
+        }
+
+        for (_, field) in variant.iter_fields() {
+            let value = match &field.value {
+                Some(value) if value.span().contains(position) => value,
+                _ => continue,
+            };
+            match field.name() {
+                "client" => {
+                    if let Some((name, span)) = value.as_string_value() {
+                        return VariantPosition::Client(name, span);
+                    }
+                }
+                "prompt" => {
+                    if let Some(variable) = PromptVariablePosition::new(value.span(), position) {
+                        return VariantPosition::PromptVariable(variable);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        VariantPosition::Variant
+    }
+}
+
+/// A cursor position on a `{#input.a.b}` style variable in a prompt.
+#[derive(Debug)]
+pub struct PromptVariablePosition<'ast> {
+    /// Each segment of the variable's path with its span, e.g. `input`, `a`, `b`.
+    pub path: Vec<(&'ast str, ast::Span)>,
+    /// The index in `path` of the segment under the cursor.
+    pub segment: usize,
+}
+
+impl<'ast> PromptVariablePosition<'ast> {
+    /// Finds the variable around `position` by scanning the source text of the prompt. The
+    /// prompt parser runs later, in the parser database, so its AST is not available here.
+    fn new(prompt_span: &'ast ast::Span, position: usize) -> Option<Self> {
+        let source = prompt_span.file.as_str();
+        let prompt = source.get(prompt_span.start..prompt_span.end)?;
+        let cursor = position.checked_sub(prompt_span.start)?;
+
+        let open = prompt.get(..cursor)?.rfind("{#")? + 2;
+        let close = open + prompt[open..].find('}')?;
+        if cursor > close {
+            return None;
+        }
+
+        let text = &prompt[open..close];
+        let is_path = text
+            .split('.')
+            .all(|part| !part.is_empty() && part.chars().all(|c| c.is_alphanumeric() || c == '_'));
+        if !is_path {
+            return None;
+        }
+
+        let mut path = Vec::new();
+        let mut start = prompt_span.start + open;
+        for part in text.split('.') {
+            path.push((
+                part,
+                ast::Span::new(prompt_span.file.clone(), start, start + part.len()),
+            ));
+            start += part.len() + 1;
+        }
+
+        let segment = path
+            .iter()
+            .position(|(_, span)| position <= span.end)
+            .unwrap_or(path.len() - 1);
+
+        Some(PromptVariablePosition { path, segment })
+    }
+}
+
+/// A cursor position in a client.
+#[derive(Debug)]
+pub enum ClientPosition {
+    /// In the client, but not somewhere more specific.
+    Client,
+    /// On the name of the client.
+    Name,
+}
+
+impl ClientPosition {
+    fn new(client: &ast::Client, position: usize) -> Self {
+        if client.identifier().span().contains(position) {
+            ClientPosition::Name
+        } else {
+            ClientPosition::Client
+        }
+    }
+}
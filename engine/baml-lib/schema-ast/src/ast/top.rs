@@ -1,6 +1,6 @@
 use super::{
     traits::WithSpan, Class, Client, Configuration, Enum, Function, GeneratorConfig, Identifier,
-    Span, Variant, WithIdentifier,
+    Span, TypeAlias, Variant, WithIdentifier,
 };
 
 /// Enum for distinguishing between top-level entries
@@ -10,6 +10,8 @@ pub enum Top {
     Enum(Enum),
     // A class declaration
     Class(Class),
+    // A type alias declaration
+    TypeAlias(TypeAlias),
     // A function declaration
     Function(Function),
 
@@ -33,6 +35,7 @@ impl Top {
             // Top::CompositeType(_) => "composite type",
             Top::Enum(_) => "enum",
             Top::Class(_) => "class",
+            Top::TypeAlias(_) => "type",
             Top::Function(_) => "function",
             Top::Client(m) if m.is_llm() => "client<llm>",
             Top::Client(_) => "client<?>",
@@ -58,6 +61,13 @@ impl Top {
         }
     }
 
+    pub fn as_type_alias(&self) -> Option<&TypeAlias> {
+        match self {
+            Top::TypeAlias(alias) => Some(alias),
+            _ => None,
+        }
+    }
+
     pub fn as_function(&self) -> Option<&Function> {
         match self {
             Top::Function(func) => Some(func),
@@ -101,6 +111,7 @@ impl WithIdentifier for Top {
             // Top::CompositeType(ct) => &ct.name,
             Top::Enum(x) => x.identifier(),
             Top::Class(x) => x.identifier(),
+            Top::TypeAlias(x) => x.identifier(),
             Top::Function(x) => x.identifier(),
             Top::Client(x) => x.identifier(),
             Top::Variant(x) => x.identifier(),
@@ -115,6 +126,7 @@ impl WithSpan for Top {
         match self {
             Top::Enum(en) => en.span(),
             Top::Class(class) => class.span(),
+            Top::TypeAlias(alias) => alias.span(),
             Top::Function(func) => func.span(),
             Top::Client(client) => client.span(),
             Top::Variant(variant) => variant.span(),
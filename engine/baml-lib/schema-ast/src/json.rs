@@ -0,0 +1,398 @@
+//! A JSON parser that keeps the span of every value, so that errors in JSON files (e.g. test
+//! inputs) can point at the offending value.
+
+use internal_baml_diagnostics::{DatamodelError, SourceFile, Span};
+
+use crate::ast::{Expression, Identifier, TypeValue, WithName};
+
+/// A JSON value, along with where it is in its file.
+#[derive(Debug, Clone)]
+pub struct JsonValue {
+    pub kind: JsonKind,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone)]
+pub enum JsonKind {
+    Null,
+    Bool(bool),
+    /// The number as written in the file.
+    Number(String),
+    String(String),
+    Array(Vec<JsonValue>),
+    /// The entries in file order, with the span of each key.
+    Object(Vec<(String, Span, JsonValue)>),
+}
+
+impl JsonValue {
+    /// The value of `key`, if this is an object that has it.
+    pub fn get(&self, key: &str) -> Option<&JsonValue> {
+        match &self.kind {
+            JsonKind::Object(entries) => entries
+                .iter()
+                .find(|(name, _, _)| name == key)
+                .map(|(_, _, value)| value),
+            _ => None,
+        }
+    }
+
+    /// What kind of value this is, for error messages, e.g. `a string`.
+    pub fn description(&self) -> &'static str {
+        match &self.kind {
+            JsonKind::Null => "null",
+            JsonKind::Bool(_) => "a bool",
+            JsonKind::Number(_) => "a number",
+            JsonKind::String(_) => "a string",
+            JsonKind::Array(_) => "a list",
+            JsonKind::Object(_) => "an object",
+        }
+    }
+
+    pub fn to_json(&self) -> serde_json::Value {
+        match &self.kind {
+            JsonKind::Null => serde_json::Value::Null,
+            JsonKind::Bool(b) => (*b).into(),
+            JsonKind::Number(num) => serde_json::from_str(num).unwrap_or(serde_json::Value::Null),
+            JsonKind::String(s) => s.as_str().into(),
+            JsonKind::Array(items) => items.iter().map(JsonValue::to_json).collect(),
+            JsonKind::Object(entries) => entries
+                .iter()
+                .map(|(key, _, value)| (key.clone(), value.to_json()))
+                .collect::<serde_json::Map<_, _>>()
+                .into(),
+        }
+    }
+
+    /// Converts a BAML expression, e.g. `{ name Bob, tags [a, b] }`. Unquoted words are
+    /// strings, except `true`, `false` and `null`.
+    pub fn from_expression(expr: &Expression) -> Result<JsonValue, DatamodelError> {
+        let kind = match expr {
+            Expression::NumericValue(num, _) => JsonKind::Number(num.clone()),
+            Expression::StringValue(s, _) => JsonKind::String(s.clone()),
+            Expression::RawStringValue(s) => JsonKind::String(s.value().to_string()),
+            Expression::Identifier(Identifier::ENV(_, span)) => {
+                return Err(DatamodelError::new_validation_error(
+                    "Environment variables are not supported here.",
+                    span.clone(),
+                ))
+            }
+            Expression::Identifier(Identifier::Primitive(TypeValue::Null, _)) => JsonKind::Null,
+            Expression::Identifier(Identifier::Local(word, _)) if word == "true" => {
+                JsonKind::Bool(true)
+            }
+            Expression::Identifier(Identifier::Local(word, _)) if word == "false" => {
+                JsonKind::Bool(false)
+            }
+            Expression::Identifier(Identifier::Ref(id, _)) => {
+                JsonKind::String(id.full_name.clone())
+            }
+            Expression::Identifier(id) => JsonKind::String(id.name().to_string()),
+            Expression::Array(items, _) => JsonKind::Array(
+                items
+                    .iter()
+                    .map(JsonValue::from_expression)
+                    .collect::<Result<_, _>>()?,
+            ),
+            Expression::Map(entries, _) => JsonKind::Object(
+                entries
+                    .iter()
+                    .map(|(key, value)| {
+                        let Some((name, span)) = key.as_string_value() else {
+                            return Err(DatamodelError::new_validation_error(
+                                "Keys must be strings.",
+                                key.span().clone(),
+                            ));
+                        };
+                        Ok((
+                            name.to_string(),
+                            span.clone(),
+                            JsonValue::from_expression(value)?,
+                        ))
+                    })
+                    .collect::<Result<_, _>>()?,
+            ),
+        };
+        Ok(JsonValue {
+            kind,
+            span: expr.span().clone(),
+        })
+    }
+}
+
+/// Parses the whole of `source` as a single JSON value.
+pub fn parse(source: &SourceFile) -> Result<JsonValue, DatamodelError> {
+    parse_span(&Span::new(source.clone(), 0, source.as_str().len()))
+}
+
+/// Parses the text at `span`, e.g. the contents of a raw string, as a single JSON value.
+pub fn parse_span(span: &Span) -> Result<JsonValue, DatamodelError> {
+    let mut parser = Parser {
+        source: &span.file,
+        text: &span.file.as_str().as_bytes()[..span.end],
+        pos: span.start,
+    };
+    let value = parser.value()?;
+    parser.skip_whitespace();
+    if parser.pos < parser.text.len() {
+        return Err(parser.error("Unexpected trailing characters"));
+    }
+    Ok(value)
+}
+
+struct Parser<'a> {
+    source: &'a SourceFile,
+    text: &'a [u8],
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn span(&self, start: usize) -> Span {
+        Span::new(self.source.clone(), start, self.pos)
+    }
+
+    fn error(&self, message: &str) -> DatamodelError {
+        let end = (self.pos + 1).min(self.text.len());
+        DatamodelError::new_validation_error(
+            &format!("Invalid JSON: {}", message),
+            Span::new(self.source.clone(), self.pos, end),
+        )
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.text.get(self.pos).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while matches!(self.peek(), Some(b' ' | b'\t' | b'\n' | b'\r')) {
+            self.pos += 1;
+        }
+    }
+
+    fn expect(&mut self, byte: u8) -> Result<(), DatamodelError> {
+        if self.peek() == Some(byte) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            Err(self.error(&format!("Expected `{}`", byte as char)))
+        }
+    }
+
+    fn value(&mut self) -> Result<JsonValue, DatamodelError> {
+        self.skip_whitespace();
+        let start = self.pos;
+        let kind = match self.peek() {
+            Some(b'{') => self.object()?,
+            Some(b'[') => self.array()?,
+            Some(b'"') => JsonKind::String(self.string()?),
+            Some(b'-' | b'0'..=b'9') => self.number()?,
+            Some(_) if self.text[self.pos..].starts_with(b"true") => {
+                self.pos += 4;
+                JsonKind::Bool(true)
+            }
+            Some(_) if self.text[self.pos..].starts_with(b"false") => {
+                self.pos += 5;
+                JsonKind::Bool(false)
+            }
+            Some(_) if self.text[self.pos..].starts_with(b"null") => {
+                self.pos += 4;
+                JsonKind::Null
+            }
+            _ => return Err(self.error("Expected a value")),
+        };
+        Ok(JsonValue {
+            kind,
+            span: self.span(start),
+        })
+    }
+
+    fn object(&mut self) -> Result<JsonKind, DatamodelError> {
+        self.expect(b'{')?;
+        let mut entries = vec![];
+        self.skip_whitespace();
+        if self.peek() == Some(b'}') {
+            self.pos += 1;
+            return Ok(JsonKind::Object(entries));
+        }
+        loop {
+            self.skip_whitespace();
+            let key_start = self.pos;
+            if self.peek() != Some(b'"') {
+                return Err(self.error("Expected a key"));
+            }
+            let key = self.string()?;
+            let key_span = self.span(key_start);
+            self.skip_whitespace();
+            self.expect(b':')?;
+            let value = self.value()?;
+            entries.push((key, key_span, value));
+            self.skip_whitespace();
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b'}') => {
+                    self.pos += 1;
+                    return Ok(JsonKind::Object(entries));
+                }
+                _ => return Err(self.error("Expected `,` or `}`")),
+            }
+        }
+    }
+
+    fn array(&mut self) -> Result<JsonKind, DatamodelError> {
+        self.expect(b'[')?;
+        let mut items = vec![];
+        self.skip_whitespace();
+        if self.peek() == Some(b']') {
+            self.pos += 1;
+            return Ok(JsonKind::Array(items));
+        }
+        loop {
+            items.push(self.value()?);
+            self.skip_whitespace();
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b']') => {
+                    self.pos += 1;
+                    return Ok(JsonKind::Array(items));
+                }
+                _ => return Err(self.error("Expected `,` or `]`")),
+            }
+        }
+    }
+
+    fn number(&mut self) -> Result<JsonKind, DatamodelError> {
+        let start = self.pos;
+        while matches!(
+            self.peek(),
+            Some(b'-' | b'+' | b'.' | b'e' | b'E' | b'0'..=b'9')
+        ) {
+            self.pos += 1;
+        }
+        let number = std::str::from_utf8(&self.text[start..self.pos]).unwrap_or_default();
+        match number.parse::<f64>() {
+            Ok(_) => Ok(JsonKind::Number(number.to_string())),
+            Err(_) => {
+                self.pos = start;
+                Err(self.error("Invalid number"))
+            }
+        }
+    }
+
+    fn string(&mut self) -> Result<String, DatamodelError> {
+        self.expect(b'"')?;
+        let mut bytes = vec![];
+        loop {
+            match self.peek() {
+                None => return Err(self.error("Unterminated string")),
+                Some(b'"') => {
+                    self.pos += 1;
+                    break;
+                }
+                Some(b'\\') => {
+                    self.pos += 1;
+                    let escaped = match self.peek() {
+                        Some(b'"') => '"',
+                        Some(b'\\') => '\\',
+                        Some(b'/') => '/',
+                        Some(b'b') => '\u{8}',
+                        Some(b'f') => '\u{c}',
+                        Some(b'n') => '\n',
+                        Some(b'r') => '\r',
+                        Some(b't') => '\t',
+                        Some(b'u') => self.unicode_escape()?,
+                        _ => return Err(self.error("Invalid escape")),
+                    };
+                    self.pos += 1;
+                    bytes.extend_from_slice(escaped.encode_utf8(&mut [0; 4]).as_bytes());
+                }
+                Some(byte) => {
+                    bytes.push(byte);
+                    self.pos += 1;
+                }
+            }
+        }
+        // The source is valid UTF-8 and escapes are only split on ASCII, so this can't fail.
+        Ok(String::from_utf8(bytes).unwrap_or_default())
+    }
+
+    /// Reads the `XXXX` of a `\uXXXX` escape, and of the low surrogate following it if any.
+    /// Leaves `pos` on the last digit.
+    fn unicode_escape(&mut self) -> Result<char, DatamodelError> {
+        let high = self.hex4()?;
+        if !(0xD800..0xDC00).contains(&high) {
+            return char::from_u32(high).ok_or_else(|| self.error("Invalid unicode escape"));
+        }
+        if !self.text[self.pos + 1..].starts_with(b"\\u") {
+            return Err(self.error("Invalid unicode escape"));
+        }
+        self.pos += 2;
+        let low = self.hex4()?;
+        if !(0xDC00..0xE000).contains(&low) {
+            return Err(self.error("Invalid unicode escape"));
+        }
+        char::from_u32(0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00))
+            .ok_or_else(|| self.error("Invalid unicode escape"))
+    }
+
+    fn hex4(&mut self) -> Result<u32, DatamodelError> {
+        let digits = self
+            .text
+            .get(self.pos + 1..self.pos + 5)
+            .and_then(|digits| std::str::from_utf8(digits).ok())
+            .and_then(|digits| u32::from_str_radix(digits, 16).ok())
+            .ok_or_else(|| self.error("Invalid unicode escape"))?;
+        self.pos += 4;
+        Ok(digits)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn source(text: &str) -> SourceFile {
+        SourceFile::from(("test.json".into(), text.to_string()))
+    }
+
+    fn text<'a>(file: &'a SourceFile, span: &Span) -> &'a str {
+        &file.as_str()[span.start..span.end]
+    }
+
+    #[test]
+    fn keeps_spans() {
+        let file =
+            source("{\n  \"input\": {\"name\": \"Bob\", \"tags\": [1, -2.5e3, true, null]}\n}");
+        let value = parse(&file).unwrap();
+        let input = value.get("input").unwrap();
+        assert_eq!(
+            text(&file, &input.span),
+            "{\"name\": \"Bob\", \"tags\": [1, -2.5e3, true, null]}"
+        );
+        assert_eq!(text(&file, &input.get("name").unwrap().span), "\"Bob\"");
+
+        let JsonKind::Array(tags) = &input.get("tags").unwrap().kind else {
+            panic!("expected a list");
+        };
+        assert_eq!(
+            tags.iter()
+                .map(|t| text(&file, &t.span))
+                .collect::<Vec<_>>(),
+            vec!["1", "-2.5e3", "true", "null"]
+        );
+    }
+
+    #[test]
+    fn unescapes_strings() {
+        let file = source(r#""a\"b\\c\n\u00e9\ud83d\ude00""#);
+        let JsonKind::String(value) = parse(&file).unwrap().kind else {
+            panic!("expected a string");
+        };
+        assert_eq!(value, "a\"b\\c\né😀");
+    }
+
+    #[test]
+    fn rejects_invalid_json() {
+        for text in ["{\"a\" 1}", "[1,]", "{} {}", "\"abc", "nul"] {
+            assert!(parse(&source(text)).is_err(), "{}", text);
+        }
+    }
+}
@@ -4,12 +4,17 @@
 #![allow(clippy::derive_partial_eq_without_eq)]
 
 pub use self::parser::parse_schema;
+pub use self::reformat::reformat;
 
 /// The AST data structure. It aims to faithfully represent the syntax of a Prisma Schema, with
 /// source span information.
 pub mod ast;
 
+/// Span-aware JSON, for test files.
+pub mod json;
+
 mod parser;
+mod reformat;
 
 /// Transform the input string into a valid (quoted and escaped) PSL string literal.
 ///
@@ -14,6 +14,7 @@ mod parse_schema;
 mod parse_serializer;
 mod parse_template_args;
 mod parse_test;
+mod parse_type_alias;
 mod parse_types;
 mod parse_variant;
 
@@ -48,7 +48,10 @@ fn parse_string_literal(token: Pair<'_>, diagnostics: &mut Diagnostics) -> Expre
             Expression::StringValue(contents.as_str().to_string(), span)
         }
         Rule::unquoted_string_literal => {
-            let content = contents.as_str().to_string();
+            // Spaces may be part of the string, but not the ones before a closing `}` or `,`.
+            let content = contents.as_str().trim_end().to_string();
+            let mut span = span;
+            span.end = span.start + content.len();
             if content.contains(" ") {
                 Expression::StringValue(content, span)
             } else {
@@ -2,7 +2,7 @@ use std::path::PathBuf;
 
 use super::{
     parse_class::parse_class, parse_config, parse_enum::parse_enum, parse_function::parse_function,
-    parse_test::parse_test_from_json, BAMLParser, Rule,
+    parse_test::parse_test_from_json, parse_type_alias::parse_type_alias, BAMLParser, Rule,
 };
 use crate::{ast::*, parser::parse_variant};
 use internal_baml_diagnostics::{DatamodelError, Diagnostics, SourceFile};
@@ -76,6 +76,12 @@ pub fn parse_schema(
                             Err(e) => diagnostics.push_error(e),
                         }
                     },
+                    Rule::type_alias => {
+                        match parse_type_alias(current, pending_block_comment.take(), &mut diagnostics) {
+                            Ok(alias) => top_level_definitions.push(Top::TypeAlias(alias)),
+                            Err(e) => diagnostics.push_error(e),
+                        }
+                    },
                     Rule::config_block => {
                         match parse_config::parse_config_block(
                             current,
@@ -106,7 +112,7 @@ pub fn parse_schema(
                             Some(Rule::empty_lines) => {
                                 // free floating
                             }
-                            Some(Rule::enum_declaration) => {
+                            Some(Rule::enum_declaration | Rule::type_alias) => {
                                 pending_block_comment = Some(current);
                             }
                             _ => (),
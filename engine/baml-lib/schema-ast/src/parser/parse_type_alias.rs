@@ -0,0 +1,53 @@
+use super::{
+    helpers::{parsing_catch_all, Pair},
+    parse_comments::*,
+    parse_identifier::parse_identifier,
+    parse_types::parse_field_type,
+    Rule,
+};
+use crate::{assert_correct_parser, ast::*};
+use internal_baml_diagnostics::{DatamodelError, Diagnostics};
+
+pub(crate) fn parse_type_alias(
+    pair: Pair<'_>,
+    doc_comment: Option<Pair<'_>>,
+    diagnostics: &mut Diagnostics,
+) -> Result<TypeAlias, DatamodelError> {
+    assert_correct_parser!(pair, Rule::type_alias);
+
+    let pair_span = pair.as_span();
+    let mut name: Option<Identifier> = None;
+    let mut value: Option<FieldType> = None;
+    let mut comment: Option<Comment> = doc_comment.and_then(parse_comment_block);
+
+    for current in pair.into_inner() {
+        match current.as_rule() {
+            Rule::TYPE_KEYWORD => {}
+            Rule::identifier => name = Some(parse_identifier(current, diagnostics)),
+            Rule::field_type => value = parse_field_type(current, diagnostics),
+            Rule::trailing_comment => {
+                comment = match (comment, parse_trailing_comment(current)) {
+                    (None, a) | (a, None) => a,
+                    (Some(a), Some(b)) => Some(Comment {
+                        text: [a.text, b.text].join("\n"),
+                    }),
+                };
+            }
+            _ => parsing_catch_all(&current, "type alias"),
+        }
+    }
+
+    match (name, value) {
+        (Some(name), Some(value)) => Ok(TypeAlias {
+            name,
+            value,
+            documentation: comment,
+            span: diagnostics.span(pair_span),
+        }),
+        (Some(name), None) => Err(DatamodelError::new_validation_error(
+            &format!("Type alias `{}` is missing a valid type.", name.name()),
+            diagnostics.span(pair_span),
+        )),
+        _ => unreachable!("Encountered impossible type alias declaration during parsing"),
+    }
+}
@@ -2,7 +2,7 @@ use super::{helpers::Pair, Rule};
 use crate::{
     assert_correct_parser, ast::*, parser::parse_identifier::parse_identifier, unreachable_rule,
 };
-use internal_baml_diagnostics::Diagnostics;
+use internal_baml_diagnostics::{DatamodelError, Diagnostics};
 
 pub fn parse_field_type(pair: Pair<'_>, diagnostics: &mut Diagnostics) -> Option<FieldType> {
     assert_correct_parser!(pair, Rule::field_type);
@@ -71,6 +71,7 @@ fn parse_base_type(pair: Pair<'_>, diagnostics: &mut Diagnostics) -> Option<Fiel
                 FieldArity::Required,
                 parse_identifier(current, diagnostics),
             )),
+            Rule::literal_type => parse_literal_type(current, diagnostics),
             Rule::array_notation => parse_array(current, diagnostics),
             Rule::dict => parse_dict(current, diagnostics),
             Rule::group => parse_group(current, diagnostics),
@@ -82,6 +83,31 @@ fn parse_base_type(pair: Pair<'_>, diagnostics: &mut Diagnostics) -> Option<Fiel
     unreachable!("A base type must be one of the above");
 }
 
+fn parse_literal_type(pair: Pair<'_>, diagnostics: &mut Diagnostics) -> Option<FieldType> {
+    assert_correct_parser!(pair, Rule::literal_type);
+
+    let span = diagnostics.span(pair.as_span());
+    let current = pair.into_inner().next().unwrap();
+    let value = match current.as_rule() {
+        Rule::quoted_string_literal => {
+            LiteralValue::String(current.into_inner().next().unwrap().as_str().to_string())
+        }
+        Rule::boolean_literal => LiteralValue::Bool(current.as_str() == "true"),
+        Rule::integer_literal => match current.as_str().parse() {
+            Ok(value) => LiteralValue::Int(value),
+            Err(_) => {
+                diagnostics.push_error(DatamodelError::new_validation_error(
+                    &format!("`{}` is too large to be an int.", current.as_str()),
+                    span,
+                ));
+                return None;
+            }
+        },
+        _ => unreachable_rule!(current, Rule::literal_type),
+    };
+    Some(FieldType::Literal(FieldArity::Required, value, span))
+}
+
 fn parse_array(pair: Pair<'_>, diagnostics: &mut Diagnostics) -> Option<FieldType> {
     assert_correct_parser!(pair, Rule::array_notation);
 
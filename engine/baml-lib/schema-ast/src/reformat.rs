@@ -0,0 +1,586 @@
+use crate::{
+    ast::{dedent, IndentationType, NewlineType},
+    parser::{BAMLParser, Rule},
+};
+use pest::{iterators::Pair, Parser};
+
+/// Returns the canonical formatting of a BAML file, or `None` if the file
+/// contains syntax errors.
+///
+/// Comments and (collapsed) blank lines are preserved. Blocks are re-indented,
+/// whitespace inside declarations is normalized, and multi-line raw strings are
+/// dedented and re-indented one level deeper than the line that opens them.
+/// Attributes stay on the line they were written on: an attribute that started
+/// on its own line is kept on a continuation line, indented one extra level.
+pub fn reformat(input: &str, indentation: IndentationType) -> Option<String> {
+    let mut pairs = BAMLParser::parse(Rule::schema, input).ok()?;
+    let schema = pairs.next()?;
+
+    // The grammar is forgiving and accepts any line; don't touch invalid input.
+    if schema.clone().into_inner().flatten().any(|pair| {
+        matches!(
+            pair.as_rule(),
+            Rule::CATCH_ALL
+                | Rule::BLOCK_LEVEL_CATCH_ALL
+                | Rule::arbitrary_block
+                | Rule::unterminated_string_literal
+        )
+    }) {
+        return None;
+    }
+
+    let newline = if input.contains("\r\n") {
+        NewlineType::Windows
+    } else {
+        NewlineType::Unix
+    };
+
+    let renderer = Renderer { input, indentation };
+    let mut out = String::with_capacity(input.len());
+    renderer.schema(schema, &mut out);
+
+    let trimmed = out.trim_end_matches('\n');
+    let mut result = String::with_capacity(trimmed.len() + 1);
+    if !trimmed.is_empty() {
+        result.push_str(trimmed);
+        result.push('\n');
+    }
+
+    Some(match newline {
+        NewlineType::Unix => result,
+        NewlineType::Windows => result.replace('\n', newline.as_ref()),
+    })
+}
+
+struct Renderer<'a> {
+    input: &'a str,
+    indentation: IndentationType,
+}
+
+impl Renderer<'_> {
+    fn schema(&self, schema: Pair<'_, Rule>, out: &mut String) {
+        let mut pending_blank = false;
+        let mut prev_was_block = false;
+        let mut started = false;
+
+        for current in schema.into_inner() {
+            match current.as_rule() {
+                Rule::EOI => {}
+                Rule::empty_lines => pending_blank |= started && self.has_blank_line(&current),
+                rule => {
+                    // Top-level blocks are always separated by one blank line.
+                    if started && (pending_blank || prev_was_block) {
+                        out.push('\n');
+                    }
+                    pending_blank = false;
+                    started = true;
+                    prev_was_block = matches!(
+                        rule,
+                        Rule::enum_declaration
+                            | Rule::interface_declaration
+                            | Rule::function_declaration
+                            | Rule::variant_block
+                            | Rule::config_block
+                    );
+
+                    match rule {
+                        Rule::comment_block => self.comment_block(current, 0, out),
+                        Rule::enum_declaration
+                        | Rule::interface_declaration
+                        | Rule::function_declaration
+                        | Rule::variant_block
+                        | Rule::config_block => self.block(current, 0, out),
+                        Rule::type_alias => self.type_alias(current, out),
+                        _ => self.verbatim(current, 0, out),
+                    }
+                }
+            }
+        }
+    }
+
+    /// Renders `keyword<args> name { contents }`.
+    fn block(&self, block: Pair<'_, Rule>, indent: usize, out: &mut String) {
+        let mut header = String::new();
+
+        for current in block.into_inner() {
+            match current.as_rule() {
+                Rule::ENUM_KEYWORD
+                | Rule::CLASS_KEYWORD
+                | Rule::FUNCTION_KEYWORD
+                | Rule::CLIENT_KEYWORD
+                | Rule::GENERATOR_KEYWORD
+                | Rule::RETRY_POLICY_KEYWORD
+                | Rule::PRINTER_KEYWORD
+                | Rule::TEST_KEYWORD
+                | Rule::VARIANT_KEYWORD
+                | Rule::SERIALIZER_KEYWORD => header.push_str(current.as_str()),
+                Rule::template_args => header.push_str(&self.template_args(current, indent)),
+                Rule::identifier => {
+                    header.push(' ');
+                    header.push_str(current.as_str());
+                }
+                Rule::BLOCK_OPEN => {
+                    header.push_str(" {");
+                    // Anything after the brace (usually a comment) stays on the header line.
+                    let rest = current.as_str()[1..].trim();
+                    if !rest.is_empty() {
+                        header.push(' ');
+                        header.push_str(rest);
+                    }
+                    self.line(indent, &header, out);
+                }
+                Rule::BLOCK_CLOSE => self.line(indent, "}", out),
+                _ => self.block_contents(current, indent + 1, out),
+            }
+        }
+    }
+
+    fn block_contents(&self, contents: Pair<'_, Rule>, indent: usize, out: &mut String) {
+        let mut pending_blank = false;
+        let mut started = false;
+
+        for current in contents.into_inner() {
+            if current.as_rule() == Rule::empty_lines {
+                pending_blank |= started && self.has_blank_line(&current);
+                continue;
+            }
+            if pending_blank {
+                out.push('\n');
+            }
+            pending_blank = false;
+            started = true;
+
+            match current.as_rule() {
+                Rule::comment_block => self.comment_block(current, indent, out),
+                Rule::serializer_block => self.block(current, indent, out),
+                Rule::block_attribute => {
+                    let attribute = self.attribute(current, indent);
+                    self.line(indent, &attribute, out)
+                }
+                Rule::input_field_declaration => {
+                    self.line_item(current, indent, Some("input"), out)
+                }
+                Rule::output_field_declaration => {
+                    self.line_item(current, indent, Some("output"), out)
+                }
+                Rule::adapter_block => self.line_item(current, indent, Some("adapter"), out),
+                Rule::field_declaration
+                | Rule::enum_value_declaration
+                | Rule::serializer_field
+                | Rule::key_value => self.line_item(current, indent, None, out),
+                _ => self.verbatim(current, indent, out),
+            }
+        }
+    }
+
+    /// Renders a single declaration (field, enum value, key/value pair, ...)
+    /// followed by its attributes and trailing comment.
+    fn line_item(
+        &self,
+        item: Pair<'_, Rule>,
+        indent: usize,
+        keyword: Option<&str>,
+        out: &mut String,
+    ) {
+        let mut line = keyword.map(String::from).unwrap_or_default();
+        let mut line_indent = indent;
+        let mut prev_end = item.as_span().start();
+
+        for current in item.into_inner() {
+            let breaks = self.input[prev_end..current.as_span().start()].contains('\n');
+            prev_end = current.as_span().end();
+
+            if breaks
+                && matches!(
+                    current.as_rule(),
+                    Rule::field_attribute | Rule::trailing_comment
+                )
+            {
+                self.line(line_indent, &line, out);
+                line.clear();
+                line_indent = indent + 1;
+            }
+
+            let text = match current.as_rule() {
+                Rule::template_args => {
+                    line.push_str(&self.template_args(current, line_indent));
+                    continue;
+                }
+                Rule::field_template_args => {
+                    line.push_str(&self.field_template_args(current));
+                    continue;
+                }
+                Rule::identifier => current.as_str().to_string(),
+                Rule::field_type | Rule::base_type => self.field_type(current),
+                Rule::function_field_type => self.function_field_type(current),
+                Rule::expression => self.expression(current, line_indent),
+                Rule::field_attribute => self.attribute(current, line_indent),
+                Rule::trailing_comment => current.as_str().trim().to_string(),
+                _ => current.as_str().trim().to_string(),
+            };
+
+            if !line.is_empty() {
+                line.push(' ');
+            }
+            line.push_str(&text);
+        }
+
+        self.line(line_indent, &line, out);
+    }
+
+    /// Renders `type Name = <type>`, keeping a trailing comment on the same line.
+    fn type_alias(&self, alias: Pair<'_, Rule>, out: &mut String) {
+        let mut line = String::new();
+
+        for current in alias.into_inner() {
+            match current.as_rule() {
+                Rule::TYPE_KEYWORD => line.push_str(current.as_str()),
+                Rule::identifier => {
+                    line.push(' ');
+                    line.push_str(current.as_str());
+                    line.push_str(" =");
+                }
+                Rule::field_type => {
+                    line.push(' ');
+                    line.push_str(&self.field_type(current));
+                }
+                _ => {
+                    line.push(' ');
+                    line.push_str(current.as_str().trim());
+                }
+            }
+        }
+
+        self.line(0, &line, out);
+    }
+
+    fn comment_block(&self, comments: Pair<'_, Rule>, indent: usize, out: &mut String) {
+        for comment in comments.into_inner() {
+            self.verbatim(comment, indent, out);
+        }
+    }
+
+    /// Writes a node as-is, only normalizing the indentation of its first line
+    /// and trailing whitespace.
+    fn verbatim(&self, pair: Pair<'_, Rule>, indent: usize, out: &mut String) {
+        let mut lines = pair.as_str().trim().lines();
+        if let Some(first) = lines.next() {
+            self.line(indent, first, out);
+        }
+        for line in lines {
+            out.push_str(line.trim_end());
+            out.push('\n');
+        }
+    }
+
+    fn attribute(&self, attribute: Pair<'_, Rule>, indent: usize) -> String {
+        let mut result = String::from(match attribute.as_rule() {
+            Rule::block_attribute => "@@",
+            _ => "@",
+        });
+
+        for current in attribute.into_inner() {
+            match current.as_rule() {
+                Rule::identifier => result.push_str(current.as_str()),
+                Rule::arguments_list => result.push_str(&self.arguments_list(current, indent)),
+                _ => result.push_str(current.as_str().trim()),
+            }
+        }
+
+        result
+    }
+
+    fn arguments_list(&self, arguments: Pair<'_, Rule>, indent: usize) -> String {
+        // `(` immediately followed by a newline keeps the arguments on their own lines.
+        let broken = arguments.as_str()[1..]
+            .trim_start_matches([' ', '\t'])
+            .starts_with(['\n', '\r']);
+
+        if broken {
+            let args = arguments
+                .into_inner()
+                .map(|arg| {
+                    format!(
+                        "{}{}",
+                        self.indent(indent + 1),
+                        self.expression(arg, indent + 1)
+                    )
+                })
+                .collect::<Vec<_>>();
+            format!("(\n{}\n{})", args.join(",\n"), self.indent(indent))
+        } else {
+            let args = arguments
+                .into_inner()
+                .map(|arg| self.expression(arg, indent))
+                .collect::<Vec<_>>();
+            format!("({})", args.join(", "))
+        }
+    }
+
+    fn template_args(&self, args: Pair<'_, Rule>, indent: usize) -> String {
+        let args = args
+            .into_inner()
+            .filter(|arg| arg.as_rule() == Rule::expression)
+            .map(|arg| self.expression(arg, indent))
+            .collect::<Vec<_>>();
+        format!("<{}>", args.join(", "))
+    }
+
+    fn field_template_args(&self, args: Pair<'_, Rule>) -> String {
+        let args = args
+            .into_inner()
+            .filter(|arg| arg.as_rule() == Rule::field_type)
+            .map(|arg| self.field_type(arg))
+            .collect::<Vec<_>>();
+        format!("<{}>", args.join(", "))
+    }
+
+    fn function_field_type(&self, field_type: Pair<'_, Rule>) -> String {
+        let current = field_type.into_inner().next().unwrap();
+        match current.as_rule() {
+            Rule::named_argument_list => {
+                let args = current
+                    .into_inner()
+                    .map(|arg| {
+                        let mut parts = arg.into_inner();
+                        let name = parts.next().unwrap().as_str();
+                        match parts.next() {
+                            Some(field_type) => {
+                                format!("{}: {}", name, self.field_type(field_type))
+                            }
+                            None => format!("{}:", name),
+                        }
+                    })
+                    .collect::<Vec<_>>();
+                format!("({})", args.join(", "))
+            }
+            _ => self.field_type(current),
+        }
+    }
+
+    fn field_type(&self, field_type: Pair<'_, Rule>) -> String {
+        match field_type.as_rule() {
+            Rule::identifier => field_type.as_str().to_string(),
+            Rule::optional_token => "?".to_string(),
+            Rule::array_suffix => "[]".to_string(),
+            Rule::union => field_type
+                .into_inner()
+                .map(|t| self.field_type(t))
+                .collect::<Vec<_>>()
+                .join(" | "),
+            Rule::tuple => format!(
+                "({})",
+                field_type
+                    .into_inner()
+                    .map(|t| self.field_type(t))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            Rule::group => format!(
+                "({})",
+                field_type
+                    .into_inner()
+                    .map(|t| self.field_type(t))
+                    .collect::<String>()
+            ),
+            Rule::dict => {
+                let parts = field_type
+                    .into_inner()
+                    .map(|t| self.field_type(t))
+                    .collect::<Vec<_>>();
+                format!("{{{}}}", parts.join(": "))
+            }
+            _ => field_type
+                .into_inner()
+                .map(|t| self.field_type(t))
+                .collect::<String>(),
+        }
+    }
+
+    fn expression(&self, expression: Pair<'_, Rule>, indent: usize) -> String {
+        let current = expression.into_inner().next().unwrap();
+        match current.as_rule() {
+            Rule::string_literal => {
+                let literal = current.into_inner().next().unwrap();
+                match literal.as_rule() {
+                    Rule::raw_string_literal => self.raw_string(literal, indent),
+                    _ => literal.as_str().trim().to_string(),
+                }
+            }
+            Rule::dict_expression => self.dict(current, indent),
+            Rule::array_expression => self.array(current, indent),
+            _ => current.as_str().trim().to_string(),
+        }
+    }
+
+    fn raw_string(&self, literal: Pair<'_, Rule>, indent: usize) -> String {
+        let text = literal.as_str();
+        let open_len = text.find('"').unwrap() + 1;
+        let hashes = text[..open_len].chars().filter(|c| *c == '#').count();
+        let close_len = hashes + 1;
+        let content = &text[open_len..text.len() - close_len];
+
+        if !content.contains('\n') {
+            return text.to_string();
+        }
+
+        // Same normalization the parser applies when building a `RawString`.
+        let value = content.trim_start_matches(['\n', '\r']).trim_end();
+        let (value, _) = dedent(value);
+
+        let mut result = String::from(&text[..open_len]);
+        result.push('\n');
+        let mut leading = true;
+        for line in value.lines() {
+            leading &= line.is_empty();
+            // The parser drops leading lines without any whitespace, so leading
+            // blank lines keep their indentation to preserve the value.
+            if leading || !line.is_empty() {
+                result.push_str(&self.indent(indent + 1));
+                result.push_str(line);
+            }
+            result.push('\n');
+        }
+        result.push_str(&self.indent(indent));
+        result.push_str(&text[text.len() - close_len..]);
+        result
+    }
+
+    fn dict(&self, dict: Pair<'_, Rule>, indent: usize) -> String {
+        let mut out = String::from("{\n");
+        let mut pending_blank = false;
+        let mut started = false;
+
+        for current in dict
+            .into_inner()
+            .flat_map(|current| match current.as_rule() {
+                // Leading comments of an entry are rendered like any other block item.
+                Rule::dict_entry => current.into_inner().collect::<Vec<_>>(),
+                _ => vec![current],
+            })
+        {
+            match current.as_rule() {
+                Rule::empty_lines => {
+                    pending_blank |= started && self.has_blank_line(&current);
+                    continue;
+                }
+                Rule::trailing_comment => {
+                    out.pop();
+                    out.push(' ');
+                    out.push_str(current.as_str().trim());
+                    out.push('\n');
+                    continue;
+                }
+                _ => {}
+            }
+
+            if pending_blank {
+                out.push('\n');
+            }
+            pending_blank = false;
+            started = true;
+
+            match current.as_rule() {
+                Rule::comment_block => self.comment_block(current, indent + 1, &mut out),
+                Rule::dict_key => {
+                    out.push_str(&self.indent(indent + 1));
+                    out.push_str(current.as_str());
+                }
+                _ => {
+                    out.push(' ');
+                    out.push_str(&self.expression(current, indent + 1));
+                    out.push('\n');
+                }
+            }
+        }
+
+        if !started {
+            return "{}".to_string();
+        }
+
+        out.push_str(&self.indent(indent));
+        out.push('}');
+        out
+    }
+
+    fn array(&self, array: Pair<'_, Rule>, indent: usize) -> String {
+        if !array.as_str().contains('\n') {
+            let items = array
+                .clone()
+                .into_inner()
+                .filter(|item| item.as_rule() == Rule::expression)
+                .map(|item| self.expression(item, indent + 1))
+                .collect::<Vec<_>>();
+            // Maps always render on several lines, and so does an array holding one.
+            if !items.iter().any(|item| item.contains('\n')) {
+                return format!("[{}]", items.join(", "));
+            }
+        }
+
+        let mut out = String::from("[\n");
+        let mut pending_blank = false;
+        let mut started = false;
+
+        for current in array.into_inner() {
+            match current.as_rule() {
+                Rule::empty_lines => {
+                    pending_blank |= started && self.has_blank_line(&current);
+                    continue;
+                }
+                Rule::trailing_comment => {
+                    out.pop();
+                    out.push(' ');
+                    out.push_str(current.as_str().trim());
+                    out.push('\n');
+                    continue;
+                }
+                _ => {}
+            }
+
+            if pending_blank {
+                out.push('\n');
+            }
+            pending_blank = false;
+            started = true;
+
+            match current.as_rule() {
+                Rule::comment_block => self.comment_block(current, indent + 1, &mut out),
+                _ => {
+                    let item = self.expression(current, indent + 1);
+                    self.line(indent + 1, &item, &mut out);
+                }
+            }
+        }
+
+        if !started {
+            return "[]".to_string();
+        }
+
+        out.push_str(&self.indent(indent));
+        out.push(']');
+        out
+    }
+
+    /// Whether an `empty_lines` node contains at least one line with nothing on it.
+    fn has_blank_line(&self, empty_lines: &Pair<'_, Rule>) -> bool {
+        let before = &self.input[..empty_lines.as_span().start()];
+        let starts_line = before.trim_end_matches([' ', '\t']).ends_with('\n')
+            || before.trim_end_matches([' ', '\t']).is_empty();
+        let newlines = empty_lines.as_str().matches('\n').count();
+
+        newlines > usize::from(!starts_line)
+    }
+
+    fn indent(&self, level: usize) -> String {
+        self.indentation.to_string().repeat(level)
+    }
+
+    fn line(&self, indent: usize, text: &str, out: &mut String) {
+        let text = text.trim_end();
+        if !text.is_empty() {
+            out.push_str(&self.indent(indent));
+            out.push_str(text);
+        }
+        out.push('\n');
+    }
+}
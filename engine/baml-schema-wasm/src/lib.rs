@@ -29,11 +29,11 @@ fn register_panic_hook() {
     });
 }
 
-// #[wasm_bindgen]
-// pub fn format(schema: String, params: String) -> String {
-//     register_panic_hook();
-//     baml_fmt::format(&schema, &params)
-// }
+#[wasm_bindgen]
+pub fn format(schema: String, params: String) -> String {
+    register_panic_hook();
+    baml_fmt::format(&schema, &params)
+}
 
 /// Docs: https://prisma.github.io/prisma-engines/doc/prisma_fmt/fn.get_config.html
 // #[wasm_bindgen]
@@ -48,6 +48,24 @@ pub fn lint(input: String) -> String {
     baml_fmt::lint(input)
 }
 
+#[wasm_bindgen]
+pub fn rename(input: String) -> String {
+    register_panic_hook();
+    baml_fmt::rename(input)
+}
+
+#[wasm_bindgen]
+pub fn render_prompt(input: String) -> String {
+    register_panic_hook();
+    baml_fmt::render_prompt(input)
+}
+
+#[wasm_bindgen]
+pub fn parse_output(input: String) -> String {
+    register_panic_hook();
+    baml_fmt::parse_output(input)
+}
+
 #[wasm_bindgen]
 pub fn validate(params: String) -> Result<(), JsError> {
     register_panic_hook();
@@ -44,6 +44,20 @@ export type ArgType =
     }[]
   }
 
+// How many tokens the prompt of an impl takes up before its inputs are added.
+export interface TokenCounts {
+  encoding: string
+  // False when the model's tokenizer isn't public, and `encoding` is an estimate.
+  exact: boolean
+  prompt: number
+  output_schema: number
+  model: string | null
+  context_size: number | null
+  max_tokens: number | null
+  // What's left of the context window for the inputs.
+  remaining: number | null
+}
+
 export type Impl = {
   type: 'llm'
   name: StringSpan
@@ -51,6 +65,7 @@ export type Impl = {
   input_replacers: { key: string; value: string }[]
   output_replacers: { key: string; value: string }[]
   client: StringSpan
+  tokens: TokenCounts
 } & (
     {
       has_v2?: false